@@ -30,7 +30,9 @@ bench_utils::generate_bench!(roact, {
 
             rules.into_iter().fold(
                 Configuration::empty()
-                    .with_generator(darklua_core::GeneratorParameters::Dense { column_span: 80 }),
+                    .with_generator(
+                        darklua_core::GeneratorParameters::default_dense().with_column_span(80)
+                    ),
                 |config, rule| config.with_rule(rule)
             )
         }),
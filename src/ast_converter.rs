@@ -1,4 +1,4 @@
-use std::{fmt, str::FromStr};
+use std::{fmt, iter, str::FromStr};
 
 use full_moon::{
     ast,
@@ -573,7 +573,7 @@ impl<'a> AstConverter<'a> {
                     let identifiers = statement
                         .names()
                         .iter()
-                        .zip(statement.type_specifiers())
+                        .zip(statement.type_specifiers().chain(iter::repeat(None)))
                         .map(|(name, type_specifier)| {
                             self.convert_typed_identifier(name, type_specifier)
                         })
@@ -852,11 +852,41 @@ impl<'a> AstConverter<'a> {
                             .into(),
                     );
                 }
+                ConvertWork::MakeTypeFunctionStatement {
+                    type_function,
+                    export_token,
+                } => {
+                    let builder = self.convert_function_body_attributes(
+                        type_function.function_body(),
+                        self.convert_token(type_function.function_token())?,
+                    )?;
+                    let mut name =
+                        Identifier::new(type_function.function_name().token().to_string());
+                    let mut type_token = None;
+                    let mut export = None;
+
+                    if self.hold_token_data {
+                        name.set_token(self.convert_token(type_function.function_name())?);
+                        type_token = Some(self.convert_token(type_function.type_token())?);
+                        export = export_token
+                            .map(|token| self.convert_token(token))
+                            .transpose()?;
+                    }
+
+                    let mut statement =
+                        builder.into_type_function_statement(name, type_token, export);
+
+                    if export_token.is_some() {
+                        statement.set_exported();
+                    }
+
+                    self.statements.push(statement.into());
+                }
                 ConvertWork::MakeLocalAssignStatement { statement } => {
                     let variables = statement
                         .names()
                         .iter()
-                        .zip(statement.type_specifiers())
+                        .zip(statement.type_specifiers().chain(iter::repeat(None)))
                         .map(|(token_ref, type_specifier)| {
                             self.convert_typed_identifier(token_ref, type_specifier)
                         })
@@ -1503,6 +1533,14 @@ impl<'a> AstConverter<'a> {
                 }
             }
             ast::Stmt::LocalAssignment(local_assign) => {
+                if local_assign
+                    .attributes()
+                    .any(|attribute| attribute.is_some())
+                {
+                    return Err(ConvertError::Statement {
+                        statement: statement.to_string(),
+                    });
+                }
                 self.work_stack.push(ConvertWork::MakeLocalAssignStatement {
                     statement: local_assign,
                 });
@@ -1568,6 +1606,15 @@ impl<'a> AstConverter<'a> {
             ast::Stmt::TypeDeclaration(type_declaration) => {
                 self.convert_type_declaration(type_declaration, None);
             }
+            ast::Stmt::ExportedTypeFunction(exported_type_function) => {
+                self.convert_type_function(
+                    exported_type_function.type_function(),
+                    Some(exported_type_function.export_token()),
+                );
+            }
+            ast::Stmt::TypeFunction(type_function) => {
+                self.convert_type_function(type_function, None);
+            }
             _ => {
                 return Err(ConvertError::Statement {
                     statement: statement.to_string(),
@@ -1608,6 +1655,19 @@ impl<'a> AstConverter<'a> {
         }
     }
 
+    fn convert_type_function(
+        &mut self,
+        type_function: &'a ast::luau::TypeFunction,
+        export_token: Option<&'a tokenizer::TokenReference>,
+    ) {
+        self.work_stack
+            .push(ConvertWork::MakeTypeFunctionStatement {
+                type_function,
+                export_token,
+            });
+        self.push_function_body_work(type_function.function_body());
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn convert_table(&mut self, table: &'a ast::TableConstructor) -> Result<(), ConvertError> {
         for field in table.fields() {
@@ -2450,7 +2510,11 @@ impl<'a> AstConverter<'a> {
             builder.set_return_type(self.pop_function_return_type()?);
         };
 
-        for (param, type_specifier) in body.parameters().iter().zip(body.type_specifiers()) {
+        for (param, type_specifier) in body
+            .parameters()
+            .iter()
+            .zip(body.type_specifiers().chain(iter::repeat(None)))
+        {
             match param {
                 ast::Parameter::Ellipsis(token) => {
                     if builder.is_variadic() {
@@ -2584,6 +2648,11 @@ impl<'a> AstConverter<'a> {
             ast::BinOp::TildeEqual(_) => BinaryOperator::NotEqual,
             ast::BinOp::TwoDots(_) => BinaryOperator::Concat,
             ast::BinOp::TwoEqual(_) => BinaryOperator::Equal,
+            ast::BinOp::Ampersand(_) => BinaryOperator::BitwiseAnd,
+            ast::BinOp::Pipe(_) => BinaryOperator::BitwiseOr,
+            ast::BinOp::Tilde(_) => BinaryOperator::BitwiseXor,
+            ast::BinOp::DoubleLessThan(_) => BinaryOperator::ShiftLeft,
+            ast::BinOp::DoubleGreaterThan(_) => BinaryOperator::ShiftRight,
             _ => {
                 return Err(ConvertError::BinaryOperator {
                     operator: operator.to_string(),
@@ -2598,6 +2667,7 @@ impl<'a> AstConverter<'a> {
             ast::UnOp::Minus(_) => UnaryOperator::Minus,
             ast::UnOp::Not(_) => UnaryOperator::Not,
             ast::UnOp::Hash(_) => UnaryOperator::Length,
+            ast::UnOp::Tilde(_) => UnaryOperator::BitwiseNot,
             _ => {
                 return Err(ConvertError::UnaryOperator {
                     operator: operator.to_string(),
@@ -2843,6 +2913,10 @@ enum ConvertWork<'a> {
     MakeLocalFunctionStatement {
         statement: &'a ast::LocalFunction,
     },
+    MakeTypeFunctionStatement {
+        type_function: &'a ast::luau::TypeFunction,
+        export_token: Option<&'a tokenizer::TokenReference>,
+    },
     MakeLocalAssignStatement {
         statement: &'a ast::LocalAssignment,
     },
@@ -3131,7 +3205,12 @@ fn get_binary_operator_token(
         | BinOp::Star(token)
         | BinOp::TildeEqual(token)
         | BinOp::TwoDots(token)
-        | BinOp::TwoEqual(token) => Ok(token),
+        | BinOp::TwoEqual(token)
+        | BinOp::Ampersand(token)
+        | BinOp::Pipe(token)
+        | BinOp::Tilde(token)
+        | BinOp::DoubleLessThan(token)
+        | BinOp::DoubleGreaterThan(token) => Ok(token),
         _ => Err(ConvertError::BinaryOperator {
             operator: operator.to_string(),
         }),
@@ -3144,7 +3223,7 @@ fn get_unary_operator_token(
     use ast::UnOp;
 
     match operator {
-        UnOp::Minus(token) | UnOp::Not(token) | UnOp::Hash(token) => Ok(token),
+        UnOp::Minus(token) | UnOp::Not(token) | UnOp::Hash(token) | UnOp::Tilde(token) => Ok(token),
         _ => Err(ConvertError::UnaryOperator {
             operator: operator.to_string(),
         }),
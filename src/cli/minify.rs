@@ -28,7 +28,9 @@ pub fn run(options: &Options, _global: &GlobalOptions) -> CommandResult {
             Configuration::empty().with_generator(
                 options
                     .column_span
-                    .map(|column_span| GeneratorParameters::Dense { column_span })
+                    .map(|column_span| {
+                        GeneratorParameters::default_dense().with_column_span(column_span)
+                    })
                     .unwrap_or_else(GeneratorParameters::default_dense),
             ),
         );
@@ -19,13 +19,17 @@ pub struct Options {
     /// Choose a specific configuration file.
     #[arg(long, short, alias = "config-path")]
     pub(crate) config: Option<PathBuf>,
-    /// Choose how Lua code is formatted ('dense', 'readable' or 'retain_lines').
+    /// Choose how Lua code is formatted ('dense', 'readable', 'retain_lines' or 'format').
     /// This will override the format given by the configuration file.
     #[arg(long)]
     format: Option<LuaFormat>,
     /// Watch files and directories for changes and automatically re-run
     #[arg(long, short)]
     watch: bool,
+    /// Concatenate all the processed files into the output file, ordered by path.
+    /// Each file is wrapped in a `do ... end` block.
+    #[arg(long)]
+    concatenate: bool,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -33,6 +37,7 @@ enum LuaFormat {
     Dense,
     Readable,
     RetainLines,
+    Format,
 }
 
 impl FromStr for LuaFormat {
@@ -44,8 +49,9 @@ impl FromStr for LuaFormat {
             "readable" => Ok(Self::Readable),
             // keep "retain-lines" for back-compatibility
             "retain_lines" | "retain-lines" => Ok(Self::RetainLines),
+            "format" => Ok(Self::Format),
             _ => Err(format!(
-                "format '{}' does not exist! (possible options are: 'dense', 'readable' or 'retain_lines'",
+                "format '{}' does not exist! (possible options are: 'dense', 'readable', 'retain_lines' or 'format'",
                 format
             )),
         }
@@ -76,9 +82,15 @@ impl Options {
             process_options = process_options.with_generator_override(match format {
                 LuaFormat::Dense => GeneratorParameters::default_dense(),
                 LuaFormat::Readable => GeneratorParameters::default_readable(),
-                LuaFormat::RetainLines => GeneratorParameters::RetainLines,
+                LuaFormat::RetainLines => GeneratorParameters::default_retain_lines(),
+                LuaFormat::Format => GeneratorParameters::default_format(),
             })
         }
+
+        if self.concatenate {
+            process_options = process_options.concatenate();
+        }
+
         process_options
     }
 }
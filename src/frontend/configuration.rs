@@ -4,11 +4,12 @@ use std::{
     str::FromStr,
 };
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
 use crate::{
-    generator::{DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, TokenBasedLuaGenerator},
+    generator::{generate_with, LuaVersion},
     nodes::Block,
+    process::utils::is_valid_identifier_prefix,
     rules::{
         bundle::{BundleRequireMode, Bundler},
         get_default_rules, Rule,
@@ -16,12 +17,25 @@ use crate::{
     Parser,
 };
 
+use super::{
+    rule_ordering::{validate_rule_ordering, RuleOrderingWarning},
+    DarkluaError, DarkluaResult,
+};
+
 const DEFAULT_COLUMN_SPAN: usize = 80;
 
 fn get_default_column_span() -> usize {
     DEFAULT_COLUMN_SPAN
 }
 
+fn get_default_trailing_commas() -> bool {
+    true
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Configuration {
@@ -31,6 +45,20 @@ pub struct Configuration {
     generator: GeneratorParameters,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     bundle: Option<BundleConfiguration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_map_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    preserve_header_comments: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    safe_mode: bool,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_generated_identifier_prefix"
+    )]
+    generated_identifier_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target: Option<LuaVersion>,
     #[serde(default, skip)]
     location: Option<PathBuf>,
 }
@@ -43,10 +71,32 @@ impl Configuration {
             rules: Vec::new(),
             generator: GeneratorParameters::default(),
             bundle: None,
+            source_map_url: None,
+            preserve_header_comments: Vec::new(),
+            safe_mode: false,
+            generated_identifier_prefix: None,
+            target: None,
             location: None,
         }
     }
 
+    /// Parses a configuration from JSON5 content, like the content of a
+    /// `.darklua.json5` file.
+    pub fn from_json5_str(content: &str) -> DarkluaResult<Self> {
+        Self::parse_json5(content).map_err(DarkluaError::from)
+    }
+
+    pub(crate) fn parse_json5(content: &str) -> Result<Self, json5::Error> {
+        json5::from_str(content)
+    }
+
+    /// Serializes the configuration to JSON5. The result can be parsed back with
+    /// [`Configuration::from_json5_str`], which gives the same rules (in the same
+    /// order) with the same parameters.
+    pub fn to_json5_str(&self) -> DarkluaResult<String> {
+        json5::to_string(self).map_err(|err| DarkluaError::serialization("json5", err.to_string()))
+    }
+
     #[inline]
     pub fn with_generator(mut self, generator: GeneratorParameters) -> Self {
         self.generator = generator;
@@ -70,6 +120,53 @@ impl Configuration {
         self
     }
 
+    /// Appends a comment referencing a source map at the end of each generated
+    /// file. The `{file}` placeholder is replaced with the output file name.
+    #[inline]
+    pub fn with_source_map_url(mut self, url: impl Into<String>) -> Self {
+        self.source_map_url = Some(url.into());
+        self
+    }
+
+    /// Keeps the comments at the top of each file (before any code) that match
+    /// the given regex pattern, even when the rules or the generator remove them.
+    #[inline]
+    pub fn with_preserved_header_comment(mut self, pattern: impl Into<String>) -> Self {
+        self.preserve_header_comments.push(pattern.into());
+        self
+    }
+
+    /// Skips the rules that can change the behavior of the code in some edge cases
+    /// (the rules where [`is_semantics_preserving`](crate::rules::RuleConfiguration::is_semantics_preserving)
+    /// returns `false`).
+    #[inline]
+    pub fn with_safe_mode(mut self) -> Self {
+        self.safe_mode = true;
+        self
+    }
+
+    #[inline]
+    pub fn is_safe_mode(&self) -> bool {
+        self.safe_mode
+    }
+
+    /// Sets the prefix of the identifiers generated by rules, like the variables created
+    /// by `remove_continue`. By default, the prefix is `__DARKLUA_`.
+    #[inline]
+    pub fn with_generated_identifier_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.generated_identifier_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the version of Lua that the generated code must run on. Rules that produce
+    /// syntax unsupported by this version are skipped, and generating code that contains
+    /// such syntax fails.
+    #[inline]
+    pub fn with_target(mut self, target: LuaVersion) -> Self {
+        self.target = Some(target);
+        self
+    }
+
     #[inline]
     pub fn with_location(mut self, location: impl Into<PathBuf>) -> Self {
         self.location = Some(location.into());
@@ -81,19 +178,56 @@ impl Configuration {
         self.rules.push(rule.into());
     }
 
+    /// Returns advisory warnings for the rules that are ordered in a way that makes
+    /// them less effective. This does not change how the rules are processed.
+    pub fn validate(&self) -> Vec<RuleOrderingWarning> {
+        validate_rule_ordering(self.rules())
+    }
+
     #[inline]
     pub(crate) fn rules<'a, 'b: 'a>(&'b self) -> impl Iterator<Item = &'a dyn Rule> {
-        self.rules.iter().map(AsRef::as_ref)
+        self.rules
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(move |rule| !self.safe_mode || rule.is_semantics_preserving())
+    }
+
+    /// Returns the rules that are not applied because the safe mode is enabled.
+    pub(crate) fn skipped_rules<'a, 'b: 'a>(&'b self) -> impl Iterator<Item = &'a dyn Rule> {
+        self.rules
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(move |rule| self.safe_mode && !rule.is_semantics_preserving())
     }
 
     #[inline]
     pub(crate) fn build_parser(&self) -> Parser {
-        self.generator.build_parser()
+        let parser = self.generator.build_parser();
+
+        match self.target {
+            Some(target) => parser.with_lua_version(target),
+            None => parser,
+        }
     }
 
-    #[inline]
-    pub(crate) fn generate_lua(&self, block: &Block, code: &str) -> String {
-        self.generator.generate_lua(block, code)
+    pub(crate) fn append_source_map_comment(&self, code: &mut String, output: &Path) {
+        if let Some(url) = self.source_map_url.as_ref() {
+            let file_name = output
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            let url = url
+                .replace("{file}", &file_name)
+                .replace('\r', "%0D")
+                .replace('\n', "%0A");
+
+            if !code.is_empty() && !code.ends_with('\n') {
+                code.push('\n');
+            }
+            code.push_str("--# sourceMappingURL=");
+            code.push_str(&url);
+            code.push('\n');
+        }
     }
 
     pub(crate) fn bundle(&self) -> Option<Bundler> {
@@ -110,15 +244,53 @@ impl Configuration {
         }
     }
 
+    #[inline]
+    pub(crate) fn preserved_header_comments(&self) -> impl Iterator<Item = &str> {
+        self.preserve_header_comments.iter().map(AsRef::as_ref)
+    }
+
+    #[inline]
+    pub(crate) fn generator(&self) -> &GeneratorParameters {
+        &self.generator
+    }
+
     #[inline]
     pub(crate) fn rules_len(&self) -> usize {
-        self.rules.len()
+        self.rules().count()
     }
 
     #[inline]
     pub(crate) fn location(&self) -> Option<&Path> {
         self.location.as_deref()
     }
+
+    #[inline]
+    pub(crate) fn generated_identifier_prefix(&self) -> Option<&str> {
+        self.generated_identifier_prefix.as_deref()
+    }
+
+    #[inline]
+    pub(crate) fn target(&self) -> Option<LuaVersion> {
+        self.target
+    }
+}
+
+fn deserialize_generated_identifier_prefix<'de, D>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let prefix = String::deserialize(deserializer)?;
+
+    if is_valid_identifier_prefix(&prefix) {
+        Ok(Some(prefix))
+    } else {
+        Err(de::Error::custom(format!(
+            "invalid generated identifier prefix `{}` (it must only contain letters, digits and underscores, and must not start with a digit)",
+            prefix
+        )))
+    }
 }
 
 impl Default for Configuration {
@@ -127,6 +299,11 @@ impl Default for Configuration {
             rules: get_default_rules(),
             generator: Default::default(),
             bundle: None,
+            source_map_url: None,
+            preserve_header_comments: Vec::new(),
+            safe_mode: false,
+            generated_identifier_prefix: None,
+            target: None,
             location: None,
         }
     }
@@ -157,59 +334,163 @@ impl std::fmt::Debug for Configuration {
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "name")]
 pub enum GeneratorParameters {
     #[serde(alias = "retain-lines")]
-    RetainLines,
+    #[non_exhaustive]
+    RetainLines {
+        #[serde(default)]
+        escape_non_ascii: bool,
+    },
+    #[non_exhaustive]
     Dense {
         #[serde(default = "get_default_column_span")]
         column_span: usize,
+        #[serde(default)]
+        always_semicolons: bool,
+        #[serde(default)]
+        spaced_binary_operators: bool,
+        #[serde(default)]
+        escape_non_ascii: bool,
     },
+    #[non_exhaustive]
     Readable {
         #[serde(default = "get_default_column_span")]
         column_span: usize,
+        #[serde(default)]
+        align_table_entries: bool,
+        #[serde(default = "get_default_trailing_commas")]
+        trailing_commas: bool,
+        #[serde(default)]
+        escape_non_ascii: bool,
+    },
+    #[non_exhaustive]
+    Format {
+        #[serde(default)]
+        escape_non_ascii: bool,
     },
 }
 
 impl Default for GeneratorParameters {
     fn default() -> Self {
-        Self::RetainLines
+        Self::default_retain_lines()
     }
 }
 
 impl GeneratorParameters {
+    pub fn default_retain_lines() -> Self {
+        Self::RetainLines {
+            escape_non_ascii: false,
+        }
+    }
+
+    pub fn default_format() -> Self {
+        Self::Format {
+            escape_non_ascii: false,
+        }
+    }
+
     pub fn default_dense() -> Self {
         Self::Dense {
             column_span: DEFAULT_COLUMN_SPAN,
+            always_semicolons: false,
+            spaced_binary_operators: false,
+            escape_non_ascii: false,
         }
     }
 
     pub fn default_readable() -> Self {
         Self::Readable {
             column_span: DEFAULT_COLUMN_SPAN,
+            align_table_entries: false,
+            trailing_commas: true,
+            escape_non_ascii: false,
         }
     }
 
-    fn generate_lua(&self, block: &Block, code: &str) -> String {
-        match self {
-            Self::RetainLines => {
-                let mut generator = TokenBasedLuaGenerator::new(code);
-                generator.write_block(block);
-                generator.into_string()
+    /// Sets the column span of the `dense` and `readable` generators. Other
+    /// generators are returned unchanged.
+    pub fn with_column_span(mut self, span: usize) -> Self {
+        match &mut self {
+            Self::Dense { column_span, .. } | Self::Readable { column_span, .. } => {
+                *column_span = span;
             }
-            Self::Dense { column_span } => {
-                let mut generator = DenseLuaGenerator::new(*column_span);
-                generator.write_block(block);
-                generator.into_string()
+            Self::RetainLines { .. } | Self::Format { .. } => {}
+        }
+        self
+    }
+
+    /// Escapes the non-ASCII characters of strings with decimal escape sequences.
+    pub fn with_escaped_non_ascii(mut self) -> Self {
+        match &mut self {
+            Self::RetainLines { escape_non_ascii }
+            | Self::Dense {
+                escape_non_ascii, ..
+            }
+            | Self::Readable {
+                escape_non_ascii, ..
             }
-            Self::Readable { column_span } => {
-                let mut generator = ReadableLuaGenerator::new(*column_span);
-                generator.write_block(block);
-                generator.into_string()
+            | Self::Format { escape_non_ascii } => {
+                *escape_non_ascii = true;
             }
         }
+        self
+    }
+
+    /// Writes a semicolon between every statement with the `dense` generator. Other
+    /// generators are returned unchanged.
+    pub fn with_always_semicolons(mut self) -> Self {
+        if let Self::Dense {
+            always_semicolons, ..
+        } = &mut self
+        {
+            *always_semicolons = true;
+        }
+        self
+    }
+
+    /// Writes a space before and after every binary operator with the `dense`
+    /// generator. Other generators are returned unchanged.
+    pub fn with_spaced_binary_operators(mut self) -> Self {
+        if let Self::Dense {
+            spaced_binary_operators,
+            ..
+        } = &mut self
+        {
+            *spaced_binary_operators = true;
+        }
+        self
+    }
+
+    /// Aligns the `=` of field entries in tables written on multiple lines with the
+    /// `readable` generator. Other generators are returned unchanged.
+    pub fn with_aligned_table_entries(mut self) -> Self {
+        if let Self::Readable {
+            align_table_entries,
+            ..
+        } = &mut self
+        {
+            *align_table_entries = true;
+        }
+        self
+    }
+
+    /// Removes the comma after the last entry of tables written on multiple lines
+    /// with the `readable` generator. Other generators are returned unchanged.
+    pub fn without_trailing_commas(mut self) -> Self {
+        if let Self::Readable {
+            trailing_commas, ..
+        } = &mut self
+        {
+            *trailing_commas = false;
+        }
+        self
+    }
+
+    pub(crate) fn generate_lua(&self, block: &Block, code: &str) -> String {
+        generate_with(self, code, |generator| generator.write_block(block))
     }
 
-    fn build_parser(&self) -> Parser {
+    pub(crate) fn build_parser(&self) -> Parser {
         match self {
-            Self::RetainLines => Parser::default().preserve_tokens(),
+            Self::RetainLines { .. } | Self::Format { .. } => Parser::default().preserve_tokens(),
             Self::Dense { .. } | Self::Readable { .. } => Parser::default(),
         }
     }
@@ -221,13 +502,10 @@ impl FromStr for GeneratorParameters {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             // keep "retain-lines" for back-compatibility
-            "retain_lines" | "retain-lines" => Self::RetainLines,
-            "dense" => Self::Dense {
-                column_span: DEFAULT_COLUMN_SPAN,
-            },
-            "readable" => Self::Readable {
-                column_span: DEFAULT_COLUMN_SPAN,
-            },
+            "retain_lines" | "retain-lines" => Self::default_retain_lines(),
+            "dense" => Self::default_dense(),
+            "readable" => Self::default_readable(),
+            "format" => Self::default_format(),
             _ => return Err(format!("invalid generator name `{}`", s)),
         })
     }
@@ -282,6 +560,154 @@ impl BundleConfiguration {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::rules::{RemoveSpaces, SimplifyBooleanComparison};
+
+    fn get_rule_names(configuration: &Configuration) -> Vec<&'static str> {
+        configuration.rules().map(|rule| rule.get_name()).collect()
+    }
+
+    #[test]
+    fn safe_mode_skips_rules_that_may_change_behavior() {
+        let configuration = Configuration::empty()
+            .with_rule(Box::<SimplifyBooleanComparison>::default() as Box<dyn Rule>)
+            .with_rule(Box::<RemoveSpaces>::default() as Box<dyn Rule>)
+            .with_safe_mode();
+
+        pretty_assertions::assert_eq!(get_rule_names(&configuration), vec!["remove_spaces"]);
+        pretty_assertions::assert_eq!(configuration.rules_len(), 1);
+        pretty_assertions::assert_eq!(
+            configuration
+                .skipped_rules()
+                .map(|rule| rule.get_name())
+                .collect::<Vec<_>>(),
+            vec!["simplify_boolean_comparison"]
+        );
+    }
+
+    #[test]
+    fn rules_that_may_change_behavior_are_applied_without_safe_mode() {
+        let configuration = Configuration::empty()
+            .with_rule(Box::<SimplifyBooleanComparison>::default() as Box<dyn Rule>)
+            .with_rule(Box::<RemoveSpaces>::default() as Box<dyn Rule>);
+
+        pretty_assertions::assert_eq!(
+            get_rule_names(&configuration),
+            vec!["simplify_boolean_comparison", "remove_spaces"]
+        );
+        pretty_assertions::assert_eq!(configuration.skipped_rules().count(), 0);
+    }
+
+    #[test]
+    fn deserialize_safe_mode() {
+        let configuration: Configuration =
+            json5::from_str("{ rules: ['simplify_boolean_comparison'], safe_mode: true }").unwrap();
+
+        assert!(configuration.is_safe_mode());
+        pretty_assertions::assert_eq!(configuration.rules_len(), 0);
+    }
+
+    mod json5_strings {
+        use super::*;
+        use crate::ErrorCategory;
+
+        fn get_rule_names(configuration: &Configuration) -> Vec<&'static str> {
+            configuration.rules().map(|rule| rule.get_name()).collect()
+        }
+
+        #[test]
+        fn round_trip_preserves_rules_and_parameters() {
+            let configuration = Configuration::from_json5_str(
+                r#"{
+                    // rules are applied in order
+                    rules: [
+                        'remove_comments',
+                        { rule: 'remove_assertions', preserve_arguments_side_effects: false, },
+                        { rule: 'inject_global_value', identifier: 'DEV', value: false },
+                    ],
+                    generator: { name: 'dense', column_span: 120, }, /* trailing comma */
+                }"#,
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                get_rule_names(&configuration),
+                vec![
+                    "remove_comments",
+                    "remove_assertions",
+                    "inject_global_value"
+                ]
+            );
+
+            let serialized = configuration.to_json5_str().unwrap();
+            let parsed = Configuration::from_json5_str(&serialized).unwrap();
+
+            pretty_assertions::assert_eq!(get_rule_names(&parsed), get_rule_names(&configuration));
+            pretty_assertions::assert_eq!(parsed.to_json5_str().unwrap(), serialized);
+            pretty_assertions::assert_eq!(
+                parsed.generator,
+                GeneratorParameters::Dense {
+                    column_span: 120,
+                    always_semicolons: false,
+                    spaced_binary_operators: false,
+                    escape_non_ascii: false,
+                }
+            );
+        }
+
+        #[test]
+        fn from_json5_str_with_unknown_field_error() {
+            let error = Configuration::from_json5_str("{ unknown: true }").unwrap_err();
+
+            pretty_assertions::assert_eq!(error.category(), ErrorCategory::Configuration);
+        }
+    }
+
+    #[test]
+    fn deserialize_generated_identifier_prefix() {
+        let configuration: Configuration =
+            json5::from_str("{ generated_identifier_prefix: '_D' }").unwrap();
+
+        pretty_assertions::assert_eq!(configuration.generated_identifier_prefix(), Some("_D"));
+    }
+
+    #[test]
+    fn deserialize_empty_generated_identifier_prefix() {
+        let configuration: Configuration =
+            json5::from_str("{ generated_identifier_prefix: '' }").unwrap();
+
+        pretty_assertions::assert_eq!(configuration.generated_identifier_prefix(), Some(""));
+    }
+
+    #[test]
+    fn deserialize_invalid_generated_identifier_prefix_error() {
+        let result = json5::from_str::<Configuration>("{ generated_identifier_prefix: '1a' }");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn default_configuration_has_no_generated_identifier_prefix() {
+        pretty_assertions::assert_eq!(Configuration::default().generated_identifier_prefix(), None);
+    }
+
+    #[test]
+    fn deserialize_target() {
+        let configuration: Configuration = json5::from_str("{ target: 'lua51' }").unwrap();
+
+        pretty_assertions::assert_eq!(configuration.target(), Some(LuaVersion::Lua51));
+    }
+
+    #[test]
+    fn deserialize_invalid_target_error() {
+        let result = json5::from_str::<Configuration>("{ target: 'lua50' }");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn default_configuration_has_no_target() {
+        pretty_assertions::assert_eq!(Configuration::default().target(), None);
+    }
 
     mod generator_parameters {
         use super::*;
@@ -291,7 +717,10 @@ mod test {
             let config: Configuration =
                 json5::from_str("{ generator: { name: 'retain_lines' } }").unwrap();
 
-            pretty_assertions::assert_eq!(config.generator, GeneratorParameters::RetainLines);
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::default_retain_lines()
+            );
         }
 
         #[test]
@@ -299,7 +728,10 @@ mod test {
             let config: Configuration =
                 json5::from_str("{ generator: { name: 'retain-lines' } }").unwrap();
 
-            pretty_assertions::assert_eq!(config.generator, GeneratorParameters::RetainLines);
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::default_retain_lines()
+            );
         }
 
         #[test]
@@ -309,7 +741,10 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Dense {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    always_semicolons: false,
+                    spaced_binary_operators: false,
+                    escape_non_ascii: false,
                 }
             );
         }
@@ -321,7 +756,46 @@ mod test {
 
             pretty_assertions::assert_eq!(
                 config.generator,
-                GeneratorParameters::Dense { column_span: 110 }
+                GeneratorParameters::Dense {
+                    column_span: 110,
+                    always_semicolons: false,
+                    spaced_binary_operators: false,
+                    escape_non_ascii: false,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_dense_params_with_always_semicolons() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', always_semicolons: true } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Dense {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    always_semicolons: true,
+                    spaced_binary_operators: false,
+                    escape_non_ascii: false,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_dense_params_with_spaced_binary_operators() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', spaced_binary_operators: true } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Dense {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    always_semicolons: false,
+                    spaced_binary_operators: true,
+                    escape_non_ascii: false,
+                }
             );
         }
 
@@ -333,7 +807,10 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Readable {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    align_table_entries: false,
+                    trailing_commas: true,
+                    escape_non_ascii: false,
                 }
             );
         }
@@ -345,7 +822,46 @@ mod test {
 
             pretty_assertions::assert_eq!(
                 config.generator,
-                GeneratorParameters::Readable { column_span: 110 }
+                GeneratorParameters::Readable {
+                    column_span: 110,
+                    align_table_entries: false,
+                    trailing_commas: true,
+                    escape_non_ascii: false,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_without_trailing_commas() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'readable', trailing_commas: false } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    align_table_entries: false,
+                    trailing_commas: false,
+                    escape_non_ascii: false,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_align_table_entries() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'readable', align_table_entries: true } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    align_table_entries: true,
+                    trailing_commas: true,
+                    escape_non_ascii: false,
+                }
             );
         }
 
@@ -353,7 +869,10 @@ mod test {
         fn deserialize_retain_lines_params_as_string() {
             let config: Configuration = json5::from_str("{generator: 'retain_lines'}").unwrap();
 
-            pretty_assertions::assert_eq!(config.generator, GeneratorParameters::RetainLines);
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::default_retain_lines()
+            );
         }
 
         #[test]
@@ -363,7 +882,10 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Dense {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    always_semicolons: false,
+                    spaced_binary_operators: false,
+                    escape_non_ascii: false,
                 }
             );
         }
@@ -375,7 +897,10 @@ mod test {
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Readable {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    align_table_entries: false,
+                    trailing_commas: true,
+                    escape_non_ascii: false,
                 }
             );
         }
@@ -391,6 +916,92 @@ mod test {
         }
     }
 
+    mod source_map_url {
+        use super::*;
+
+        #[test]
+        fn deserialize_source_map_url() {
+            let config: Configuration =
+                json5::from_str("{ source_map_url: 'main.lua.map' }").unwrap();
+
+            pretty_assertions::assert_eq!(config.source_map_url, Some("main.lua.map".to_owned()));
+        }
+
+        #[test]
+        fn append_comment_without_trailing_new_line() {
+            let config = Configuration::empty().with_source_map_url("main.lua.map");
+            let mut code = "return nil".to_owned();
+
+            config.append_source_map_comment(&mut code, Path::new("out/main.lua"));
+
+            pretty_assertions::assert_eq!(code, "return nil\n--# sourceMappingURL=main.lua.map\n");
+        }
+
+        #[test]
+        fn append_comment_with_trailing_new_line() {
+            let config = Configuration::empty().with_source_map_url("main.lua.map");
+            let mut code = "return nil\n".to_owned();
+
+            config.append_source_map_comment(&mut code, Path::new("out/main.lua"));
+
+            pretty_assertions::assert_eq!(code, "return nil\n--# sourceMappingURL=main.lua.map\n");
+        }
+
+        #[test]
+        fn append_comment_replaces_file_placeholder() {
+            let config = Configuration::empty().with_source_map_url("maps/{file}.map");
+            let mut code = String::new();
+
+            config.append_source_map_comment(&mut code, Path::new("out/init.lua"));
+
+            pretty_assertions::assert_eq!(code, "--# sourceMappingURL=maps/init.lua.map\n");
+        }
+
+        #[test]
+        fn append_comment_escapes_new_lines() {
+            let config = Configuration::empty().with_source_map_url("a\nb");
+            let mut code = String::new();
+
+            config.append_source_map_comment(&mut code, Path::new("out/init.lua"));
+
+            pretty_assertions::assert_eq!(code, "--# sourceMappingURL=a%0Ab\n");
+        }
+
+        #[test]
+        fn append_nothing_without_url() {
+            let config = Configuration::empty();
+            let mut code = "return nil".to_owned();
+
+            config.append_source_map_comment(&mut code, Path::new("out/main.lua"));
+
+            pretty_assertions::assert_eq!(code, "return nil");
+        }
+    }
+
+    mod preserve_header_comments {
+        use super::*;
+
+        #[test]
+        fn deserialize_preserve_header_comments() {
+            let config: Configuration =
+                json5::from_str("{ preserve_header_comments: ['^--!', 'Copyright'] }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.preserved_header_comments().collect::<Vec<_>>(),
+                vec!["^--!", "Copyright"]
+            );
+        }
+
+        #[test]
+        fn serialize_without_preserve_header_comments() {
+            let config = Configuration::empty();
+
+            assert!(!json5::to_string(&config)
+                .unwrap()
+                .contains("preserve_header_comments"));
+        }
+    }
+
     mod bundle_configuration {
         use crate::rules::require::PathRequireMode;
 
@@ -483,4 +1094,52 @@ mod test {
             );
         }
     }
+
+    mod validate {
+        use super::*;
+
+        #[test]
+        fn default_rules_have_no_warnings() {
+            let config = Configuration::default();
+
+            pretty_assertions::assert_eq!(config.validate(), Vec::new());
+        }
+
+        #[test]
+        fn empty_configuration_has_no_warnings() {
+            pretty_assertions::assert_eq!(Configuration::empty().validate(), Vec::new());
+        }
+
+        #[test]
+        fn warns_when_remove_empty_do_runs_before_remove_unused_if_branch() {
+            let config: Configuration =
+                json5::from_str("{ rules: ['remove_empty_do', 'remove_unused_if_branch'] }")
+                    .unwrap();
+
+            let warnings = config.validate();
+
+            pretty_assertions::assert_eq!(warnings.len(), 1);
+            pretty_assertions::assert_eq!(warnings[0].rule(), "remove_empty_do");
+            pretty_assertions::assert_eq!(warnings[0].run_after(), "remove_unused_if_branch");
+        }
+
+        #[test]
+        fn no_warning_when_rule_runs_again_after_its_dependency() {
+            let config: Configuration = json5::from_str(
+                "{ rules: ['remove_empty_do', 'remove_unused_if_branch', 'remove_empty_do'] }",
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(config.validate(), Vec::new());
+        }
+
+        #[test]
+        fn no_warning_when_rules_are_ordered() {
+            let config: Configuration =
+                json5::from_str("{ rules: ['remove_unused_if_branch', 'remove_empty_do'] }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(config.validate(), Vec::new());
+        }
+    }
 }
@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+/// Describes the content that would be written to a file, recorded when processing
+/// with [`Options::dry_run`](crate::Options::dry_run).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunEntry {
+    path: PathBuf,
+    original: Option<String>,
+    transformed: String,
+}
+
+impl DryRunEntry {
+    pub(crate) fn new(
+        path: impl Into<PathBuf>,
+        original: Option<String>,
+        transformed: impl Into<String>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            original,
+            transformed: transformed.into(),
+        }
+    }
+
+    /// The path where the file would be written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The current content of the file, or `None` if the file does not exist.
+    pub fn original(&self) -> Option<&str> {
+        self.original.as_deref()
+    }
+
+    /// The content that would be written to the file.
+    pub fn transformed(&self) -> &str {
+        &self.transformed
+    }
+
+    /// Returns `true` if writing the file would modify its content.
+    pub fn changed(&self) -> bool {
+        self.original.as_deref() != Some(self.transformed.as_str())
+    }
+}
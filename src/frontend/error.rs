@@ -4,10 +4,15 @@ use std::{
     collections::HashSet,
     ffi::{OsStr, OsString},
     fmt::{self, Display},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
-use crate::{process::LuaSerializerError, rules::Rule, ParserError};
+use crate::{
+    generator::{LuaSyntax, LuaVersion},
+    process::LuaSerializerError,
+    rules::{bundle::BUNDLER_RULE_NAME, Rule},
+    ParserError,
+};
 
 use super::{
     resources::ResourceError,
@@ -45,6 +50,21 @@ enum ErrorKind {
     CyclicWork {
         work: Vec<(WorkData, Vec<PathBuf>)>,
     },
+    CyclicRequire {
+        cycle: Vec<PathBuf>,
+    },
+    ConcatenationReturn {
+        path: PathBuf,
+    },
+    ConcatenationExportedType {
+        path: PathBuf,
+    },
+    ConcatenationWithoutOutput,
+    UnsupportedSyntax {
+        path: PathBuf,
+        syntax: LuaSyntax,
+        target: LuaVersion,
+    },
     Deserialization {
         message: String,
         data_type: &'static str,
@@ -68,6 +88,48 @@ enum ErrorKind {
     },
 }
 
+/// The category of a [`DarkluaError`], to group errors when reporting them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorCategory {
+    /// A file could not be parsed.
+    Parser,
+    /// A rule failed to process a file.
+    Rule,
+    /// The bundler failed to process a file.
+    Bundle,
+    /// A file could not be found, read or written.
+    IO,
+    /// The configuration could not be found or is invalid.
+    Configuration,
+    /// Any other error.
+    Other,
+}
+
+/// The location in a file associated with a [`DarkluaError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorLocation {
+    path: PathBuf,
+    line: usize,
+    column: usize,
+}
+
+impl ErrorLocation {
+    /// The path of the file where the error occurred.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The line of the error, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the error, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
 pub type DarkluaResult<T> = Result<T, DarkluaError>;
 
 #[derive(Debug, Clone)]
@@ -192,6 +254,47 @@ impl DarkluaError {
         })
     }
 
+    /// Creates an error for a cycle of `require` calls. The cycle starts and ends
+    /// with the same module path.
+    pub(crate) fn cyclic_require(cycle: Vec<PathBuf>) -> Self {
+        Self::new(ErrorKind::CyclicRequire { cycle })
+    }
+
+    pub(crate) fn serialization(data_type: &'static str, message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Serialization {
+            message: message.into(),
+            data_type,
+        })
+    }
+
+    /// Creates an error for a file that can not be concatenated with other files
+    /// because it returns from the file.
+    pub(crate) fn concatenation_return(path: impl Into<PathBuf>) -> Self {
+        Self::new(ErrorKind::ConcatenationReturn { path: path.into() })
+    }
+
+    /// Creates an error for a file that can not be concatenated with other files
+    /// because it exports types, which are not allowed inside `do` blocks.
+    pub(crate) fn concatenation_exported_type(path: impl Into<PathBuf>) -> Self {
+        Self::new(ErrorKind::ConcatenationExportedType { path: path.into() })
+    }
+
+    pub(crate) fn concatenation_without_output() -> Self {
+        Self::new(ErrorKind::ConcatenationWithoutOutput)
+    }
+
+    pub(crate) fn unsupported_syntax(
+        path: impl Into<PathBuf>,
+        syntax: LuaSyntax,
+        target: LuaVersion,
+    ) -> Self {
+        Self::new(ErrorKind::UnsupportedSyntax {
+            path: path.into(),
+            syntax,
+            target,
+        })
+    }
+
     pub(crate) fn invalid_resource_path(
         path: impl Into<String>,
         message: impl Into<String>,
@@ -219,6 +322,48 @@ impl DarkluaError {
             message: message.into(),
         })
     }
+
+    pub fn category(&self) -> ErrorCategory {
+        match &*self.kind {
+            ErrorKind::Parser { .. } => ErrorCategory::Parser,
+            ErrorKind::RuleError { rule_name, .. } if rule_name == BUNDLER_RULE_NAME => {
+                ErrorCategory::Bundle
+            }
+            ErrorKind::RuleError { .. } => ErrorCategory::Rule,
+            ErrorKind::CyclicRequire { .. }
+            | ErrorKind::ConcatenationReturn { .. }
+            | ErrorKind::ConcatenationExportedType { .. } => ErrorCategory::Bundle,
+            ErrorKind::ResourceNotFound { .. }
+            | ErrorKind::IO { .. }
+            | ErrorKind::InvalidResourcePath { .. }
+            | ErrorKind::InvalidResourceExtension { .. }
+            | ErrorKind::OsStringConversion { .. } => ErrorCategory::IO,
+            ErrorKind::InvalidConfiguration { .. }
+            | ErrorKind::MultipleConfigurationFound { .. }
+            | ErrorKind::ConcatenationWithoutOutput
+            | ErrorKind::Deserialization { .. } => ErrorCategory::Configuration,
+            ErrorKind::UncachedWork { .. }
+            | ErrorKind::CyclicWork { .. }
+            | ErrorKind::Serialization { .. }
+            | ErrorKind::UnsupportedSyntax { .. }
+            | ErrorKind::Custom { .. } => ErrorCategory::Other,
+        }
+    }
+
+    /// Returns the location of the error in a file, when it is known. Only parsing
+    /// errors currently provide a location.
+    pub fn location(&self) -> Option<ErrorLocation> {
+        match &*self.kind {
+            ErrorKind::Parser { path, error } => {
+                error.position().map(|(line, column)| ErrorLocation {
+                    path: path.clone(),
+                    line,
+                    column,
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<ResourceError> for DarkluaError {
@@ -374,6 +519,44 @@ impl Display for DarkluaError {
                     }
                 )?;
             }
+            ErrorKind::CyclicRequire { cycle } => {
+                let paths: Vec<_> = cycle
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+
+                write!(f, "cyclic require detected with `{}`", paths.join("` > `"))?;
+            }
+            ErrorKind::ConcatenationReturn { path } => {
+                write!(
+                    f,
+                    "unable to concatenate `{}`: the file contains a `return` statement outside of a function",
+                    path.display()
+                )?;
+            }
+            ErrorKind::ConcatenationExportedType { path } => {
+                write!(
+                    f,
+                    "unable to concatenate `{}`: the file exports types, which can not be declared inside a `do` block",
+                    path.display()
+                )?;
+            }
+            ErrorKind::ConcatenationWithoutOutput => {
+                write!(f, "an output file is required to concatenate files")?;
+            }
+            ErrorKind::UnsupportedSyntax {
+                path,
+                syntax,
+                target,
+            } => {
+                write!(
+                    f,
+                    "unable to generate code for `{}`: {} are not supported by {}",
+                    path.display(),
+                    syntax,
+                    target
+                )?;
+            }
             ErrorKind::Deserialization { message, data_type } => {
                 write!(f, "unable to read {} data: {}", data_type, message)?;
             }
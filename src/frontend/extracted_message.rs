@@ -0,0 +1,36 @@
+use std::path::{Path, PathBuf};
+
+/// Describes a message found by the `extract_messages` rule in a processed file. The
+/// entries can be obtained with
+/// [`WorkerTree::extracted_messages`](crate::WorkerTree::extracted_messages).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedMessageEntry {
+    path: PathBuf,
+    function: String,
+    message: String,
+}
+
+impl ExtractedMessageEntry {
+    pub(crate) fn new(path: impl Into<PathBuf>, function: String, message: String) -> Self {
+        Self {
+            path: path.into(),
+            function,
+            message,
+        }
+    }
+
+    /// The path of the processed file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The function that received the message (like `error` or `assert`).
+    pub fn function(&self) -> &str {
+        &self.function
+    }
+
+    /// The content of the message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// The version of the manifest format. It is incremented when a change to the
+/// format could break existing readers.
+const MANIFEST_VERSION: u32 = 1;
+
+/// Describes a file written during processing, recorded when a manifest is requested
+/// with [`Options::with_manifest`](crate::Options::with_manifest).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ManifestEntry {
+    input: PathBuf,
+    output: PathBuf,
+    changed: bool,
+    size: usize,
+}
+
+impl ManifestEntry {
+    pub(crate) fn new(
+        input: impl Into<PathBuf>,
+        output: impl Into<PathBuf>,
+        changed: bool,
+        size: usize,
+    ) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+            changed,
+            size,
+        }
+    }
+
+    /// The path of the processed file.
+    pub fn input(&self) -> &Path {
+        &self.input
+    }
+
+    /// The path where the file was written.
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    /// Returns `true` if the written content is different from the input content.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// The size of the written content, in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Manifest<'a> {
+    version: u32,
+    files: &'a [&'a ManifestEntry],
+}
+
+pub(crate) fn serialize_manifest(entries: &[&ManifestEntry]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&Manifest {
+        version: MANIFEST_VERSION,
+        files: entries,
+    })
+}
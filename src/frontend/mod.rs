@@ -1,7 +1,15 @@
 mod configuration;
+mod dry_run;
 mod error;
+mod extracted_message;
+mod manifest;
 mod options;
+mod processing_report;
+mod requires;
 mod resources;
+mod rule_ordering;
+mod statistics;
+mod timing_report;
 mod utils;
 mod work_cache;
 mod work_item;
@@ -9,10 +17,18 @@ mod worker;
 mod worker_tree;
 
 pub use configuration::{BundleConfiguration, Configuration, GeneratorParameters};
-pub use error::{DarkluaError, DarkluaResult};
+pub use dry_run::DryRunEntry;
+pub use error::{DarkluaError, DarkluaResult, ErrorCategory, ErrorLocation};
+pub use extracted_message::ExtractedMessageEntry;
+pub use manifest::ManifestEntry;
 pub use options::Options;
+pub use processing_report::ProcessingReportEntry;
+pub use requires::collect_requires;
 pub use resources::Resources;
+pub use rule_ordering::RuleOrderingWarning;
 use serde::Serialize;
+pub use statistics::FileStatistics;
+pub use timing_report::{RuleTiming, RuleTimingEntry};
 use work_item::WorkItem;
 use worker::Worker;
 pub use worker_tree::WorkerTree;
@@ -1,15 +1,60 @@
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 use super::configuration::{Configuration, GeneratorParameters};
 
+type OutputTransformFn = dyn FnMut(&str, &Path) -> String;
+
+pub(crate) struct OutputTransform(Box<OutputTransformFn>);
+
+impl OutputTransform {
+    pub(crate) fn apply(&mut self, code: &str, output: &Path) -> String {
+        (self.0)(code, output)
+    }
+}
+
+impl fmt::Debug for OutputTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OutputTransform").finish()
+    }
+}
+
+type FileFilterFn = dyn Fn(&Path, &str) -> bool;
+
+pub(crate) struct FileFilter(Box<FileFilterFn>);
+
+impl FileFilter {
+    pub(crate) fn should_process(&self, path: &Path, content: &str) -> bool {
+        (self.0)(path, content)
+    }
+}
+
+impl fmt::Debug for FileFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FileFilter").finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct Options {
     input: PathBuf,
     config_path: Option<PathBuf>,
     config: Option<Configuration>,
     config_generator_override: Option<GeneratorParameters>,
+    generator_overrides: Vec<(String, GeneratorParameters)>,
     output: Option<PathBuf>,
     fail_fast: bool,
+    processing_report: bool,
+    timing: bool,
+    stats: bool,
+    dry_run: bool,
+    manifest: Option<PathBuf>,
+    output_transform: Option<OutputTransform>,
+    file_filter: Option<FileFilter>,
+    copy_filtered_files: bool,
+    byte_order_mark: bool,
+    final_new_line: Option<bool>,
+    concatenate: bool,
 }
 
 impl Options {
@@ -20,7 +65,19 @@ impl Options {
             config: None,
             output: None,
             fail_fast: false,
+            processing_report: false,
+            timing: false,
+            stats: false,
+            dry_run: false,
+            manifest: None,
+            output_transform: None,
+            file_filter: None,
+            copy_filtered_files: false,
+            byte_order_mark: false,
+            final_new_line: None,
+            concatenate: false,
             config_generator_override: None,
+            generator_overrides: Vec::new(),
         }
     }
 
@@ -44,11 +101,136 @@ impl Options {
         self
     }
 
+    /// Records which rules were applied on each file and if they modified it. The
+    /// report can be obtained with [`WorkerTree::processing_report`](crate::WorkerTree::processing_report).
+    pub fn with_processing_report(mut self) -> Self {
+        self.processing_report = true;
+        self
+    }
+
+    /// Measures the time spent applying each rule on each file. The measures can be
+    /// obtained with [`WorkerTree::timing_report`](crate::WorkerTree::timing_report),
+    /// which lists the rules sorted by their total time.
+    pub fn with_timing(mut self) -> Self {
+        self.timing = true;
+        self
+    }
+
+    /// Records the size of each file before and after processing, and the number of
+    /// statements and expressions of the generated code. The statistics can be obtained
+    /// with [`WorkerTree::file_statistics`](crate::WorkerTree::file_statistics).
+    pub fn with_stats(mut self) -> Self {
+        self.stats = true;
+        self
+    }
+
+    /// Processes the files without writing them. The content that would be written
+    /// to each file can be obtained with [`WorkerTree::dry_run_entries`](crate::WorkerTree::dry_run_entries).
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Writes a JSON manifest at the given path after processing, listing each written
+    /// file. The manifest is not written when doing a [dry run](Options::dry_run).
+    ///
+    /// The manifest has the following format, where files are sorted by input path:
+    ///
+    /// ```json
+    /// {
+    ///   "version": 1,
+    ///   "files": [
+    ///     { "input": "src/a.lua", "output": "out/a.lua", "changed": true, "size": 128 }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// A file is `changed` when the written content is different from its input, and
+    /// `size` is the length of the written content in bytes. The entries can also be
+    /// obtained with [`WorkerTree::manifest_entries`](crate::WorkerTree::manifest_entries).
+    pub fn with_manifest(mut self, path: impl Into<PathBuf>) -> Self {
+        self.manifest = Some(path.into());
+        self
+    }
+
+    /// Applies a function on the generated code of each file before it is written. The
+    /// function receives the generated code and the path where it will be written, and
+    /// returns the code to write.
+    ///
+    /// Note that this function operates on the generated text and not on the syntax tree.
+    /// It runs after every rule and after the code generation, so it can be used to add
+    /// a header or to apply a custom text replacement.
+    pub fn with_output_transform(
+        mut self,
+        transform: impl FnMut(&str, &Path) -> String + 'static,
+    ) -> Self {
+        self.output_transform = Some(OutputTransform(Box::new(transform)));
+        self
+    }
+
+    /// Only processes the files accepted by the given function. The function receives
+    /// the path of each file and its content, and returns false to skip the file. Skipped
+    /// files are not written, unless [`copy_filtered_files`](Options::copy_filtered_files)
+    /// is enabled.
+    pub fn with_file_filter(mut self, filter: impl Fn(&Path, &str) -> bool + 'static) -> Self {
+        self.file_filter = Some(FileFilter(Box::new(filter)));
+        self
+    }
+
+    /// Writes the files skipped by the [file filter](Options::with_file_filter) to their
+    /// output without any change.
+    pub fn copy_filtered_files(mut self) -> Self {
+        self.copy_filtered_files = true;
+        self
+    }
+
+    /// Writes the UTF-8 byte order mark at the beginning of each generated file.
+    pub fn with_byte_order_mark(mut self) -> Self {
+        self.byte_order_mark = true;
+        self
+    }
+
+    /// Controls the end of each generated file: when true, the file ends with exactly one
+    /// new line, and when false, the file does not end with a new line. By default, the
+    /// generated code is written as is.
+    pub fn with_final_new_line(mut self, final_new_line: bool) -> Self {
+        self.final_new_line = Some(final_new_line);
+        self
+    }
+
+    /// Writes all the processed files into a single file at the [output](Options::with_output)
+    /// path, instead of writing each file. The files are sorted by path and each one is
+    /// wrapped in a `do ... end` block, so that its local variables do not leak into the
+    /// next files. Requires are not resolved: use the bundle configuration for that.
+    ///
+    /// A file that contains a `return` statement outside of a function can not be
+    /// concatenated, since it would stop the execution of the files that follow it. A
+    /// file that exports types can not be concatenated either, because exported types
+    /// can not be declared inside a `do` block.
+    pub fn concatenate(mut self) -> Self {
+        self.concatenate = true;
+        self
+    }
+
     pub fn with_generator_override(mut self, generator: impl Into<GeneratorParameters>) -> Self {
         self.config_generator_override = Some(generator.into());
         self
     }
 
+    /// Uses a different generator for the files where the output path matches the
+    /// given glob pattern. When multiple patterns match a file, the first one added
+    /// is used. Files that do not match any pattern use the generator from the
+    /// configuration.
+    pub fn with_generator_override_for(
+        mut self,
+        pattern: impl Into<String>,
+        generator: impl Into<GeneratorParameters>,
+    ) -> Self {
+        self.generator_overrides
+            .push((pattern.into(), generator.into()));
+        self
+    }
+
     pub fn input(&self) -> &Path {
         &self.input
     }
@@ -61,6 +243,42 @@ impl Options {
         self.fail_fast
     }
 
+    pub fn should_record_processing_report(&self) -> bool {
+        self.processing_report
+    }
+
+    pub fn should_record_timing(&self) -> bool {
+        self.timing
+    }
+
+    pub fn should_record_stats(&self) -> bool {
+        self.stats
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn manifest_path(&self) -> Option<&Path> {
+        self.manifest.as_ref().map(AsRef::as_ref)
+    }
+
+    pub fn should_copy_filtered_files(&self) -> bool {
+        self.copy_filtered_files
+    }
+
+    pub fn should_write_byte_order_mark(&self) -> bool {
+        self.byte_order_mark
+    }
+
+    pub fn final_new_line(&self) -> Option<bool> {
+        self.final_new_line
+    }
+
+    pub fn should_concatenate(&self) -> bool {
+        self.concatenate
+    }
+
     pub fn configuration_path(&self) -> Option<&Path> {
         self.config_path.as_ref().map(AsRef::as_ref)
     }
@@ -69,7 +287,21 @@ impl Options {
         self.config_generator_override.as_ref()
     }
 
+    pub fn generator_overrides(&self) -> impl Iterator<Item = (&str, &GeneratorParameters)> {
+        self.generator_overrides
+            .iter()
+            .map(|(pattern, generator)| (pattern.as_str(), generator))
+    }
+
     pub fn take_configuration(&mut self) -> Option<Configuration> {
         self.config.take()
     }
+
+    pub(crate) fn take_output_transform(&mut self) -> Option<OutputTransform> {
+        self.output_transform.take()
+    }
+
+    pub(crate) fn take_file_filter(&mut self) -> Option<FileFilter> {
+        self.file_filter.take()
+    }
 }
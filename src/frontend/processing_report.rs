@@ -0,0 +1,35 @@
+use std::path::{Path, PathBuf};
+
+/// Describes the application of a rule on a file, recorded when processing
+/// with [`Options::with_processing_report`](crate::Options::with_processing_report).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessingReportEntry {
+    path: PathBuf,
+    rule_name: &'static str,
+    changed: bool,
+}
+
+impl ProcessingReportEntry {
+    pub(crate) fn new(path: impl Into<PathBuf>, rule_name: &'static str, changed: bool) -> Self {
+        Self {
+            path: path.into(),
+            rule_name,
+            changed,
+        }
+    }
+
+    /// The path of the processed file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The name of the applied rule.
+    pub fn rule_name(&self) -> &'static str {
+        self.rule_name
+    }
+
+    /// Returns `true` if the rule has modified the file.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+}
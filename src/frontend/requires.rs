@@ -0,0 +1,108 @@
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    nodes::FunctionCall,
+    process::{IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor},
+    rules::{
+        bundle::BundleRequireMode,
+        require::{is_require_call, PathRequireMode},
+        Context, ContextBuilder,
+    },
+    utils::normalize_path,
+    Parser,
+};
+
+use super::{BundleConfiguration, DarkluaError, DarkluaResult, Resources};
+
+struct CollectPathRequires<'a, 'b, 'resources, 'code> {
+    identifier_tracker: IdentifierTracker,
+    require_mode: &'a PathRequireMode,
+    context: &'a Context<'b, 'resources, 'code>,
+    requires: Vec<PathBuf>,
+    errors: Vec<DarkluaError>,
+}
+
+impl<'a, 'b, 'resources, 'code> CollectPathRequires<'a, 'b, 'resources, 'code> {
+    fn new(require_mode: &'a PathRequireMode, context: &'a Context<'b, 'resources, 'code>) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            require_mode,
+            context,
+            requires: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn into_requires(mut self) -> DarkluaResult<Vec<PathBuf>> {
+        if self.errors.is_empty() {
+            Ok(self.requires)
+        } else {
+            Err(self.errors.remove(0))
+        }
+    }
+}
+
+impl Deref for CollectPathRequires<'_, '_, '_, '_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for CollectPathRequires<'_, '_, '_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for CollectPathRequires<'_, '_, '_, '_> {
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        if !is_require_call(call, self) {
+            return;
+        }
+
+        match self.require_mode.find_require(call, self.context) {
+            Ok(Some(path)) => {
+                if !self.requires.contains(&path) {
+                    self.requires.push(path);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => self.errors.push(err),
+        }
+    }
+}
+
+/// Parses a file and returns the paths of the modules it requires directly, in the order
+/// in which they first appear. The paths are resolved with the require mode of the given
+/// bundle configuration, but the file is not bundled. Source aliases are resolved
+/// relative to the directory of the file.
+///
+/// An error is returned if the file can not be read or parsed, or if a required module
+/// can not be found.
+pub fn collect_requires(
+    resources: &Resources,
+    path: &Path,
+    config: &BundleConfiguration,
+) -> Result<Vec<PathBuf>, DarkluaError> {
+    let content = resources.get(path)?;
+
+    let mut block = Parser::default()
+        .parse(&content)
+        .map_err(|parser_error| DarkluaError::parser_error(path, parser_error))?;
+
+    let context = ContextBuilder::new(normalize_path(path), resources, &content).build();
+
+    match config.require_mode() {
+        BundleRequireMode::Path(path_require_mode) => {
+            let mut require_mode = path_require_mode.clone();
+            require_mode.initialize(&context)?;
+
+            let mut processor = CollectPathRequires::new(&require_mode, &context);
+            ScopeVisitor::visit_block(&mut block, &mut processor);
+            processor.into_requires()
+        }
+    }
+}
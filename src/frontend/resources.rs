@@ -203,6 +203,56 @@ fn walk_file_system(location: PathBuf) -> impl Iterator<Item = PathBuf> {
     })
 }
 
+#[cfg(feature = "zip")]
+fn read_zip_archive(archive: &[u8]) -> ResourceResult<HashMap<PathBuf, String>> {
+    use std::io::{Cursor, Read};
+
+    let archive_path = Path::new("<zip archive>");
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive))
+        .map_err(|err| ResourceError::io_error(archive_path, err.into()))?;
+
+    let mut data = HashMap::new();
+
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|err| ResourceError::io_error(archive_path, err.into()))?;
+
+        if file.is_dir() {
+            continue;
+        }
+
+        let path = match file.enclosed_name() {
+            Some(path) => normalize_path(path),
+            None => {
+                log::warn!(
+                    "skipping zip archive entry `{}` because its path is not valid",
+                    file.name()
+                );
+                continue;
+            }
+        };
+
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)
+            .map_err(|err| ResourceError::io_error(&path, err))?;
+
+        match String::from_utf8(content) {
+            Ok(content) => {
+                data.insert(path, content);
+            }
+            Err(_) => {
+                log::warn!(
+                    "skipping zip archive entry `{}` because it is not a valid UTF-8 file",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    Ok(data)
+}
+
 #[derive(Debug, Clone)]
 pub struct Resources {
     source: Source,
@@ -221,6 +271,18 @@ impl Resources {
         }
     }
 
+    /// Creates resources from the files of a zip archive. The files are loaded in
+    /// memory when the resources are created, so writing or removing files does not
+    /// modify the archive. Entries that are not valid UTF-8 files are skipped.
+    #[cfg(feature = "zip")]
+    pub fn from_zip_archive(archive: impl AsRef<[u8]>) -> ResourceResult<Self> {
+        let data = read_zip_archive(archive.as_ref())?;
+
+        Ok(Self {
+            source: Source::Memory(Arc::new(Mutex::new(data))),
+        })
+    }
+
     pub fn collect_work(&self, location: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
         self.source.walk(location.as_ref()).filter(|path| {
             matches!(
@@ -357,4 +419,88 @@ mod test {
             );
         }
     }
+
+    #[cfg(feature = "zip")]
+    mod zip_archive {
+        use std::io::{Cursor, Write};
+        use std::iter::FromIterator;
+
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        use super::*;
+
+        fn create_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+            let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+            for (path, content) in files {
+                writer
+                    .start_file(*path, SimpleFileOptions::default())
+                    .unwrap();
+                writer.write_all(content).unwrap();
+            }
+
+            writer.finish().unwrap().into_inner()
+        }
+
+        #[test]
+        fn read_content_of_archive_file() {
+            let resources =
+                Resources::from_zip_archive(create_archive(&[("test.lua", b"return true")]))
+                    .unwrap();
+
+            assert_eq!(resources.get(any_path()), Ok(ANY_CONTENT.to_string()));
+        }
+
+        #[test]
+        fn archive_directory_is_a_directory() {
+            let resources =
+                Resources::from_zip_archive(create_archive(&[("src/test.lua", b"return true")]))
+                    .unwrap();
+
+            assert_eq!(resources.is_directory("src"), Ok(true));
+            assert_eq!(resources.is_file("src/test.lua"), Ok(true));
+        }
+
+        #[test]
+        fn write_does_not_remove_archive_files() {
+            let resources =
+                Resources::from_zip_archive(create_archive(&[("test.lua", b"return true")]))
+                    .unwrap();
+
+            resources.write("out.lua", "return false").unwrap();
+
+            assert_eq!(resources.get(any_path()), Ok(ANY_CONTENT.to_string()));
+            assert_eq!(resources.get("out.lua"), Ok("return false".to_string()));
+        }
+
+        #[test]
+        fn skip_files_that_are_not_utf8() {
+            let resources = Resources::from_zip_archive(create_archive(&[
+                ("src/test.lua", b"return true"),
+                ("src/image.png", &[0xFF, 0xFE, 0x00]),
+            ]))
+            .unwrap();
+
+            assert_eq!(resources.exists("src/image.png"), Ok(false));
+        }
+
+        #[test]
+        fn collect_work_contains_archive_files() {
+            let resources = Resources::from_zip_archive(create_archive(&[
+                ("src/test.lua", b"return true"),
+                ("src/README.md", b"# readme"),
+            ]))
+            .unwrap();
+
+            assert_eq!(
+                Vec::from_iter(resources.collect_work("src")),
+                vec![PathBuf::from("src/test.lua")]
+            );
+        }
+
+        #[test]
+        fn invalid_archive_errors() {
+            assert!(Resources::from_zip_archive(b"not an archive").is_err());
+        }
+    }
 }
@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::rules::{
+    Rule, COMPUTE_EXPRESSIONS_RULE_NAME, CONVERT_INDEX_TO_FIELD_RULE_NAME,
+    FILTER_AFTER_EARLY_RETURN_RULE_NAME, REMOVE_EMPTY_DO_RULE_NAME,
+    REMOVE_UNUSED_IF_BRANCH_RULE_NAME, REMOVE_UNUSED_VARIABLE_RULE_NAME,
+    REMOVE_UNUSED_WHILE_RULE_NAME,
+};
+
+/// Each entry describes a rule (the first name) that is more effective when it runs
+/// after another rule (the second name), with the reason why.
+const RULE_DEPENDENCIES: &[(&str, &str, &str)] = &[
+    (
+        REMOVE_UNUSED_IF_BRANCH_RULE_NAME,
+        COMPUTE_EXPRESSIONS_RULE_NAME,
+        "conditions can only be evaluated once their expressions are computed",
+    ),
+    (
+        REMOVE_UNUSED_WHILE_RULE_NAME,
+        COMPUTE_EXPRESSIONS_RULE_NAME,
+        "conditions can only be evaluated once their expressions are computed",
+    ),
+    (
+        REMOVE_EMPTY_DO_RULE_NAME,
+        REMOVE_UNUSED_IF_BRANCH_RULE_NAME,
+        "removing if branches can leave empty do statements",
+    ),
+    (
+        REMOVE_UNUSED_VARIABLE_RULE_NAME,
+        REMOVE_UNUSED_IF_BRANCH_RULE_NAME,
+        "removed branches may contain the only references to a variable",
+    ),
+    (
+        REMOVE_UNUSED_VARIABLE_RULE_NAME,
+        FILTER_AFTER_EARLY_RETURN_RULE_NAME,
+        "unreachable statements may contain the only references to a variable",
+    ),
+    (
+        CONVERT_INDEX_TO_FIELD_RULE_NAME,
+        COMPUTE_EXPRESSIONS_RULE_NAME,
+        "computed strings can then be converted to fields",
+    ),
+];
+
+/// An advisory warning produced by [`Configuration::validate`](crate::Configuration::validate)
+/// when a rule runs before another rule that would make it more effective.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleOrderingWarning {
+    rule: &'static str,
+    run_after: &'static str,
+    reason: &'static str,
+}
+
+impl RuleOrderingWarning {
+    /// The name of the rule that should be moved.
+    pub fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    /// The name of the rule that should run before [`rule`](RuleOrderingWarning::rule).
+    pub fn run_after(&self) -> &'static str {
+        self.run_after
+    }
+
+    /// Why the rule is more effective after the other rule.
+    pub fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl fmt::Display for RuleOrderingWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule `{}` is more effective after rule `{}` ({})",
+            self.rule, self.run_after, self.reason
+        )
+    }
+}
+
+pub(crate) fn validate_rule_ordering<'a>(
+    rules: impl Iterator<Item = &'a dyn Rule>,
+) -> Vec<RuleOrderingWarning> {
+    let names: Vec<_> = rules.map(|rule| rule.get_name()).collect();
+
+    let last_position = |name: &str| names.iter().rposition(|rule_name| *rule_name == name);
+
+    RULE_DEPENDENCIES
+        .iter()
+        .filter(|&&(rule, run_after, _)| {
+            // a rule is only ineffective if it never runs after the last run of
+            // the rule it depends on
+            matches!(
+                (last_position(rule), last_position(run_after)),
+                (Some(rule_position), Some(run_after_position))
+                    if rule_position < run_after_position
+            )
+        })
+        .map(|&(rule, run_after, reason)| RuleOrderingWarning {
+            rule,
+            run_after,
+            reason,
+        })
+        .collect()
+}
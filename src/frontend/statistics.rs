@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+
+use crate::nodes::Block;
+use crate::process::{DefaultVisitor, NodeCounter, NodeVisitor};
+
+/// Describes the code generated for a file, recorded when processing with
+/// [`Options::with_stats`](crate::Options::with_stats).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStatistics {
+    path: PathBuf,
+    input_size: usize,
+    output_size: usize,
+    statement_count: usize,
+    expression_count: usize,
+}
+
+impl FileStatistics {
+    pub(crate) fn new(
+        path: impl Into<PathBuf>,
+        input_size: usize,
+        output_size: usize,
+        block: &mut Block,
+    ) -> Self {
+        let mut counter = NodeCounter::new();
+        DefaultVisitor::visit_block(block, &mut counter);
+
+        Self {
+            path: path.into(),
+            input_size,
+            output_size,
+            statement_count: counter.statement_count,
+            expression_count: counter.expression_count,
+        }
+    }
+
+    /// The path of the processed file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The size of the original content of the file, in bytes.
+    pub fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    /// The size of the generated code, in bytes.
+    pub fn output_size(&self) -> usize {
+        self.output_size
+    }
+
+    /// The number of statements of the generated code, including nested statements and
+    /// last statements (like `return` or `break`).
+    pub fn statement_count(&self) -> usize {
+        self.statement_count
+    }
+
+    /// The number of expressions of the generated code, including nested expressions.
+    pub fn expression_count(&self) -> usize {
+        self.expression_count
+    }
+}
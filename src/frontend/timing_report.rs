@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Describes the time spent applying a rule on a file, recorded when processing
+/// with [`Options::with_timing`](crate::Options::with_timing).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTimingEntry {
+    path: PathBuf,
+    rule_name: &'static str,
+    duration: Duration,
+}
+
+impl RuleTimingEntry {
+    pub(crate) fn new(
+        path: impl Into<PathBuf>,
+        rule_name: &'static str,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            rule_name,
+            duration,
+        }
+    }
+
+    /// The path of the processed file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The name of the applied rule.
+    pub fn rule_name(&self) -> &'static str {
+        self.rule_name
+    }
+
+    /// The wall-clock time spent applying the rule on the file.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// Describes the total time spent applying a rule on every processed file. The
+/// report can be obtained with [`WorkerTree::timing_report`](crate::WorkerTree::timing_report).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTiming {
+    rule_name: &'static str,
+    total_duration: Duration,
+    file_count: usize,
+}
+
+impl RuleTiming {
+    pub(crate) fn new(rule_name: &'static str) -> Self {
+        Self {
+            rule_name,
+            total_duration: Duration::ZERO,
+            file_count: 0,
+        }
+    }
+
+    pub(crate) fn add_entry(&mut self, entry: &RuleTimingEntry) {
+        self.total_duration += entry.duration();
+        self.file_count += 1;
+    }
+
+    /// The name of the rule.
+    pub fn rule_name(&self) -> &'static str {
+        self.rule_name
+    }
+
+    /// The wall-clock time spent applying the rule on all files.
+    pub fn total_duration(&self) -> Duration {
+        self.total_duration
+    }
+
+    /// The number of times the rule was applied on a file.
+    pub fn file_count(&self) -> usize {
+        self.file_count
+    }
+}
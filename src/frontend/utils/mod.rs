@@ -1,3 +1,9 @@
+use regex::Regex;
+
+use crate::parser::read_header_comments;
+
+const BYTE_ORDER_MARK: char = '\u{FEFF}';
+
 pub(crate) fn maybe_plural(count: usize) -> &'static str {
     if count > 1 {
         "s"
@@ -6,6 +12,47 @@ pub(crate) fn maybe_plural(count: usize) -> &'static str {
     }
 }
 
+/// Adds the header comments of the original code that match one of the patterns
+/// at the top of the generated code, unless the generated code already starts
+/// with them.
+pub(crate) fn prepend_header_comments(code: &mut String, original_code: &str, patterns: &[Regex]) {
+    if patterns.is_empty() {
+        return;
+    }
+
+    let existing_comments = read_header_comments(code);
+
+    let header: String = read_header_comments(original_code)
+        .into_iter()
+        .filter(|comment| {
+            patterns.iter().any(|pattern| pattern.is_match(comment))
+                && !existing_comments.contains(comment)
+        })
+        .map(|comment| comment + "\n")
+        .collect();
+
+    code.insert_str(0, &header);
+}
+
+/// Adds the UTF-8 byte order mark at the beginning of the code, unless it is
+/// already present.
+pub(crate) fn prepend_byte_order_mark(code: &mut String) {
+    if !code.starts_with(BYTE_ORDER_MARK) {
+        code.insert(0, BYTE_ORDER_MARK);
+    }
+}
+
+/// Removes the new lines at the end of the code, and adds a single one when
+/// `final_new_line` is true.
+pub(crate) fn set_final_new_line(code: &mut String, final_new_line: bool) {
+    let length = code.trim_end_matches(['\n', '\r']).len();
+    code.truncate(length);
+
+    if final_new_line {
+        code.push('\n');
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -24,4 +71,100 @@ mod test {
     fn maybe_plural_gives_s_when_size_is_zero() {
         assert_eq!(maybe_plural(0), "");
     }
+
+    fn prepend(code: &str, original_code: &str, patterns: &[&str]) -> String {
+        let patterns: Vec<_> = patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern).unwrap())
+            .collect();
+        let mut code = code.to_owned();
+        prepend_header_comments(&mut code, original_code, &patterns);
+        code
+    }
+
+    #[test]
+    fn prepend_header_comments_without_patterns() {
+        assert_eq!(
+            prepend("return nil", "-- header\nreturn nil", &[]),
+            "return nil"
+        );
+    }
+
+    #[test]
+    fn prepend_matching_header_comments() {
+        assert_eq!(
+            prepend(
+                "return nil",
+                "--[[ Copyright 2024 ]]\n-- other\n--!strict\nreturn nil",
+                &["Copyright", "^--!"]
+            ),
+            "--[[ Copyright 2024 ]]\n--!strict\nreturn nil"
+        );
+    }
+
+    #[test]
+    fn prepend_header_comments_already_generated() {
+        assert_eq!(
+            prepend(
+                "-- Copyright\nreturn nil",
+                "-- Copyright\nreturn nil",
+                &["Copyright"]
+            ),
+            "-- Copyright\nreturn nil"
+        );
+    }
+
+    #[test]
+    fn prepend_header_comments_ignores_comments_after_code() {
+        assert_eq!(
+            prepend("return nil", "return nil -- Copyright", &["Copyright"]),
+            "return nil"
+        );
+    }
+
+    #[test]
+    fn prepend_byte_order_mark_to_code() {
+        let mut code = "return nil".to_owned();
+        prepend_byte_order_mark(&mut code);
+        assert_eq!(code.as_bytes()[..3], [0xEF, 0xBB, 0xBF]);
+        assert_eq!(&code[3..], "return nil");
+    }
+
+    #[test]
+    fn prepend_byte_order_mark_when_already_present() {
+        let mut code = "\u{FEFF}return nil".to_owned();
+        prepend_byte_order_mark(&mut code);
+        assert_eq!(code, "\u{FEFF}return nil");
+    }
+
+    fn final_new_line(code: &str, final_new_line: bool) -> String {
+        let mut code = code.to_owned();
+        set_final_new_line(&mut code, final_new_line);
+        code
+    }
+
+    #[test]
+    fn add_final_new_line() {
+        assert_eq!(final_new_line("return nil", true), "return nil\n");
+    }
+
+    #[test]
+    fn keep_single_final_new_line() {
+        assert_eq!(final_new_line("return nil\n", true), "return nil\n");
+    }
+
+    #[test]
+    fn replace_multiple_final_new_lines_with_one() {
+        assert_eq!(final_new_line("return nil\r\n\n\n", true), "return nil\n");
+    }
+
+    #[test]
+    fn remove_final_new_lines() {
+        assert_eq!(final_new_line("return nil\r\n\n", false), "return nil");
+    }
+
+    #[test]
+    fn remove_final_new_line_without_new_line() {
+        assert_eq!(final_new_line("return nil", false), "return nil");
+    }
 }
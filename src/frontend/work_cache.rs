@@ -14,6 +14,7 @@ pub struct WorkCache<'a> {
     resources: &'a Resources,
     input_to_block: FrozenMap<PathBuf, Box<Block>>,
     input_to_output: HashMap<PathBuf, PathBuf>,
+    input_to_code: HashMap<PathBuf, String>,
 }
 
 impl Clone for WorkCache<'_> {
@@ -22,6 +23,7 @@ impl Clone for WorkCache<'_> {
             resources: self.resources,
             input_to_block: Default::default(),
             input_to_output: self.input_to_output.clone(),
+            input_to_code: self.input_to_code.clone(),
         }
     }
 }
@@ -31,6 +33,7 @@ impl fmt::Debug for WorkCache<'_> {
         f.debug_struct("WorkCache")
             .field("resources", &self.resources)
             .field("input_to_output", &self.input_to_output)
+            .field("input_to_code", &self.input_to_code)
             .finish_non_exhaustive()
     }
 }
@@ -41,6 +44,7 @@ impl<'a> WorkCache<'a> {
             resources,
             input_to_block: Default::default(),
             input_to_output: Default::default(),
+            input_to_code: Default::default(),
         }
     }
 
@@ -49,11 +53,21 @@ impl<'a> WorkCache<'a> {
         source: impl Into<PathBuf>,
         output: impl Into<PathBuf>,
     ) {
-        self.input_to_output.insert(source.into(), output.into());
+        let source = source.into();
+        self.input_to_code.remove(&source);
+        self.input_to_output.insert(source, output.into());
+    }
+
+    /// Links a source to generated code that was not written (when doing a dry run).
+    pub fn link_source_to_code(&mut self, source: impl Into<PathBuf>, code: String) {
+        let source = source.into();
+        self.input_to_output.remove(&source);
+        self.input_to_code.insert(source, code);
     }
 
     pub fn contains(&self, source: impl AsRef<Path>) -> bool {
-        self.input_to_output.contains_key(source.as_ref())
+        let source = source.as_ref();
+        self.input_to_output.contains_key(source) || self.input_to_code.contains_key(source)
     }
 
     pub fn get_block(&self, source: impl AsRef<Path>, parser: &Parser) -> DarkluaResult<&Block> {
@@ -71,7 +85,12 @@ impl<'a> WorkCache<'a> {
     }
 
     fn read_block(&self, source: &Path, parser: &Parser) -> DarkluaResult<Block> {
-        if let Some(output_path) = self.input_to_output.get(source) {
+        if let Some(code) = self.input_to_code.get(source) {
+            parser.parse(code).map_err(|parser_error| {
+                DarkluaError::parser_error(source, parser_error)
+                    .context("parsing an already generated file")
+            })
+        } else if let Some(output_path) = self.input_to_output.get(source) {
             let content = self.resources.get(output_path)?;
             parser.parse(&content).map_err(|parser_error| {
                 DarkluaError::parser_error(output_path, parser_error)
@@ -5,7 +5,10 @@ use std::{
 
 use crate::{nodes::Block, utils::Timer};
 
-use super::{DarkluaError, DarkluaResult};
+use super::{
+    DarkluaError, DarkluaResult, DryRunEntry, ExtractedMessageEntry, FileStatistics, ManifestEntry,
+    ProcessingReportEntry, RuleTimingEntry,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct Progress {
@@ -127,6 +130,13 @@ pub(crate) struct WorkItem {
     pub(crate) data: WorkData,
     pub(crate) status: WorkStatus,
     pub(crate) external_file_dependencies: HashSet<PathBuf>,
+    pub(crate) processing_report: Vec<ProcessingReportEntry>,
+    pub(crate) rule_timings: Vec<RuleTimingEntry>,
+    pub(crate) extracted_messages: Vec<ExtractedMessageEntry>,
+    pub(crate) dry_run_entry: Option<DryRunEntry>,
+    pub(crate) manifest_entry: Option<ManifestEntry>,
+    pub(crate) statistics: Option<FileStatistics>,
+    pub(crate) concatenated_code: Option<String>,
 }
 
 impl WorkItem {
@@ -138,6 +148,13 @@ impl WorkItem {
             },
             status: Default::default(),
             external_file_dependencies: Default::default(),
+            processing_report: Default::default(),
+            rule_timings: Default::default(),
+            extracted_messages: Default::default(),
+            dry_run_entry: None,
+            manifest_entry: None,
+            statistics: None,
+            concatenated_code: None,
         }
     }
 
@@ -160,5 +177,12 @@ impl WorkItem {
     pub(crate) fn reset(&mut self) {
         self.status = WorkStatus::NotStarted;
         self.external_file_dependencies.clear();
+        self.processing_report.clear();
+        self.rule_timings.clear();
+        self.extracted_messages.clear();
+        self.dry_run_entry = None;
+        self.manifest_entry = None;
+        self.statistics = None;
+        self.concatenated_code = None;
     }
 }
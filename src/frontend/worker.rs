@@ -1,16 +1,22 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use wax::Pattern;
 
 use super::{
     configuration::Configuration,
+    options::{FileFilter, OutputTransform},
     resources::Resources,
-    utils::maybe_plural,
+    utils::{maybe_plural, prepend_byte_order_mark, prepend_header_comments, set_final_new_line},
     work_cache::WorkCache,
     work_item::{WorkItem, WorkProgress, WorkStatus},
-    DarkluaError, DarkluaResult, Options,
+    DarkluaError, DarkluaResult, DryRunEntry, ExtractedMessageEntry, FileStatistics, ManifestEntry,
+    Options, ProcessingReportEntry, RuleTimingEntry,
 };
 
 use crate::{
-    nodes::Block,
+    generator::find_unsupported_syntax,
+    nodes::{Block, LastStatement, Statement},
     rules::{bundle::Bundler, ContextBuilder, Rule, RuleConfiguration},
     utils::{normalize_path, Timer},
     GeneratorParameters,
@@ -18,12 +24,57 @@ use crate::{
 
 const DEFAULT_CONFIG_PATHS: [&str; 2] = [".darklua.json", ".darklua.json5"];
 
+/// Returns true if the block contains a `return` statement that exits the file, which
+/// is any `return` statement that is not inside a function.
+fn has_file_return(block: &Block) -> bool {
+    matches!(block.get_last_statement(), Some(LastStatement::Return(_)))
+        || block.iter_statements().any(|statement| match statement {
+            Statement::Do(do_statement) => has_file_return(do_statement.get_block()),
+            Statement::If(if_statement) => {
+                if_statement
+                    .iter_branches()
+                    .any(|branch| has_file_return(branch.get_block()))
+                    || if_statement.get_else_block().is_some_and(has_file_return)
+            }
+            Statement::While(while_statement) => has_file_return(while_statement.get_block()),
+            Statement::Repeat(repeat_statement) => has_file_return(repeat_statement.get_block()),
+            Statement::NumericFor(for_statement) => has_file_return(for_statement.get_block()),
+            Statement::GenericFor(for_statement) => has_file_return(for_statement.get_block()),
+            _ => false,
+        })
+}
+
+/// Returns true if the block exports a type. Exported types can only be declared at
+/// the top level of a file.
+fn has_exported_type(block: &Block) -> bool {
+    block.iter_statements().any(|statement| match statement {
+        Statement::TypeDeclaration(type_declaration) => type_declaration.is_exported(),
+        Statement::TypeFunction(type_function) => type_function.is_exported(),
+        _ => false,
+    })
+}
+
 #[derive(Debug)]
 pub(crate) struct Worker<'a> {
     resources: &'a Resources,
     cache: WorkCache<'a>,
     configuration: Configuration,
+    // a worker is created for each process call, so the modules parsed by the
+    // bundler are not kept between process calls
     cached_bundler: Option<Bundler>,
+    record_processing_report: bool,
+    record_timing: bool,
+    record_stats: bool,
+    dry_run: bool,
+    record_manifest: bool,
+    output_transform: Option<OutputTransform>,
+    file_filter: Option<FileFilter>,
+    copy_filtered_files: bool,
+    byte_order_mark: bool,
+    final_new_line: Option<bool>,
+    concatenate: bool,
+    generator_overrides: Vec<(wax::Glob<'static>, GeneratorParameters)>,
+    header_comment_patterns: Vec<Regex>,
 }
 
 impl<'a> Worker<'a> {
@@ -33,12 +84,37 @@ impl<'a> Worker<'a> {
             cache: WorkCache::new(resources),
             configuration: Configuration::default(),
             cached_bundler: None,
+            record_processing_report: false,
+            record_timing: false,
+            record_stats: false,
+            dry_run: false,
+            record_manifest: false,
+            output_transform: None,
+            file_filter: None,
+            copy_filtered_files: false,
+            byte_order_mark: false,
+            final_new_line: None,
+            concatenate: false,
+            generator_overrides: Vec::new(),
+            header_comment_patterns: Vec::new(),
         }
     }
 
     pub(crate) fn setup_worker(&mut self, options: &mut Options) -> DarkluaResult<()> {
         let configuration_setup_timer = Timer::now();
 
+        self.record_processing_report = options.should_record_processing_report();
+        self.record_timing = options.should_record_timing();
+        self.record_stats = options.should_record_stats();
+        self.dry_run = options.is_dry_run();
+        self.record_manifest = options.manifest_path().is_some();
+        self.output_transform = options.take_output_transform();
+        self.file_filter = options.take_file_filter();
+        self.copy_filtered_files = options.should_copy_filtered_files();
+        self.byte_order_mark = options.should_write_byte_order_mark();
+        self.final_new_line = options.final_new_line();
+        self.concatenate = options.should_concatenate();
+
         if let Some(config) = options.take_configuration() {
             self.configuration = config;
             if let Some(config_path) = options.configuration_path() {
@@ -88,27 +164,74 @@ impl<'a> Worker<'a> {
             }
         };
 
+        for warning in self.configuration.validate() {
+            log::warn!("{}", warning);
+        }
+
+        for rule in self.configuration.skipped_rules() {
+            log::info!(
+                "skip rule `{}` because it may change the behavior of the code (safe mode is enabled)",
+                rule.get_name()
+            );
+        }
+
         if let Some(generator) = options.generator_override() {
             log::trace!(
                 "override with {} generator",
                 match generator {
-                    GeneratorParameters::RetainLines => "`retain_lines`".to_owned(),
-                    GeneratorParameters::Dense { column_span } =>
+                    GeneratorParameters::RetainLines { .. } => "`retain_lines`".to_owned(),
+                    GeneratorParameters::Dense { column_span, .. } =>
                         format!("dense ({})", column_span),
-                    GeneratorParameters::Readable { column_span } =>
+                    GeneratorParameters::Readable { column_span, .. } =>
                         format!("readable ({})", column_span),
+                    GeneratorParameters::Format { .. } => "`format`".to_owned(),
                 }
             );
             self.configuration.set_generator(generator.clone());
         }
 
+        for (pattern, generator) in options.generator_overrides() {
+            let glob = wax::Glob::new(pattern).map_err(|err| {
+                DarkluaError::custom(format!(
+                    "unable to create generator override matcher from `{}`: {}",
+                    pattern, err
+                ))
+            })?;
+            self.generator_overrides
+                .push((glob.into_owned(), generator.clone()));
+        }
+
+        self.header_comment_patterns = self
+            .configuration
+            .preserved_header_comments()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|err| {
+                    DarkluaError::custom(format!(
+                        "unable to create header comment matcher from `{}`: {}",
+                        pattern, err
+                    ))
+                })
+            })
+            .collect::<DarkluaResult<_>>()?;
+
+        if self
+            .iter_generators()
+            .any(|generator| matches!(generator, GeneratorParameters::Format { .. }))
+            && (self.configuration.rules_len() > 0 || self.configuration.bundle().is_some())
+        {
+            return Err(DarkluaError::custom(concat!(
+                "the `format` generator can only be used with a configuration that does not ",
+                "define any rule or bundle options (use `rules: []` to disable the default rules)"
+            )));
+        }
+
         log::trace!(
             "configuration setup in {}",
             configuration_setup_timer.duration_label()
         );
         log::debug!(
             "using configuration: {}",
-            json5::to_string(&self.configuration).unwrap_or_else(|err| {
+            self.configuration.to_json5_str().unwrap_or_else(|err| {
                 format!("? (unable to serialize configuration: {})", err)
             })
         );
@@ -120,6 +243,22 @@ impl<'a> Worker<'a> {
         &self.configuration
     }
 
+    fn iter_generators(&self) -> impl Iterator<Item = &GeneratorParameters> {
+        std::iter::once(self.configuration.generator()).chain(
+            self.generator_overrides
+                .iter()
+                .map(|(_, generator)| generator),
+        )
+    }
+
+    fn get_generator(&self, output: &Path) -> &GeneratorParameters {
+        self.generator_overrides
+            .iter()
+            .find(|(glob, _)| glob.is_match(output))
+            .map(|(_, generator)| generator)
+            .unwrap_or_else(|| self.configuration.generator())
+    }
+
     pub(crate) fn advance_work(&mut self, work_item: &mut WorkItem) -> DarkluaResult<()> {
         match &work_item.status {
             WorkStatus::NotStarted => {
@@ -127,7 +266,17 @@ impl<'a> Worker<'a> {
 
                 let content = self.resources.get(work_item.source())?;
 
-                let parser = self.configuration.build_parser();
+                if let Some(file_filter) = self.file_filter.as_ref() {
+                    if !file_filter.should_process(work_item.source(), &content) {
+                        log::debug!("skip `{}` (filtered out)", source_display);
+                        return self.skip_work(work_item, content);
+                    }
+                }
+
+                let mut parser = self.get_generator(work_item.data.output()).build_parser();
+                if let Some(target) = self.configuration.target() {
+                    parser = parser.with_lua_version(target);
+                }
 
                 log::debug!("beginning work on `{}`", source_display);
 
@@ -153,7 +302,7 @@ impl<'a> Worker<'a> {
 
     fn read_configuration(&self, config: &Path) -> DarkluaResult<Configuration> {
         let config_content = self.resources.get(config)?;
-        json5::from_str(&config_content)
+        Configuration::parse_json5(&config_content)
             .map_err(|err| {
                 DarkluaError::invalid_configuration_file(config).context(err.to_string())
             })
@@ -260,6 +409,8 @@ impl<'a> Worker<'a> {
 
             let source = work_item.data.source();
 
+            let original_block = self.record_processing_report.then(|| block.clone());
+
             let rule_result = rule.process(block, &context).map_err(|rule_error| {
                 let error = DarkluaError::rule_error(source, rule, index, rule_error);
 
@@ -273,12 +424,36 @@ impl<'a> Worker<'a> {
                 error
             });
 
+            let extracted_messages = context.take_extracted_messages();
+
             work_item
                 .external_file_dependencies
                 .extend(context.into_dependencies());
 
             rule_result?;
 
+            work_item.extracted_messages.extend(
+                extracted_messages.into_iter().map(|(function, message)| {
+                    ExtractedMessageEntry::new(source, function, message)
+                }),
+            );
+
+            if let Some(original_block) = original_block {
+                work_item.processing_report.push(ProcessingReportEntry::new(
+                    source,
+                    rule.get_name(),
+                    original_block != *block,
+                ));
+            }
+
+            if self.record_timing {
+                work_item.rule_timings.push(RuleTimingEntry::new(
+                    source,
+                    rule.get_name(),
+                    rule_timer.duration(),
+                ));
+            }
+
             let rule_duration = rule_timer.duration_label();
             log::trace!(
                 "[{}] ⨽completed `{}` in {}",
@@ -298,9 +473,32 @@ impl<'a> Worker<'a> {
             source_display,
         );
 
+        if self.concatenate {
+            if has_file_return(progress.block()) {
+                return Err(DarkluaError::concatenation_return(work_item.data.source()));
+            }
+            if has_exported_type(progress.block()) {
+                return Err(DarkluaError::concatenation_exported_type(
+                    work_item.data.source(),
+                ));
+            }
+        }
+
+        if let Some(target) = self.configuration.target() {
+            if let Some(syntax) = find_unsupported_syntax(progress.mutate_block(), target) {
+                return Err(DarkluaError::unsupported_syntax(
+                    work_item.data.source(),
+                    syntax,
+                    target,
+                ));
+            }
+        }
+
         log::trace!("begin generating code for `{}`", source_display);
 
-        if cfg!(test) || (cfg!(debug_assertions) && log::log_enabled!(log::Level::Trace)) {
+        if !self.dry_run
+            && (cfg!(test) || (cfg!(debug_assertions) && log::log_enabled!(log::Level::Trace)))
+        {
             log::trace!(
                 "generate AST debugging view at `{}`",
                 work_item.data.output().display()
@@ -311,10 +509,34 @@ impl<'a> Worker<'a> {
 
         let generator_timer = Timer::now();
 
-        let lua_code = self
-            .configuration
+        let mut lua_code = self
+            .get_generator(work_item.data.output())
             .generate_lua(progress.block(), &work_progress.content);
 
+        prepend_header_comments(
+            &mut lua_code,
+            &work_progress.content,
+            &self.header_comment_patterns,
+        );
+
+        self.configuration
+            .append_source_map_comment(&mut lua_code, work_item.data.output());
+
+        if let Some(output_transform) = self.output_transform.as_mut() {
+            lua_code = output_transform.apply(&lua_code, work_item.data.output());
+        }
+
+        // when concatenating, these are applied on the concatenated file
+        if !self.concatenate {
+            if let Some(final_new_line) = self.final_new_line {
+                set_final_new_line(&mut lua_code, final_new_line);
+            }
+
+            if self.byte_order_mark {
+                prepend_byte_order_mark(&mut lua_code);
+            }
+        }
+
         let generator_time = generator_timer.duration_label();
         log::debug!(
             "generated code for `{}` in {}",
@@ -322,10 +544,81 @@ impl<'a> Worker<'a> {
             generator_time,
         );
 
-        self.resources.write(work_item.data.output(), &lua_code)?;
+        if self.record_stats {
+            work_item.statistics = Some(FileStatistics::new(
+                work_item.data.source(),
+                work_progress.content.len(),
+                lua_code.len(),
+                progress.mutate_block(),
+            ));
+        }
+
+        if self.concatenate {
+            work_item.concatenated_code = Some(lua_code);
+
+            self.cache
+                .link_source_to_output(normalized_source, work_item.data.source());
+        } else {
+            let changed = lua_code != work_progress.content;
+            self.write_output(work_item, normalized_source, lua_code, changed)?;
+        }
+
+        work_item.status = WorkStatus::done();
+        Ok(())
+    }
 
-        self.cache
-            .link_source_to_output(normalized_source, work_item.data.output());
+    fn write_output(
+        &mut self,
+        work_item: &mut WorkItem,
+        normalized_source: PathBuf,
+        lua_code: String,
+        changed: bool,
+    ) -> DarkluaResult<()> {
+        if self.record_manifest {
+            work_item.manifest_entry = Some(ManifestEntry::new(
+                work_item.data.source(),
+                work_item.data.output(),
+                changed,
+                lua_code.len(),
+            ));
+        }
+
+        if self.dry_run {
+            let output = work_item.data.output();
+            let original = if self.resources.exists(output)? {
+                Some(self.resources.get(output)?)
+            } else {
+                None
+            };
+            log::debug!("skip writing `{}` (dry run)", output.display());
+            // the output is not written, so files requiring this one read the
+            // generated code from the cache
+            self.cache
+                .link_source_to_code(normalized_source, lua_code.clone());
+            work_item.dry_run_entry = Some(DryRunEntry::new(output, original, lua_code));
+        } else {
+            self.resources.write(work_item.data.output(), &lua_code)?;
+            self.cache
+                .link_source_to_output(normalized_source, work_item.data.output());
+        }
+        Ok(())
+    }
+
+    fn skip_work(&mut self, work_item: &mut WorkItem, content: String) -> DarkluaResult<()> {
+        let normalized_source = normalize_path(work_item.data.source());
+
+        // other files can still require the skipped file, so the cache points to its
+        // unchanged content
+        if self.copy_filtered_files && self.concatenate {
+            work_item.concatenated_code = Some(content);
+            self.cache
+                .link_source_to_output(normalized_source, work_item.data.source());
+        } else if self.copy_filtered_files {
+            self.write_output(work_item, normalized_source, content, false)?;
+        } else {
+            self.cache
+                .link_source_to_output(normalized_source, work_item.data.source());
+        }
 
         work_item.status = WorkStatus::done();
         Ok(())
@@ -336,7 +629,14 @@ impl<'a> Worker<'a> {
         source: &Path,
         original_code: &'src str,
     ) -> ContextBuilder<'block, 'a, 'src> {
-        let builder = ContextBuilder::new(normalize_path(source), self.resources, original_code);
+        let mut builder =
+            ContextBuilder::new(normalize_path(source), self.resources, original_code);
+        if let Some(prefix) = self.configuration.generated_identifier_prefix() {
+            builder = builder.with_generated_identifier_prefix(prefix);
+        }
+        if let Some(target) = self.configuration.target() {
+            builder = builder.with_target(target);
+        }
         if let Some(project_location) = self.configuration.location() {
             builder.with_project_location(project_location)
         } else {
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
@@ -7,14 +7,15 @@ use petgraph::{algo::toposort, graph::NodeIndex, stable_graph::StableDiGraph, vi
 use xxhash_rust::xxh3::xxh3_64;
 
 use crate::{
-    frontend::utils::maybe_plural,
+    frontend::utils::{maybe_plural, prepend_byte_order_mark, set_final_new_line},
     utils::{clear_luau_configuration_cache, Timer},
-    DarkluaError,
+    DarkluaError, ErrorCategory,
 };
 
 use super::{
-    normalize_path, work_item::WorkStatus, Configuration, DarkluaResult, Options, Resources,
-    WorkItem, Worker,
+    manifest::serialize_manifest, normalize_path, work_item::WorkStatus, Configuration,
+    DarkluaResult, DryRunEntry, ExtractedMessageEntry, FileStatistics, ManifestEntry, Options,
+    ProcessingReportEntry, Resources, RuleTiming, RuleTimingEntry, WorkItem, Worker,
 };
 
 #[derive(Debug, Default)]
@@ -24,6 +25,9 @@ pub struct WorkerTree {
     external_dependencies: HashMap<PathBuf, HashSet<NodeIndex>>,
     remove_files: Vec<PathBuf>,
     last_configuration_hash: Option<u64>,
+    last_concatenate: bool,
+    concatenated_manifest_entry: Option<ManifestEntry>,
+    concatenated_dry_run_entry: Option<DryRunEntry>,
 }
 
 impl WorkerTree {
@@ -31,7 +35,15 @@ impl WorkerTree {
         log::trace!("start collecting work");
         let collect_work_timer = Timer::now();
 
-        if let Some(output) = options.output().map(Path::to_path_buf) {
+        if options.should_concatenate() {
+            let output = options
+                .output()
+                .ok_or_else(DarkluaError::concatenation_without_output)?;
+
+            for source in resources.collect_work(options.input()) {
+                self.add_source_if_missing(source, Some(output.to_path_buf()));
+            }
+        } else if let Some(output) = options.output().map(Path::to_path_buf) {
             if resources.is_file(options.input())? {
                 if resources.is_directory(&output)? {
                     let file_name = options.input().file_name().ok_or_else(|| {
@@ -110,7 +122,13 @@ impl WorkerTree {
         if self.has_configuration_changed(worker.configuration()) {
             log::debug!("configuration change detected");
             self.reset();
+        } else if self.last_concatenate != options.should_concatenate() {
+            // the results of files processed with the other mode do not contain the
+            // expected code to concatenate
+            log::debug!("concatenation mode change detected");
+            self.reset();
         }
+        self.last_concatenate = options.should_concatenate();
 
         let total_not_done = self
             .graph
@@ -123,14 +141,13 @@ impl WorkerTree {
         }
 
         let work_timer = Timer::now();
+        let mut done_count = 0;
 
         'work_loop: loop {
             let mut add_edges = Vec::new();
 
             match toposort(&self.graph, None) {
                 Ok(node_indexes) => {
-                    let mut done_count = 0;
-
                     for node_index in node_indexes {
                         let work_item = self
                             .graph
@@ -222,6 +239,102 @@ impl WorkerTree {
 
         log::info!("executed work in {}", work_timer.duration_label());
 
+        if options.should_concatenate() {
+            self.write_concatenated_files(resources, &options)?;
+        }
+
+        if let Some(manifest_path) = options.manifest_path() {
+            if options.is_dry_run() {
+                log::debug!(
+                    "skip writing manifest `{}` (dry run)",
+                    manifest_path.display()
+                );
+            } else {
+                let manifest = serialize_manifest(&self.manifest_entries())?;
+                resources.write(manifest_path, &manifest)?;
+                log::info!("manifest written to `{}`", manifest_path.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_concatenated_files(
+        &mut self,
+        resources: &Resources,
+        options: &Options,
+    ) -> DarkluaResult<()> {
+        self.concatenated_manifest_entry = None;
+        self.concatenated_dry_run_entry = None;
+
+        let output = options
+            .output()
+            .ok_or_else(DarkluaError::concatenation_without_output)?;
+
+        if self.iter_errors().next().is_some() {
+            log::warn!(
+                "skip writing `{}` because some files could not be processed",
+                output.display()
+            );
+            return Ok(());
+        }
+
+        let mut files: Vec<_> = self
+            .graph
+            .node_weights()
+            .filter_map(|work_item| {
+                work_item
+                    .concatenated_code
+                    .as_ref()
+                    .map(|code| (work_item.source(), code))
+            })
+            .collect();
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut code = String::new();
+        for (_, file_code) in files.iter() {
+            code.push_str("do\n");
+            code.push_str(file_code.trim_end());
+            code.push_str("\nend\n");
+        }
+
+        if let Some(final_new_line) = options.final_new_line() {
+            set_final_new_line(&mut code, final_new_line);
+        }
+
+        if options.should_write_byte_order_mark() {
+            prepend_byte_order_mark(&mut code);
+        }
+
+        if options.manifest_path().is_some() {
+            // the concatenated code is always different from the input, since each
+            // file is wrapped in a `do` block
+            self.concatenated_manifest_entry = Some(ManifestEntry::new(
+                options.input(),
+                output,
+                true,
+                code.len(),
+            ));
+        }
+
+        if options.is_dry_run() {
+            log::debug!("skip writing `{}` (dry run)", output.display());
+            let original = if resources.exists(output)? {
+                Some(resources.get(output)?)
+            } else {
+                None
+            };
+            self.concatenated_dry_run_entry = Some(DryRunEntry::new(output, original, code));
+        } else {
+            resources.write(output, &code)?;
+            log::info!(
+                "concatenated {} file{} into `{}`",
+                files.len(),
+                maybe_plural(files.len()),
+                output.display()
+            );
+        }
+
         Ok(())
     }
 
@@ -238,6 +351,21 @@ impl WorkerTree {
         self.iter_errors().collect()
     }
 
+    /// Returns the number of errors.
+    pub fn total_errors(&self) -> usize {
+        self.iter_errors().count()
+    }
+
+    /// Returns the number of errors for each category. Categories without any
+    /// error are not included.
+    pub fn error_counts(&self) -> BTreeMap<ErrorCategory, usize> {
+        let mut counts = BTreeMap::new();
+        for error in self.iter_errors() {
+            *counts.entry(error.category()).or_default() += 1;
+        }
+        counts
+    }
+
     fn iter_errors(&self) -> impl Iterator<Item = &DarkluaError> {
         self.graph
             .node_weights()
@@ -257,6 +385,106 @@ impl WorkerTree {
             .count()
     }
 
+    /// Returns the rules applied on each processed file, if the processing report
+    /// was enabled with [`Options::with_processing_report`]. Entries are sorted by
+    /// file path and then follow the order in which rules were applied.
+    pub fn processing_report(&self) -> Vec<&ProcessingReportEntry> {
+        let mut work_items: Vec<_> = self.graph.node_weights().collect();
+        work_items.sort_by(|a, b| a.source().cmp(b.source()));
+
+        work_items
+            .into_iter()
+            .flat_map(|work_item| work_item.processing_report.iter())
+            .collect()
+    }
+
+    /// Returns the time spent applying each rule on each processed file, if timing
+    /// was enabled with [`Options::with_timing`]. Entries are sorted by file path and
+    /// then follow the order in which rules were applied.
+    pub fn rule_timing_entries(&self) -> Vec<&RuleTimingEntry> {
+        let mut work_items: Vec<_> = self.graph.node_weights().collect();
+        work_items.sort_by(|a, b| a.source().cmp(b.source()));
+
+        work_items
+            .into_iter()
+            .flat_map(|work_item| work_item.rule_timings.iter())
+            .collect()
+    }
+
+    /// Returns the messages collected by the `extract_messages` rule on each processed
+    /// file. Entries are sorted by file path and then follow the order in which messages
+    /// were found.
+    pub fn extracted_messages(&self) -> Vec<&ExtractedMessageEntry> {
+        let mut work_items: Vec<_> = self.graph.node_weights().collect();
+        work_items.sort_by(|a, b| a.source().cmp(b.source()));
+
+        work_items
+            .into_iter()
+            .flat_map(|work_item| work_item.extracted_messages.iter())
+            .collect()
+    }
+
+    /// Returns the total time spent applying each rule on all processed files, if
+    /// timing was enabled with [`Options::with_timing`]. Rules are sorted from the
+    /// slowest to the fastest.
+    pub fn timing_report(&self) -> Vec<RuleTiming> {
+        let mut timings: BTreeMap<&'static str, RuleTiming> = BTreeMap::new();
+
+        for entry in self
+            .graph
+            .node_weights()
+            .flat_map(|work_item| work_item.rule_timings.iter())
+        {
+            timings
+                .entry(entry.rule_name())
+                .or_insert_with(|| RuleTiming::new(entry.rule_name()))
+                .add_entry(entry);
+        }
+
+        let mut report: Vec<_> = timings.into_values().collect();
+        report.sort_by(|a, b| b.total_duration().cmp(&a.total_duration()));
+        report
+    }
+
+    /// Returns the content that would be written for each processed file, if the
+    /// processing was done with [`Options::dry_run`]. Entries are sorted by the path
+    /// of the written file.
+    pub fn dry_run_entries(&self) -> Vec<&DryRunEntry> {
+        let mut entries: Vec<_> = self
+            .graph
+            .node_weights()
+            .filter_map(|work_item| work_item.dry_run_entry.as_ref())
+            .chain(self.concatenated_dry_run_entry.as_ref())
+            .collect();
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        entries
+    }
+
+    /// Returns the files written during the last processing, if a manifest was requested
+    /// with [`Options::with_manifest`]. Entries are sorted by input path.
+    pub fn manifest_entries(&self) -> Vec<&ManifestEntry> {
+        let mut entries: Vec<_> = self
+            .graph
+            .node_weights()
+            .filter_map(|work_item| work_item.manifest_entry.as_ref())
+            .chain(self.concatenated_manifest_entry.as_ref())
+            .collect();
+        entries.sort_by(|a, b| a.input().cmp(b.input()));
+        entries
+    }
+
+    /// Returns the statistics of each processed file, if they were enabled with
+    /// [`Options::with_stats`]. Entries are sorted by file path.
+    pub fn file_statistics(&self) -> Vec<&FileStatistics> {
+        let mut entries: Vec<_> = self
+            .graph
+            .node_weights()
+            .filter_map(|work_item| work_item.statistics.as_ref())
+            .collect();
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        entries
+    }
+
     pub fn iter_external_dependencies(&self) -> impl Iterator<Item = &Path> {
         self.external_dependencies
             .iter()
@@ -268,8 +496,13 @@ impl WorkerTree {
             work_item.reset();
         });
         self.external_dependencies.clear();
+        self.concatenated_manifest_entry = None;
+        self.concatenated_dry_run_entry = None;
     }
 
+    /// Restarts the work of the given source (or of all sources inside a directory)
+    /// and of every file that depends on it, like bundled files that require it.
+    /// Other files keep their previous results.
     pub fn source_changed(&mut self, path: impl AsRef<Path>) {
         let path = normalize_path(path.as_ref());
 
@@ -393,7 +626,7 @@ impl WorkerTree {
             log::debug!("restart work for {}", item.source().display());
             for path in item.external_file_dependencies.iter() {
                 if let Some(container) = self.external_dependencies.get_mut(path) {
-                    container.remove(&node_index);
+                    container.remove(&dependent_node);
                 }
             }
             item.reset();
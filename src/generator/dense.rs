@@ -1,13 +1,18 @@
-use crate::generator::{utils, LuaGenerator};
+use std::io;
+
+use crate::generator::{utils, GeneratorOutput, LuaGenerator, WriterOutput};
 use crate::nodes;
 
 /// This implementation of [LuaGenerator](trait.LuaGenerator.html) attempts to produce Lua code as
 /// small as possible. It is not meant to be read by humans.
 #[derive(Debug, Clone)]
-pub struct DenseLuaGenerator {
+pub struct DenseLuaGenerator<O = String> {
     column_span: usize,
+    always_semicolons: bool,
+    spaced_binary_operators: bool,
+    escape_non_ascii: bool,
     current_line_length: usize,
-    output: String,
+    output: O,
     last_push_length: usize,
 }
 
@@ -17,12 +22,58 @@ impl DenseLuaGenerator {
     pub fn new(column_span: usize) -> Self {
         Self {
             column_span,
+            always_semicolons: false,
+            spaced_binary_operators: false,
+            escape_non_ascii: false,
             current_line_length: 0,
             output: String::new(),
             last_push_length: 0,
         }
     }
 
+    /// Generates the code of the block and writes it into the given writer. The code
+    /// is written progressively while it is generated, instead of being entirely kept
+    /// in memory. The output is identical to the String produced by `into_string`.
+    pub fn write_block_to<W: io::Write>(self, block: &nodes::Block, writer: W) -> io::Result<W> {
+        let mut output = WriterOutput::new(writer);
+        output.push_str(&self.output);
+
+        let mut generator = DenseLuaGenerator {
+            column_span: self.column_span,
+            always_semicolons: self.always_semicolons,
+            spaced_binary_operators: self.spaced_binary_operators,
+            escape_non_ascii: self.escape_non_ascii,
+            current_line_length: self.current_line_length,
+            output,
+            last_push_length: self.last_push_length,
+        };
+        generator.write_block(block);
+        generator.output.finish()
+    }
+}
+
+impl<O: GeneratorOutput> DenseLuaGenerator<O> {
+    /// Writes a semicolon between every statement, instead of only writing them when
+    /// needed to avoid ambiguous syntax.
+    pub fn with_always_semicolons(mut self) -> Self {
+        self.always_semicolons = true;
+        self
+    }
+
+    /// Writes a space before and after every binary operator, instead of only writing
+    /// spaces when needed to separate tokens.
+    pub fn with_spaced_binary_operators(mut self) -> Self {
+        self.spaced_binary_operators = true;
+        self
+    }
+
+    /// Escapes the non-ASCII characters of strings with decimal escape sequences, so
+    /// that the output only contains ASCII characters inside strings.
+    pub fn with_escaped_non_ascii(mut self) -> Self {
+        self.escape_non_ascii = true;
+        self
+    }
+
     /// Appends a string to the current content of the DenseLuaGenerator. A space may be added
     /// depending of the last character of the current content and the first character pushed.
     fn push_str(&mut self, content: &str) {
@@ -120,7 +171,7 @@ impl DenseLuaGenerator {
 
     #[inline]
     fn needs_space(&self, next_character: char) -> bool {
-        if let Some(previous) = self.output.chars().last() {
+        if let Some(previous) = self.output.as_str().chars().last() {
             utils::should_break_with_space(previous, next_character)
         } else {
             false
@@ -129,7 +180,7 @@ impl DenseLuaGenerator {
 
     /// Consumes the LuaGenerator and produce a String object.
     pub fn into_string(self) -> String {
-        self.output
+        self.output.into_string()
     }
 
     #[inline]
@@ -164,6 +215,22 @@ impl DenseLuaGenerator {
         self.raw_push_str(content);
     }
 
+    /// Pushes a string surrounded by spaces. A new line replaces the space before the
+    /// string when it does not fit on the current line.
+    fn push_spaced_str(&mut self, content: &str) {
+        if self.fits_on_current_line(1 + content.len()) {
+            self.push_space();
+        } else {
+            self.push_new_line();
+        }
+        self.raw_push_str(content);
+        if self.fits_on_current_line(2) {
+            // the space becomes the last pushed content, so the next push does not
+            // add another space to separate itself from the string
+            self.raw_push_char(' ');
+        }
+    }
+
     /// Same as `push_str_and_break_if` but for a single character
     fn push_char_and_break_if<F>(&mut self, content: char, predicate: F)
     where
@@ -182,11 +249,21 @@ impl DenseLuaGenerator {
     }
 
     fn get_last_push_str(&self) -> &str {
-        self.output
-            .get((self.output.len() - self.last_push_length)..)
+        let output = self.output.as_str();
+        output
+            .get((output.len() - self.last_push_length)..)
             .unwrap_or("")
     }
 
+    /// Commits the generated code that is before the line where the last push starts,
+    /// since the generator never modifies it.
+    fn commit_output(&mut self) {
+        let output = self.output.as_str();
+        if let Some(index) = output[..output.len() - self.last_push_length].rfind('\n') {
+            self.output.commit(index);
+        }
+    }
+
     fn write_function_parameters(
         &mut self,
         parameters: &[nodes::TypedIdentifier],
@@ -282,10 +359,10 @@ impl Default for DenseLuaGenerator {
     }
 }
 
-impl LuaGenerator for DenseLuaGenerator {
+impl<O: GeneratorOutput> LuaGenerator for DenseLuaGenerator<O> {
     /// Consumes the LuaGenerator and produce a String object.
     fn into_string(self) -> String {
-        self.output
+        self.output.into_string()
     }
 
     fn write_block(&mut self, block: &nodes::Block) {
@@ -293,13 +370,17 @@ impl LuaGenerator for DenseLuaGenerator {
 
         while let Some(statement) = statements.next() {
             self.write_statement(statement);
+            self.commit_output();
 
             if let Some(next_statement) = statements.peek() {
-                if utils::starts_with_parenthese(next_statement)
-                    && utils::ends_with_prefix(statement)
+                if self.always_semicolons
+                    || (utils::starts_with_parenthese(next_statement)
+                        && utils::ends_with_prefix(statement))
                 {
                     self.push_char(';');
                 }
+            } else if self.always_semicolons && block.get_last_statement().is_some() {
+                self.push_char(';');
             }
         }
 
@@ -585,6 +666,40 @@ impl LuaGenerator for DenseLuaGenerator {
         }
     }
 
+    fn write_type_function_statement(&mut self, statement: &nodes::TypeFunctionStatement) {
+        if statement.is_exported() {
+            self.push_str("export");
+        }
+        self.push_str("type function");
+        self.push_str(statement.get_name());
+
+        if let Some(generics) = statement.get_generic_parameters() {
+            self.write_function_generics(generics);
+        }
+
+        self.push_char('(');
+
+        let parameters = statement.get_parameters();
+        self.write_function_parameters(
+            parameters,
+            statement.is_variadic(),
+            statement.get_variadic_type(),
+        );
+        self.push_char(')');
+
+        if let Some(return_type) = statement.get_return_type() {
+            self.push_char(':');
+            self.write_function_return_type(return_type);
+        }
+
+        let block = statement.get_block();
+
+        if !block.is_empty() {
+            self.write_block(block);
+        }
+        self.push_str("end");
+    }
+
     fn write_type_declaration_statement(&mut self, statement: &nodes::TypeDeclarationStatement) {
         if statement.is_exported() {
             self.push_str("export");
@@ -667,6 +782,7 @@ impl LuaGenerator for DenseLuaGenerator {
         }
 
         match operator {
+            _ if self.spaced_binary_operators => self.push_spaced_str(operator.to_str()),
             BinaryOperator::Concat => self.push_str_and_break_if("..", utils::break_concat),
             _ => self.push_str(operator.to_str()),
         }
@@ -685,6 +801,7 @@ impl LuaGenerator for DenseLuaGenerator {
             Length => self.push_char('#'),
             Minus => self.push_char_and_break_if('-', utils::break_minus),
             Not => self.push_str("not"),
+            BitwiseNot => self.push_char('~'),
         }
 
         let expression = unary.get_expression();
@@ -834,24 +951,8 @@ impl LuaGenerator for DenseLuaGenerator {
                     self.push_str(&result);
                 }
             }
-            Hex(number) => {
-                let mut result = format!(
-                    "0{}{:x}",
-                    if number.is_x_uppercase() { 'X' } else { 'x' },
-                    number.get_raw_integer()
-                );
-
-                if let Some(exponent) = number.get_exponent() {
-                    let exponent_char = number
-                        .is_exponent_uppercase()
-                        .map(|is_uppercase| if is_uppercase { 'P' } else { 'p' })
-                        .unwrap_or('p');
-
-                    result.push(exponent_char);
-                    result.push_str(&format!("{}", exponent));
-                };
-
-                self.push_str(&result);
+            Hex(_) => {
+                self.push_str(&utils::write_number(number));
             }
             Binary(number) => {
                 self.push_str(&format!(
@@ -882,7 +983,7 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_string(&mut self, string: &nodes::StringExpression) {
-        let result = utils::write_string(string.get_value());
+        let result = utils::write_shortest_string(string.get_value(), self.escape_non_ascii);
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -899,7 +1000,10 @@ impl LuaGenerator for DenseLuaGenerator {
         for segment in interpolated_string.iter_segments() {
             match segment {
                 nodes::InterpolationSegment::String(string_segment) => {
-                    self.raw_push_str(&utils::write_interpolated_string_segment(string_segment));
+                    self.raw_push_str(&utils::write_interpolated_string_segment(
+                        string_segment,
+                        self.escape_non_ascii,
+                    ));
                 }
                 nodes::InterpolationSegment::Value(value) => {
                     self.raw_push_char('{');
@@ -974,7 +1078,7 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_string_type(&mut self, string_type: &nodes::StringType) {
-        let result = utils::write_string(string_type.get_value());
+        let result = utils::write_shortest_string(string_type.get_value(), self.escape_non_ascii);
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -0,0 +1,245 @@
+use super::utils;
+
+const INDENTATION: &str = "    ";
+const MAX_NEW_LINES: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameKind {
+    Block,
+    Bracket,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    kind: FrameKind,
+    // the indentation level of the line where the block or bracket was opened
+    indentation: usize,
+}
+
+/// Keeps track of the layout when the [`TokenBasedLuaGenerator`](super::TokenBasedLuaGenerator)
+/// formats code. Whitespaces from the tokens are dropped and replaced with a canonical
+/// indentation and spacing, while the line breaks and the comments are kept.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FormatState {
+    frames: Vec<Frame>,
+    block_depth: usize,
+    line_indentation: usize,
+    closed_block_indentation: usize,
+    pending_new_lines: usize,
+    force_new_line: bool,
+    statement_start: bool,
+    attach_next: bool,
+    last_is_comment: bool,
+    last_content: String,
+}
+
+impl FormatState {
+    pub(crate) fn open_block(&mut self) {
+        self.block_depth += 1;
+        if self.block_depth > 1 {
+            self.frames.push(Frame {
+                kind: FrameKind::Block,
+                indentation: self.line_indentation,
+            });
+        }
+    }
+
+    pub(crate) fn close_block(&mut self, is_empty: bool) {
+        if self.block_depth > 1 {
+            if let Some(frame) = self.frames.pop() {
+                self.closed_block_indentation = frame.indentation;
+            }
+            if !is_empty {
+                self.force_new_line = true;
+            }
+        }
+        self.block_depth = self.block_depth.saturating_sub(1);
+        self.statement_start = false;
+    }
+
+    pub(crate) fn start_statement(&mut self) {
+        self.force_new_line = true;
+        self.statement_start = true;
+    }
+
+    /// Comments at the end of a block are indented like statements.
+    pub(crate) fn start_block_end(&mut self) {
+        self.statement_start = true;
+    }
+
+    /// Prevents a space from being inserted before the next content.
+    pub(crate) fn attach_next(&mut self) {
+        self.attach_next = true;
+    }
+
+    pub(crate) fn write_whitespace(&mut self, content: &str) {
+        self.pending_new_lines += utils::count_new_lines(content);
+    }
+
+    pub(crate) fn write_comment(
+        &mut self,
+        output: &mut String,
+        content: &str,
+        is_single_line_comment: bool,
+    ) {
+        let new_lines = self.take_new_lines(output);
+        if new_lines > 0 {
+            let indentation = self.get_indentation(None);
+            self.break_line(output, new_lines, indentation);
+        } else if output
+            .chars()
+            .last()
+            .filter(|c| !c.is_whitespace())
+            .is_some()
+            && (self.last_is_comment || !matches!(self.last_content.as_str(), "(" | "["))
+        {
+            output.push(' ');
+        }
+
+        output.push_str(content);
+
+        if is_single_line_comment {
+            self.force_new_line = true;
+        }
+        self.last_is_comment = true;
+    }
+
+    pub(crate) fn write_content(&mut self, output: &mut String, content: &str) {
+        if content.is_empty() {
+            return;
+        }
+
+        let new_lines = self.take_new_lines(output);
+        if new_lines > 0 {
+            let indentation = self.get_indentation(Some(content));
+            self.break_line(output, new_lines, indentation);
+        } else if self.needs_space(output, content) {
+            output.push(' ');
+        }
+
+        if is_closing_bracket(content) {
+            self.frames.pop();
+        }
+
+        output.push_str(content);
+
+        if is_opening_bracket(content) {
+            self.frames.push(Frame {
+                kind: FrameKind::Bracket,
+                indentation: self.line_indentation,
+            });
+        }
+
+        self.statement_start = false;
+        self.attach_next = false;
+        self.last_is_comment = false;
+        self.last_content.clear();
+        self.last_content.push_str(content);
+    }
+
+    pub(crate) fn finish(&mut self, output: &mut String) {
+        trim_end_spaces(output);
+        while output.ends_with('\n') {
+            output.pop();
+        }
+        if !output.is_empty() {
+            output.push('\n');
+        }
+    }
+
+    fn take_new_lines(&mut self, output: &str) -> usize {
+        let new_lines = if output.is_empty() {
+            0
+        } else if self.force_new_line {
+            self.pending_new_lines.max(1)
+        } else {
+            self.pending_new_lines
+        };
+
+        self.pending_new_lines = 0;
+        self.force_new_line = false;
+
+        new_lines.min(MAX_NEW_LINES)
+    }
+
+    fn break_line(&mut self, output: &mut String, new_lines: usize, indentation: usize) {
+        trim_end_spaces(output);
+        for _ in 0..new_lines {
+            output.push('\n');
+        }
+        for _ in 0..indentation {
+            output.push_str(INDENTATION);
+        }
+        self.line_indentation = indentation;
+    }
+
+    fn get_indentation(&self, content: Option<&str>) -> usize {
+        let frame_indentation = self
+            .frames
+            .last()
+            .map(|frame| frame.indentation + 1)
+            .unwrap_or(0);
+
+        match content {
+            Some(content) if is_closing_bracket(content) => self
+                .frames
+                .last()
+                .map(|frame| frame.indentation)
+                .unwrap_or(0),
+            Some("end" | "else" | "elseif" | "until") => self.closed_block_indentation,
+            Some("then" | "do") => frame_indentation,
+            _ => {
+                let in_block = self
+                    .frames
+                    .last()
+                    .map(|frame| frame.kind == FrameKind::Block)
+                    .unwrap_or(true);
+
+                if in_block && !self.statement_start {
+                    frame_indentation + 1
+                } else {
+                    frame_indentation
+                }
+            }
+        }
+    }
+
+    fn needs_space(&self, output: &str, content: &str) -> bool {
+        let last_character = match output.chars().last() {
+            Some(character) if !character.is_whitespace() => character,
+            _ => return false,
+        };
+
+        if let Some(next_character) = content.chars().next() {
+            if utils::should_break_with_space(last_character, next_character) {
+                return true;
+            }
+        }
+
+        if self.last_is_comment {
+            return !matches!(content, ")" | "]" | "," | ";");
+        }
+
+        if self.attach_next {
+            return false;
+        }
+
+        !matches!(
+            (self.last_content.as_str(), content),
+            (_, ")" | "]" | "," | ";" | ":") | ("(" | "[" | "#", _) | ("{", "}")
+        )
+    }
+}
+
+fn is_opening_bracket(content: &str) -> bool {
+    matches!(content, "(" | "[" | "{")
+}
+
+fn is_closing_bracket(content: &str) -> bool {
+    matches!(content, ")" | "]" | "}")
+}
+
+fn trim_end_spaces(output: &mut String) {
+    let trimmed_length = output.trim_end_matches([' ', '\t']).len();
+    output.truncate(trimmed_length);
+}
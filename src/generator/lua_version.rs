@@ -0,0 +1,252 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::nodes::{
+    BinaryExpression, BinaryOperator, Block, CompoundAssignStatement, Expression, LastStatement,
+    Type, TypeDeclarationStatement, TypeFunctionStatement, UnaryExpression, UnaryOperator,
+};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+
+/// The version of Lua that the generated code must run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LuaVersion {
+    Lua51,
+    Lua52,
+    Lua53,
+    Lua54,
+    Luau,
+}
+
+impl LuaVersion {
+    /// Returns true if code written for this version can contain the given syntax.
+    pub fn supports(&self, syntax: LuaSyntax) -> bool {
+        match syntax {
+            LuaSyntax::CompoundAssignment
+            | LuaSyntax::Continue
+            | LuaSyntax::IfExpression
+            | LuaSyntax::InterpolatedString
+            | LuaSyntax::Type => *self == Self::Luau,
+            LuaSyntax::FloorDivision => matches!(self, Self::Lua53 | Self::Lua54 | Self::Luau),
+            LuaSyntax::BitwiseOperator => matches!(self, Self::Lua53 | Self::Lua54),
+        }
+    }
+}
+
+impl fmt::Display for LuaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lua51 => write!(f, "Lua 5.1"),
+            Self::Lua52 => write!(f, "Lua 5.2"),
+            Self::Lua53 => write!(f, "Lua 5.3"),
+            Self::Lua54 => write!(f, "Lua 5.4"),
+            Self::Luau => write!(f, "Luau"),
+        }
+    }
+}
+
+/// A syntax that is only available in some versions of Lua.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LuaSyntax {
+    /// Compound assignments like `a += 1`
+    CompoundAssignment,
+    /// `continue` statements
+    Continue,
+    /// If expressions like `if a then b else c`
+    IfExpression,
+    /// Interpolated strings like `` `value = {value}` ``
+    InterpolatedString,
+    /// Type annotations, type casts and type declarations
+    Type,
+    /// The floor division operator (`//`)
+    FloorDivision,
+    /// The bitwise operators (`&`, `|`, `~`, `<<` and `>>`)
+    BitwiseOperator,
+}
+
+impl fmt::Display for LuaSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CompoundAssignment => write!(f, "compound assignments"),
+            Self::Continue => write!(f, "continue statements"),
+            Self::IfExpression => write!(f, "if expressions"),
+            Self::InterpolatedString => write!(f, "interpolated strings"),
+            Self::Type => write!(f, "types"),
+            Self::FloorDivision => write!(f, "floor divisions"),
+            Self::BitwiseOperator => write!(f, "bitwise operators"),
+        }
+    }
+}
+
+struct FindUnsupportedSyntax {
+    target: LuaVersion,
+    found: Option<LuaSyntax>,
+}
+
+impl FindUnsupportedSyntax {
+    fn verify(&mut self, syntax: LuaSyntax) {
+        if self.found.is_none() && !self.target.supports(syntax) {
+            self.found = Some(syntax);
+        }
+    }
+}
+
+impl NodeProcessor for FindUnsupportedSyntax {
+    fn process_compound_assign_statement(&mut self, _: &mut CompoundAssignStatement) {
+        self.verify(LuaSyntax::CompoundAssignment);
+    }
+
+    fn process_last_statement(&mut self, statement: &mut LastStatement) {
+        if let LastStatement::Continue(_) = statement {
+            self.verify(LuaSyntax::Continue);
+        }
+    }
+
+    fn process_type_declaration(&mut self, _: &mut TypeDeclarationStatement) {
+        self.verify(LuaSyntax::Type);
+    }
+
+    fn process_type_function(&mut self, _: &mut TypeFunctionStatement) {
+        self.verify(LuaSyntax::Type);
+    }
+
+    fn process_expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::If(_) => self.verify(LuaSyntax::IfExpression),
+            Expression::InterpolatedString(_) => self.verify(LuaSyntax::InterpolatedString),
+            Expression::TypeCast(_) => self.verify(LuaSyntax::Type),
+            _ => {}
+        }
+    }
+
+    fn process_binary_expression(&mut self, binary: &mut BinaryExpression) {
+        match binary.operator() {
+            BinaryOperator::DoubleSlash => self.verify(LuaSyntax::FloorDivision),
+            BinaryOperator::BitwiseAnd
+            | BinaryOperator::BitwiseOr
+            | BinaryOperator::BitwiseXor
+            | BinaryOperator::ShiftLeft
+            | BinaryOperator::ShiftRight => self.verify(LuaSyntax::BitwiseOperator),
+            _ => {}
+        }
+    }
+
+    fn process_unary_expression(&mut self, unary: &mut UnaryExpression) {
+        if unary.operator() == UnaryOperator::BitwiseNot {
+            self.verify(LuaSyntax::BitwiseOperator);
+        }
+    }
+
+    fn process_type(&mut self, _: &mut Type) {
+        self.verify(LuaSyntax::Type);
+    }
+}
+
+/// Returns the first syntax of the block that the given Lua version does not support.
+pub(crate) fn find_unsupported_syntax(block: &mut Block, target: LuaVersion) -> Option<LuaSyntax> {
+    let mut processor = FindUnsupportedSyntax {
+        target,
+        found: None,
+    };
+    DefaultVisitor::visit_block(block, &mut processor);
+    processor.found
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::Parser;
+
+    fn find(code: &str, target: LuaVersion) -> Option<LuaSyntax> {
+        let mut block = Parser::default().parse(code).expect("unable to parse code");
+        find_unsupported_syntax(&mut block, target)
+    }
+
+    #[test]
+    fn deserialize_lua51() {
+        let version: LuaVersion = json5::from_str("'lua51'").unwrap();
+        pretty_assertions::assert_eq!(version, LuaVersion::Lua51);
+    }
+
+    #[test]
+    fn deserialize_luau() {
+        let version: LuaVersion = json5::from_str("'luau'").unwrap();
+        pretty_assertions::assert_eq!(version, LuaVersion::Luau);
+    }
+
+    #[test]
+    fn deserialize_unknown_version_error() {
+        assert!(json5::from_str::<LuaVersion>("'lua50'").is_err());
+    }
+
+    #[test]
+    fn lua51_does_not_support_if_expression() {
+        pretty_assertions::assert_eq!(
+            find("return if a then b else c", LuaVersion::Lua51),
+            Some(LuaSyntax::IfExpression)
+        );
+    }
+
+    #[test]
+    fn lua51_does_not_support_compound_assignment() {
+        pretty_assertions::assert_eq!(
+            find("a += 1", LuaVersion::Lua51),
+            Some(LuaSyntax::CompoundAssignment)
+        );
+    }
+
+    #[test]
+    fn lua51_does_not_support_interpolated_string() {
+        pretty_assertions::assert_eq!(
+            find("return `{a}`", LuaVersion::Lua51),
+            Some(LuaSyntax::InterpolatedString)
+        );
+    }
+
+    #[test]
+    fn lua51_does_not_support_continue() {
+        pretty_assertions::assert_eq!(
+            find("while true do continue end", LuaVersion::Lua51),
+            Some(LuaSyntax::Continue)
+        );
+    }
+
+    #[test]
+    fn lua51_does_not_support_type_annotation() {
+        pretty_assertions::assert_eq!(
+            find("local a: number = 1", LuaVersion::Lua51),
+            Some(LuaSyntax::Type)
+        );
+    }
+
+    #[test]
+    fn lua51_does_not_support_floor_division() {
+        pretty_assertions::assert_eq!(
+            find("return a // b", LuaVersion::Lua51),
+            Some(LuaSyntax::FloorDivision)
+        );
+    }
+
+    #[test]
+    fn lua53_supports_floor_division() {
+        pretty_assertions::assert_eq!(find("return a // b", LuaVersion::Lua53), None);
+    }
+
+    #[test]
+    fn luau_supports_if_expression() {
+        pretty_assertions::assert_eq!(find("return if a then b else c", LuaVersion::Luau), None);
+    }
+
+    #[test]
+    fn lua51_supports_plain_code() {
+        pretty_assertions::assert_eq!(
+            find(
+                "local a = 1 while a < 10 do a = a + 1 end return a",
+                LuaVersion::Lua51
+            ),
+            None
+        );
+    }
+}
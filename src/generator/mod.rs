@@ -2,12 +2,22 @@
 //! and its implementations.
 
 mod dense;
+mod format;
+mod lua_version;
+mod output;
 mod readable;
+mod render;
 mod token_based;
 mod utils;
 
 pub use dense::DenseLuaGenerator;
+pub(crate) use lua_version::find_unsupported_syntax;
+pub use lua_version::{LuaSyntax, LuaVersion};
+pub use output::GeneratorOutput;
+pub(crate) use output::WriterOutput;
 pub use readable::ReadableLuaGenerator;
+pub(crate) use render::generate_with;
+pub use render::{render_expression, render_statement, render_type};
 pub use token_based::TokenBasedLuaGenerator;
 
 use crate::nodes;
@@ -37,6 +47,7 @@ pub trait LuaGenerator {
             Repeat(statement) => self.write_repeat_statement(statement),
             While(statement) => self.write_while_statement(statement),
             TypeDeclaration(statement) => self.write_type_declaration_statement(statement),
+            TypeFunction(statement) => self.write_type_function_statement(statement),
         }
     }
 
@@ -53,6 +64,7 @@ pub trait LuaGenerator {
     fn write_repeat_statement(&mut self, repeat: &nodes::RepeatStatement);
     fn write_while_statement(&mut self, while_statement: &nodes::WhileStatement);
     fn write_type_declaration_statement(&mut self, statement: &nodes::TypeDeclarationStatement);
+    fn write_type_function_statement(&mut self, statement: &nodes::TypeFunctionStatement);
 
     fn write_variable(&mut self, variable: &nodes::Variable) {
         use nodes::Variable::*;
@@ -309,11 +321,20 @@ mod test {
             $generator:expr => (
                 $($name:ident => $code:literal),+,
             )
+        ) => {
+            blocks_consistency!($generator, $crate::Parser::default() => (
+                $($name => $code),+,
+            ));
+        };
+        (
+            $generator:expr, $parser:expr => (
+                $($name:ident => $code:literal),+,
+            )
         ) => {
             $(
                 #[test]
                 fn $name() {
-                    let parser = $crate::Parser::default();
+                    let parser = $parser;
 
                     let expected_block = parser.parse($code)
                         .expect(&format!("unable to parse `{}`", $code));
@@ -336,11 +357,20 @@ mod test {
             $generator:expr => (
                 $($name:ident($input:expr) => $expected:literal),+,
             )
+        ) => {
+            binary_precedence!($generator, $crate::Parser::default() => (
+                $($name($input) => $expected),+,
+            ));
+        };
+        (
+            $generator:expr, $parser:expr => (
+                $($name:ident($input:expr) => $expected:literal),+,
+            )
         ) => {
             $(
                 #[test]
                 fn $name() {
-                    let parser = $crate::Parser::default();
+                    let parser = $parser;
 
                     let expected_block = parser.parse(&format!("return {}", $expected))
                         .unwrap();
@@ -399,7 +429,29 @@ mod $mod_name {
             concat_variable_arguments_with_number => "return ... ..1",
             double_unary_minus => "return - -10",
             binary_minus_with_unary_minus => "return 100- -10",
+            string_with_new_lines => "return 'a\\nb\\nc\\nd\\ne'",
+            string_with_closing_long_bracket => "return 'a]]\\\\b\\\\c\\\\d\\\\e\\\\f'",
+            string_ending_with_partial_closing_long_bracket => "return 'a]]\\\\b\\\\c\\\\d\\\\e\\\\f]='",
+            string_with_escape_followed_by_digit => "return '\\0271'",
+            interpolated_string_with_table => "return `{ {a = 1} }`",
+            interpolated_string_with_nested_table => "return `{ { {} } }`",
+            interpolated_string_with_nested_interpolated_string => "return `outer {`inner {x}`}`",
+            interpolated_string_with_table_in_nested_interpolated_string => "return `{`{ {a = `{b}`} }`}`",
+            interpolated_string_with_closing_brace_string => "return `{'}'} {\"{\"}`",
+            type_function => "type function Identity(t) return t end",
+            exported_type_function => "export type function Identity(t) return t end",
         ));
+
+        blocks_consistency!(
+            $generator,
+            $crate::Parser::default().with_lua_version($crate::generator::LuaVersion::Lua54) => (
+                bitwise_xor_with_bitwise_not => "return a~~b",
+                not_equal_with_bitwise_not => "return a~=~b",
+                double_bitwise_not => "return ~~a",
+                shift_left_and_shift_right => "return a<<b>>c",
+                bitwise_operators_with_numbers => "return 1&2|3~4",
+            )
+        );
     }
 
     mod numbers {
@@ -412,6 +464,13 @@ mod $mod_name {
             hex_number => "0x12",
             hex_number_with_letter => "0x12a",
             hex_with_exponent => "0x12p4",
+            hex_float => "0x1.8",
+            hex_float_with_exponent => "0x1.8p3",
+            hex_float_without_integer => "0x.1p4",
+            hex_float_with_leading_zero_fraction => "0x1.08",
+            hex_with_negative_exponent => "0x1p-4",
+            hex_float_with_negative_exponent => "0x1.8p-3",
+            hex_float_with_many_fraction_digits => "0x1.00000000000000001p0",
         ));
     }
 
@@ -560,6 +619,106 @@ mod $mod_name {
                 )
             ) => "(not if condition then true else false) and 'ok'",
         ));
+
+        binary_precedence!(
+            $generator,
+            $crate::Parser::default().with_lua_version($crate::generator::LuaVersion::Lua54) => (
+                bitwise_or_wraps_left_comparison(
+                    BinaryExpression::new(
+                        BinaryOperator::BitwiseOr,
+                        BinaryExpression::new(
+                            BinaryOperator::Equal,
+                            Expression::identifier("a"),
+                            Expression::identifier("b"),
+                        ),
+                        Expression::identifier("c"),
+                    )
+                ) => "(a == b) | c",
+                bitwise_and_wraps_right_bitwise_or(
+                    BinaryExpression::new(
+                        BinaryOperator::BitwiseAnd,
+                        Expression::identifier("a"),
+                        BinaryExpression::new(
+                            BinaryOperator::BitwiseOr,
+                            Expression::identifier("b"),
+                            Expression::identifier("c"),
+                        ),
+                    )
+                ) => "a & (b | c)",
+                bitwise_or_does_not_wrap_right_bitwise_and(
+                    BinaryExpression::new(
+                        BinaryOperator::BitwiseOr,
+                        Expression::identifier("a"),
+                        BinaryExpression::new(
+                            BinaryOperator::BitwiseAnd,
+                            Expression::identifier("b"),
+                            Expression::identifier("c"),
+                        ),
+                    )
+                ) => "a | b & c",
+                bitwise_xor_wraps_left_bitwise_or(
+                    BinaryExpression::new(
+                        BinaryOperator::BitwiseXor,
+                        BinaryExpression::new(
+                            BinaryOperator::BitwiseOr,
+                            Expression::identifier("a"),
+                            Expression::identifier("b"),
+                        ),
+                        Expression::identifier("c"),
+                    )
+                ) => "(a | b) ~ c",
+                shift_left_does_not_wrap_left_concat(
+                    BinaryExpression::new(
+                        BinaryOperator::ShiftLeft,
+                        BinaryExpression::new(
+                            BinaryOperator::Concat,
+                            Expression::identifier("a"),
+                            Expression::identifier("b"),
+                        ),
+                        Expression::identifier("c"),
+                    )
+                ) => "a .. b << c",
+                concat_wraps_left_shift_right(
+                    BinaryExpression::new(
+                        BinaryOperator::Concat,
+                        BinaryExpression::new(
+                            BinaryOperator::ShiftRight,
+                            Expression::identifier("a"),
+                            Expression::identifier("b"),
+                        ),
+                        Expression::identifier("c"),
+                    )
+                ) => "(a >> b) .. c",
+                shift_left_wraps_right_shift_right(
+                    BinaryExpression::new(
+                        BinaryOperator::ShiftLeft,
+                        Expression::identifier("a"),
+                        BinaryExpression::new(
+                            BinaryOperator::ShiftRight,
+                            Expression::identifier("b"),
+                            Expression::identifier("c"),
+                        ),
+                    )
+                ) => "a << (b >> c)",
+                bitwise_not_wraps_bitwise_and(
+                    UnaryExpression::new(
+                        UnaryOperator::BitwiseNot,
+                        BinaryExpression::new(
+                            BinaryOperator::BitwiseAnd,
+                            Expression::identifier("a"),
+                            Expression::identifier("b"),
+                        ),
+                    )
+                ) => "~(a & b)",
+                bitwise_xor_with_bitwise_not(
+                    BinaryExpression::new(
+                        BinaryOperator::BitwiseXor,
+                        Expression::identifier("a"),
+                        UnaryExpression::new(UnaryOperator::BitwiseNot, Expression::identifier("b")),
+                    )
+                ) => "a ~ ~b",
+            )
+        );
     }
 
     mod snapshots {
@@ -1122,4 +1281,474 @@ mod $mod_name {
     snapshot_generator!(dense, DenseLuaGenerator::default());
     snapshot_generator!(readable, ReadableLuaGenerator::default());
     snapshot_generator!(token_based, TokenBasedLuaGenerator::new(""));
+
+    mod dense_semicolons {
+        use super::*;
+        use crate::Parser;
+
+        const CODE: &str = "local a = 1 print(a) do a = a + 1 call() end (fn)() return a";
+
+        fn generate(generator: DenseLuaGenerator) -> String {
+            let block = Parser::default().parse(CODE).unwrap();
+            let mut generator = generator;
+            generator.write_block(&block);
+            generator.into_string()
+        }
+
+        #[test]
+        fn minimal_semicolons() {
+            insta::assert_snapshot!(
+                "dense_minimal_semicolons",
+                generate(DenseLuaGenerator::default())
+            );
+        }
+
+        #[test]
+        fn always_semicolons() {
+            insta::assert_snapshot!(
+                "dense_always_semicolons",
+                generate(DenseLuaGenerator::default().with_always_semicolons())
+            );
+        }
+
+        #[test]
+        fn minimal_and_always_semicolons_parse_to_the_same_block() {
+            let parser = Parser::default();
+            let minimal = parser
+                .parse(&generate(DenseLuaGenerator::default()))
+                .unwrap();
+            let always = parser
+                .parse(&generate(
+                    DenseLuaGenerator::default().with_always_semicolons(),
+                ))
+                .unwrap();
+
+            pretty_assertions::assert_eq!(minimal, always);
+            pretty_assertions::assert_eq!(minimal, parser.parse(CODE).unwrap());
+        }
+    }
+
+    mod dense_token_separation {
+        use super::*;
+        use crate::Parser;
+
+        fn generate(generator: DenseLuaGenerator, code: &str) -> String {
+            let block = Parser::default().parse(code).unwrap();
+            let mut generator = generator;
+            generator.write_block(&block);
+            generator.into_string()
+        }
+
+        macro_rules! test_output {
+            ($($name:ident($code:literal) => ($dense:literal, $spaced:literal)),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let parser = Parser::default();
+                        let expected_block = parser.parse($code).unwrap();
+
+                        let dense = generate(DenseLuaGenerator::default(), $code);
+                        pretty_assertions::assert_eq!(dense, $dense);
+                        pretty_assertions::assert_eq!(parser.parse(&dense).unwrap(), expected_block);
+
+                        let spaced = generate(
+                            DenseLuaGenerator::default().with_spaced_binary_operators(),
+                            $code,
+                        );
+                        pretty_assertions::assert_eq!(spaced, $spaced);
+                        pretty_assertions::assert_eq!(parser.parse(&spaced).unwrap(), expected_block);
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            binary_minus_followed_by_unary_minus("return a - -b")
+                => ("return a- -b", "return a - -b"),
+            binary_minus_followed_by_negative_number("return 1 - -1")
+                => ("return 1- -1", "return 1 - -1"),
+            unary_minus_followed_by_unary_minus("return - -a")
+                => ("return- -a", "return- -a"),
+            number_concat_number("return 9 ..3")
+                => ("return 9 .. 3", "return 9 .. 3"),
+            identifier_concat_number("return a .. 1")
+                => ("return a.. 1", "return a .. 1"),
+            concat_variable_arguments("return a .. ...")
+                => ("return a.. ...", "return a .. ..."),
+            arithmetic_operators("return a + b * c")
+                => ("return a+b*c", "return a + b * c"),
+            comparison_operators("return a >= b == (c < d)")
+                => ("return a>=b==(c<d)", "return a >= b == (c < d)"),
+            logical_operators("return a and b or c")
+                => ("return a and b or c", "return a and b or c"),
+        );
+    }
+
+    mod readable_table_alignment {
+        use super::*;
+        use crate::Parser;
+
+        const CODE: &str = "local config = { name = true, [1] = 1, version = 2, x = 0, 10, \
+            enabled = false, nested = { a = 1, long_name = 2 } }";
+
+        fn generate(generator: ReadableLuaGenerator, code: &str) -> String {
+            let block = Parser::default().parse(code).unwrap();
+            let mut generator = generator;
+            generator.write_block(&block);
+            generator.into_string()
+        }
+
+        #[test]
+        fn not_aligned() {
+            insta::assert_snapshot!(
+                "readable_table_not_aligned",
+                generate(ReadableLuaGenerator::default(), CODE)
+            );
+        }
+
+        #[test]
+        fn aligned() {
+            insta::assert_snapshot!(
+                "readable_table_aligned",
+                generate(
+                    ReadableLuaGenerator::default().with_aligned_table_entries(),
+                    CODE
+                )
+            );
+        }
+
+        #[test]
+        fn single_line_table_is_not_aligned() {
+            pretty_assertions::assert_eq!(
+                generate(
+                    ReadableLuaGenerator::default().with_aligned_table_entries(),
+                    "local point = { x = 1 }"
+                ),
+                generate(ReadableLuaGenerator::default(), "local point = { x = 1 }"),
+            );
+        }
+    }
+
+    mod readable_table_trailing_commas {
+        use super::*;
+        use crate::Parser;
+
+        const CODE: &str = "local config = { name = true, [1] = 1, version = 2, x = 0, 10, \
+            enabled = false, nested = { a = 1, long_name = 2 } }";
+
+        fn generate(generator: impl LuaGenerator) -> String {
+            let block = Parser::default().parse(CODE).unwrap();
+            let mut generator = generator;
+            generator.write_block(&block);
+            generator.into_string()
+        }
+
+        #[test]
+        fn with_trailing_commas() {
+            insta::assert_snapshot!(
+                "readable_table_with_trailing_commas",
+                generate(ReadableLuaGenerator::default())
+            );
+        }
+
+        #[test]
+        fn without_trailing_commas() {
+            insta::assert_snapshot!(
+                "readable_table_without_trailing_commas",
+                generate(ReadableLuaGenerator::default().without_trailing_commas())
+            );
+        }
+
+        #[test]
+        fn generated_code_is_equivalent() {
+            let parser = Parser::default();
+            let expected = parser.parse(CODE).unwrap();
+
+            for code in [
+                generate(ReadableLuaGenerator::default()),
+                generate(ReadableLuaGenerator::default().without_trailing_commas()),
+            ] {
+                pretty_assertions::assert_eq!(expected, parser.parse(&code).unwrap());
+            }
+        }
+
+        #[test]
+        fn dense_generator_does_not_write_trailing_commas() {
+            let code = generate(DenseLuaGenerator::default());
+
+            assert!(!code.contains(",}"));
+        }
+    }
+
+    mod dense_writer {
+        use super::*;
+        use crate::Parser;
+
+        fn get_code() -> String {
+            (0..500)
+                .map(|i| {
+                    format!(
+                        "local value{i} = {{ name = 'item{i}', index = {i} }}\n\
+                        function module.process{i}(a, b, ...)\n\
+                            if a > b then return a .. b elseif a == {i} then return nil end\n\
+                            for k, v in pairs(value{i}) do print(k, v, ...) end\n\
+                            return (a + b) * {i} - #value{i}.name\n\
+                        end\n"
+                    )
+                })
+                .collect()
+        }
+
+        fn assert_same_output(generator: DenseLuaGenerator) {
+            let block = Parser::default().parse(&get_code()).unwrap();
+
+            let mut string_generator = generator.clone();
+            string_generator.write_block(&block);
+            let expected = string_generator.into_string();
+
+            let written = generator.write_block_to(&block, Vec::new()).unwrap();
+
+            pretty_assertions::assert_eq!(String::from_utf8(written).unwrap(), expected);
+        }
+
+        #[test]
+        fn writes_same_output_as_into_string() {
+            assert_same_output(DenseLuaGenerator::default());
+        }
+
+        #[test]
+        fn writes_same_output_as_into_string_with_small_column_span() {
+            assert_same_output(DenseLuaGenerator::new(20));
+        }
+
+        #[test]
+        fn writes_same_output_as_into_string_with_always_semicolons() {
+            assert_same_output(DenseLuaGenerator::default().with_always_semicolons());
+        }
+    }
+
+    mod escape_non_ascii {
+        use crate::frontend::GeneratorParameters;
+        use crate::generator::generate_with;
+
+        fn generate(code: &str, parameters: &GeneratorParameters) -> String {
+            let block = parameters.build_parser().parse(code).unwrap();
+            generate_with(parameters, code, |generator| generator.write_block(&block))
+        }
+
+        /// Decodes the bytes of a string literal the way Lua reads them, so that
+        /// strings written with different escape sequences can be compared.
+        fn decode_string_literal(literal: &str) -> Vec<u8> {
+            if literal.starts_with('[') {
+                let level = literal[1..].chars().take_while(|c| *c == '=').count();
+                let content = &literal[level + 2..literal.len() - level - 2];
+                return content
+                    .strip_prefix('\n')
+                    .unwrap_or(content)
+                    .as_bytes()
+                    .to_vec();
+            }
+
+            let content = literal[1..literal.len() - 1].as_bytes();
+            let mut bytes = Vec::new();
+            let mut index = 0;
+
+            while index < content.len() {
+                if content[index] != b'\\' {
+                    bytes.push(content[index]);
+                    index += 1;
+                    continue;
+                }
+
+                index += 1;
+                match content[index] {
+                    b'n' => bytes.push(b'\n'),
+                    b't' => bytes.push(b'\t'),
+                    b'r' => bytes.push(b'\r'),
+                    b'a' => bytes.push(7),
+                    b'b' => bytes.push(8),
+                    b'v' => bytes.push(11),
+                    b'f' => bytes.push(12),
+                    b'x' => {
+                        let digits = std::str::from_utf8(&content[index + 1..index + 3]).unwrap();
+                        bytes.push(u8::from_str_radix(digits, 16).unwrap());
+                        index += 2;
+                    }
+                    b'u' => {
+                        let end = index + content[index..].iter().position(|b| *b == b'}').unwrap();
+                        let digits = std::str::from_utf8(&content[index + 2..end]).unwrap();
+                        let character =
+                            char::from_u32(u32::from_str_radix(digits, 16).unwrap()).unwrap();
+                        let mut buffer = [0; 4];
+                        bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                        index = end;
+                    }
+                    b'0'..=b'9' => {
+                        let length = content[index..]
+                            .iter()
+                            .take(3)
+                            .take_while(|b| b.is_ascii_digit())
+                            .count();
+                        let digits = std::str::from_utf8(&content[index..index + length]).unwrap();
+                        bytes.push(digits.parse().unwrap());
+                        index += length - 1;
+                    }
+                    other => bytes.push(other),
+                }
+                index += 1;
+            }
+
+            bytes
+        }
+
+        fn get_returned_bytes(code: &str) -> Vec<u8> {
+            let literal = code
+                .trim()
+                .strip_prefix("return")
+                .unwrap_or_else(|| panic!("expected return statement in `{}`", code))
+                .trim();
+            decode_string_literal(literal)
+        }
+
+        fn token_based_parameters(escape_non_ascii: bool) -> Vec<GeneratorParameters> {
+            vec![
+                GeneratorParameters::RetainLines { escape_non_ascii },
+                GeneratorParameters::Format { escape_non_ascii },
+            ]
+        }
+
+        fn all_parameters(escape_non_ascii: bool) -> Vec<GeneratorParameters> {
+            let mut parameters = token_based_parameters(escape_non_ascii);
+            parameters.push(GeneratorParameters::Dense {
+                column_span: 80,
+                always_semicolons: false,
+                spaced_binary_operators: false,
+                escape_non_ascii,
+            });
+            parameters.push(GeneratorParameters::Readable {
+                column_span: 80,
+                align_table_entries: false,
+                trailing_commas: true,
+                escape_non_ascii,
+            });
+            parameters
+        }
+
+        macro_rules! test_escape_non_ascii {
+            ($parameters:ident => $($name:ident ($code:literal) => $escaped:literal),* $(,)?) => {
+                $(
+                    mod $name {
+                        use super::*;
+
+                        #[test]
+                        fn escaped_output() {
+                            let parameters = GeneratorParameters::RetainLines {
+                                escape_non_ascii: true,
+                            };
+
+                            pretty_assertions::assert_eq!(generate($code, &parameters), $escaped);
+                        }
+
+                        #[test]
+                        fn raw_output() {
+                            pretty_assertions::assert_eq!(
+                                generate($code, &GeneratorParameters::default_retain_lines()),
+                                $code
+                            );
+                        }
+
+                        #[test]
+                        fn reparse_to_the_same_bytes() {
+                            let expected = get_returned_bytes($code);
+
+                            for escape_non_ascii in [false, true] {
+                                for parameters in $parameters(escape_non_ascii) {
+                                    let output = generate($code, &parameters);
+
+                                    pretty_assertions::assert_eq!(
+                                        get_returned_bytes(&output),
+                                        expected,
+                                        "generated `{}` with {:?}",
+                                        output,
+                                        parameters,
+                                    );
+
+                                    if escape_non_ascii {
+                                        assert!(output.is_ascii(), "`{}` is not ASCII", output);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                )*
+            };
+        }
+
+        test_escape_non_ascii!(all_parameters =>
+            emoji("return '🎉'") => "return '\\240\\159\\142\\137'",
+            latin_1_character("return 'café'") => "return 'caf\\195\\169'",
+            followed_by_digit("return 'é1'") => "return '\\195\\1691'",
+            mixed_with_escape_sequences("return 'é\\n\\'é\\''")
+                => "return '\\195\\169\\n\\'\\195\\169\\''",
+            long_string("return [[🎉 café]]") => "return '\\240\\159\\142\\137 caf\\195\\169'",
+            interpolated_string("return `🎉 {value} café`")
+                => "return `\\240\\159\\142\\137 {value} caf\\195\\169`",
+            ascii_string("return 'abc'") => "return 'abc'",
+        );
+
+        // the value of a string node stores escaped bytes as characters, so only the
+        // token-based generators can write back a byte that is not valid UTF-8
+        test_escape_non_ascii!(token_based_parameters =>
+            latin_1_escaped_byte("return 'caf\\xE9'") => "return 'caf\\xE9'",
+        );
+
+        #[test]
+        fn dense_generator_escapes_non_ascii() {
+            let parameters = GeneratorParameters::Dense {
+                column_span: 80,
+                always_semicolons: false,
+                spaced_binary_operators: false,
+                escape_non_ascii: true,
+            };
+
+            pretty_assertions::assert_eq!(
+                generate("return 'café', `🎉 {value}`", &parameters),
+                "return'caf\\195\\169',`\\240\\159\\142\\137 {value}`"
+            );
+        }
+
+        #[test]
+        fn dense_generator_writes_non_ascii() {
+            pretty_assertions::assert_eq!(
+                generate("return 'café'", &GeneratorParameters::default_dense()),
+                "return'café'"
+            );
+        }
+
+        #[test]
+        fn deserialize_retain_lines_with_escape_non_ascii() {
+            let parameters: GeneratorParameters =
+                json5::from_str("{ name: 'retain_lines', escape_non_ascii: true }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                parameters,
+                GeneratorParameters::RetainLines {
+                    escape_non_ascii: true
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_format_with_escape_non_ascii() {
+            let parameters: GeneratorParameters =
+                json5::from_str("{ name: 'format', escape_non_ascii: true }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                parameters,
+                GeneratorParameters::Format {
+                    escape_non_ascii: true
+                }
+            );
+        }
+    }
 }
@@ -0,0 +1,119 @@
+use std::io;
+
+/// The destination of the code produced by the [`DenseLuaGenerator`](super::DenseLuaGenerator).
+///
+/// Generators only read and modify the end of the generated code. When they know
+/// that the code before a given index will not change anymore, they call
+/// [`commit`](GeneratorOutput::commit) so that the output can release it.
+pub trait GeneratorOutput {
+    fn push(&mut self, character: char);
+    fn push_str(&mut self, content: &str);
+    fn pop(&mut self) -> Option<char>;
+    /// Returns the part of the generated code that has not been committed.
+    fn as_str(&self) -> &str;
+    /// Marks the content before `index` (from [`as_str`](GeneratorOutput::as_str)) as
+    /// final.
+    fn commit(&mut self, index: usize);
+    fn into_string(self) -> String;
+}
+
+impl GeneratorOutput for String {
+    #[inline]
+    fn push(&mut self, character: char) {
+        String::push(self, character);
+    }
+
+    #[inline]
+    fn push_str(&mut self, content: &str) {
+        String::push_str(self, content);
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<char> {
+        String::pop(self)
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn commit(&mut self, _index: usize) {}
+
+    #[inline]
+    fn into_string(self) -> String {
+        self
+    }
+}
+
+/// The minimum amount of bytes to accumulate before writing committed content.
+const MINIMUM_WRITE_LENGTH: usize = 8 * 1024;
+
+/// A generator output that writes the committed code into a writer.
+pub(crate) struct WriterOutput<W> {
+    buffer: String,
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> WriterOutput<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            buffer: String::new(),
+            writer,
+            error: None,
+        }
+    }
+
+    /// Writes the remaining content and returns the writer.
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.writer.write_all(self.buffer.as_bytes())?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: io::Write> GeneratorOutput for WriterOutput<W> {
+    #[inline]
+    fn push(&mut self, character: char) {
+        self.buffer.push(character);
+    }
+
+    #[inline]
+    fn push_str(&mut self, content: &str) {
+        self.buffer.push_str(content);
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<char> {
+        self.buffer.pop()
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    fn commit(&mut self, index: usize) {
+        if index < MINIMUM_WRITE_LENGTH || self.error.is_some() {
+            return;
+        }
+
+        match self.writer.write_all(self.buffer[..index].as_bytes()) {
+            Ok(()) => {
+                self.buffer.drain(..index);
+            }
+            Err(error) => {
+                self.error = Some(error);
+            }
+        }
+    }
+
+    fn into_string(self) -> String {
+        self.buffer
+    }
+}
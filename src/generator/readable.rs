@@ -16,6 +16,7 @@ enum StatementType {
     Repeat,
     While,
     TypeDeclaration,
+    TypeFunction,
     Return,
     Break,
     Continue,
@@ -38,6 +39,7 @@ impl From<&nodes::Statement> for StatementType {
             Repeat(_) => Self::Repeat,
             While(_) => Self::While,
             TypeDeclaration(_) => Self::TypeDeclaration,
+            TypeFunction(_) => Self::TypeFunction,
         }
     }
 }
@@ -58,6 +60,9 @@ impl From<&nodes::LastStatement> for StatementType {
 #[derive(Debug, Clone)]
 pub struct ReadableLuaGenerator {
     column_span: usize,
+    align_table_entries: bool,
+    trailing_commas: bool,
+    escape_non_ascii: bool,
     indentation: usize,
     current_line_length: usize,
     current_indentation: usize,
@@ -70,6 +75,9 @@ impl ReadableLuaGenerator {
     pub fn new(column_span: usize) -> Self {
         Self {
             column_span,
+            align_table_entries: false,
+            trailing_commas: true,
+            escape_non_ascii: false,
             indentation: 4,
             current_line_length: 0,
             current_indentation: 0,
@@ -79,6 +87,27 @@ impl ReadableLuaGenerator {
         }
     }
 
+    /// Aligns the `=` of field entries (like `name = value`) when a table is written
+    /// on multiple lines. Index entries and array entries are not aligned.
+    pub fn with_aligned_table_entries(mut self) -> Self {
+        self.align_table_entries = true;
+        self
+    }
+
+    /// Removes the comma written after the last entry of tables written on multiple
+    /// lines.
+    pub fn without_trailing_commas(mut self) -> Self {
+        self.trailing_commas = false;
+        self
+    }
+
+    /// Escapes the non-ASCII characters of strings with decimal escape sequences, so
+    /// that the output only contains ASCII characters inside strings.
+    pub fn with_escaped_non_ascii(mut self) -> Self {
+        self.escape_non_ascii = true;
+        self
+    }
+
     #[inline]
     fn can_add_new_line(&self) -> bool {
         self.can_add_new_line_stack.last().copied().unwrap_or(true)
@@ -263,6 +292,17 @@ impl ReadableLuaGenerator {
                 })
     }
 
+    /// Writes a field entry, padding the field name with spaces up to the given width.
+    fn write_table_field_entry(&mut self, entry: &nodes::TableFieldEntry, name_width: usize) {
+        let name = entry.get_field().get_name();
+        self.raw_push_str(name);
+        for _ in name.len()..name_width {
+            self.raw_push_char(' ');
+        }
+        self.raw_push_str(" = ");
+        self.write_expression(entry.get_value());
+    }
+
     fn is_small_expression(&self, expression: &nodes::Expression) -> bool {
         use nodes::Expression::*;
         match expression {
@@ -813,6 +853,42 @@ impl LuaGenerator for ReadableLuaGenerator {
         }
     }
 
+    fn write_type_function_statement(&mut self, statement: &nodes::TypeFunctionStatement) {
+        if statement.is_exported() {
+            self.push_str("export ");
+        }
+        self.push_str("type function ");
+        self.raw_push_str(statement.get_name());
+
+        if let Some(generics) = statement.get_generic_parameters() {
+            self.write_function_generics(generics);
+        }
+
+        self.raw_push_char('(');
+
+        let parameters = statement.get_parameters();
+        self.write_function_parameters(
+            parameters,
+            statement.is_variadic(),
+            statement.get_variadic_type(),
+        );
+        self.raw_push_char(')');
+
+        if let Some(return_type) = statement.get_return_type() {
+            self.write_function_return_type_suffix(return_type);
+        }
+
+        let block = statement.get_block();
+
+        if block.is_empty() {
+            self.raw_push_str(" end");
+        } else {
+            self.push_new_line();
+            self.indent_and_write_block(block);
+            self.push_str("end");
+        }
+    }
+
     fn write_type_declaration_statement(&mut self, statement: &nodes::TypeDeclarationStatement) {
         if statement.is_exported() {
             self.push_str("export");
@@ -916,6 +992,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             Length => self.push_char('#'),
             Minus => self.push_str_and_break_if("-", utils::break_minus),
             Not => self.push_str("not "),
+            BitwiseNot => self.push_char('~'),
         }
 
         let expression = unary.get_expression();
@@ -1075,14 +1152,39 @@ impl LuaGenerator for ReadableLuaGenerator {
                     }
                 });
             } else {
+                let field_name_width = if self.align_table_entries {
+                    entries
+                        .iter()
+                        .filter_map(|entry| match entry {
+                            nodes::TableEntry::Field(entry) => {
+                                Some(entry.get_field().get_name().len())
+                            }
+                            _ => None,
+                        })
+                        .max()
+                        .unwrap_or(0)
+                } else {
+                    0
+                };
+
+                let last_index = table_len.saturating_sub(1);
+
                 self.push_indentation();
 
-                entries.iter().for_each(|entry| {
+                entries.iter().enumerate().for_each(|(index, entry)| {
                     self.push_new_line();
                     self.write_indentation();
-                    self.write_table_entry(entry);
 
-                    self.raw_push_char(',');
+                    match entry {
+                        nodes::TableEntry::Field(entry) => {
+                            self.write_table_field_entry(entry, field_name_width)
+                        }
+                        _ => self.write_table_entry(entry),
+                    }
+
+                    if self.trailing_commas || index != last_index {
+                        self.raw_push_char(',');
+                    }
                 });
 
                 self.pop_indentation();
@@ -1095,11 +1197,7 @@ impl LuaGenerator for ReadableLuaGenerator {
 
     fn write_table_entry(&mut self, entry: &nodes::TableEntry) {
         match entry {
-            nodes::TableEntry::Field(entry) => {
-                self.raw_push_str(entry.get_field().get_name());
-                self.raw_push_str(" = ");
-                self.write_expression(entry.get_value());
-            }
+            nodes::TableEntry::Field(entry) => self.write_table_field_entry(entry, 0),
             nodes::TableEntry::Index(entry) => {
                 self.raw_push_char('[');
                 self.push_can_add_new_line(false);
@@ -1117,7 +1215,7 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_string(&mut self, string: &nodes::StringExpression) {
-        let result = utils::write_string(string.get_value());
+        let result = utils::write_string(string.get_value(), self.escape_non_ascii);
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -1134,7 +1232,10 @@ impl LuaGenerator for ReadableLuaGenerator {
         for segment in interpolated_string.iter_segments() {
             match segment {
                 nodes::InterpolationSegment::String(string_segment) => {
-                    self.raw_push_str(&utils::write_interpolated_string_segment(string_segment));
+                    self.raw_push_str(&utils::write_interpolated_string_segment(
+                        string_segment,
+                        self.escape_non_ascii,
+                    ));
                 }
                 nodes::InterpolationSegment::Value(value) => {
                     self.raw_push_char('{');
@@ -1224,7 +1325,7 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_string_type(&mut self, string_type: &nodes::StringType) {
-        let result = utils::write_string(string_type.get_value());
+        let result = utils::write_string(string_type.get_value(), self.escape_non_ascii);
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -0,0 +1,208 @@
+use crate::frontend::GeneratorParameters;
+use crate::generator::{
+    DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, TokenBasedLuaGenerator,
+};
+use crate::nodes::{Expression, Statement, Type};
+
+/// Creates the generator described by the parameters, writes into it with the given
+/// function and returns the generated code.
+pub(crate) fn generate_with(
+    parameters: &GeneratorParameters,
+    code: &str,
+    write: impl FnOnce(&mut dyn LuaGenerator),
+) -> String {
+    match parameters {
+        GeneratorParameters::RetainLines { escape_non_ascii } => {
+            let mut generator = TokenBasedLuaGenerator::new(code);
+            if *escape_non_ascii {
+                generator = generator.with_escaped_non_ascii();
+            }
+            write(&mut generator);
+            generator.into_string()
+        }
+        GeneratorParameters::Dense {
+            column_span,
+            always_semicolons,
+            spaced_binary_operators,
+            escape_non_ascii,
+        } => {
+            let mut generator = DenseLuaGenerator::new(*column_span);
+            if *always_semicolons {
+                generator = generator.with_always_semicolons();
+            }
+            if *spaced_binary_operators {
+                generator = generator.with_spaced_binary_operators();
+            }
+            if *escape_non_ascii {
+                generator = generator.with_escaped_non_ascii();
+            }
+            write(&mut generator);
+            generator.into_string()
+        }
+        GeneratorParameters::Readable {
+            column_span,
+            align_table_entries,
+            trailing_commas,
+            escape_non_ascii,
+        } => {
+            let mut generator = ReadableLuaGenerator::new(*column_span);
+            if *align_table_entries {
+                generator = generator.with_aligned_table_entries();
+            }
+            if !*trailing_commas {
+                generator = generator.without_trailing_commas();
+            }
+            if *escape_non_ascii {
+                generator = generator.with_escaped_non_ascii();
+            }
+            write(&mut generator);
+            generator.into_string()
+        }
+        GeneratorParameters::Format { escape_non_ascii } => {
+            let mut generator = TokenBasedLuaGenerator::new(code).with_format();
+            if *escape_non_ascii {
+                generator = generator.with_escaped_non_ascii();
+            }
+            write(&mut generator);
+            generator.into_string()
+        }
+    }
+}
+
+/// Generates the code of an expression with the generator described by the
+/// parameters.
+///
+/// The token-based generators (`retain_lines` and `format`) do not have access to
+/// the original code, so the expression must not contain tokens that reference it.
+/// Expressions built without tokens, or with tokens replaced using
+/// `replace_referenced_tokens`, can be rendered with any generator.
+pub fn render_expression(expression: &Expression, parameters: &GeneratorParameters) -> String {
+    generate_with(parameters, "", |generator| {
+        generator.write_expression(expression)
+    })
+}
+
+/// Generates the code of a statement with the generator described by the
+/// parameters. See [`render_expression`] for the restrictions that apply to
+/// token-based generators.
+pub fn render_statement(statement: &Statement, parameters: &GeneratorParameters) -> String {
+    generate_with(parameters, "", |generator| {
+        generator.write_statement(statement)
+    })
+}
+
+/// Generates the code of a type with the generator described by the parameters.
+/// See [`render_expression`] for the restrictions that apply to token-based
+/// generators.
+pub fn render_type(r#type: &Type, parameters: &GeneratorParameters) -> String {
+    generate_with(parameters, "", |generator| generator.write_type(r#type))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::nodes::{
+        BinaryExpression, BinaryOperator, LastStatement, LocalAssignStatement, StringExpression,
+        TableExpression,
+    };
+    use crate::Parser;
+
+    fn all_parameters() -> Vec<GeneratorParameters> {
+        vec![
+            GeneratorParameters::default_retain_lines(),
+            GeneratorParameters::default_dense(),
+            GeneratorParameters::Dense {
+                column_span: 80,
+                always_semicolons: true,
+                spaced_binary_operators: false,
+                escape_non_ascii: false,
+            },
+            GeneratorParameters::Dense {
+                column_span: 80,
+                always_semicolons: false,
+                spaced_binary_operators: true,
+                escape_non_ascii: false,
+            },
+            GeneratorParameters::default_readable(),
+            GeneratorParameters::default_format(),
+        ]
+    }
+
+    fn parse_expression(code: &str) -> Expression {
+        let block = Parser::default()
+            .parse(&format!("return {}", code))
+            .unwrap_or_else(|err| panic!("unable to parse `{}`: {}", code, err));
+
+        match block.get_last_statement() {
+            Some(LastStatement::Return(statement)) => {
+                statement.iter_expressions().next().unwrap().clone()
+            }
+            _ => panic!("expected return statement"),
+        }
+    }
+
+    fn assert_expression_reparses(expression: Expression) {
+        for parameters in all_parameters() {
+            let code = render_expression(&expression, &parameters);
+
+            pretty_assertions::assert_eq!(parse_expression(&code), expression);
+        }
+    }
+
+    #[test]
+    fn render_binary_expression() {
+        assert_expression_reparses(
+            BinaryExpression::new(
+                BinaryOperator::Asterisk,
+                Expression::from(BinaryExpression::new(
+                    BinaryOperator::Plus,
+                    Expression::identifier("a"),
+                    Expression::from(1),
+                ))
+                .in_parentheses(),
+                Expression::identifier("b"),
+            )
+            .into(),
+        );
+    }
+
+    #[test]
+    fn render_table_expression() {
+        assert_expression_reparses(
+            TableExpression::default()
+                .append_field("name", StringExpression::from_value("darklua"))
+                .append_array_value(Expression::from(true))
+                .append_index(Expression::from(2), Expression::nil())
+                .into(),
+        );
+    }
+
+    #[test]
+    fn render_binary_expression_dense() {
+        let expression: Expression = BinaryExpression::new(
+            BinaryOperator::Concat,
+            StringExpression::from_value("a"),
+            Expression::identifier("b"),
+        )
+        .into();
+
+        insta::assert_snapshot!(
+            render_expression(&expression, &GeneratorParameters::default_dense()),
+            @"'a'..b"
+        );
+    }
+
+    #[test]
+    fn render_statement_reparses() {
+        let statement: Statement = LocalAssignStatement::from_variable("value")
+            .with_value(TableExpression::default().append_array_value(Expression::from(1)))
+            .into();
+
+        for parameters in all_parameters() {
+            let code = render_statement(&statement, &parameters);
+            let block = Parser::default().parse(&code).unwrap();
+
+            pretty_assertions::assert_eq!(block.iter_statements().next(), Some(&statement));
+        }
+    }
+}
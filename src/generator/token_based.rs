@@ -1,7 +1,7 @@
 use std::iter;
 
 use crate::{
-    generator::{utils, LuaGenerator},
+    generator::{format::FormatState, utils, LuaGenerator},
     nodes::*,
 };
 
@@ -13,6 +13,8 @@ pub struct TokenBasedLuaGenerator<'a> {
     output: String,
     currently_commenting: bool,
     current_line: usize,
+    format: Option<FormatState>,
+    escape_non_ascii: bool,
 }
 
 impl<'a> TokenBasedLuaGenerator<'a> {
@@ -22,6 +24,54 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             output: String::new(),
             currently_commenting: false,
             current_line: 1,
+            format: None,
+            escape_non_ascii: false,
+        }
+    }
+
+    /// Replaces the whitespaces of the tokens with a canonical indentation and spacing.
+    /// Comments and line breaks from the tokens are preserved.
+    ///
+    /// The layout is computed from the tokens, so the result is only reliable when the
+    /// nodes all have their original tokens (for example, when no rules were applied).
+    pub fn with_format(mut self) -> Self {
+        self.format = Some(FormatState::default());
+        self
+    }
+
+    /// Escapes the non-ASCII characters written directly in the strings (like `"é"`),
+    /// so that the output only contains ASCII characters inside strings. Escape
+    /// sequences already present in the strings are preserved as-is.
+    pub fn with_escaped_non_ascii(mut self) -> Self {
+        self.escape_non_ascii = true;
+        self
+    }
+
+    /// Returns a copy of the token with its non-ASCII characters escaped if needed.
+    fn escape_string_token(
+        &self,
+        token: &Token,
+        rewrite: impl FnOnce(&str) -> String,
+    ) -> Option<Token> {
+        if !self.escape_non_ascii {
+            return None;
+        }
+
+        let content = token.read(self.original_code);
+
+        if content.is_ascii() {
+            return None;
+        }
+
+        let mut token = token.clone();
+        token.replace_with_content(rewrite(content));
+        Some(token)
+    }
+
+    #[inline]
+    fn attach_next(&mut self) {
+        if let Some(format) = &mut self.format {
+            format.attach_next();
         }
     }
 
@@ -32,6 +82,17 @@ impl<'a> TokenBasedLuaGenerator<'a> {
 
     fn write_trivia(&mut self, trivia: &Trivia) {
         let content = trivia.read(self.original_code);
+
+        if let Some(format) = &mut self.format {
+            match trivia.kind() {
+                TriviaKind::Comment => {
+                    format.write_comment(&mut self.output, content, is_single_line_comment(content))
+                }
+                TriviaKind::Whitespace => format.write_whitespace(content),
+            }
+            return;
+        }
+
         self.push_str(content);
 
         match trivia.kind() {
@@ -60,7 +121,15 @@ impl<'a> TokenBasedLuaGenerator<'a> {
 
         let content = token.read(self.original_code);
 
-        if !content.is_empty() {
+        if let Some(format) = &mut self.format {
+            if !space_check {
+                format.attach_next();
+            }
+            format.write_content(&mut self.output, content);
+            if !space_check {
+                format.attach_next();
+            }
+        } else if !content.is_empty() {
             if self.currently_commenting {
                 self.uncomment();
             }
@@ -89,9 +158,16 @@ impl<'a> TokenBasedLuaGenerator<'a> {
     }
 
     fn write_block_with_tokens(&mut self, block: &Block, tokens: &BlockTokens) {
+        if let Some(format) = &mut self.format {
+            format.open_block();
+        }
+
         let mut iterator = block.iter_statements().enumerate().peekable();
 
         while let Some((index, statement)) = iterator.next() {
+            if let Some(format) = &mut self.format {
+                format.start_statement();
+            }
             self.write_statement(statement);
 
             if let Some(semicolon) = tokens.semicolons.get(index).unwrap_or(&None) {
@@ -106,12 +182,22 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         }
 
         if let Some(statement) = block.get_last_statement() {
+            if let Some(format) = &mut self.format {
+                format.start_statement();
+            }
             self.write_last_statement(statement);
         }
 
         if let Some(token) = &tokens.final_token {
+            if let Some(format) = &mut self.format {
+                format.start_block_end();
+            }
             self.write_token(token);
         }
+
+        if let Some(format) = &mut self.format {
+            format.close_block(block.is_empty());
+        }
     }
 
     fn write_return_with_tokens(&mut self, statement: &ReturnStatement, tokens: &ReturnTokens) {
@@ -176,11 +262,13 @@ impl<'a> TokenBasedLuaGenerator<'a> {
     ) {
         self.write_prefix(call.get_prefix());
         if let Some(method) = call.get_method() {
+            self.attach_next();
             if let Some(colon) = &tokens.colon {
                 self.write_token(colon);
             } else {
                 self.write_symbol(":");
             }
+            self.attach_next();
             self.write_identifier(method);
         }
         self.write_arguments(call.get_arguments());
@@ -216,6 +304,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         arguments: &TupleArguments,
         tokens: &TupleArgumentsTokens,
     ) {
+        self.attach_next();
         self.write_token(&tokens.opening_parenthese);
 
         let last_value_index = arguments.len().saturating_sub(1);
@@ -275,6 +364,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
 
     fn write_index_with_tokens(&mut self, index: &IndexExpression, tokens: &IndexExpressionTokens) {
         self.write_prefix(index.get_prefix());
+        self.attach_next();
         self.write_token(&tokens.opening_bracket);
         self.write_expression(index.get_index());
         self.write_token(&tokens.closing_bracket);
@@ -342,6 +432,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             self.write_function_generics(generics);
         }
 
+        self.attach_next();
         self.write_token(&tokens.opening_parenthese);
 
         let last_parameter_index = parameter_count.saturating_sub(1);
@@ -444,11 +535,13 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             });
 
         if let Some(method) = name.get_method() {
+            self.attach_next();
             if let Some(colon) = &tokens.colon {
                 self.write_token(colon);
             } else {
                 self.write_symbol(":");
             }
+            self.attach_next();
             self.write_identifier(method);
         }
     }
@@ -643,6 +736,34 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         self.write_token(&tokens.end);
     }
 
+    fn write_type_function_with_tokens(
+        &mut self,
+        statement: &TypeFunctionStatement,
+        tokens: &TypeFunctionTokens,
+    ) {
+        if statement.is_exported() {
+            if let Some(export_token) = &tokens.export {
+                self.write_token(export_token);
+            } else {
+                self.write_symbol("export");
+            }
+        }
+        self.write_token(&tokens.r#type);
+        self.write_token(&tokens.function);
+        self.write_identifier(statement.get_identifier());
+
+        self.write_function_attributes(
+            tokens,
+            statement.get_generic_parameters(),
+            statement.parameters_count(),
+            statement.iter_parameters(),
+            statement.is_variadic(),
+            statement.get_variadic_type(),
+            statement.get_return_type(),
+            statement.get_block(),
+        );
+    }
+
     fn write_type_declaration_with_tokens(
         &mut self,
         statement: &TypeDeclarationStatement,
@@ -683,7 +804,9 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         generic_parameters: &GenericParametersWithDefaults,
         tokens: &GenericParametersTokens,
     ) {
+        self.attach_next();
         self.write_token(&tokens.opening_list);
+        self.attach_next();
 
         let last_index = generic_parameters.len().saturating_sub(1);
 
@@ -726,6 +849,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             }
         }
 
+        self.attach_next();
         self.write_token(&tokens.closing_list);
     }
 
@@ -753,7 +877,9 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         parameters: &TypeParameters,
         tokens: &TypeParametersTokens,
     ) {
+        self.attach_next();
         self.write_token(&tokens.opening_list);
+        self.attach_next();
         let last_index = parameters.len().saturating_sub(1);
 
         for (i, parameter) in parameters.iter().enumerate() {
@@ -768,6 +894,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             }
         }
 
+        self.attach_next();
         self.write_token(&tokens.closing_list);
     }
 
@@ -880,6 +1007,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         tokens: &ExpressionTypeTokens,
     ) {
         self.write_token(&tokens.r#typeof);
+        self.attach_next();
         self.write_token(&tokens.opening_parenthese);
         self.write_expression(expression_type.get_expression());
         self.write_token(&tokens.closing_parenthese);
@@ -966,7 +1094,9 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         generic_parameters: &GenericParameters,
         tokens: &GenericParametersTokens,
     ) {
+        self.attach_next();
         self.write_token(&tokens.opening_list);
+        self.attach_next();
 
         let last_index = generic_parameters.len().saturating_sub(1);
 
@@ -996,6 +1126,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             }
         }
 
+        self.attach_next();
         self.write_token(&tokens.closing_list);
     }
 
@@ -1034,6 +1165,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         } else {
             self.write_type(inner_type);
         }
+        self.attach_next();
         self.write_token(token);
     }
 
@@ -1109,15 +1241,26 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         tokens: &InterpolatedStringTokens,
     ) {
         self.write_token(&tokens.opening_tick);
+        self.attach_next();
 
         for segment in interpolated_string.iter_segments() {
             match segment {
                 InterpolationSegment::String(string_segment) => {
                     if let Some(token) = string_segment.get_token() {
-                        self.write_token(token);
+                        if let Some(escaped_token) =
+                            self.escape_string_token(token, utils::escape_non_ascii)
+                        {
+                            self.write_token(&escaped_token);
+                        } else {
+                            self.write_token(token);
+                        }
                     } else {
-                        self.write_symbol(&utils::write_interpolated_string_segment(string_segment))
+                        self.write_symbol(&utils::write_interpolated_string_segment(
+                            string_segment,
+                            self.escape_non_ascii,
+                        ))
                     }
+                    self.attach_next();
                 }
                 InterpolationSegment::Value(value) => {
                     if let Some(tokens) = value.get_tokens() {
@@ -1128,6 +1271,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
                             &self.generate_string_value_segment_tokens(value),
                         );
                     }
+                    self.attach_next();
                 }
             }
         }
@@ -1142,7 +1286,12 @@ impl<'a> TokenBasedLuaGenerator<'a> {
     ) {
         self.write_token(&tokens.opening_brace);
         let expression = value.get_expression();
-        if self.output.ends_with('{') {
+        let starts_with_table = utils::starts_with_table(expression).is_some();
+        if self.format.is_some() {
+            if !starts_with_table {
+                self.attach_next();
+            }
+        } else if self.output.ends_with('{') {
             if let Some(table) = utils::starts_with_table(expression) {
                 if table
                     .get_tokens()
@@ -1160,6 +1309,9 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             }
         }
         self.write_expression(expression);
+        if !starts_with_table {
+            self.attach_next();
+        }
         self.write_token(&tokens.closing_brace);
     }
 
@@ -1345,6 +1497,45 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         }
     }
 
+    fn generate_type_function_tokens(
+        &self,
+        statement: &TypeFunctionStatement,
+    ) -> TypeFunctionTokens {
+        TypeFunctionTokens {
+            r#type: Token::from_content("type"),
+            function_body: FunctionBodyTokens {
+                function: Token::from_content("function"),
+                opening_parenthese: Token::from_content("("),
+                closing_parenthese: Token::from_content(")"),
+                end: Token::from_content("end"),
+                parameter_commas: intersect_with_token(
+                    comma_token(),
+                    statement.parameters_count() + usize::from(statement.is_variadic()),
+                ),
+                variable_arguments: if statement.is_variadic() {
+                    Some(Token::from_content("..."))
+                } else {
+                    None
+                },
+                variable_arguments_colon: if statement.has_variadic_type() {
+                    Some(Token::from_content(":"))
+                } else {
+                    None
+                },
+                return_type_colon: if statement.has_return_type() {
+                    Some(Token::from_content(":"))
+                } else {
+                    None
+                },
+            },
+            export: if statement.is_exported() {
+                Some(Token::from_content("export"))
+            } else {
+                None
+            },
+        }
+    }
+
     fn generate_type_declaration_tokens(
         &self,
         statement: &TypeDeclarationStatement,
@@ -1608,6 +1799,11 @@ impl<'a> TokenBasedLuaGenerator<'a> {
     }
 
     fn write_symbol(&mut self, symbol: &str) {
+        if let Some(format) = &mut self.format {
+            format.write_content(&mut self.output, symbol);
+            return;
+        }
+
         if self.currently_commenting {
             self.uncomment();
         } else if self.needs_space(symbol.chars().next().expect("symbol cannot be empty")) {
@@ -1617,6 +1813,13 @@ impl<'a> TokenBasedLuaGenerator<'a> {
     }
 
     fn write_symbol_without_space_check(&mut self, symbol: &str) {
+        if let Some(format) = &mut self.format {
+            format.attach_next();
+            format.write_content(&mut self.output, symbol);
+            format.attach_next();
+            return;
+        }
+
         if self.currently_commenting {
             self.uncomment();
         }
@@ -1667,20 +1870,7 @@ impl<'a> TokenBasedLuaGenerator<'a> {
 }
 
 fn is_single_line_comment(content: &str) -> bool {
-    let is_multiline_comment = content.starts_with("--[") && {
-        if let Some((closing_bracket_index, _)) =
-            content.chars().skip(3).enumerate().find(|(_, c)| *c == '[')
-        {
-            content
-                .get(3..closing_bracket_index)
-                .map(|substring| substring.chars().all(|c| c == '='))
-                .unwrap_or(true)
-        } else {
-            false
-        }
-    };
-
-    !is_multiline_comment
+    Comment::new(content).kind() == CommentKind::Line
 }
 
 #[inline]
@@ -1689,7 +1879,10 @@ fn comma_token() -> Token {
 }
 
 impl LuaGenerator for TokenBasedLuaGenerator<'_> {
-    fn into_string(self) -> String {
+    fn into_string(mut self) -> String {
+        if let Some(format) = &mut self.format {
+            format.finish(&mut self.output);
+        }
         self.output
     }
 
@@ -1839,6 +2032,17 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
         }
     }
 
+    fn write_type_function_statement(&mut self, statement: &TypeFunctionStatement) {
+        if let Some(tokens) = statement.get_tokens() {
+            self.write_type_function_with_tokens(statement, tokens);
+        } else {
+            self.write_type_function_with_tokens(
+                statement,
+                &self.generate_type_function_tokens(statement),
+            );
+        }
+    }
+
     fn write_type_declaration_statement(&mut self, statement: &TypeDeclarationStatement) {
         if let Some(tokens) = statement.get_tokens() {
             self.write_type_declaration_with_tokens(statement, tokens);
@@ -1917,6 +2121,10 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
             self.write_token(&Token::from_content(unary.operator().to_str()));
         }
 
+        if unary.operator() != UnaryOperator::Not {
+            self.attach_next();
+        }
+
         let expression = unary.get_expression();
         match expression {
             Expression::Binary(binary) if !binary.operator().precedes_unary_expression() => {
@@ -2026,9 +2234,25 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
 
     fn write_string(&mut self, string: &StringExpression) {
         if let Some(token) = string.get_token() {
-            self.write_token(token);
+            let escaped_token = self.escape_string_token(token, |content| {
+                if content.starts_with('[') {
+                    // long strings do not support escape sequences
+                    utils::write_string(string.get_value(), true)
+                } else {
+                    utils::escape_non_ascii(content)
+                }
+            });
+
+            if let Some(escaped_token) = escaped_token {
+                self.write_token(&escaped_token);
+            } else {
+                self.write_token(token);
+            }
         } else {
-            self.write_symbol(&utils::write_string(string.get_value()));
+            self.write_symbol(&utils::write_string(
+                string.get_value(),
+                self.escape_non_ascii,
+            ));
         }
     }
 
@@ -2132,7 +2356,10 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
         if let Some(token) = string_type.get_token() {
             self.write_token(token);
         } else {
-            self.write_symbol(&utils::write_string(string_type.get_value()));
+            self.write_symbol(&utils::write_string(
+                string_type.get_value(),
+                self.escape_non_ascii,
+            ));
         }
     }
 
@@ -2227,13 +2454,23 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
     }
 
     fn write_variadic_type_pack(&mut self, variadic_type_pack: &VariadicTypePack) {
-        self.push_str("...");
+        if let Some(format) = &mut self.format {
+            format.write_content(&mut self.output, "...");
+            format.attach_next();
+        } else {
+            self.push_str("...");
+        }
         self.write_type(variadic_type_pack.get_type());
     }
 
     fn write_generic_type_pack(&mut self, generic_type_pack: &GenericTypePack) {
         self.write_identifier(generic_type_pack.get_name());
-        self.push_str("...");
+        if let Some(format) = &mut self.format {
+            format.attach_next();
+            format.write_content(&mut self.output, "...");
+        } else {
+            self.push_str("...");
+        }
     }
 }
 
@@ -2452,4 +2689,52 @@ mod test {
 
         insta::assert_snapshot!("inserts_a_new_line_after_custom_added_comments", output);
     }
+
+    mod format {
+        use super::*;
+
+        macro_rules! test_format {
+            ($($name:ident => $code:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let parser = crate::Parser::default().preserve_tokens();
+                        let block = parser
+                            .parse($code)
+                            .unwrap_or_else(|error| panic!("failed to parse `{}`: {:?}", $code, error));
+
+                        let mut generator = TokenBasedLuaGenerator::new($code).with_format();
+                        generator.write_block(&block);
+                        let output = generator.into_string();
+
+                        let original_block = crate::Parser::default().parse($code).unwrap();
+                        let formatted_block = crate::Parser::default()
+                            .parse(&output)
+                            .unwrap_or_else(|error| panic!("failed to parse generated code `{}`: {:?}", &output, error));
+
+                        pretty_assertions::assert_eq!(original_block, formatted_block);
+
+                        insta::assert_snapshot!(stringify!($name), output);
+                    }
+                )*
+            };
+        }
+
+        test_format!(
+            leading_comment => "-- leading comment\nlocal a=1",
+            leading_block_comment => "--[[ block\n   comment ]]\nlocal a   =   1",
+            trailing_comment => "local a=1 -- trailing comment\nlocal b=2",
+            trailing_comment_in_table => "local t={\n  a=1, -- first\n  b=2 -- second\n}",
+            standalone_comment_between_statements => "local a=1\n\n-- standalone\n\nlocal b=2",
+            standalone_comment_at_end_of_block => "if a then\ncall()\n-- after call\nend",
+            comment_in_empty_block => "function f()\n-- nothing here\nend",
+            comment_before_else => "if a then\n  call()\n  -- before else\nelse\n  other()\nend",
+            nested_blocks => "local function f(a,b)\nfor i=1,10 do\nif a then return b end\nend\nend",
+            inline_block_comment => "call(--[[ first ]] a, b --[[ second ]])",
+            multiline_call => "call(\n a,\n b\n)",
+            method_call_and_index => "local value=object : method ( a ) [ 'key' ]",
+            collapses_blank_lines => "local a=1\n\n\n\n\nlocal b=2",
+            trailing_spaces => "print( 'hello' )   \n",
+        );
+    }
 }
@@ -240,9 +240,17 @@ pub fn write_number(number: &NumberExpression) -> String {
         }
         NumberExpression::Hex(number) => {
             format!(
-                "0{}{:x}{}",
+                "0{}{:x}{}{}",
                 if number.is_x_uppercase() { 'X' } else { 'x' },
                 number.get_raw_integer(),
+                number
+                    .get_raw_fraction()
+                    .map(|(fraction, digits)| if digits == 0 {
+                        ".".to_owned()
+                    } else {
+                        format!(".{:0width$x}", fraction, width = digits as usize)
+                    })
+                    .unwrap_or_default(),
                 number
                     .get_exponent()
                     .map(|exponent| {
@@ -266,7 +274,8 @@ pub fn write_number(number: &NumberExpression) -> String {
 }
 
 fn needs_escaping(character: char) -> bool {
-    !(character.is_ascii_graphic() || character == ' ') || character == '\\'
+    (character.is_ascii() && !(character.is_ascii_graphic() || character == ' '))
+        || character == '\\'
 }
 
 fn needs_quoted_string(character: char) -> bool {
@@ -283,14 +292,29 @@ fn escape(character: char) -> String {
         '\u{8}' => "\\b".to_owned(),
         '\u{B}' => "\\v".to_owned(),
         '\u{C}' => "\\f".to_owned(),
-        _ => {
-            if character.len_utf8() == 1 {
-                format!("\\{}", character as u8)
+        _ => format!("\\{}", character as u8),
+    }
+}
+
+/// Writes each byte of the UTF-8 encoding of a non-ASCII character as a decimal
+/// escape sequence (`\ddd`), which is supported by every Lua version.
+fn escape_utf8_bytes(character: char, next_is_digit: bool) -> String {
+    let mut buffer = [0; 4];
+    let bytes = character.encode_utf8(&mut buffer).as_bytes();
+    let last_index = bytes.len() - 1;
+
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(index, byte)| {
+            // a decimal escape followed by a digit would read that digit too
+            if index == last_index && next_is_digit {
+                format!("\\{:03}", byte)
             } else {
-                format!("\\u{{{:x}}}", character as u32)
+                format!("\\{}", byte)
             }
-        }
-    }
+        })
+        .collect()
 }
 
 #[inline]
@@ -298,7 +322,7 @@ pub fn count_new_lines(string: &str) -> usize {
     string.chars().filter(|c| *c == '\n').count()
 }
 
-pub fn write_string(value: &str) -> String {
+pub fn write_string(value: &str, escape_non_ascii: bool) -> String {
     if value.is_empty() {
         return "''".to_owned();
     }
@@ -328,11 +352,53 @@ pub fn write_string(value: &str) -> String {
     {
         write_long_bracket(value)
     } else {
-        write_quoted(value)
+        write_quoted(value, escape_non_ascii)
+    }
+}
+
+/// Writes the shortest valid representation of a string: quoted with either
+/// single or double quotes, or as a long string with the lowest bracket level
+/// that does not conflict with its content.
+pub fn write_shortest_string(value: &str, escape_non_ascii: bool) -> String {
+    let mut candidates = vec![
+        write_quoted_with(value, '\'', escape_non_ascii),
+        write_quoted_with(value, '"', escape_non_ascii),
+    ];
+
+    if !value.contains(needs_quoted_string) {
+        candidates.push(write_long_bracket(value));
+    }
+
+    candidates
+        .into_iter()
+        .min_by_key(String::len)
+        .expect("at least one string representation should exist")
+}
+
+/// Escapes the non-ASCII characters of the content of a quoted string or of an
+/// interpolated string segment, leaving everything else (including the existing
+/// escape sequences) unchanged.
+pub fn escape_non_ascii(content: &str) -> String {
+    let mut result = String::new();
+    result.reserve(content.len());
+
+    let mut characters = content.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character.is_ascii() {
+            result.push(character);
+        } else {
+            let next_is_digit = characters.peek().filter(|c| c.is_ascii_digit()).is_some();
+            result.push_str(&escape_utf8_bytes(character, next_is_digit));
+        }
     }
+
+    result
 }
 
-pub fn write_interpolated_string_segment(segment: &StringSegment) -> String {
+pub fn write_interpolated_string_segment(
+    segment: &StringSegment,
+    escape_non_ascii: bool,
+) -> String {
     let value = segment.get_value();
 
     if value.is_empty() {
@@ -343,7 +409,8 @@ pub fn write_interpolated_string_segment(segment: &StringSegment) -> String {
 
     result.reserve(value.len());
 
-    for character in value.chars() {
+    let mut characters = value.chars().peekable();
+    while let Some(character) = characters.next() {
         match character {
             '`' | '{' => {
                 result.push('\\');
@@ -352,6 +419,10 @@ pub fn write_interpolated_string_segment(segment: &StringSegment) -> String {
             _ if needs_escaping(character) => {
                 result.push_str(&escape(character));
             }
+            _ if escape_non_ascii && !character.is_ascii() => {
+                let next_is_digit = characters.peek().filter(|c| c.is_ascii_digit()).is_some();
+                result.push_str(&escape_utf8_bytes(character, next_is_digit));
+            }
             _ => {
                 result.push(character);
             }
@@ -362,33 +433,48 @@ pub fn write_interpolated_string_segment(segment: &StringSegment) -> String {
 }
 
 fn write_long_bracket(value: &str) -> String {
-    let mut i: usize = value.ends_with(']').into();
-    let mut equals = "=".repeat(i);
+    let mut equals = String::new();
+    // the closing bracket must not appear in the content, including when the end
+    // of the content and the closing bracket form a closing bracket together
     loop {
-        if !value.contains(&format!("]{}]", equals)) {
+        let closing_bracket = format!("]{}]", equals);
+        let content = format!("{}{}", value, closing_bracket);
+        if content.find(&closing_bracket) == Some(value.len()) {
             break;
-        } else {
-            i += 1;
-            equals = "=".repeat(i);
-        };
+        }
+        equals.push('=');
     }
     let needs_extra_new_line = if value.starts_with('\n') { "\n" } else { "" };
     format!("[{}[{}{}]{}]", equals, needs_extra_new_line, value, equals)
 }
 
-fn write_quoted(value: &str) -> String {
+fn write_quoted(value: &str, escape_non_ascii: bool) -> String {
+    write_quoted_with(value, get_quote_symbol(value), escape_non_ascii)
+}
+
+fn write_quoted_with(value: &str, quote_symbol: char, escape_non_ascii: bool) -> String {
     let mut quoted = String::new();
     quoted.reserve(value.len() + 2);
 
-    let quote_symbol = get_quote_symbol(value);
     quoted.push(quote_symbol);
 
-    for character in value.chars() {
+    let mut characters = value.chars().peekable();
+    while let Some(character) = characters.next() {
         if character == quote_symbol {
             quoted.push('\\');
             quoted.push(quote_symbol);
         } else if needs_escaping(character) {
-            quoted.push_str(&escape(character));
+            let escaped = escape(character);
+            let is_decimal_escape = escaped[1..].chars().all(|c| c.is_ascii_digit());
+            // a decimal escape followed by a digit would read that digit too
+            if is_decimal_escape && characters.peek().filter(|c| c.is_ascii_digit()).is_some() {
+                quoted.push_str(&format!("\\{:03}", character as u8));
+            } else {
+                quoted.push_str(&escaped);
+            }
+        } else if escape_non_ascii && !character.is_ascii() {
+            let next_is_digit = characters.peek().filter(|c| c.is_ascii_digit()).is_some();
+            quoted.push_str(&escape_utf8_bytes(character, next_is_digit));
         } else {
             quoted.push(character);
         }
@@ -413,6 +499,39 @@ fn get_quote_symbol(value: &str) -> char {
 mod test {
     use super::*;
 
+    mod should_break_with_space {
+        use super::*;
+
+        macro_rules! test_output {
+            ($($name:ident($ending:literal, $next:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, should_break_with_space($ending, $next));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            minus_before_minus('-', '-') => true,
+            dot_before_dot('.', '.') => true,
+            dot_before_digit('.', '3') => true,
+            digit_before_dot('9', '.') => true,
+            digit_before_letter('1', 'e') => true,
+            letter_before_digit('a', '1') => true,
+            letter_before_underscore('a', '_') => true,
+            open_bracket_before_open_bracket('[', '[') => true,
+            close_bracket_before_close_bracket(']', ']') => true,
+            greater_than_before_equal('>', '=') => true,
+            minus_before_letter('-', 'a') => false,
+            letter_before_minus('a', '-') => false,
+            dot_before_letter('.', 'a') => false,
+            close_parenthese_before_letter(')', 'a') => false,
+            equal_before_open_bracket('=', '[') => false,
+        );
+    }
+
     mod write_string {
         use super::*;
 
@@ -421,7 +540,7 @@ mod test {
                 $(
                     #[test]
                     fn $name() {
-                        assert_eq!($value, write_string(&$input));
+                        assert_eq!($value, write_string(&$input, true));
                     }
                 )*
             };
@@ -447,9 +566,11 @@ mod test {
             double_quote("\"") => "'\"'",
             null("\0") => "'\\0'",
             escape("\u{1B}") => "'\\27'",
-            extended_ascii("\u{C3}") => "'\\u{c3}'",
-            unicode("\u{25C1}") => "'\\u{25c1}'",
-            escape_degree_symbol("°") => "'\\u{b0}'",
+            escape_followed_by_digit("\u{1B}1") => "'\\0271'",
+            extended_ascii("\u{C3}") => "'\\195\\131'",
+            unicode("\u{25C1}") => "'\\226\\151\\129'",
+            unicode_followed_by_digit("é1") => "'\\195\\1691'",
+            escape_degree_symbol("°") => "'\\194\\176'",
             im_cool("I'm cool") => "\"I'm cool\"",
             ends_with_closing_bracket("oof]") => "'oof]'",
             multiline_ends_with_closing_bracket("oof\noof]") => "'oof\\noof]'",
@@ -461,7 +582,86 @@ mod test {
                 => "[[\n\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]",
 
             large_multiline_with_unicode("\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof\u{10FFFF}")
-                => "'\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof\\u{10ffff}'",
+                => "'\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof\\244\\143\\191\\191'",
+            large_multiline_ends_with_partial_closing_bracket("ooof\nooof\nooof]]\nooof\nooof\nooof\nooof\nooof\noof]=")
+                => "[==[ooof\nooof\nooof]]\nooof\nooof\nooof\nooof\nooof\noof]=]==]",
+        );
+    }
+
+    mod write_string_without_escaping_non_ascii {
+        use super::*;
+
+        macro_rules! test_output {
+            ($($name:ident($input:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, write_string(&$input, false));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            extended_ascii("\u{C3}") => "'\u{C3}'",
+            unicode("\u{25C1}") => "'\u{25C1}'",
+            degree_symbol("°") => "'°'",
+            emoji_with_new_line("🎉\n") => "'🎉\\n'",
+            null_followed_by_digit("\u{0}1") => "'\\0001'",
+        );
+    }
+
+    mod escape_non_ascii {
+        use super::*;
+
+        macro_rules! test_output {
+            ($($name:ident($input:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, escape_non_ascii(&$input));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            ascii("abc\\n") => "abc\\n",
+            latin_1_character("café") => "caf\\195\\169",
+            emoji("🎉") => "\\240\\159\\142\\137",
+            followed_by_digit("é0") => "\\195\\1690",
+        );
+    }
+
+    mod write_shortest_string {
+        use super::*;
+
+        macro_rules! test_output {
+            ($($name:ident($input:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, write_shortest_string(&$input, true));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            empty("") => "''",
+            abc("abc") => "'abc'",
+            single_quote("'") => "\"'\"",
+            double_quote("\"") => "'\"'",
+            both_quotes("'\"'") => "\"'\\\"'\"",
+            few_special_characters("a\tb\nc") => "'a\\tb\\nc'",
+            new_lines("a\nb\nc\nd") => "[[a\nb\nc\nd]]",
+            starts_with_new_line("\na\nb\nc\nd") => "[[\n\na\nb\nc\nd]]",
+            backslashes("C:\\a\\b\\c") => "[[C:\\a\\b\\c]]",
+            contains_closing_bracket("a]]\\b\\c\\d\\e\\f") => "[=[a]]\\b\\c\\d\\e\\f]=]",
+            contains_both_closing_brackets("a]]]=]\\b\\c\\d\\e\\f\\g\\h")
+                => "[==[a]]]=]\\b\\c\\d\\e\\f\\g\\h]==]",
+            ends_with_closing_bracket("\\a\\b\\c\\d\\e]") => "[=[\\a\\b\\c\\d\\e]]=]",
+            unicode_new_lines("\u{25C1}\na\nb\nc") => "'\\226\\151\\129\\na\\nb\\nc'",
         );
     }
 }
@@ -10,7 +10,9 @@ pub mod rules;
 mod utils;
 
 pub use frontend::{
-    convert_data, process, BundleConfiguration, Configuration, DarkluaError, GeneratorParameters,
-    Options, Resources, WorkerTree,
+    collect_requires, convert_data, process, BundleConfiguration, Configuration, DarkluaError,
+    DryRunEntry, ErrorCategory, ErrorLocation, ExtractedMessageEntry, FileStatistics,
+    GeneratorParameters, ManifestEntry, Options, ProcessingReportEntry, Resources,
+    RuleOrderingWarning, RuleTiming, RuleTimingEntry, WorkerTree,
 };
 pub use parser::{Parser, ParserError};
@@ -165,6 +165,12 @@ impl From<TupleArguments> for Arguments {
     }
 }
 
+impl From<Vec<Expression>> for Arguments {
+    fn from(values: Vec<Expression>) -> Self {
+        Self::Tuple(TupleArguments::new(values))
+    }
+}
+
 impl From<TableExpression> for Arguments {
     fn from(table: TableExpression) -> Self {
         Self::Table(table)
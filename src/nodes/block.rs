@@ -1,4 +1,9 @@
-use crate::nodes::{LastStatement, ReturnStatement, Statement, Token};
+use std::collections::HashSet;
+
+use crate::generator::{DenseLuaGenerator, LuaGenerator};
+use crate::nodes::{DoStatement, LastStatement, ReturnStatement, Statement, Token};
+use crate::process::processors::CollectGlobalNames;
+use crate::process::{NodeVisitor, ScopeVisitor};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BlockTokens {
@@ -225,6 +230,57 @@ impl Block {
         self.last_statement.replace(statement.into())
     }
 
+    /// Appends the statements and the last statement of another block at the end of this
+    /// block. If this block already ends with a last statement (like a `return`), the
+    /// statements can't be appended after it, so the other block is given back as an error.
+    pub fn append_block(&mut self, mut other: Block) -> Result<(), Block> {
+        if self.last_statement.is_some() {
+            return Err(other);
+        }
+
+        for statement in other.take_statements() {
+            self.push_statement(statement);
+        }
+        self.last_statement = other.take_last_statement();
+
+        Ok(())
+    }
+
+    /// Merges multiple blocks into a single block, where each block is wrapped in a
+    /// `do ... end` statement so that the local variables of each module do not collide.
+    pub fn merge_modules(blocks: Vec<Block>) -> Self {
+        blocks.into_iter().fold(Block::default(), |merged, block| {
+            merged.with_statement(DoStatement::new(block))
+        })
+    }
+
+    /// Returns the names of the identifiers that are referenced or assigned in the block
+    /// without being bound to a local variable, a function parameter or a loop variable
+    /// declared inside the block. These are the globals or upvalues used by the block.
+    pub fn free_identifiers(&self) -> HashSet<String> {
+        let mut block = self.clone();
+        let mut collector = CollectGlobalNames::default();
+        ScopeVisitor::visit_block(&mut block, &mut collector);
+        collector.into()
+    }
+
+    /// Returns true if both blocks have the same structure, ignoring their tokens (the
+    /// positions, whitespaces and comments). The derived `PartialEq` implementation
+    /// compares tokens, so a block parsed with its tokens is not equal to the same block
+    /// parsed without them.
+    ///
+    /// The blocks are compared by writing them with the [`DenseLuaGenerator`], which
+    /// does not use tokens.
+    pub fn structurally_eq(&self, other: &Block) -> bool {
+        fn generate(block: &Block) -> String {
+            let mut generator = DenseLuaGenerator::default();
+            generator.write_block(block);
+            generator.into_string()
+        }
+
+        generate(self) == generate(other)
+    }
+
     pub fn clear(&mut self) {
         self.statements.clear();
         self.last_statement.take();
@@ -283,6 +339,41 @@ mod test {
         statements.into_iter().next().unwrap()
     }
 
+    #[test]
+    fn block_with_tokens_is_structurally_equal_to_block_without_tokens() {
+        let code = "local a = 1 -- comment\nreturn a";
+        let block = Parser::default().parse(code).unwrap();
+        let block_with_tokens = parse_block_with_tokens(code);
+
+        assert_ne!(block, block_with_tokens);
+        assert!(block.structurally_eq(&block_with_tokens));
+        assert!(block_with_tokens.structurally_eq(&block));
+    }
+
+    #[test]
+    fn blocks_with_different_trivia_are_structurally_equal() {
+        let block = parse_block_with_tokens("local a = 1\nreturn a");
+        let other = parse_block_with_tokens("-- header\nlocal  a=1 --[[ value ]] return   a ;");
+
+        assert!(block.structurally_eq(&other));
+    }
+
+    #[test]
+    fn blocks_with_different_statements_are_not_structurally_equal() {
+        let block = parse_block_with_tokens("local a = 1\nreturn a");
+        let other = parse_block_with_tokens("local a = 2\nreturn a");
+
+        assert!(!block.structurally_eq(&other));
+    }
+
+    #[test]
+    fn blocks_with_different_identifiers_are_not_structurally_equal() {
+        let block = Parser::default().parse("print(a)").unwrap();
+        let other = Parser::default().parse("print(b)").unwrap();
+
+        assert!(!block.structurally_eq(&other));
+    }
+
     #[test]
     fn default_block_is_empty() {
         let block = Block::default();
@@ -563,4 +654,115 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn append_block_moves_statements_and_last_statement() {
+        let mut block = Block::default().with_statement(DoStatement::default());
+
+        let result = block.append_block(
+            Block::default()
+                .with_statement(RepeatStatement::new(Block::default(), true))
+                .with_last_statement(LastStatement::new_break()),
+        );
+
+        assert_eq!(result, Ok(()));
+        pretty_assertions::assert_eq!(
+            block,
+            Block::default()
+                .with_statement(DoStatement::default())
+                .with_statement(RepeatStatement::new(Block::default(), true))
+                .with_last_statement(LastStatement::new_break())
+        );
+    }
+
+    #[test]
+    fn append_block_pads_semicolons_when_block_has_tokens() {
+        let mut block = parse_block_with_tokens("do end;");
+
+        block
+            .append_block(Block::default().with_statement(DoStatement::default()))
+            .unwrap();
+
+        assert_eq!(block.statements_len(), 2);
+        assert_eq!(block.get_tokens().unwrap().semicolons.len(), 2);
+    }
+
+    #[test]
+    fn append_block_to_block_with_return_is_rejected() {
+        let mut block = Block::default()
+            .with_statement(DoStatement::default())
+            .with_last_statement(ReturnStatement::default());
+        let other = Block::default().with_statement(DoStatement::default());
+
+        assert_eq!(block.append_block(other.clone()), Err(other));
+        pretty_assertions::assert_eq!(
+            block,
+            Block::default()
+                .with_statement(DoStatement::default())
+                .with_last_statement(ReturnStatement::default())
+        );
+    }
+
+    #[test]
+    fn merge_modules_wraps_each_block_in_do_statement() {
+        let parser = Parser::default();
+        let first = parser.parse("local value = 1 print(value)").unwrap();
+        let second = parser.parse("local value = 'a' print(value)").unwrap();
+
+        pretty_assertions::assert_eq!(
+            Block::merge_modules(vec![first, second]),
+            parser
+                .parse("do local value = 1 print(value) end do local value = 'a' print(value) end")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_modules_without_blocks_is_empty() {
+        assert!(Block::merge_modules(Vec::new()).is_empty());
+    }
+
+    mod free_identifiers {
+        use super::*;
+
+        macro_rules! test_free_identifiers {
+            ($($name:ident($input:literal) => [$($identifier:literal),* $(,)?]),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let block = Parser::default()
+                            .parse($input)
+                            .expect("code should parse");
+
+                        let expected: HashSet<String> =
+                            vec![$($identifier.to_owned()),*].into_iter().collect();
+
+                        pretty_assertions::assert_eq!(block.free_identifiers(), expected);
+                    }
+                )*
+            };
+        }
+
+        test_free_identifiers!(
+            empty_block("") => [],
+            global_call("print('hello')") => ["print"],
+            local_variable("local a = 1 return a") => [],
+            local_variable_referenced_before_declaration("local a = a") => ["a"],
+            global_assignment("counter = counter + 1") => ["counter"],
+            global_function_name("function process() end") => ["process"],
+            field_function_name("function module.process() end") => ["module"],
+            local_function_recursive("local function fact(n) return fact(n - 1) end") => [],
+            local_variable_shadowing_global("print(value) local value = 1 print(value)")
+                => ["print", "value"],
+            local_variable_in_nested_block("do local value = 1 end return value") => ["value"],
+            closure_upvalue("local count = 0 return function() count = count + step end")
+                => ["step"],
+            function_parameters("return function(a, b, ...) return a + b + c end") => ["c"],
+            method_self("function object:get() return self.value end") => ["object"],
+            numeric_for_variable("for i = 1, n do print(i) end print(i)") => ["n", "print", "i"],
+            generic_for_variables("for key, value in pairs(t) do use(key, value) end")
+                => ["pairs", "t", "use"],
+            repeat_condition_sees_locals("repeat local done = check() until done") => ["check"],
+        );
+    }
 }
@@ -18,6 +18,11 @@ pub enum BinaryOperator {
     Percent,
     Caret,
     Concat,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 #[inline]
@@ -146,7 +151,7 @@ impl BinaryOperator {
         };
         needs_parentheses
             || ends_with_if_expression(left)
-            || (matches!(self, BinaryOperator::LowerThan)
+            || (matches!(self, BinaryOperator::LowerThan | BinaryOperator::ShiftLeft)
                 && ends_with_type_cast_to_type_name_without_type_parameters(left))
     }
 
@@ -182,6 +187,11 @@ impl BinaryOperator {
             Self::Percent => "%",
             Self::Caret => "^",
             Self::Concat => "..",
+            Self::BitwiseAnd => "&",
+            Self::BitwiseOr => "|",
+            Self::BitwiseXor => "~",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
         }
     }
 
@@ -195,10 +205,14 @@ impl BinaryOperator {
             | Self::LowerOrEqualThan
             | Self::GreaterThan
             | Self::GreaterOrEqualThan => 2,
-            Self::Concat => 3,
-            Self::Plus | Self::Minus => 4,
-            Self::Asterisk | Self::Slash | Self::DoubleSlash | Self::Percent => 5,
-            Self::Caret => 7,
+            Self::BitwiseOr => 3,
+            Self::BitwiseXor => 4,
+            Self::BitwiseAnd => 5,
+            Self::ShiftLeft | Self::ShiftRight => 6,
+            Self::Concat => 7,
+            Self::Plus | Self::Minus => 8,
+            Self::Asterisk | Self::Slash | Self::DoubleSlash | Self::Percent => 9,
+            Self::Caret => 11,
         }
     }
 }
@@ -28,7 +28,7 @@ pub use table::*;
 pub use type_cast::*;
 pub use unary::*;
 
-use crate::nodes::{FunctionCall, Identifier, Token, Variable};
+use crate::nodes::{Arguments, FunctionCall, Identifier, Token, Variable};
 
 use super::impl_token_fns;
 
@@ -74,6 +74,34 @@ impl Expression {
     pub fn in_parentheses(self) -> Self {
         Self::Parenthese(ParentheseExpression::new(self).into())
     }
+
+    /// Creates a field expression (`expression.field`). The expression is wrapped
+    /// in parentheses if it can't be used directly as a prefix.
+    pub fn index_field<S: Into<Identifier>>(self, field: S) -> Self {
+        Prefix::from(self).index_field(field).into()
+    }
+
+    /// Creates an index expression (`expression[index]`). The expression is wrapped
+    /// in parentheses if it can't be used directly as a prefix.
+    pub fn index<E: Into<Expression>>(self, index: E) -> Self {
+        Prefix::from(self).index(index).into()
+    }
+
+    /// Creates a function call (`expression(...)`). The expression is wrapped in
+    /// parentheses if it can't be used directly as a prefix.
+    pub fn call<A: Into<Arguments>>(self, arguments: A) -> Self {
+        Prefix::from(self).call(arguments).into()
+    }
+
+    /// Creates a method call (`expression:method(...)`). The expression is wrapped
+    /// in parentheses if it can't be used directly as a prefix.
+    pub fn method_call<S: Into<Identifier>, A: Into<Arguments>>(
+        self,
+        method: S,
+        arguments: A,
+    ) -> Self {
+        Prefix::from(self).method_call(method, arguments).into()
+    }
 }
 
 impl From<bool> for Expression {
@@ -351,4 +379,42 @@ mod test {
             f64_minus_zero => -0.0,
         );
     }
+
+    mod builders {
+        use crate::generator::{LuaGenerator, ReadableLuaGenerator};
+        use crate::nodes::{Expression, StringExpression};
+
+        fn generate(expression: Expression) -> String {
+            let mut generator = ReadableLuaGenerator::default();
+            generator.write_expression(&expression);
+            generator.into_string()
+        }
+
+        #[test]
+        fn method_call_on_identifier_fields() {
+            let expression = Expression::identifier("obj")
+                .index_field("a")
+                .index_field("b")
+                .method_call("c", vec![Expression::from(1), Expression::from(2)]);
+
+            assert_eq!(generate(expression), "obj.a.b:c(1, 2)");
+        }
+
+        #[test]
+        fn method_call_on_string_adds_parentheses() {
+            let expression =
+                Expression::from(StringExpression::from_value("abc")).method_call("upper", vec![]);
+
+            assert_eq!(generate(expression), "('abc'):upper()");
+        }
+
+        #[test]
+        fn index_on_call_result() {
+            let expression = Expression::identifier("get")
+                .call(vec![])
+                .index(Expression::from(1));
+
+            assert_eq!(generate(expression), "get()[1]");
+        }
+    }
 }
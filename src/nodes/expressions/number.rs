@@ -71,7 +71,8 @@ impl DecimalNumber {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HexNumber {
     integer: u64,
-    exponent: Option<(u32, bool)>,
+    fraction: Option<(u64, u32)>,
+    exponent: Option<(i32, bool)>,
     is_x_uppercase: bool,
     token: Option<Token>,
 }
@@ -80,6 +81,7 @@ impl HexNumber {
     pub fn new(integer: u64, is_x_uppercase: bool) -> Self {
         Self {
             integer,
+            fraction: None,
             exponent: None,
             is_x_uppercase,
             token: None,
@@ -101,7 +103,14 @@ impl HexNumber {
         self.token.as_ref()
     }
 
-    pub fn with_exponent(mut self, exponent: u32, is_uppercase: bool) -> Self {
+    /// Adds a fractional part to the number (like the `8` in `0x1.8p3`). The fraction
+    /// is given as the value of its hexadecimal digits and the number of digits.
+    pub fn with_fraction(mut self, fraction: u64, digits: u32) -> Self {
+        self.fraction.replace((fraction, digits));
+        self
+    }
+
+    pub fn with_exponent(mut self, exponent: i32, is_uppercase: bool) -> Self {
         self.exponent.replace((exponent, is_uppercase));
         self
     }
@@ -126,17 +135,33 @@ impl HexNumber {
         self.integer
     }
 
+    /// Returns the value of the fractional digits and the number of digits.
+    #[inline]
+    pub fn get_raw_fraction(&self) -> Option<(u64, u32)> {
+        self.fraction
+    }
+
     #[inline]
-    pub fn get_exponent(&self) -> Option<u32> {
+    pub fn get_exponent(&self) -> Option<i32> {
         self.exponent.map(|(value, _)| value)
     }
 
     pub fn compute_value(&self) -> f64 {
-        if let Some((exponent, _)) = self.exponent {
-            (self.integer * 2_u64.pow(exponent)) as f64
+        let exponent = self.get_exponent().unwrap_or(0);
+
+        let mut value = if self.integer == 0 {
+            0.0
         } else {
-            self.integer as f64
+            self.integer as f64 * 2_f64.powi(exponent)
+        };
+
+        if let Some((fraction, digits)) = self.fraction.filter(|(fraction, _)| *fraction != 0) {
+            // each hexadecimal digit of the fraction divides its value by 2^4
+            let fraction_exponent = exponent.saturating_sub((digits as i32).saturating_mul(4));
+            value += fraction as f64 * 2_f64.powi(fraction_exponent);
         }
+
+        value
     }
 
     super::impl_token_fns!(iter = [token]);
@@ -331,6 +356,55 @@ fn filter_underscore(number: &str) -> String {
     number.chars().filter(|c| c != &'_').collect()
 }
 
+/// Parses the digits of an hexadecimal number that follow the `0x` prefix, with an
+/// optional fractional part (like `1.8` in `0x1.8p3`).
+fn parse_hex_number(digits: &str, is_uppercase: bool) -> Result<HexNumber, NumberParsingError> {
+    let filtered = filter_underscore(digits);
+
+    let (integer, fraction) = match filtered.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (filtered.as_str(), None),
+    };
+
+    let number = match fraction {
+        Some(fraction) => {
+            if integer.is_empty() && fraction.is_empty() {
+                return Err(NumberParsingError::InvalidHexadecimalNumber);
+            }
+            let integer = if integer.is_empty() {
+                0
+            } else {
+                u64::from_str_radix(integer, 16)
+                    .map_err(|_| NumberParsingError::InvalidHexadecimalNumber)?
+            };
+            if !fraction.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(NumberParsingError::InvalidHexadecimalNumber);
+            }
+            // digits after the first 16 significant digits do not fit in the fraction
+            // value and are beyond the precision of a double-precision number
+            let significant_start = fraction.find(|c| c != '0').unwrap_or(fraction.len());
+            let fraction = &fraction[..fraction.len().min(significant_start + 16)];
+            let fraction_value = if fraction.is_empty() {
+                0
+            } else {
+                u64::from_str_radix(fraction, 16)
+                    .map_err(|_| NumberParsingError::InvalidHexadecimalNumber)?
+            };
+
+            HexNumber::new(integer, is_uppercase)
+                .with_fraction(fraction_value, fraction.len() as u32)
+        }
+        None => {
+            let integer = u64::from_str_radix(integer, 16)
+                .map_err(|_| NumberParsingError::InvalidHexadecimalNumber)?;
+
+            HexNumber::new(integer, is_uppercase)
+        }
+    };
+
+    Ok(number)
+}
+
 impl FromStr for NumberExpression {
     type Err = NumberParsingError;
 
@@ -357,17 +431,11 @@ impl FromStr for NumberExpression {
                             .and_then(|string| string.parse().ok())
                             .ok_or(Self::Err::InvalidHexadecimalExponent)?;
                         let before_exponent = value.get(position + 1..index).unwrap();
-                        let number = u64::from_str_radix(before_exponent, 16)
-                            .map_err(|_| Self::Err::InvalidHexadecimalNumber)?;
 
-                        HexNumber::new(number, is_uppercase)
+                        parse_hex_number(before_exponent, is_uppercase)?
                             .with_exponent(exponent, exponent_is_uppercase)
                     } else {
-                        let filtered = filter_underscore(value.get(position + 1..).unwrap());
-                        let number = u64::from_str_radix(&filtered, 16)
-                            .map_err(|_| Self::Err::InvalidHexadecimalNumber)?;
-
-                        HexNumber::new(number, is_uppercase)
+                        parse_hex_number(value.get(position + 1..).unwrap(), is_uppercase)?
                     }
                     .into()
                 } else if notation == 'b' || notation == 'B' {
@@ -551,6 +619,28 @@ mod test {
             parse_hex_number_with_mixed_case("0x1bF2A") => HexNumber::new(114_474, false),
             parse_hex_with_exponent("0x12p4") => HexNumber::new(18, false).with_exponent(4, false),
             parse_hex_with_exponent_uppercase("0xABP3") => HexNumber::new(171, false).with_exponent(3, true),
+            parse_hex_float("0x1.8") => HexNumber::new(1, false).with_fraction(8, 1),
+            parse_hex_float_with_exponent("0x1.8p3") => HexNumber::new(1, false)
+                .with_fraction(8, 1)
+                .with_exponent(3, false),
+            parse_hex_float_without_integer("0x.1p4") => HexNumber::new(0, false)
+                .with_fraction(1, 1)
+                .with_exponent(4, false),
+            parse_hex_float_with_trailing_dot("0x1.p2") => HexNumber::new(1, false)
+                .with_fraction(0, 0)
+                .with_exponent(2, false),
+            parse_hex_float_with_leading_zero_fraction("0xA.08") => HexNumber::new(10, false).with_fraction(8, 2),
+            parse_hex_with_negative_exponent("0x1p-3") => HexNumber::new(1, false).with_exponent(-3, false),
+            parse_hex_with_negative_exponent_uppercase("0x1P-3") => HexNumber::new(1, false).with_exponent(-3, true),
+            parse_hex_with_positive_exponent_sign("0x1p+3") => HexNumber::new(1, false).with_exponent(3, false),
+            parse_hex_float_with_negative_exponent("0x1.8p-3") => HexNumber::new(1, false)
+                .with_fraction(8, 1)
+                .with_exponent(-3, false),
+            parse_hex_float_with_many_fraction_digits("0x1.00000000000000001p0") => HexNumber::new(1, false)
+                .with_fraction(1, 17)
+                .with_exponent(0, false),
+            parse_hex_float_with_too_many_significant_digits("0x.123456789abcdef01234") => HexNumber::new(0, false)
+                .with_fraction(0x123456789abcdef0, 16),
             parse_binary_zero("0b0") => BinaryNumber::new(0, false),
             parse_binary_zero_with_underscore_before_b("0_b1") => BinaryNumber::new(1, false),
             parse_binary_zero_with_underscore("0b1010_1100") => BinaryNumber::new(0b1010_1100, false),
@@ -570,11 +660,13 @@ mod test {
             invalid_underscore_before_negative_exponent_uppercase("1E_-1") => NumberParsingError::InvalidDecimalExponent,
             invalid_underscore_before_positive_exponent_uppercase("1E_+1") => NumberParsingError::InvalidDecimalExponent,
             missing_hex_exponent_value("0x1p") => NumberParsingError::InvalidHexadecimalExponent,
-            negative_hex_exponent_value("0x1p-3") => NumberParsingError::InvalidHexadecimalExponent,
             missing_hex_exponent_value_uppercase("0x1P") => NumberParsingError::InvalidHexadecimalExponent,
             invalid_hex_exponent_value("0x1p1Z") => NumberParsingError::InvalidHexadecimalExponent,
             invalid_hex_exponent_value_uppercase("0x1P1Z") => NumberParsingError::InvalidHexadecimalExponent,
-            negative_hex_exponent_value_uppercase("0x1P-3") => NumberParsingError::InvalidHexadecimalExponent,
+            missing_negative_hex_exponent_value("0x1p-") => NumberParsingError::InvalidHexadecimalExponent,
+            invalid_digit_in_hex_fraction("0x1.00000000000000000000000g") => NumberParsingError::InvalidHexadecimalNumber,
+            hex_float_without_digits("0x.p1") => NumberParsingError::InvalidHexadecimalNumber,
+            hex_float_with_two_dots("0x1.2.3") => NumberParsingError::InvalidHexadecimalNumber,
             invalid_digit_in_binary("0b190") => NumberParsingError::InvalidBinaryNumber,
             invalid_digit_in_binary_uppercase("0B190") => NumberParsingError::InvalidBinaryNumber,
         );
@@ -609,6 +701,14 @@ mod test {
             hex_number("0x12") => 0x12,
             hex_number_with_letter("0x12a") => 0x12a,
             hex_with_exponent("0x12p4") => 0x120,
+            hex_float("0x1.8") => 1.5,
+            hex_float_with_exponent("0x1.8p3") => 12.0,
+            hex_float_without_integer("0x.1p4") => 1.0,
+            hex_float_with_leading_zero_fraction("0x1.08") => 1.03125,
+            hex_with_negative_exponent("0x1p-4") => 0.0625,
+            hex_float_with_negative_exponent("0x1.8p-3") => 0.1875,
+            hex_float_with_many_fraction_digits("0x1.00000000000000001p0") => 1.0,
+            hex_float_with_leading_zeros_in_fraction("0x.00000000000000000008p80") => 0.5,
             binary_zero("0b0") => 0b0,
             binary_ten("0b1010") => 0b1010,
         );
@@ -1,5 +1,6 @@
 use crate::nodes::{
-    Expression, FieldExpression, FunctionCall, Identifier, IndexExpression, ParentheseExpression,
+    Arguments, Expression, FieldExpression, FunctionCall, Identifier, IndexExpression,
+    ParentheseExpression,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -15,6 +16,32 @@ impl Prefix {
     pub fn from_name<S: Into<Identifier>>(name: S) -> Self {
         Self::Identifier(name.into())
     }
+
+    /// Creates a field expression (`prefix.field`) from this prefix.
+    pub fn index_field<S: Into<Identifier>>(self, field: S) -> Self {
+        FieldExpression::new(self, field).into()
+    }
+
+    /// Creates an index expression (`prefix[index]`) from this prefix.
+    pub fn index<E: Into<Expression>>(self, index: E) -> Self {
+        IndexExpression::new(self, index).into()
+    }
+
+    /// Creates a function call (`prefix(...)`) using this prefix.
+    pub fn call<A: Into<Arguments>>(self, arguments: A) -> FunctionCall {
+        FunctionCall::from_prefix(self).with_arguments(arguments)
+    }
+
+    /// Creates a method call (`prefix:method(...)`) using this prefix.
+    pub fn method_call<S: Into<Identifier>, A: Into<Arguments>>(
+        self,
+        method: S,
+        arguments: A,
+    ) -> FunctionCall {
+        FunctionCall::from_prefix(self)
+            .with_method(method)
+            .with_arguments(arguments)
+    }
 }
 
 impl From<Expression> for Prefix {
@@ -85,3 +112,56 @@ impl From<ParentheseExpression> for Prefix {
         Self::Parenthese(expression)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generator::{LuaGenerator, ReadableLuaGenerator};
+    use crate::nodes::StringExpression;
+
+    fn generate(expression: impl Into<Expression>) -> String {
+        let mut generator = ReadableLuaGenerator::default();
+        generator.write_expression(&expression.into());
+        generator.into_string()
+    }
+
+    #[test]
+    fn index_field_chain() {
+        let prefix = Prefix::from_name("obj").index_field("a").index_field("b");
+
+        assert_eq!(generate(prefix), "obj.a.b");
+    }
+
+    #[test]
+    fn index_with_string() {
+        let prefix = Prefix::from_name("obj").index(StringExpression::from_value("key"));
+
+        assert_eq!(generate(prefix), "obj['key']");
+    }
+
+    #[test]
+    fn call_with_arguments() {
+        let call = Prefix::from_name("print").call(vec![Expression::from(1), Expression::from(2)]);
+
+        assert_eq!(generate(call), "print(1, 2)");
+    }
+
+    #[test]
+    fn method_call_on_two_fields() {
+        let call = Prefix::from_name("obj")
+            .index_field("a")
+            .index_field("b")
+            .method_call("c", vec![Expression::from(1), Expression::from(2)]);
+
+        assert_eq!(generate(call), "obj.a.b:c(1, 2)");
+    }
+
+    #[test]
+    fn chain_after_call() {
+        let call = Prefix::from(Prefix::from_name("game").method_call("GetService", vec![]))
+            .index_field("LocalPlayer")
+            .call(Arguments::default());
+
+        assert_eq!(generate(call), "game:GetService().LocalPlayer()");
+    }
+}
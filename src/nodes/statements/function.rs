@@ -3,6 +3,8 @@ use crate::nodes::{
     Identifier, Token, TypedIdentifier,
 };
 
+const SELF_PARAMETER_NAME: &str = "self";
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FunctionNameTokens {
     pub periods: Vec<Token>,
@@ -89,6 +91,13 @@ impl FunctionName {
         self.method.is_some()
     }
 
+    /// Returns true if the name defines a method (like `function object:method() end`),
+    /// which receives an implicit `self` parameter.
+    #[inline]
+    pub fn is_method(&self) -> bool {
+        self.has_method()
+    }
+
     #[inline]
     pub fn get_name(&self) -> &Identifier {
         &self.name
@@ -301,11 +310,73 @@ impl FunctionStatement {
         &mut self.parameters
     }
 
+    /// Returns the `self` parameter that a method definition receives implicitly, or
+    /// `None` if the function is not a method.
+    pub fn implicit_self_parameter(&self) -> Option<TypedIdentifier> {
+        self.name
+            .is_method()
+            .then(|| TypedIdentifier::new(SELF_PARAMETER_NAME))
+    }
+
+    /// Returns true if the function can be converted to a method definition with
+    /// [`convert_to_method`](Self::convert_to_method): its name has at least one field
+    /// and its first parameter is a `self` parameter without a type.
+    pub fn has_explicit_self_parameter(&self) -> bool {
+        !self.name.is_method()
+            && !self.name.field_names.is_empty()
+            && self.parameters.first().is_some_and(|parameter| {
+                parameter.get_name() == SELF_PARAMETER_NAME && !parameter.has_type()
+            })
+    }
+
+    /// Converts a method definition into a function with an explicit `self` parameter
+    /// (`function object:method() end` becomes `function object.method(self) end`).
     pub fn remove_method(&mut self) {
         if let Some(method_name) = self.name.remove_method() {
             self.name.push_field(method_name);
-            self.parameters.insert(0, TypedIdentifier::new("self"));
+            self.parameters
+                .insert(0, TypedIdentifier::new(SELF_PARAMETER_NAME));
+        }
+    }
+
+    /// Converts a function with an explicit `self` parameter into a method definition
+    /// (`function object.method(self) end` becomes `function object:method() end`).
+    /// Returns false and leaves the function unchanged when
+    /// [`has_explicit_self_parameter`](Self::has_explicit_self_parameter) is false.
+    pub fn convert_to_method(&mut self) -> bool {
+        if !self.has_explicit_self_parameter() {
+            return false;
+        }
+
+        if let Some(method_name) = self.name.field_names.pop() {
+            self.name.method = Some(method_name);
+            self.parameters.remove(0);
+
+            if let Some(tokens) = &mut self.tokens {
+                if !tokens.parameter_commas.is_empty() {
+                    tokens.parameter_commas.remove(0);
+                }
+            }
+            if let Some(tokens) = &mut self.name.tokens {
+                tokens.periods.pop();
+            }
         }
+
+        true
+    }
+
+    /// Consumes the function and returns it as a method definition, if it can be
+    /// converted with [`convert_to_method`](Self::convert_to_method).
+    pub fn with_method_definition(mut self) -> Self {
+        self.convert_to_method();
+        self
+    }
+
+    /// Consumes the function and returns it with an explicit `self` parameter, if it
+    /// is a method definition.
+    pub fn with_explicit_self_parameter(mut self) -> Self {
+        self.remove_method();
+        self
     }
 
     #[inline]
@@ -330,3 +401,104 @@ impl FunctionStatement {
         iter = [parameters, generic_parameters, tokens]
     );
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn method_definition() -> FunctionStatement {
+        FunctionStatement::new(
+            FunctionName::from_name("Class").with_method("update"),
+            Block::default(),
+            vec![TypedIdentifier::new("delta")],
+            false,
+        )
+    }
+
+    #[test]
+    fn method_definition_is_method() {
+        let function = method_definition();
+
+        assert!(function.get_name().is_method());
+        pretty_assertions::assert_eq!(
+            function.get_name().get_method(),
+            Some(&Identifier::new("update"))
+        );
+    }
+
+    #[test]
+    fn function_with_fields_is_not_method() {
+        let name = FunctionName::from_name("Class").with_field("update");
+
+        assert!(!name.is_method());
+    }
+
+    #[test]
+    fn method_definition_has_implicit_self_parameter() {
+        pretty_assertions::assert_eq!(
+            method_definition().implicit_self_parameter(),
+            Some(TypedIdentifier::new("self"))
+        );
+    }
+
+    #[test]
+    fn function_without_method_has_no_implicit_self_parameter() {
+        let function = FunctionStatement::from_name("update", Block::default());
+
+        pretty_assertions::assert_eq!(function.implicit_self_parameter(), None);
+    }
+
+    #[test]
+    fn with_explicit_self_parameter_removes_method() {
+        pretty_assertions::assert_eq!(
+            method_definition().with_explicit_self_parameter(),
+            FunctionStatement::new(
+                FunctionName::from_name("Class").with_field("update"),
+                Block::default(),
+                vec![TypedIdentifier::new("self"), TypedIdentifier::new("delta")],
+                false,
+            )
+        );
+    }
+
+    #[test]
+    fn with_method_definition_restores_method() {
+        pretty_assertions::assert_eq!(
+            method_definition()
+                .with_explicit_self_parameter()
+                .with_method_definition(),
+            method_definition()
+        );
+    }
+
+    #[test]
+    fn convert_to_method_without_fields_is_false() {
+        let mut function =
+            FunctionStatement::from_name("update", Block::default()).with_parameter("self");
+
+        assert!(!function.has_explicit_self_parameter());
+        assert!(!function.convert_to_method());
+        pretty_assertions::assert_eq!(function.parameters_count(), 1);
+    }
+
+    #[test]
+    fn convert_to_method_without_self_parameter_is_false() {
+        let mut function = FunctionStatement::new(
+            FunctionName::from_name("Class").with_field("update"),
+            Block::default(),
+            vec![TypedIdentifier::new("object")],
+            false,
+        );
+
+        assert!(!function.convert_to_method());
+        assert!(!function.get_name().is_method());
+    }
+
+    #[test]
+    fn convert_to_method_of_method_is_false() {
+        let mut function = method_definition();
+
+        assert!(!function.convert_to_method());
+        pretty_assertions::assert_eq!(function, method_definition());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::nodes::{
+    Block, FunctionBodyTokens, FunctionReturnType, FunctionVariadicType, GenericParameters,
+    Identifier, Token, TypedIdentifier,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeFunctionTokens {
+    pub r#type: Token,
+    pub function_body: FunctionBodyTokens,
+    pub export: Option<Token>,
+}
+
+impl TypeFunctionTokens {
+    super::impl_token_fns!(
+        target = [r#type, function_body]
+        iter = [export]
+    );
+}
+
+impl std::ops::Deref for TypeFunctionTokens {
+    type Target = FunctionBodyTokens;
+
+    fn deref(&self) -> &Self::Target {
+        &self.function_body
+    }
+}
+
+impl std::ops::DerefMut for TypeFunctionTokens {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.function_body
+    }
+}
+
+/// A Luau user-defined type function (`type function name(...) ... end`).
+///
+/// The body of a type function runs when types are analyzed, in an environment that is
+/// separated from the rest of the code. For this reason, the default visitors do not go
+/// through its block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeFunctionStatement {
+    identifier: Identifier,
+    block: Block,
+    parameters: Vec<TypedIdentifier>,
+    is_variadic: bool,
+    variadic_type: Option<FunctionVariadicType>,
+    return_type: Option<FunctionReturnType>,
+    generic_parameters: Option<GenericParameters>,
+    exported: bool,
+    tokens: Option<Box<TypeFunctionTokens>>,
+}
+
+impl TypeFunctionStatement {
+    pub fn new(
+        identifier: impl Into<Identifier>,
+        block: Block,
+        parameters: Vec<TypedIdentifier>,
+        is_variadic: bool,
+    ) -> Self {
+        Self {
+            identifier: identifier.into(),
+            block,
+            parameters,
+            is_variadic,
+            variadic_type: None,
+            return_type: None,
+            generic_parameters: None,
+            exported: false,
+            tokens: None,
+        }
+    }
+
+    pub fn from_name(identifier: impl Into<Identifier>, block: impl Into<Block>) -> Self {
+        Self::new(identifier, block.into(), Vec::new(), false)
+    }
+
+    pub fn with_tokens(mut self, tokens: TypeFunctionTokens) -> Self {
+        self.tokens = Some(tokens.into());
+        self
+    }
+
+    #[inline]
+    pub fn set_tokens(&mut self, tokens: TypeFunctionTokens) {
+        self.tokens = Some(tokens.into());
+    }
+
+    #[inline]
+    pub fn get_tokens(&self) -> Option<&TypeFunctionTokens> {
+        self.tokens.as_deref()
+    }
+
+    #[inline]
+    pub fn mutate_tokens(&mut self) -> Option<&mut TypeFunctionTokens> {
+        self.tokens.as_deref_mut()
+    }
+
+    pub fn export(mut self) -> Self {
+        self.exported = true;
+        self
+    }
+
+    #[inline]
+    pub fn set_exported(&mut self) {
+        self.exported = true;
+    }
+
+    #[inline]
+    pub fn remove_exported(&mut self) {
+        self.exported = false;
+        if let Some(tokens) = self.tokens.as_mut() {
+            tokens.export.take();
+        }
+    }
+
+    #[inline]
+    pub fn is_exported(&self) -> bool {
+        self.exported
+    }
+
+    pub fn with_parameter(mut self, parameter: impl Into<TypedIdentifier>) -> Self {
+        self.parameters.push(parameter.into());
+        self
+    }
+
+    pub fn variadic(mut self) -> Self {
+        self.is_variadic = true;
+        self
+    }
+
+    pub fn with_variadic_type(mut self, r#type: impl Into<FunctionVariadicType>) -> Self {
+        self.is_variadic = true;
+        self.variadic_type = Some(r#type.into());
+        self
+    }
+
+    pub fn set_variadic_type(&mut self, r#type: impl Into<FunctionVariadicType>) {
+        self.is_variadic = true;
+        self.variadic_type = Some(r#type.into());
+    }
+
+    #[inline]
+    pub fn get_variadic_type(&self) -> Option<&FunctionVariadicType> {
+        self.variadic_type.as_ref()
+    }
+
+    #[inline]
+    pub fn has_variadic_type(&self) -> bool {
+        self.variadic_type.is_some()
+    }
+
+    #[inline]
+    pub fn mutate_variadic_type(&mut self) -> Option<&mut FunctionVariadicType> {
+        self.variadic_type.as_mut()
+    }
+
+    pub fn with_return_type(mut self, return_type: impl Into<FunctionReturnType>) -> Self {
+        self.return_type = Some(return_type.into());
+        self
+    }
+
+    pub fn set_return_type(&mut self, return_type: impl Into<FunctionReturnType>) {
+        self.return_type = Some(return_type.into());
+    }
+
+    #[inline]
+    pub fn get_return_type(&self) -> Option<&FunctionReturnType> {
+        self.return_type.as_ref()
+    }
+
+    #[inline]
+    pub fn has_return_type(&self) -> bool {
+        self.return_type.is_some()
+    }
+
+    #[inline]
+    pub fn mutate_return_type(&mut self) -> Option<&mut FunctionReturnType> {
+        self.return_type.as_mut()
+    }
+
+    pub fn with_generic_parameters(mut self, generic_parameters: GenericParameters) -> Self {
+        self.generic_parameters = Some(generic_parameters);
+        self
+    }
+
+    #[inline]
+    pub fn set_generic_parameters(&mut self, generic_parameters: GenericParameters) {
+        self.generic_parameters = Some(generic_parameters);
+    }
+
+    #[inline]
+    pub fn get_generic_parameters(&self) -> Option<&GenericParameters> {
+        self.generic_parameters.as_ref()
+    }
+
+    #[inline]
+    pub fn mutate_parameters(&mut self) -> &mut Vec<TypedIdentifier> {
+        &mut self.parameters
+    }
+
+    #[inline]
+    pub fn mutate_block(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
+    #[inline]
+    pub fn mutate_identifier(&mut self) -> &mut Identifier {
+        &mut self.identifier
+    }
+
+    #[inline]
+    pub fn get_block(&self) -> &Block {
+        &self.block
+    }
+
+    #[inline]
+    pub fn get_parameters(&self) -> &Vec<TypedIdentifier> {
+        &self.parameters
+    }
+
+    #[inline]
+    pub fn iter_parameters(&self) -> impl Iterator<Item = &TypedIdentifier> {
+        self.parameters.iter()
+    }
+
+    #[inline]
+    pub fn iter_mut_parameters(&mut self) -> impl Iterator<Item = &mut TypedIdentifier> {
+        self.parameters.iter_mut()
+    }
+
+    #[inline]
+    pub fn get_identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    #[inline]
+    pub fn get_name(&self) -> &str {
+        self.identifier.get_name()
+    }
+
+    #[inline]
+    pub fn is_variadic(&self) -> bool {
+        self.is_variadic
+    }
+
+    #[inline]
+    pub fn parameters_count(&self) -> usize {
+        self.parameters.len()
+    }
+
+    super::impl_token_fns!(
+        target = [identifier]
+        iter = [parameters, generic_parameters, tokens]
+    );
+}
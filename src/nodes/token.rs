@@ -54,6 +54,72 @@ impl TriviaKind {
     }
 }
 
+/// The kind of a comment: a line comment (`-- comment`) or a block comment
+/// (`--[[ comment ]]`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommentKind {
+    Line,
+    Block,
+}
+
+/// A comment read from a trivia, obtained with [`Trivia::as_comment`] or
+/// [`Token::iter_comments`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Comment<'a> {
+    kind: CommentKind,
+    content: &'a str,
+}
+
+impl<'a> Comment<'a> {
+    /// Creates a comment from its content, including the `--` prefix.
+    pub fn new(content: &'a str) -> Self {
+        let kind = if get_block_comment_level(content).is_some() {
+            CommentKind::Block
+        } else {
+            CommentKind::Line
+        };
+        Self { kind, content }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> CommentKind {
+        self.kind
+    }
+
+    /// The complete content of the comment, including the comment delimiters.
+    #[inline]
+    pub fn content(&self) -> &'a str {
+        self.content
+    }
+
+    /// The text of the comment, without the comment delimiters.
+    pub fn text(&self) -> &'a str {
+        match self.kind {
+            CommentKind::Line => self.content.get(2..).unwrap_or_default(),
+            CommentKind::Block => {
+                let level = get_block_comment_level(self.content).unwrap_or_default();
+                let delimiter_length = level + 2;
+                let text = self.content.get(2 + delimiter_length..).unwrap_or_default();
+                let closing = format!("]{}]", "=".repeat(level));
+                text.strip_suffix(closing.as_str()).unwrap_or(text)
+            }
+        }
+    }
+}
+
+/// Returns the number of `=` in the opening bracket of a block comment, or `None`
+/// if the content is not a block comment.
+fn get_block_comment_level(content: &str) -> Option<usize> {
+    let bracket = content.strip_prefix("--[")?;
+    let level = bracket.chars().take_while(|c| *c == '=').count();
+
+    if bracket.get(level..)?.starts_with('[') {
+        Some(level)
+    } else {
+        None
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Trivia {
     position: Position,
@@ -83,6 +149,15 @@ impl Trivia {
         self.kind.clone()
     }
 
+    /// Returns the comment if the trivia is a comment. The code is used to read the
+    /// trivia when it refers to the original code.
+    pub fn as_comment<'a>(&'a self, code: &'a str) -> Option<Comment<'a>> {
+        match self.kind {
+            TriviaKind::Comment => Some(Comment::new(self.read(code))),
+            TriviaKind::Whitespace => None,
+        }
+    }
+
     pub fn get_line_number(&self) -> Option<usize> {
         match &self.position {
             Position::LineNumber { line_number, .. }
@@ -169,6 +244,15 @@ impl Token {
         self.trailing_trivia.iter()
     }
 
+    /// Iterates over the comments of the leading trivia and then the trailing
+    /// trivia. The code is used to read the comments that refer to the original code.
+    pub fn iter_comments<'a>(&'a self, code: &'a str) -> impl Iterator<Item = Comment<'a>> {
+        self.leading_trivia
+            .iter()
+            .chain(self.trailing_trivia.iter())
+            .filter_map(move |trivia| trivia.as_comment(code))
+    }
+
     pub fn read<'a: 'b, 'b>(&'a self, code: &'b str) -> &'b str {
         match &self.position {
             Position::LineNumberReference { start, end, .. } => code
@@ -292,4 +376,66 @@ mod test {
 
         assert_eq!("true", token.read(""));
     }
+
+    macro_rules! test_comment {
+        ($($name:ident($content:literal) => ($kind:expr, $text:literal)),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    let comment = Comment::new($content);
+
+                    pretty_assertions::assert_eq!(comment.kind(), $kind);
+                    pretty_assertions::assert_eq!(comment.content(), $content);
+                    pretty_assertions::assert_eq!(comment.text(), $text);
+                }
+            )*
+        };
+    }
+
+    test_comment!(
+        line_comment("-- line") => (CommentKind::Line, " line"),
+        empty_line_comment("--") => (CommentKind::Line, ""),
+        line_comment_with_bracket("--[ line") => (CommentKind::Line, "[ line"),
+        line_comment_with_bracket_and_equal("--[= line") => (CommentKind::Line, "[= line"),
+        block_comment("--[[ block ]]") => (CommentKind::Block, " block "),
+        empty_block_comment("--[[]]") => (CommentKind::Block, ""),
+        block_comment_with_level("--[==[ block ]] ]==]") => (CommentKind::Block, " block ]] "),
+        multiline_block_comment("--[[\nfirst\nsecond\n]]") => (CommentKind::Block, "\nfirst\nsecond\n"),
+    );
+
+    #[test]
+    fn iter_comments_of_parsed_code() {
+        let code = "--[[ block ]]\nlocal a = 1 -- line\n";
+        let block = crate::Parser::default()
+            .preserve_tokens()
+            .parse(code)
+            .unwrap();
+
+        let assign = match block.first_statement() {
+            Some(crate::nodes::Statement::LocalAssign(assign)) => assign,
+            _ => panic!("local assignment expected"),
+        };
+
+        let local_comments: Vec<_> = assign
+            .get_tokens()
+            .unwrap()
+            .local
+            .iter_comments(code)
+            .map(|comment| (comment.kind(), comment.text()))
+            .collect();
+
+        pretty_assertions::assert_eq!(local_comments, vec![(CommentKind::Block, " block ")]);
+
+        let value_comments: Vec<_> = match assign.iter_values().next() {
+            Some(crate::nodes::Expression::Number(number)) => number
+                .get_token()
+                .unwrap()
+                .iter_comments(code)
+                .map(|comment| (comment.kind(), comment.content()))
+                .collect(),
+            _ => panic!("number expected"),
+        };
+
+        pretty_assertions::assert_eq!(value_comments, vec![(CommentKind::Line, "-- line")]);
+    }
 }
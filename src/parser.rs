@@ -1,9 +1,13 @@
 use std::fmt;
 
-use full_moon::ast::Ast;
+use full_moon::{
+    ast::Ast,
+    tokenizer::{Lexer, LexerResult, TokenType},
+};
 
 use crate::{
     ast_converter::{AstConverter, ConvertError},
+    generator::{LuaSyntax, LuaVersion},
     nodes::*,
     utils::Timer,
 };
@@ -11,12 +15,13 @@ use crate::{
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Parser {
     hold_token_data: bool,
+    lua_version: Option<LuaVersion>,
 }
 
 impl Parser {
     pub fn parse(&self, code: &str) -> Result<Block, ParserError> {
         let full_moon_parse_timer = Timer::now();
-        let parse_result = full_moon::parse(code);
+        let parse_result = full_moon::parse_fallible(code, self.full_moon_version()).into_result();
         log::trace!(
             "full-moon parsing done in {}",
             full_moon_parse_timer.duration_label()
@@ -38,16 +43,53 @@ impl Parser {
         self
     }
 
+    /// Parses code meant to run on the given Lua version. Code for Lua 5.3 and
+    /// Lua 5.4 is parsed with the bitwise operators. Otherwise, the code is parsed
+    /// as Luau, which darklua can convert to the other Lua versions.
+    pub fn with_lua_version(mut self, lua_version: LuaVersion) -> Self {
+        self.lua_version = Some(lua_version);
+        self
+    }
+
     pub(crate) fn is_preserving_tokens(&self) -> bool {
         self.hold_token_data
     }
 
+    fn full_moon_version(&self) -> full_moon::LuaVersion {
+        // Luau and Lua 5.4 can't be parsed at the same time: a Luau type like
+        // `A<B<C>>` would end with the `>>` operator
+        match self.lua_version {
+            Some(version) if version.supports(LuaSyntax::BitwiseOperator) => {
+                full_moon::LuaVersion::lua54()
+            }
+            _ => full_moon::LuaVersion::luau(),
+        }
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn convert_ast(&self, ast: Ast) -> Result<Block, ConvertError> {
         AstConverter::new(self.hold_token_data).convert(&ast)
     }
 }
 
+/// Returns the comments written before the first token of the code (like a
+/// license header), exactly as they appear in the code.
+pub(crate) fn read_header_comments(code: &str) -> Vec<String> {
+    match Lexer::new(code, full_moon::LuaVersion::luau()).current() {
+        Some(LexerResult::Ok(token)) | Some(LexerResult::Recovered(token, _)) => token
+            .leading_trivia()
+            .filter(|trivia| {
+                matches!(
+                    trivia.token_type(),
+                    TokenType::SingleLineComment { .. } | TokenType::MultiLineComment { .. }
+                )
+            })
+            .map(ToString::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 #[derive(Clone, Debug)]
 enum ParserErrorKind {
     Parsing(Vec<full_moon::Error>),
@@ -71,6 +113,19 @@ impl ParserError {
             kind: ParserErrorKind::Converting(err).into(),
         }
     }
+
+    /// Returns the line and the column (both starting at 1) where the first
+    /// syntax error starts. Errors that happen after the code has been parsed
+    /// do not have a position.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match &*self.kind {
+            ParserErrorKind::Parsing(errors) => errors.first().map(|error| {
+                let (start, _) = error.range();
+                (start.line(), start.character())
+            }),
+            ParserErrorKind::Converting(_) => None,
+        }
+    }
 }
 
 impl fmt::Display for ParserError {
@@ -97,10 +152,13 @@ mod test {
 
     macro_rules! test_parse {
         ($($name:ident($input:literal) => $value:expr),* $(,)?) => {
+            test_parse!(Parser::default() => $($name($input) => $value),*);
+        };
+        ($parser:expr => $($name:ident($input:literal) => $value:expr),* $(,)?) => {
             $(
                 #[test]
                 fn $name() {
-                    let parser = Parser::default();
+                    let parser = $parser;
                     let block = parser.parse($input)
                         .expect(&format!("failed to parse `{}`", $input));
 
@@ -182,6 +240,25 @@ mod test {
                 InterpolatedStringExpression::new(vec![StringSegment::from_value("a").into()])
             ).into()]
         )),
+        return_backtick_string_with_table_constructor_value("return `{ {a = 1} }`") => ReturnStatement::one(InterpolatedStringExpression::new(
+            vec![ValueSegment::new(
+                TableExpression::default().append_field("a", 1)
+            ).into()]
+        )),
+        return_backtick_string_with_nested_interpolated_value("return `outer {`inner {x}`}`") => ReturnStatement::one(InterpolatedStringExpression::new(
+            vec![
+                StringSegment::from_value("outer ").into(),
+                ValueSegment::new(
+                    InterpolatedStringExpression::new(vec![
+                        StringSegment::from_value("inner ").into(),
+                        ValueSegment::new(Expression::identifier("x")).into(),
+                    ])
+                ).into(),
+            ]
+        )),
+        return_backtick_string_with_closing_brace_string_value("return `{'}'}`") => ReturnStatement::one(InterpolatedStringExpression::new(
+            vec![ValueSegment::new(StringExpression::from_value("}")).into()]
+        )),
         empty_while_true_do("while true do end") => WhileStatement::new(Block::default(), true),
         while_false_do_break("while false do break end") => WhileStatement::new(
             LastStatement::new_break(),
@@ -427,6 +504,15 @@ mod test {
                 .variadic(),
         local_function_return("local function name() return end")
             => LocalFunctionStatement::from_name("name", ReturnStatement::default()),
+        empty_type_function("type function name() end")
+            => TypeFunctionStatement::from_name("name", Block::default()),
+        type_function_with_one_parameter("type function name(t) return t end")
+            => TypeFunctionStatement::from_name(
+                "name",
+                ReturnStatement::one(Expression::identifier("t")),
+            ).with_parameter("t"),
+        empty_exported_type_function("export type function name() end")
+            => TypeFunctionStatement::from_name("name", Block::default()).export(),
 
         empty_function_statement("function name() end")
             => FunctionStatement::from_name("name", Block::default()),
@@ -510,6 +596,116 @@ mod test {
         ),
     );
 
+    mod lua54 {
+        use super::*;
+
+        test_parse!(Parser::default().with_lua_version(LuaVersion::Lua54) =>
+            return_bitwise_and("return a & b") => ReturnStatement::one(
+                BinaryExpression::new(
+                    BinaryOperator::BitwiseAnd,
+                    Expression::identifier("a"),
+                    Expression::identifier("b"),
+                ),
+            ),
+            return_bitwise_or("return a | b") => ReturnStatement::one(
+                BinaryExpression::new(
+                    BinaryOperator::BitwiseOr,
+                    Expression::identifier("a"),
+                    Expression::identifier("b"),
+                ),
+            ),
+            return_bitwise_xor("return a ~ b") => ReturnStatement::one(
+                BinaryExpression::new(
+                    BinaryOperator::BitwiseXor,
+                    Expression::identifier("a"),
+                    Expression::identifier("b"),
+                ),
+            ),
+            return_shift_left("return a << 2") => ReturnStatement::one(
+                BinaryExpression::new(BinaryOperator::ShiftLeft, Expression::identifier("a"), 2),
+            ),
+            return_shift_right("return a >> 2") => ReturnStatement::one(
+                BinaryExpression::new(BinaryOperator::ShiftRight, Expression::identifier("a"), 2),
+            ),
+            return_bitwise_not("return ~a") => ReturnStatement::one(
+                UnaryExpression::new(UnaryOperator::BitwiseNot, Expression::identifier("a")),
+            ),
+            return_bitwise_operators_precedence("return a | b ~ c & d << e .. f") => ReturnStatement::one(
+                BinaryExpression::new(
+                    BinaryOperator::BitwiseOr,
+                    Expression::identifier("a"),
+                    BinaryExpression::new(
+                        BinaryOperator::BitwiseXor,
+                        Expression::identifier("b"),
+                        BinaryExpression::new(
+                            BinaryOperator::BitwiseAnd,
+                            Expression::identifier("c"),
+                            BinaryExpression::new(
+                                BinaryOperator::ShiftLeft,
+                                Expression::identifier("d"),
+                                BinaryExpression::new(
+                                    BinaryOperator::Concat,
+                                    Expression::identifier("e"),
+                                    Expression::identifier("f"),
+                                ),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+            return_variadic_function_with_bitwise_and("return function(a, ...) return a & b end")
+                => ReturnStatement::one(
+                    FunctionExpression::from_block(ReturnStatement::one(BinaryExpression::new(
+                        BinaryOperator::BitwiseAnd,
+                        Expression::identifier("a"),
+                        Expression::identifier("b"),
+                    )))
+                    .with_parameter("a")
+                    .variadic(),
+                ),
+            return_variadic_function_with_pipe_in_string("return function(...) end, '|'")
+                => ReturnStatement::one(FunctionExpression::default().variadic())
+                    .with_expression(StringExpression::from_value("|")),
+            return_bitwise_or_with_comparison("return a | b == c") => ReturnStatement::one(
+                BinaryExpression::new(
+                    BinaryOperator::Equal,
+                    BinaryExpression::new(
+                        BinaryOperator::BitwiseOr,
+                        Expression::identifier("a"),
+                        Expression::identifier("b"),
+                    ),
+                    Expression::identifier("c"),
+                ),
+            ),
+            return_bitwise_not_with_shift("return ~a >> b") => ReturnStatement::one(
+                BinaryExpression::new(
+                    BinaryOperator::ShiftRight,
+                    UnaryExpression::new(UnaryOperator::BitwiseNot, Expression::identifier("a")),
+                    Expression::identifier("b"),
+                ),
+            ),
+        );
+
+        #[test]
+        fn parse_bitwise_operator_as_luau_error() {
+            assert!(Parser::default().parse("return a << 2").is_err());
+        }
+    }
+
+    #[test]
+    fn parse_nested_generic_types_ending_with_shift_right_symbol() {
+        Parser::default()
+            .parse("type A = B<C<D>>")
+            .expect("nested generic types should parse");
+    }
+
+    #[test]
+    fn parse_local_attribute_error() {
+        let result = Parser::default().parse("local value <close> = resource");
+
+        assert!(result.is_err());
+    }
+
     mod parse_with_tokens {
         use super::*;
 
@@ -526,7 +722,7 @@ mod test {
                                     "failed to parse `{}`: {}\nfull-moon result:\n{:#?}",
                                     $input,
                                     err,
-                                    full_moon::parse($input)
+                                    full_moon::parse_fallible($input, full_moon::LuaVersion::luau()).into_result()
                                 );
                             }
                         };
@@ -830,6 +1026,15 @@ mod test {
                 r#return: spaced_token(0, 6),
                 commas: Vec::new(),
             }),
+            return_hexadecimal_float("return 0x1.8p3") => ReturnStatement::one(
+                HexNumber::new(1, false)
+                    .with_fraction(8, 1)
+                    .with_exponent(3, false)
+                    .with_token(token_at_first_line(7, 14))
+            ).with_tokens(ReturnTokens {
+                r#return: spaced_token(0, 6),
+                commas: Vec::new(),
+            }),
             return_empty_table("return {--[[ inside ]]}") => ReturnStatement::one(
                 TableExpression::default().with_tokens(TableTokens {
                     opening_brace: token_at_first_line(7, 8)
@@ -3426,4 +3631,32 @@ mod test {
             }),
         );
     }
+
+    mod read_header_comments {
+        use super::*;
+
+        macro_rules! test_header_comments {
+            ($($name:ident($input:literal) => [$($comment:literal),* $(,)?]),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let expected: Vec<&str> = vec![$($comment),*];
+                        pretty_assertions::assert_eq!(read_header_comments($input), expected);
+                    }
+                )*
+            };
+        }
+
+        test_header_comments!(
+            empty("") => [],
+            without_comments("return nil") => [],
+            single_line_comment("-- hello\nreturn nil") => ["-- hello"],
+            block_comment("--[[ license ]]\nreturn nil") => ["--[[ license ]]"],
+            block_comment_with_equals("--[==[ license ]==] return nil") => ["--[==[ license ]==]"],
+            multiple_comments("--!strict\n\n-- Copyright\nlocal a = 1") => ["--!strict", "-- Copyright"],
+            only_comments("-- a\n-- b\n") => ["-- a", "-- b"],
+            ignore_comments_after_first_token("local a -- comment\n-- other\n") => [],
+            ignore_shebang("#!/usr/bin/env lua\n-- comment\nprint()") => ["-- comment"],
+        );
+    }
 }
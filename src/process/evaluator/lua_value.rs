@@ -1,4 +1,4 @@
-use crate::nodes::{Expression, NumberExpression, StringExpression};
+use crate::nodes::{Expression, StringExpression};
 
 /// Represents an evaluated Expression result.
 #[derive(Debug, Clone, PartialEq)]
@@ -81,26 +81,13 @@ impl LuaValue {
     }
 
     /// Attempt to convert the Lua value into a number value. This will convert strings when
-    /// possible and return the same value otherwise.
+    /// possible and return the same value otherwise. Strings are converted following the
+    /// rules of Lua's `tonumber` function: surrounding whitespaces are ignored, and
+    /// strings with a format that is not converted the same way by every Lua version
+    /// (like negative hexadecimal numbers) are not converted.
     pub fn number_coercion(self) -> Self {
         match &self {
-            Self::String(string) => {
-                let string = string.trim();
-
-                let number = if string.starts_with('-') {
-                    string
-                        .get(1..)
-                        .and_then(|string| string.parse::<NumberExpression>().ok())
-                        .map(|number| number.compute_value() * -1.0)
-                } else {
-                    string
-                        .parse::<NumberExpression>()
-                        .ok()
-                        .map(|number| number.compute_value())
-                };
-
-                number.map(LuaValue::Number)
-            }
+            Self::String(string) => string_to_number(string).map(LuaValue::Number),
             _ => None,
         }
         .unwrap_or(self)
@@ -117,6 +104,85 @@ impl LuaValue {
     }
 }
 
+fn is_lua_whitespace(character: char) -> bool {
+    matches!(character, ' ' | '\t' | '\n' | '\r' | '\u{B}' | '\u{C}')
+}
+
+fn string_to_number(string: &str) -> Option<f64> {
+    let string = string.trim_matches(is_lua_whitespace);
+
+    let (is_negative, unsigned) = if let Some(unsigned) = string.strip_prefix('-') {
+        (true, unsigned)
+    } else {
+        (false, string.strip_prefix('+').unwrap_or(string))
+    };
+
+    let value = if let Some(digits) = unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+    {
+        // negative hexadecimal strings and values that do not fit in a signed
+        // integer are converted differently depending on the Lua version
+        if is_negative {
+            return None;
+        }
+        parse_hexadecimal(digits)?
+    } else {
+        parse_decimal(unsigned)?
+    };
+
+    Some(if is_negative { -value } else { value })
+}
+
+fn parse_hexadecimal(digits: &str) -> Option<f64> {
+    if digits.is_empty()
+        || !digits
+            .chars()
+            .all(|character| character.is_ascii_hexdigit())
+    {
+        return None;
+    }
+
+    let value = u64::from_str_radix(digits, 16).ok()?;
+
+    if value > i64::MAX as u64 {
+        return None;
+    }
+
+    Some(value as f64)
+}
+
+fn parse_decimal(number: &str) -> Option<f64> {
+    let (mantissa, exponent) = match number.find(['e', 'E']) {
+        Some(index) => (&number[..index], Some(&number[index + 1..])),
+        None => (number, None),
+    };
+
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+        None => (mantissa, ""),
+    };
+
+    let is_digits = |string: &str| string.chars().all(|character| character.is_ascii_digit());
+
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return None;
+    }
+
+    if let Some(exponent) = exponent {
+        let exponent_digits = exponent
+            .strip_prefix('-')
+            .or_else(|| exponent.strip_prefix('+'))
+            .unwrap_or(exponent);
+
+        if exponent_digits.is_empty() || !is_digits(exponent_digits) {
+            return None;
+        }
+    }
+
+    number.parse().ok()
+}
+
 impl Default for LuaValue {
     fn default() -> Self {
         Self::Unknown
@@ -231,17 +297,49 @@ mod test {
             negative_integer("-3") => -3.0,
             hex_zero("0x0") => 0.0,
             hex_integer("0xA") => 10.0,
-            negative_hex_integer("-0xA") => -10.0,
+            uppercase_hex_integer("0XFF") => 255.0,
+            hex_with_surrounding_spaces(" 0x10 ") => 16.0,
+            hex_with_plus_sign("+0x10") => 16.0,
             float("0.5") => 0.5,
             negative_float("-0.5") => -0.5,
-            float_starting_with_dot(".5") => 0.5
+            float_starting_with_dot(".5") => 0.5,
+            float_ending_with_dot("5.") => 5.0,
+            integer_with_plus_sign("+5") => 5.0,
+            integer_with_exponent("1e2") => 100.0,
+            integer_with_uppercase_exponent("1E2") => 100.0,
+            integer_with_positive_exponent("1e+2") => 100.0,
+            float_with_negative_exponent("2.5e-1") => 0.25,
+            integer_with_surrounding_spaces(" 3 ") => 3.0,
+            integer_with_surrounding_new_lines("\n3\r\n") => 3.0,
+            integer_with_vertical_tab_and_form_feed("\u{B}3\u{C}") => 3.0
         );
 
         no_number_coercion!(
             letter_suffix("123a"),
             hex_prefix("0x"),
             space_between_minus("- 1"),
-            two_seperated_digits(" 1 2")
+            two_seperated_digits(" 1 2"),
+            empty(""),
+            only_spaces("   "),
+            only_minus("-"),
+            only_dot("."),
+            double_minus("--1"),
+            minus_and_plus("-+1"),
+            letter_x_suffix("3x"),
+            underscore_separator("1_000"),
+            binary_integer("0b101"),
+            negative_hex_integer("-0xA"),
+            hex_with_invalid_digit("0x1G"),
+            hex_float("0x1.8"),
+            hex_with_exponent("0x1p4"),
+            hex_larger_than_signed_integer("0x8000000000000000"),
+            exponent_without_digits("1e"),
+            exponent_with_only_sign("1e+"),
+            exponent_without_mantissa("e1"),
+            infinity("inf"),
+            uppercase_infinity("INFINITY"),
+            not_a_number("nan"),
+            non_breaking_space("\u{A0}1")
         );
     }
 }
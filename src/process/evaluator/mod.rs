@@ -3,8 +3,25 @@ mod lua_value;
 pub use lua_value::*;
 
 use crate::nodes::*;
+use crate::process::utils::MAX_EXACT_INTEGER;
 
 /// A struct to convert an Expression node into a LuaValue object.
+///
+/// The evaluator computes the value of expressions when it can be known without
+/// running the code, and tells if evaluating an expression may have side effects.
+/// ```rust
+/// # use darklua_core::nodes::{BinaryExpression, BinaryOperator, Expression, FunctionCall};
+/// # use darklua_core::process::{Evaluator, LuaValue};
+/// let evaluator = Evaluator::default();
+///
+/// let sum = Expression::from(BinaryExpression::new(BinaryOperator::Plus, 1.0, 2.0));
+/// assert_eq!(evaluator.evaluate(&sum), LuaValue::Number(3.0));
+/// assert!(!evaluator.has_side_effects(&sum));
+///
+/// let call = Expression::from(FunctionCall::from_name("print"));
+/// assert_eq!(evaluator.evaluate(&call), LuaValue::Unknown);
+/// assert!(evaluator.has_side_effects(&call));
+/// ```
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Evaluator {
     pure_metamethods: bool,
@@ -15,11 +32,23 @@ impl Evaluator {
     /// metamethods can have side effects. For example, indexing a normal table in Lua does not
     /// have any side effects, but if the table is a metatable, it's `__index` metamethod can
     /// possibly have side effects (since it can be a function call).
+    /// ```rust
+    /// # use darklua_core::nodes::{Expression, FieldExpression};
+    /// # use darklua_core::process::Evaluator;
+    /// let field = Expression::from(FieldExpression::new(Expression::identifier("object"), "value"));
+    ///
+    /// assert!(Evaluator::default().has_side_effects(&field));
+    /// assert!(!Evaluator::default()
+    ///     .assume_pure_metamethods()
+    ///     .has_side_effects(&field));
+    /// ```
     pub fn assume_pure_metamethods(mut self) -> Self {
         self.pure_metamethods = true;
         self
     }
 
+    /// Computes the value of the expression. Returns [`LuaValue::Unknown`] when the
+    /// value can not be known statically (like the result of a function call).
     pub fn evaluate(&self, expression: &Expression) -> LuaValue {
         match expression {
             Expression::False(_) => LuaValue::False,
@@ -77,6 +106,8 @@ impl Evaluator {
         }
     }
 
+    /// Returns true if the expression may evaluate to more than one value (or to
+    /// no values at all), like function calls or `...`.
     #[allow(clippy::only_used_in_recursion)]
     pub fn can_return_multiple_values(&self, expression: &Expression) -> bool {
         match expression {
@@ -105,6 +136,10 @@ impl Evaluator {
         }
     }
 
+    /// Returns true if evaluating the expression may have side effects, like calling
+    /// a function or triggering a metamethod. When this returns false, the
+    /// expression can be removed or reordered without changing the behavior of
+    /// the code.
     pub fn has_side_effects(&self, expression: &Expression) -> bool {
         match expression {
             Expression::False(_)
@@ -333,6 +368,13 @@ impl Evaluator {
             BinaryOperator::GreaterOrEqualThan => {
                 self.evaluate_relational(expression, |a, b| a >= b)
             }
+            BinaryOperator::BitwiseAnd => self.evaluate_bitwise(expression, |a, b| a & b),
+            BinaryOperator::BitwiseOr => self.evaluate_bitwise(expression, |a, b| a | b),
+            BinaryOperator::BitwiseXor => self.evaluate_bitwise(expression, |a, b| a ^ b),
+            BinaryOperator::ShiftLeft => self.evaluate_bitwise(expression, shift_left),
+            BinaryOperator::ShiftRight => {
+                self.evaluate_bitwise(expression, |a, b| shift_left(a, b.wrapping_neg()))
+            }
         }
     }
 
@@ -369,6 +411,24 @@ impl Evaluator {
         }
     }
 
+    fn evaluate_bitwise<F>(&self, expression: &BinaryExpression, operation: F) -> LuaValue
+    where
+        F: Fn(i64, i64) -> i64,
+    {
+        let left = self.evaluate(expression.left());
+        let right = self.evaluate(expression.right());
+
+        match (left, right) {
+            (LuaValue::Number(left), LuaValue::Number(right)) => {
+                match (float_to_integer(left), float_to_integer(right)) {
+                    (Some(left), Some(right)) => integer_to_value(operation(left, right)),
+                    _ => LuaValue::Unknown,
+                }
+            }
+            _ => LuaValue::Unknown,
+        }
+    }
+
     fn evaluate_relational<F>(&self, expression: &BinaryExpression, operation: F) -> LuaValue
     where
         F: Fn(f64, f64) -> bool,
@@ -427,6 +487,12 @@ impl Evaluator {
                     _ => LuaValue::Unknown,
                 }
             }
+            UnaryOperator::BitwiseNot => match self.evaluate(expression.get_expression()) {
+                LuaValue::Number(value) => float_to_integer(value)
+                    .map(|value| integer_to_value(!value))
+                    .unwrap_or(LuaValue::Unknown),
+                _ => LuaValue::Unknown,
+            },
             _ => LuaValue::Unknown,
         }
     }
@@ -457,6 +523,41 @@ impl Evaluator {
     }
 }
 
+// 2^63, the first float that does not fit into a 64-bit integer
+const INTEGER_LIMIT: f64 = 9_223_372_036_854_775_808.0;
+
+/// Bitwise operators in Lua convert their operands to integers, which only works for
+/// floats that have an exact integer representation.
+fn float_to_integer(value: f64) -> Option<i64> {
+    if value.fract() == 0.0 && (-INTEGER_LIMIT..INTEGER_LIMIT).contains(&value) {
+        Some(value as i64)
+    } else {
+        None
+    }
+}
+
+/// Integers are only converted back to numbers when every Lua version represents them
+/// exactly, since integers beyond 2^53 would become imprecise floats.
+fn integer_to_value(value: i64) -> LuaValue {
+    if value.unsigned_abs() <= MAX_EXACT_INTEGER as u64 {
+        LuaValue::Number(value as f64)
+    } else {
+        LuaValue::Unknown
+    }
+}
+
+/// Lua shifts are logical: vacated bits are filled with zeros, shifting by 64 bits
+/// or more gives zero and negative displacements shift in the other direction.
+fn shift_left(value: i64, displacement: i64) -> i64 {
+    if displacement <= -64 || displacement >= 64 {
+        0
+    } else if displacement >= 0 {
+        ((value as u64) << displacement) as i64
+    } else {
+        ((value as u64) >> -displacement) as i64
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -742,6 +843,76 @@ mod test {
                 StringExpression::from_value("2"),
                 StringExpression::from_value("3")
             ) => LuaValue::Number(5.0),
+            hex_string_plus_number(
+                BinaryOperator::Plus,
+                StringExpression::from_value("0x10"),
+                1.0
+            ) => LuaValue::Number(17.0),
+            number_minus_uppercase_hex_string(
+                BinaryOperator::Minus,
+                20.0,
+                StringExpression::from_value("0XA")
+            ) => LuaValue::Number(10.0),
+            padded_string_plus_number(
+                BinaryOperator::Plus,
+                StringExpression::from_value(" 3 "),
+                1.0
+            ) => LuaValue::Number(4.0),
+            string_with_new_lines_times_number(
+                BinaryOperator::Asterisk,
+                StringExpression::from_value("\n2\t"),
+                3.0
+            ) => LuaValue::Number(6.0),
+            string_floor_division_string(
+                BinaryOperator::DoubleSlash,
+                StringExpression::from_value("7"),
+                StringExpression::from_value(" 2")
+            ) => LuaValue::Number(3.0),
+            hex_string_floor_division_number(
+                BinaryOperator::DoubleSlash,
+                StringExpression::from_value("0x11"),
+                2.0
+            ) => LuaValue::Number(8.0),
+            string_pow_string(
+                BinaryOperator::Caret,
+                StringExpression::from_value("2"),
+                StringExpression::from_value("1e1")
+            ) => LuaValue::Number(1024.0),
+            number_pow_hex_string(
+                BinaryOperator::Caret,
+                2.0,
+                StringExpression::from_value("0x3")
+            ) => LuaValue::Number(8.0),
+            invalid_string_plus_number(
+                BinaryOperator::Plus,
+                StringExpression::from_value("3x"),
+                1.0
+            ) => LuaValue::Unknown,
+            number_plus_string_with_underscore(
+                BinaryOperator::Plus,
+                1.0,
+                StringExpression::from_value("1_000")
+            ) => LuaValue::Unknown,
+            negative_hex_string_plus_number(
+                BinaryOperator::Plus,
+                StringExpression::from_value("-0x10"),
+                1.0
+            ) => LuaValue::Unknown,
+            infinity_string_times_number(
+                BinaryOperator::Asterisk,
+                StringExpression::from_value("inf"),
+                2.0
+            ) => LuaValue::Unknown,
+            invalid_string_floor_division_number(
+                BinaryOperator::DoubleSlash,
+                StringExpression::from_value("0b11"),
+                2.0
+            ) => LuaValue::Unknown,
+            number_pow_invalid_string(
+                BinaryOperator::Caret,
+                2.0,
+                StringExpression::from_value("0x1p2")
+            ) => LuaValue::Unknown,
             concat_strings(
                 BinaryOperator::Concat,
                 StringExpression::from_value("2"),
@@ -792,6 +963,116 @@ mod test {
                 100.0,
                 StringExpression::from_value("100")
             ) => LuaValue::Unknown,
+            twelve_bitwise_and_ten(
+                BinaryOperator::BitwiseAnd,
+                12.0,
+                10.0
+            ) => LuaValue::Number(8.0),
+            twelve_bitwise_or_three(
+                BinaryOperator::BitwiseOr,
+                12.0,
+                3.0
+            ) => LuaValue::Number(15.0),
+            twelve_bitwise_xor_ten(
+                BinaryOperator::BitwiseXor,
+                12.0,
+                10.0
+            ) => LuaValue::Number(6.0),
+            minus_one_bitwise_and_two_hundred_fifty_five(
+                BinaryOperator::BitwiseAnd,
+                -1.0,
+                255.0
+            ) => LuaValue::Number(255.0),
+            float_bitwise_and_number(
+                BinaryOperator::BitwiseAnd,
+                1.5,
+                1.0
+            ) => LuaValue::Unknown,
+            string_bitwise_or_number(
+                BinaryOperator::BitwiseOr,
+                StringExpression::from_value("1"),
+                2.0
+            ) => LuaValue::Unknown,
+            identifier_bitwise_or_number(
+                BinaryOperator::BitwiseOr,
+                Expression::identifier("value"),
+                2.0
+            ) => LuaValue::Unknown,
+            too_large_number_bitwise_or_zero(
+                BinaryOperator::BitwiseOr,
+                1e19,
+                0.0
+            ) => LuaValue::Unknown,
+            one_shift_left_four(
+                BinaryOperator::ShiftLeft,
+                1.0,
+                4.0
+            ) => LuaValue::Number(16.0),
+            one_shift_left_zero(
+                BinaryOperator::ShiftLeft,
+                1.0,
+                0.0
+            ) => LuaValue::Number(1.0),
+            one_shift_left_fifty_three(
+                BinaryOperator::ShiftLeft,
+                1.0,
+                53.0
+            ) => LuaValue::Number(9007199254740992.0),
+            minus_one_shift_left_fifty_three(
+                BinaryOperator::ShiftLeft,
+                -1.0,
+                53.0
+            ) => LuaValue::Number(-9007199254740992.0),
+            three_shift_left_fifty_two(
+                BinaryOperator::ShiftLeft,
+                3.0,
+                52.0
+            ) => LuaValue::Unknown,
+            one_shift_left_sixty_two(
+                BinaryOperator::ShiftLeft,
+                1.0,
+                62.0
+            ) => LuaValue::Unknown,
+            one_shift_left_sixty_three(
+                BinaryOperator::ShiftLeft,
+                1.0,
+                63.0
+            ) => LuaValue::Unknown,
+            one_shift_left_sixty_four(
+                BinaryOperator::ShiftLeft,
+                1.0,
+                64.0
+            ) => LuaValue::Number(0.0),
+            one_shift_left_minus_one(
+                BinaryOperator::ShiftLeft,
+                2.0,
+                -1.0
+            ) => LuaValue::Number(1.0),
+            sixteen_shift_right_two(
+                BinaryOperator::ShiftRight,
+                16.0,
+                2.0
+            ) => LuaValue::Number(4.0),
+            one_shift_right_minus_three(
+                BinaryOperator::ShiftRight,
+                1.0,
+                -3.0
+            ) => LuaValue::Number(8.0),
+            minus_one_shift_right_sixty_three(
+                BinaryOperator::ShiftRight,
+                -1.0,
+                63.0
+            ) => LuaValue::Number(1.0),
+            minus_one_shift_right_sixty_four(
+                BinaryOperator::ShiftRight,
+                -1.0,
+                64.0
+            ) => LuaValue::Number(0.0),
+            minus_sixteen_shift_right_one(
+                BinaryOperator::ShiftRight,
+                -16.0,
+                1.0
+            ) => LuaValue::Unknown,
         );
 
         macro_rules! evaluate_equality {
@@ -1055,7 +1336,13 @@ mod test {
             minus_one(Minus, DecimalNumber::new(1.0)) => LuaValue::from(-1.0),
             minus_zero(Minus, DecimalNumber::new(-0.0)) => LuaValue::from(-0.0),
             minus_negative_number(Minus, DecimalNumber::new(-5.0)) => LuaValue::from(5.0),
-            minus_string_converted_to_number(Minus, StringExpression::from_value("1")) => LuaValue::from(-1.0)
+            minus_string_converted_to_number(Minus, StringExpression::from_value("1")) => LuaValue::from(-1.0),
+            bitwise_not_zero(BitwiseNot, DecimalNumber::new(0.0)) => LuaValue::from(-1.0),
+            bitwise_not_five(BitwiseNot, DecimalNumber::new(5.0)) => LuaValue::from(-6.0),
+            bitwise_not_minus_one(BitwiseNot, Expression::from(-1.0)) => LuaValue::from(0.0),
+            bitwise_not_float(BitwiseNot, DecimalNumber::new(0.5)) => LuaValue::Unknown,
+            bitwise_not_string(BitwiseNot, StringExpression::from_value("1")) => LuaValue::Unknown,
+            bitwise_not_identifier(BitwiseNot, Expression::identifier("foo")) => LuaValue::Unknown
         );
     }
 
@@ -1,4 +1,7 @@
 //! Defines how rules can process and mutate Lua nodes.
+//!
+//! The [`Evaluator`] can also be used on its own to compute the value of
+//! expressions (as a [`LuaValue`]) and to find if they have side effects.
 
 mod evaluator;
 mod expression_serializer;
@@ -17,6 +20,5 @@ pub(crate) use expression_serializer::*;
 pub use node_counter::NodeCounter;
 pub use node_processor::{NodePostProcessor, NodeProcessor};
 pub use post_visitor::{DefaultPostVisitor, NodePostVisitor};
-pub(crate) use scope_visitor::IdentifierTracker;
-pub use scope_visitor::{Scope, ScopePostVisitor, ScopeVisitor};
+pub use scope_visitor::{IdentifierTracker, Scope, ScopePostVisitor, ScopeVisitor};
 pub use visitors::{DefaultVisitor, NodeVisitor};
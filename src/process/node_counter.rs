@@ -4,6 +4,7 @@ use crate::process::NodeProcessor;
 #[derive(Clone, Debug, Default)]
 pub struct NodeCounter {
     pub block_count: usize,
+    pub statement_count: usize,
     pub function_call_count: usize,
     pub assign_count: usize,
     pub compound_assign: usize,
@@ -35,6 +36,10 @@ impl NodeProcessor for NodeCounter {
         self.block_count += 1;
     }
 
+    fn process_statement(&mut self, _: &mut Statement) {
+        self.statement_count += 1;
+    }
+
     fn process_function_call(&mut self, _: &mut FunctionCall) {
         self.function_call_count += 1;
     }
@@ -64,6 +69,7 @@ impl NodeProcessor for NodeCounter {
     }
 
     fn process_last_statement(&mut self, statement: &mut LastStatement) {
+        self.statement_count += 1;
         match statement {
             LastStatement::Break(_) => self.break_count += 1,
             LastStatement::Continue(_) => self.continue_count += 1,
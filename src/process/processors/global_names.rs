@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::Identifier;
+use crate::process::{IdentifierTracker, NodeProcessor};
+
+/// Collects the identifiers that are used without being declared as a local variable.
+/// This processor must be used with the [`ScopeVisitor`](crate::process::ScopeVisitor).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CollectGlobalNames {
+    identifier_tracker: IdentifierTracker,
+    names: HashSet<String>,
+}
+
+impl Deref for CollectGlobalNames {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for CollectGlobalNames {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl From<CollectGlobalNames> for Vec<String> {
+    fn from(collector: CollectGlobalNames) -> Self {
+        collector.names.into_iter().collect()
+    }
+}
+
+impl From<CollectGlobalNames> for HashSet<String> {
+    fn from(collector: CollectGlobalNames) -> Self {
+        collector.names
+    }
+}
+
+impl NodeProcessor for CollectGlobalNames {
+    fn process_variable_expression(&mut self, variable: &mut Identifier) {
+        if !self.is_identifier_used(variable.get_name()) {
+            self.names.insert(variable.get_name().to_owned());
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 mod find_identifier;
 mod find_usage;
+mod global_names;
 
 pub use find_identifier::*;
 pub(crate) use find_usage::*;
+pub(crate) use global_names::*;
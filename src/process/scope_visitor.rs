@@ -449,8 +449,11 @@ impl<T: NodeProcessor + NodePostProcessor + Scope> NodePostVisitor<T> for ScopeP
     }
 }
 
+/// A [`Scope`] implementation that keeps track of the local identifiers declared in the
+/// visited scopes. It can be used by processors that need to know if an identifier refers to
+/// a global variable.
 #[derive(Debug, Clone, Default)]
-pub(crate) struct IdentifierTracker {
+pub struct IdentifierTracker {
     identifiers: Vec<HashSet<String>>,
 }
 
@@ -465,16 +468,20 @@ impl IdentifierTracker {
         }
     }
 
+    /// Creates a new tracker without any identifiers.
     pub fn new() -> IdentifierTracker {
         Self {
             identifiers: Vec::new(),
         }
     }
 
+    /// Returns `true` if a local variable with the given name is declared in the current
+    /// scope or in one of its parent scopes.
     pub fn is_identifier_used(&self, identifier: &str) -> bool {
         self.identifiers.iter().any(|set| set.contains(identifier))
     }
 
+    /// Generates an identifier that is not used in the current scope and inserts it.
     pub fn generate_identifier(&mut self) -> String {
         let mut permutator = identifier_permutator();
 
@@ -487,6 +494,8 @@ impl IdentifierTracker {
         identifier
     }
 
+    /// Generates an identifier that starts with the given prefix and that is not used in the
+    /// current scope, then inserts it.
     pub fn generate_identifier_with_prefix(&mut self, prefix: impl Into<String>) -> String {
         let mut identifier = prefix.into();
         if identifier.is_empty() {
@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use crate::nodes::{Block, Expression, Identifier, LocalFunctionStatement};
+use crate::process::{NodeProcessor, NodeVisitor, Scope, ScopeVisitor};
+
+use super::is_valid_identifier;
+
+/// The prefix of the identifiers generated by rules when no other prefix is configured.
+pub(crate) const DEFAULT_GENERATED_IDENTIFIER_PREFIX: &str = "__DARKLUA_";
+
+/// Returns true if identifiers can start with the given prefix. An empty prefix is valid.
+pub(crate) fn is_valid_identifier_prefix(prefix: &str) -> bool {
+    prefix.is_empty() || is_valid_identifier(&format!("{}_", prefix))
+}
+
+/// Collects every identifier declared or referenced in a block, in any scope.
+#[derive(Default)]
+struct CollectIdentifierNames {
+    names: HashSet<String>,
+}
+
+impl NodeProcessor for CollectIdentifierNames {
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        self.names.insert(identifier.get_name().to_owned());
+    }
+}
+
+impl Scope for CollectIdentifierNames {
+    fn push(&mut self) {}
+
+    fn pop(&mut self) {}
+
+    fn insert(&mut self, identifier: &mut String) {
+        self.names.insert(identifier.to_owned());
+    }
+
+    fn insert_self(&mut self) {
+        self.names.insert("self".to_owned());
+    }
+
+    fn insert_local(&mut self, identifier: &mut String, _value: Option<&mut Expression>) {
+        self.names.insert(identifier.to_owned());
+    }
+
+    fn insert_local_function(&mut self, function: &mut LocalFunctionStatement) {
+        self.names
+            .insert(function.get_identifier().get_name().to_owned());
+    }
+}
+
+/// Generates the names of the variables that rules add to a block. Each name starts with
+/// the given prefix and never matches an identifier found in the block or a name
+/// previously generated.
+#[derive(Debug, Clone)]
+pub(crate) struct IdentifierGenerator {
+    prefix: String,
+    used_names: HashSet<String>,
+}
+
+impl IdentifierGenerator {
+    pub(crate) fn new(prefix: impl Into<String>, block: &mut Block) -> Self {
+        let mut prefix = prefix.into();
+        if !is_valid_identifier_prefix(&prefix) {
+            log::warn!(
+                "invalid generated identifier prefix `{}`, using `{}` instead",
+                prefix,
+                DEFAULT_GENERATED_IDENTIFIER_PREFIX
+            );
+            prefix = DEFAULT_GENERATED_IDENTIFIER_PREFIX.to_owned();
+        }
+
+        let mut collector = CollectIdentifierNames::default();
+        ScopeVisitor::visit_block(block, &mut collector);
+
+        Self {
+            prefix,
+            used_names: collector.names,
+        }
+    }
+
+    /// Returns the prefixed name, followed by a numeric suffix when that name is
+    /// already used.
+    pub(crate) fn generate(&mut self, name: &str) -> String {
+        let base_name = format!("{}{}", self.prefix, name);
+
+        let mut identifier = base_name.clone();
+        let mut suffix = 0;
+
+        while self.used_names.contains(&identifier) || !is_valid_identifier(&identifier) {
+            suffix += 1;
+            identifier = format!("{}_{}", base_name, suffix);
+        }
+
+        self.used_names.insert(identifier.clone());
+        identifier
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Parser;
+
+    fn new_generator(prefix: &str, code: &str) -> IdentifierGenerator {
+        let mut block = Parser::default().parse(code).expect("unable to parse code");
+        IdentifierGenerator::new(prefix, &mut block)
+    }
+
+    #[test]
+    fn generate_with_default_prefix() {
+        let mut generator = new_generator(DEFAULT_GENERATED_IDENTIFIER_PREFIX, "");
+
+        pretty_assertions::assert_eq!(generator.generate("VAR"), "__DARKLUA_VAR");
+    }
+
+    #[test]
+    fn generate_with_custom_prefix() {
+        let mut generator = new_generator("_D", "");
+
+        pretty_assertions::assert_eq!(generator.generate("VAR"), "_DVAR");
+    }
+
+    #[test]
+    fn generate_same_name_twice_adds_suffix() {
+        let mut generator = new_generator("_", "");
+
+        pretty_assertions::assert_eq!(generator.generate("a"), "_a");
+        pretty_assertions::assert_eq!(generator.generate("a"), "_a_1");
+    }
+
+    #[test]
+    fn generate_avoids_referenced_global() {
+        let mut generator = new_generator("_", "print(_a)");
+
+        pretty_assertions::assert_eq!(generator.generate("a"), "_a_1");
+    }
+
+    #[test]
+    fn generate_avoids_nested_local() {
+        let mut generator = new_generator("_", "local function f(_a) local _a_1 = _a end");
+
+        pretty_assertions::assert_eq!(generator.generate("a"), "_a_2");
+    }
+
+    #[test]
+    fn generate_with_invalid_prefix_uses_default_prefix() {
+        let mut generator = new_generator("1a", "");
+
+        pretty_assertions::assert_eq!(generator.generate("VAR"), "__DARKLUA_VAR");
+    }
+
+    #[test]
+    fn is_valid_identifier_prefix_is_true() {
+        assert!(is_valid_identifier_prefix(""));
+        assert!(is_valid_identifier_prefix("_"));
+        assert!(is_valid_identifier_prefix("end"));
+        assert!(is_valid_identifier_prefix("__DARKLUA_"));
+    }
+
+    #[test]
+    fn is_valid_identifier_prefix_is_false() {
+        assert!(!is_valid_identifier_prefix("1a"));
+        assert!(!is_valid_identifier_prefix("a-b"));
+        assert!(!is_valid_identifier_prefix(" "));
+    }
+
+    #[test]
+    fn generate_with_empty_prefix_avoids_keyword() {
+        let mut generator = new_generator("", "");
+
+        pretty_assertions::assert_eq!(generator.generate("end"), "end_1");
+    }
+}
@@ -1,5 +1,7 @@
+mod identifier_generator;
 mod permutator;
 
+pub(crate) use identifier_generator::*;
 pub(crate) use permutator::Permutator;
 
 pub(crate) type CharPermutator = Permutator<std::str::Chars<'static>>;
@@ -14,6 +16,9 @@ pub(crate) fn generate_identifier(permutator: &mut CharPermutator) -> String {
         .expect("the permutator should always ultimately return a valid identifier")
 }
 
+/// The largest integer that a Lua number represents exactly (2^53).
+pub(crate) const MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
 pub(crate) const KEYWORDS: [&str; 21] = [
     "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in", "local",
     "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
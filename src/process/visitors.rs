@@ -38,6 +38,7 @@ pub trait NodeVisitor<T: NodeProcessor> {
             Statement::TypeDeclaration(statement) => {
                 Self::visit_type_declaration(statement, processor)
             }
+            Statement::TypeFunction(statement) => Self::visit_type_function(statement, processor),
         };
     }
 
@@ -331,6 +332,27 @@ pub trait NodeVisitor<T: NodeProcessor> {
         Self::visit_block(statement.mutate_block(), processor);
     }
 
+    /// Visits the types of a type function. Its block is not visited, because it does not
+    /// run with the rest of the code.
+    fn visit_type_function(statement: &mut TypeFunctionStatement, processor: &mut T) {
+        processor.process_type_function(statement);
+
+        for r#type in statement
+            .iter_mut_parameters()
+            .filter_map(TypedIdentifier::mutate_type)
+        {
+            Self::visit_type(r#type, processor);
+        }
+
+        if let Some(variadic_type) = statement.mutate_variadic_type() {
+            Self::visit_function_variadic_type(variadic_type, processor);
+        }
+
+        if let Some(return_type) = statement.mutate_return_type() {
+            Self::visit_function_return_type(return_type, processor);
+        }
+    }
+
     fn visit_type_declaration(statement: &mut TypeDeclarationStatement, processor: &mut T) {
         processor.process_type_declaration(statement);
 
@@ -782,4 +804,19 @@ mod test {
         assert_eq!(counter.interpolated_string_count, 1);
         assert_eq!(counter.expression_count, 2);
     }
+
+    #[test]
+    fn visit_statements_and_last_statement() {
+        let mut counter = NodeCounter::new();
+        let mut block = Block::default()
+            .with_statement(DoStatement::new(
+                Block::default().with_statement(LocalAssignStatement::from_variable("value")),
+            ))
+            .with_last_statement(LastStatement::new_break());
+
+        DefaultVisitor::visit_block(&mut block, &mut counter);
+
+        assert_eq!(counter.statement_count, 3);
+        assert_eq!(counter.break_count, 1);
+    }
 }
@@ -6,11 +6,12 @@ use crate::nodes::{
     Block, BlockTokens, DoTokens, FunctionBodyTokens, GenericForTokens, Identifier,
     IfStatementTokens, LastStatement, LocalAssignTokens, LocalFunctionTokens, NumericForTokens,
     ParentheseExpression, ParentheseTokens, Prefix, RepeatTokens, ReturnTokens, Statement, Token,
-    TriviaKind, TypeDeclarationTokens, Variable, WhileTokens,
+    TriviaKind, TypeDeclarationTokens, TypeFunctionTokens, Variable, WhileTokens,
 };
 use crate::rules::{
     verify_property_collisions, verify_required_any_properties, Context, Rule, RuleConfiguration,
-    RuleConfigurationError, RuleProcessResult, RuleProperties,
+    RuleConfigurationError, RuleProcessResult, RuleProperties, RulePropertyDescription,
+    RulePropertyType,
 };
 
 use super::{FlawlessRule, ShiftTokenLine};
@@ -299,6 +300,49 @@ impl Rule for AppendTextComment {
                                 });
                             }
                         }
+                        Statement::TypeFunction(type_function) => {
+                            let is_exported = type_function.is_exported();
+                            if let Some(tokens) = type_function.mutate_tokens() {
+                                if is_exported {
+                                    self.location.append_comment(
+                                        tokens
+                                            .export
+                                            .get_or_insert_with(|| Token::from_content("export")),
+                                        text,
+                                    );
+                                } else {
+                                    self.location.append_comment(&mut tokens.r#type, text);
+                                }
+                            } else {
+                                let mut token = Token::from_content(if is_exported {
+                                    "export"
+                                } else {
+                                    "type"
+                                });
+                                self.location.append_comment(&mut token, text);
+
+                                let (r#type, export) = if is_exported {
+                                    (Token::from_content("type"), Some(token))
+                                } else {
+                                    (token, None)
+                                };
+
+                                type_function.set_tokens(TypeFunctionTokens {
+                                    r#type,
+                                    function_body: FunctionBodyTokens {
+                                        function: Token::from_content("function"),
+                                        opening_parenthese: Token::from_content("("),
+                                        closing_parenthese: Token::from_content(")"),
+                                        end: Token::from_content("end"),
+                                        parameter_commas: Vec::new(),
+                                        variable_arguments: None,
+                                        variable_arguments_colon: None,
+                                        return_type_colon: None,
+                                    },
+                                    export,
+                                });
+                            }
+                        }
                     }
                 } else if let Some(statement) = block.mutate_last_statement() {
                     match statement {
@@ -457,6 +501,15 @@ impl RuleConfiguration for AppendTextComment {
 
         properties
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("text", RulePropertyType::String),
+            RulePropertyDescription::new("file", RulePropertyType::String),
+            RulePropertyDescription::new("location", RulePropertyType::Choice(&["start", "end"]))
+                .with_default("start"),
+        ]
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
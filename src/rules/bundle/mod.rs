@@ -1,3 +1,4 @@
+mod parsed_modules;
 pub(crate) mod path_require_mode;
 mod rename_type_declaration;
 mod require_mode;
@@ -10,6 +11,7 @@ use crate::rules::{
 };
 use crate::Parser;
 
+use parsed_modules::ParsedModules;
 pub(crate) use rename_type_declaration::RenameTypeDeclarationProcessor;
 pub use require_mode::BundleRequireMode;
 use wax::Pattern;
@@ -21,6 +23,7 @@ pub(crate) struct BundleOptions {
     parser: Parser,
     modules_identifier: String,
     excludes: Option<wax::Any<'static>>,
+    parsed_modules: ParsedModules,
 }
 
 impl BundleOptions {
@@ -52,6 +55,7 @@ impl BundleOptions {
                     .expect("exclude globs errors should be filtered and only emit a warning");
                 Some(any_pattern)
             },
+            parsed_modules: ParsedModules::default(),
         }
     }
 
@@ -59,6 +63,10 @@ impl BundleOptions {
         &self.parser
     }
 
+    fn parsed_modules(&self) -> &ParsedModules {
+        &self.parsed_modules
+    }
+
     fn modules_identifier(&self) -> &str {
         &self.modules_identifier
     }
@@ -175,4 +183,92 @@ mod test {
 
         assert_json_snapshot!("default_bundler", rule);
     }
+
+    fn bundle_entry(
+        bundler: &Bundler,
+        resources: &crate::Resources,
+        path: &str,
+    ) -> RuleProcessResult {
+        let code = resources.get(path).unwrap();
+        let mut block = Parser::default().parse(&code).unwrap();
+        let context = crate::rules::ContextBuilder::new(path, resources, &code).build();
+
+        bundler.process(&mut block, &context)
+    }
+
+    fn diamond_resources() -> crate::Resources {
+        let resources = crate::Resources::from_memory();
+        resources
+            .write("src/shared.lua", "return { value = true }")
+            .unwrap();
+        resources
+            .write("src/a.lua", "return require('./shared')")
+            .unwrap();
+        resources
+            .write("src/b.lua", "return require('./shared')")
+            .unwrap();
+        resources
+            .write(
+                "src/main.lua",
+                "local a = require('./a') local b = require('./b')",
+            )
+            .unwrap();
+        resources
+            .write("src/other.lua", "local shared = require('./shared')")
+            .unwrap();
+        resources
+    }
+
+    #[test]
+    fn diamond_dependency_parses_shared_module_once() {
+        let resources = diamond_resources();
+        let bundler = new_rule();
+
+        bundle_entry(&bundler, &resources, "src/main.lua").unwrap();
+        bundle_entry(&bundler, &resources, "src/other.lua").unwrap();
+
+        // `a.lua`, `b.lua` and `shared.lua`
+        pretty_assertions::assert_eq!(bundler.options.parsed_modules().parse_count(), 3);
+    }
+
+    #[test]
+    fn changed_module_is_parsed_again() {
+        let resources = diamond_resources();
+        let bundler = new_rule();
+
+        bundle_entry(&bundler, &resources, "src/main.lua").unwrap();
+        resources
+            .write("src/shared.lua", "return { value = false }")
+            .unwrap();
+        bundle_entry(&bundler, &resources, "src/other.lua").unwrap();
+
+        pretty_assertions::assert_eq!(bundler.options.parsed_modules().parse_count(), 4);
+    }
+
+    #[test]
+    fn cycle_with_parsed_modules_errors() {
+        let resources = crate::Resources::from_memory();
+        resources
+            .write("src/value1.lua", "return require('./value2')")
+            .unwrap();
+        resources
+            .write("src/value2.lua", "return require('./value1')")
+            .unwrap();
+        resources
+            .write("src/main.lua", "local value = require('./value1')")
+            .unwrap();
+        resources
+            .write("src/other.lua", "local value = require('./value2')")
+            .unwrap();
+        let bundler = new_rule();
+
+        pretty_assertions::assert_eq!(
+            bundle_entry(&bundler, &resources, "src/main.lua").unwrap_err(),
+            "cyclic require detected with `src/value1.lua` > `src/value2.lua` > `src/value1.lua`"
+        );
+        pretty_assertions::assert_eq!(
+            bundle_entry(&bundler, &resources, "src/other.lua").unwrap_err(),
+            "cyclic require detected with `src/value2.lua` > `src/value1.lua` > `src/value2.lua`"
+        );
+    }
 }
@@ -0,0 +1,119 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::frontend::DarkluaResult;
+use crate::nodes::Block;
+
+#[derive(Debug)]
+struct ParsedModule {
+    content: String,
+    block: Block,
+}
+
+const DEFAULT_CAPACITY: usize = 256;
+
+/// Keeps the blocks parsed while bundling, so that a module required from multiple
+/// entry points is only parsed once. A parsed block is re-used only when the content
+/// of the module is identical to the content that was parsed.
+///
+/// The bundler is created for each `process` call, so the parsed modules are not
+/// kept between two calls (like in watch mode). To bound the memory used by large
+/// projects, the cache is cleared when it reaches its capacity.
+#[derive(Debug)]
+pub(crate) struct ParsedModules {
+    modules: RefCell<HashMap<PathBuf, ParsedModule>>,
+    capacity: usize,
+    parse_count: Cell<usize>,
+}
+
+impl Default for ParsedModules {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl ParsedModules {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            modules: RefCell::new(HashMap::new()),
+            capacity,
+            parse_count: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn get_or_parse(
+        &self,
+        path: &Path,
+        content: &str,
+        parse: impl FnOnce() -> DarkluaResult<Block>,
+    ) -> DarkluaResult<Block> {
+        if let Some(module) = self.modules.borrow().get(path) {
+            if module.content == content {
+                log::trace!("re-use parsed module `{}`", path.display());
+                return Ok(module.block.clone());
+            }
+        }
+
+        let block = parse()?;
+        self.parse_count.set(self.parse_count.get() + 1);
+
+        let mut modules = self.modules.borrow_mut();
+        if modules.len() >= self.capacity && !modules.contains_key(path) {
+            log::trace!("clear {} parsed modules", modules.len());
+            modules.clear();
+        }
+        modules.insert(
+            path.to_path_buf(),
+            ParsedModule {
+                content: content.to_owned(),
+                block: block.clone(),
+            },
+        );
+
+        Ok(block)
+    }
+
+    /// The number of modules that had to be parsed.
+    #[cfg(test)]
+    pub(crate) fn parse_count(&self) -> usize {
+        self.parse_count.get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_module(modules: &ParsedModules, path: &str) {
+        modules
+            .get_or_parse(Path::new(path), "return nil", || Ok(Block::default()))
+            .unwrap();
+    }
+
+    #[test]
+    fn re_uses_parsed_module() {
+        let modules = ParsedModules::default();
+
+        parse_module(&modules, "a.lua");
+        parse_module(&modules, "a.lua");
+
+        pretty_assertions::assert_eq!(modules.parse_count(), 1);
+    }
+
+    #[test]
+    fn cache_is_cleared_when_capacity_is_reached() {
+        let modules = ParsedModules::with_capacity(2);
+
+        parse_module(&modules, "a.lua");
+        parse_module(&modules, "b.lua");
+        parse_module(&modules, "c.lua");
+
+        pretty_assertions::assert_eq!(modules.modules.borrow().len(), 1);
+
+        parse_module(&modules, "c.lua");
+        parse_module(&modules, "a.lua");
+
+        pretty_assertions::assert_eq!(modules.parse_count(), 4);
+    }
+}
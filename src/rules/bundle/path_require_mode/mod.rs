@@ -155,18 +155,15 @@ impl<'a, 'b, 'code, 'resources> RequirePathProcessor<'a, 'b, 'code, 'resources>
                 .find(|(_, path)| **path == require_path)
                 .map(|(i, _)| i)
             {
-                let require_stack_paths: Vec<_> = self
+                let cycle: Vec<_> = self
                     .require_stack
                     .iter()
                     .skip(i)
-                    .map(|path| path.display().to_string())
-                    .chain(iter::once(require_path.display().to_string()))
+                    .cloned()
+                    .chain(iter::once(require_path.to_path_buf()))
                     .collect();
 
-                return Err(DarkluaError::custom(format!(
-                    "cyclic require detected with `{}`",
-                    require_stack_paths.join("` > `")
-                )));
+                return Err(DarkluaError::cyclic_require(cycle));
             }
 
             self.require_stack.push(require_path.to_path_buf());
@@ -186,6 +183,40 @@ impl<'a, 'b, 'code, 'resources> RequirePathProcessor<'a, 'b, 'code, 'resources>
         }
     }
 
+    fn parse_module(&self, path: &Path, content: &str) -> DarkluaResult<Block> {
+        let parser_timer = Timer::now();
+        let mut block = self
+            .options
+            .parser()
+            .parse(content)
+            .map_err(|parser_error| DarkluaError::parser_error(path.to_path_buf(), parser_error))?;
+        log::debug!(
+            "parsed `{}` in {}",
+            path.display(),
+            parser_timer.duration_label()
+        );
+
+        if self.options.parser().is_preserving_tokens() {
+            log::trace!("replacing token references of {}", path.display());
+            let context = ContextBuilder::new(path, self.resources, content).build();
+            // run `replace_referenced_tokens` rule to avoid generating invalid code
+            // when using the token-based generator
+            let replace_tokens = ReplaceReferencedTokens::default();
+
+            let apply_replace_tokens_timer = Timer::now();
+
+            replace_tokens.flawless_process(&mut block, &context);
+
+            log::trace!(
+                "replaced token references for `{}` in {}",
+                path.display(),
+                apply_replace_tokens_timer.duration_label()
+            );
+        }
+
+        Ok(block)
+    }
+
     fn require_resource(&mut self, path: impl AsRef<Path>) -> DarkluaResult<RequiredResource> {
         let path = path.as_ref();
         log::trace!("look for resource `{}`", path.display());
@@ -194,37 +225,10 @@ impl<'a, 'b, 'code, 'resources> RequirePathProcessor<'a, 'b, 'code, 'resources>
         match path.extension() {
             Some(extension) => match extension.to_string_lossy().as_ref() {
                 "lua" | "luau" => {
-                    let parser_timer = Timer::now();
                     let mut block =
                         self.options
-                            .parser()
-                            .parse(&content)
-                            .map_err(|parser_error| {
-                                DarkluaError::parser_error(path.to_path_buf(), parser_error)
-                            })?;
-                    log::debug!(
-                        "parsed `{}` in {}",
-                        path.display(),
-                        parser_timer.duration_label()
-                    );
-
-                    if self.options.parser().is_preserving_tokens() {
-                        log::trace!("replacing token references of {}", path.display());
-                        let context = ContextBuilder::new(path, self.resources, &content).build();
-                        // run `replace_referenced_tokens` rule to avoid generating invalid code
-                        // when using the token-based generator
-                        let replace_tokens = ReplaceReferencedTokens::default();
-
-                        let apply_replace_tokens_timer = Timer::now();
-
-                        replace_tokens.flawless_process(&mut block, &context);
-
-                        log::trace!(
-                            "replaced token references for `{}` in {}",
-                            path.display(),
-                            apply_replace_tokens_timer.duration_label()
-                        );
-                    }
+                            .parsed_modules()
+                            .get_or_parse(path, &content, || self.parse_module(path, &content))?;
 
                     let current_source = mem::replace(&mut self.source, path.to_path_buf());
 
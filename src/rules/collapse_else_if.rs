@@ -0,0 +1,137 @@
+use crate::nodes::{Block, IfBranch, IfBranchTokens, IfStatement, Statement};
+use crate::process::{DefaultPostVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+/// Returns the if statement from the else block when it is the only statement of
+/// the block.
+fn take_nested_if_statement(if_statement: &mut IfStatement) -> Option<IfStatement> {
+    let else_block = if_statement.mutate_else_block().as_mut()?;
+
+    if else_block.statements_len() != 1 || else_block.get_last_statement().is_some() {
+        return None;
+    }
+
+    match else_block.first_statement() {
+        Some(Statement::If(_)) => {}
+        _ => return None,
+    }
+
+    match else_block.take_statements().pop() {
+        Some(Statement::If(nested_if)) => Some(nested_if),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Processor;
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_if_statement(&mut self, if_statement: &mut IfStatement) {
+        let mut nested_if = match take_nested_if_statement(if_statement) {
+            Some(nested_if) => nested_if,
+            None => return,
+        };
+
+        let nested_tokens = nested_if.get_tokens().cloned();
+        let nested_else_block = nested_if.take_else_block();
+
+        let mut branches = nested_if.mutate_branches().drain(..);
+
+        if let Some(mut first_branch) = branches.next() {
+            // the first branch of the nested statement starts with the `if` keyword,
+            // which becomes the `elseif` keyword of the new branch
+            let branch = IfBranch::new(
+                first_branch.get_condition().clone(),
+                first_branch.take_block(),
+            );
+
+            if_statement.push_branch(match &nested_tokens {
+                Some(tokens) => {
+                    let mut elseif = tokens.r#if.clone();
+                    elseif.replace_with_content("elseif");
+                    branch.with_tokens(IfBranchTokens {
+                        elseif,
+                        then: tokens.then.clone(),
+                    })
+                }
+                None => branch,
+            });
+        }
+
+        for branch in branches {
+            if_statement.push_branch(branch);
+        }
+
+        *if_statement.mutate_else_block() = nested_else_block;
+
+        if let Some(tokens) = if_statement.mutate_tokens() {
+            tokens.r#else = nested_tokens.and_then(|tokens| tokens.r#else);
+        }
+    }
+}
+
+pub const COLLAPSE_ELSE_IF_RULE_NAME: &str = "collapse_else_if";
+
+/// A rule that merges an `if` statement nested alone in an `else` block into the
+/// parent `if` statement using `elseif` branches.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CollapseElseIf {}
+
+impl FlawlessRule for CollapseElseIf {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor;
+        DefaultPostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for CollapseElseIf {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        COLLAPSE_ELSE_IF_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> CollapseElseIf {
+        CollapseElseIf::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_collapse_else_if", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'collapse_else_if',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
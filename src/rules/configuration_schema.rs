@@ -0,0 +1,316 @@
+use serde_json::{json, Map, Value};
+
+use crate::rules::{get_all_rule_names, Rule};
+
+/// The type of value accepted by a rule property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulePropertyType {
+    Boolean,
+    String,
+    /// A string that must be one of the given values.
+    Choice(&'static [&'static str]),
+    Usize,
+    StringList,
+    /// A require mode, written as a string (like `"path"`) or as an object with a `name` field.
+    RequireMode,
+    /// A string, a boolean, a number or `null`, converted to the equivalent Lua value.
+    LuaValue,
+}
+
+impl RulePropertyType {
+    fn to_schema(self) -> Value {
+        match self {
+            Self::Boolean => json!({ "type": "boolean" }),
+            Self::String => json!({ "type": "string" }),
+            Self::Choice(values) => json!({ "type": "string", "enum": values }),
+            Self::Usize => json!({ "type": "integer", "minimum": 0 }),
+            Self::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+            Self::RequireMode => json!({
+                "anyOf": [
+                    { "type": "string", "enum": ["path", "roblox"] },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string", "enum": ["path", "roblox"] },
+                        },
+                        "required": ["name"],
+                    },
+                ],
+            }),
+            Self::LuaValue => json!({ "type": ["string", "boolean", "number", "null"] }),
+        }
+    }
+}
+
+/// Describes a property that can be given to a rule, returned by
+/// [`RuleConfiguration::describe_properties`](crate::rules::RuleConfiguration::describe_properties).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RulePropertyDescription {
+    name: &'static str,
+    property_type: RulePropertyType,
+    default: Option<Value>,
+    required: bool,
+}
+
+impl RulePropertyDescription {
+    pub fn new(name: &'static str, property_type: RulePropertyType) -> Self {
+        Self {
+            name,
+            property_type,
+            default: None,
+            required: false,
+        }
+    }
+
+    pub fn with_default(mut self, default: impl Into<Value>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline]
+    pub fn property_type(&self) -> RulePropertyType {
+        self.property_type
+    }
+
+    #[inline]
+    pub fn default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    #[inline]
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+fn rule_schema(rule: &dyn Rule) -> Value {
+    let mut properties = Map::new();
+    properties.insert("rule".to_owned(), json!({ "const": rule.get_name() }));
+
+    let mut required = vec!["rule"];
+
+    for property in rule.describe_properties() {
+        let mut schema = property.property_type.to_schema();
+
+        if let (Some(default), Value::Object(schema)) = (property.default, &mut schema) {
+            schema.insert("default".to_owned(), default);
+        }
+        if property.required {
+            required.push(property.name);
+        }
+
+        properties.insert(property.name.to_owned(), schema);
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Returns a [JSON schema](https://json-schema.org/) describing a rule in the `rules` list
+/// of a configuration file. A rule can be written as its name, unless it has required
+/// properties, or as an object with a `rule` field and the rule properties. The
+/// definition of each rule is available under `definitions`, with the name of the rule.
+pub fn configuration_schema() -> Value {
+    let mut definitions = Map::new();
+    let mut rules_without_required_properties = Vec::new();
+    let mut rule_objects = Vec::new();
+
+    for name in get_all_rule_names() {
+        let rule = name
+            .parse::<Box<dyn Rule>>()
+            .expect("rule names should be valid");
+
+        if !rule
+            .describe_properties()
+            .iter()
+            .any(RulePropertyDescription::is_required)
+        {
+            rules_without_required_properties.push(name);
+        }
+
+        definitions.insert(name.to_owned(), rule_schema(rule.as_ref()));
+        rule_objects.push(json!({ "$ref": format!("#/definitions/{}", name) }));
+    }
+
+    let mut any_of = vec![json!({ "type": "string", "enum": rules_without_required_properties })];
+    any_of.extend(rule_objects);
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "darklua rule",
+        "definitions": definitions,
+        "anyOf": any_of,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::get_default_rules;
+
+    /// Validates a value against the subset of JSON schema features used by the
+    /// configuration schema.
+    fn is_valid(schema: &Value, value: &Value, root: &Value) -> bool {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.trim_start_matches("#/definitions/");
+            return is_valid(&root["definitions"][name], value, root);
+        }
+
+        if let Some(schemas) = schema.get("anyOf").and_then(Value::as_array) {
+            return schemas.iter().any(|schema| is_valid(schema, value, root));
+        }
+
+        if let Some(expected) = schema.get("const") {
+            return expected == value;
+        }
+
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            if !values.contains(value) {
+                return false;
+            }
+        }
+
+        let matches_type = |type_name: &str| match type_name {
+            "boolean" => value.is_boolean(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_u64() || value.is_i64(),
+            "null" => value.is_null(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => false,
+        };
+
+        match schema.get("type") {
+            Some(Value::String(type_name)) if !matches_type(type_name.as_str()) => return false,
+            Some(Value::Array(types))
+                if !types.iter().filter_map(Value::as_str).any(matches_type) =>
+            {
+                return false
+            }
+            _ => {}
+        }
+
+        if let (Some(items), Some(values)) = (schema.get("items"), value.as_array()) {
+            if !values.iter().all(|value| is_valid(items, value, root)) {
+                return false;
+            }
+        }
+
+        if let Some(object) = value.as_object() {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                if !required
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .all(|key| object.contains_key(key))
+                {
+                    return false;
+                }
+            }
+
+            for (key, property_value) in object {
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(property_schema) => {
+                        if !is_valid(property_schema, property_value, root) {
+                            return false;
+                        }
+                    }
+                    None => {
+                        if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+                            return false;
+                        }
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn validate(value: &Value) -> bool {
+        let schema = configuration_schema();
+        is_valid(&schema, value, &schema)
+    }
+
+    #[test]
+    fn schema_has_a_definition_for_each_rule() {
+        let schema = configuration_schema();
+
+        for name in get_all_rule_names() {
+            assert!(
+                schema["definitions"].get(name).is_some(),
+                "missing definition for rule `{}`",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn default_rules_are_valid() {
+        for rule in get_default_rules() {
+            let value = serde_json::to_value(&rule).unwrap();
+
+            assert!(validate(&value), "invalid rule: {}", value);
+        }
+    }
+
+    #[test]
+    fn configured_rule_is_valid() {
+        assert!(validate(&json!({
+            "rule": "remove_comments",
+            "except": ["^--!"],
+        })));
+    }
+
+    #[test]
+    fn rule_with_required_property_is_valid() {
+        assert!(validate(&json!({
+            "rule": "inject_global_value",
+            "identifier": "DEV",
+            "value": false,
+        })));
+    }
+
+    #[test]
+    fn rule_with_unknown_property_is_invalid() {
+        assert!(!validate(&json!({
+            "rule": "remove_comments",
+            "prop": "something",
+        })));
+    }
+
+    #[test]
+    fn rule_with_invalid_property_type_is_invalid() {
+        assert!(!validate(&json!({
+            "rule": "convert_concat_to_table_concat",
+            "minimum_operands": "ten",
+        })));
+    }
+
+    #[test]
+    fn rule_with_missing_required_property_is_invalid() {
+        assert!(!validate(&json!("inject_global_value")));
+        assert!(!validate(&json!({ "rule": "convert_require" })));
+    }
+
+    #[test]
+    fn unknown_rule_name_is_invalid() {
+        assert!(!validate(&json!("unknown_rule")));
+    }
+}
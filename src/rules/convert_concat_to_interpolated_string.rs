@@ -0,0 +1,171 @@
+use crate::generator::LuaSyntax;
+use crate::nodes::{
+    BinaryOperator, Block, Expression, InterpolatedStringExpression, StringSegment, ValueSegment,
+};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+use super::convert_concat_to_table_concat::collect_operands;
+
+fn is_string(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::String(_) | Expression::InterpolatedString(_)
+    )
+}
+
+/// Builds an interpolated string from a chain of `..` operations that contains at
+/// least one string and one other value.
+fn convert(expression: &Expression) -> Option<InterpolatedStringExpression> {
+    let mut operands = Vec::new();
+    collect_operands(expression, &mut operands);
+
+    if !operands.iter().any(|operand| is_string(operand))
+        || operands.iter().all(|operand| is_string(operand))
+    {
+        return None;
+    }
+
+    let mut interpolated_string = InterpolatedStringExpression::empty();
+
+    for operand in operands {
+        match operand {
+            Expression::String(string) => {
+                interpolated_string.push_segment(StringSegment::from_value(string.get_value()));
+            }
+            Expression::InterpolatedString(inner) => {
+                for segment in inner.iter_segments() {
+                    interpolated_string.push_segment(segment.clone());
+                }
+            }
+            Expression::Parenthese(parenthese) => {
+                interpolated_string
+                    .push_segment(ValueSegment::new(parenthese.inner_expression().clone()));
+            }
+            _ => {
+                interpolated_string.push_segment(ValueSegment::new(operand.clone()));
+            }
+        }
+    }
+
+    Some(interpolated_string)
+}
+
+#[derive(Default)]
+struct Processor;
+
+impl NodeProcessor for Processor {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if matches!(expression, Expression::Binary(binary) if binary.operator() == BinaryOperator::Concat)
+        {
+            if let Some(interpolated_string) = convert(expression) {
+                *expression = interpolated_string.into();
+            }
+        }
+    }
+}
+
+pub const CONVERT_CONCAT_TO_INTERPOLATED_STRING_RULE_NAME: &str =
+    "convert_concat_to_interpolated_string";
+
+/// A rule that converts chains of `..` operations that contain strings into
+/// interpolated strings. Since interpolated strings are only available in Luau,
+/// the rule has no effect unless `target_luau` is enabled.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConvertConcatToInterpolatedString {
+    target_luau: bool,
+}
+
+impl FlawlessRule for ConvertConcatToInterpolatedString {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        if !self.target_luau || !context.supports_syntax(LuaSyntax::InterpolatedString) {
+            return;
+        }
+
+        let mut processor = Processor;
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ConvertConcatToInterpolatedString {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "target_luau" => {
+                    self.target_luau = value.expect_bool(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONVERT_CONCAT_TO_INTERPOLATED_STRING_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.target_luau {
+            properties.insert(
+                "target_luau".to_owned(),
+                RulePropertyValue::Boolean(self.target_luau),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("target_luau", RulePropertyType::Boolean)
+                .with_default(false),
+        ]
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConvertConcatToInterpolatedString {
+        ConvertConcatToInterpolatedString::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_concat_to_interpolated_string", rule);
+    }
+
+    #[test]
+    fn serialize_rule_targeting_luau() {
+        let rule: Box<dyn Rule> = Box::new(ConvertConcatToInterpolatedString { target_luau: true });
+
+        assert_json_snapshot!("convert_concat_to_interpolated_string_targeting_luau", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'convert_concat_to_interpolated_string',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,250 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    BinaryOperator, Block, Expression, FieldExpression, FunctionCall, Prefix, TableExpression,
+    UnaryOperator,
+};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodeProcessor, NodeVisitor, ScopeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+const TABLE_LIBRARY: &str = "table";
+const CONCAT_FUNCTION: &str = "concat";
+const DEFAULT_MINIMUM_OPERANDS: usize = 10;
+
+/// Pushes the operands of a chain of `..` operations, from left to right.
+pub(crate) fn collect_operands<'a>(expression: &'a Expression, operands: &mut Vec<&'a Expression>) {
+    match expression {
+        Expression::Binary(binary) if binary.operator() == BinaryOperator::Concat => {
+            collect_operands(binary.left(), operands);
+            collect_operands(binary.right(), operands);
+        }
+        _ => operands.push(expression),
+    }
+}
+
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+    minimum_operands: usize,
+}
+
+impl Processor {
+    fn new(minimum_operands: usize) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            evaluator: Evaluator::default(),
+            minimum_operands,
+        }
+    }
+
+    /// Returns true if the expression can evaluate to a number, which the `..`
+    /// operator converts to a string.
+    fn may_be_number(&self, expression: &Expression) -> bool {
+        if matches!(self.evaluator.evaluate(expression), LuaValue::Number(_)) {
+            return true;
+        }
+
+        match expression {
+            Expression::Number(_) => true,
+            Expression::Binary(binary) => match binary.operator() {
+                BinaryOperator::And | BinaryOperator::Or => {
+                    self.may_be_number(binary.left()) || self.may_be_number(binary.right())
+                }
+                BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::LowerThan
+                | BinaryOperator::LowerOrEqualThan
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterOrEqualThan
+                | BinaryOperator::Concat => false,
+                _ => true,
+            },
+            Expression::Unary(unary) => !matches!(unary.operator(), UnaryOperator::Not),
+            Expression::Parenthese(parenthese) => self.may_be_number(parenthese.inner_expression()),
+            Expression::TypeCast(type_cast) => self.may_be_number(type_cast.get_expression()),
+            Expression::If(if_expression) => {
+                self.may_be_number(if_expression.get_result())
+                    || if_expression
+                        .iter_branches()
+                        .any(|branch| self.may_be_number(branch.get_result()))
+                    || self.may_be_number(if_expression.get_else_result())
+            }
+            _ => false,
+        }
+    }
+
+    fn convert(&self, expression: &Expression) -> Option<Expression> {
+        if self.is_identifier_used(TABLE_LIBRARY) {
+            return None;
+        }
+
+        let mut operands = Vec::new();
+        collect_operands(expression, &mut operands);
+
+        if operands.len() < self.minimum_operands
+            || operands.iter().any(|operand| self.may_be_number(operand))
+        {
+            return None;
+        }
+
+        let last_index = operands.len() - 1;
+        let table = operands.into_iter().enumerate().fold(
+            TableExpression::default(),
+            |table, (index, operand)| {
+                // the last value of a table would expand to all the values returned
+                // by a call, but `..` only uses the first one
+                if index == last_index && self.evaluator.can_return_multiple_values(operand) {
+                    table.append_array_value(operand.clone().in_parentheses())
+                } else {
+                    table.append_array_value(operand.clone())
+                }
+            },
+        );
+
+        Some(
+            FunctionCall::from_prefix(FieldExpression::new(
+                Prefix::from_name(TABLE_LIBRARY),
+                CONCAT_FUNCTION,
+            ))
+            .with_argument(table)
+            .into(),
+        )
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Binary(binary) = expression {
+            if binary.operator() == BinaryOperator::Concat {
+                if let Some(call) = self.convert(expression) {
+                    *expression = call;
+                }
+            }
+        }
+    }
+}
+
+pub const CONVERT_CONCAT_TO_TABLE_CONCAT_RULE_NAME: &str = "convert_concat_to_table_concat";
+
+/// A rule that converts long chains of `..` operations into a call to
+/// `table.concat` with the operands.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConvertConcatToTableConcat {
+    minimum_operands: usize,
+}
+
+impl Default for ConvertConcatToTableConcat {
+    fn default() -> Self {
+        Self {
+            minimum_operands: DEFAULT_MINIMUM_OPERANDS,
+        }
+    }
+}
+
+impl FlawlessRule for ConvertConcatToTableConcat {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::new(self.minimum_operands);
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ConvertConcatToTableConcat {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "minimum_operands" => {
+                    self.minimum_operands = value.expect_usize(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONVERT_CONCAT_TO_TABLE_CONCAT_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.minimum_operands != DEFAULT_MINIMUM_OPERANDS {
+            properties.insert(
+                "minimum_operands".to_owned(),
+                RulePropertyValue::Usize(self.minimum_operands),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("minimum_operands", RulePropertyType::Usize)
+                .with_default(DEFAULT_MINIMUM_OPERANDS),
+        ]
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConvertConcatToTableConcat {
+        ConvertConcatToTableConcat::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_concat_to_table_concat", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_minimum_operands() {
+        let rule: Box<dyn Rule> = Box::new(ConvertConcatToTableConcat {
+            minimum_operands: 4,
+        });
+
+        assert_json_snapshot!("convert_concat_to_table_concat_with_minimum_operands", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'convert_concat_to_table_concat',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,112 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Block, Expression, FunctionCall, InterpolationSegment};
+use crate::process::{IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+const TOSTRING_IDENTIFIER: &str = "tostring";
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        let string = match expression {
+            Expression::InterpolatedString(string) if string.len() == 1 => string,
+            _ => return,
+        };
+
+        if self.is_identifier_used(TOSTRING_IDENTIFIER) {
+            return;
+        }
+
+        let value = match string.iter_segments().next() {
+            Some(InterpolationSegment::Value(segment)) => segment.get_expression().clone(),
+            _ => return,
+        };
+
+        *expression = FunctionCall::from_name(TOSTRING_IDENTIFIER)
+            .with_argument(value)
+            .into();
+    }
+}
+
+pub const CONVERT_INTERPOLATION_TO_TOSTRING_RULE_NAME: &str = "convert_interpolation_to_tostring";
+
+/// A rule that converts interpolated strings containing only a single value
+/// into a call to `tostring`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConvertInterpolationToToString {}
+
+impl FlawlessRule for ConvertInterpolationToToString {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ConvertInterpolationToToString {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONVERT_INTERPOLATION_TO_TOSTRING_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConvertInterpolationToToString {
+        ConvertInterpolationToToString::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_interpolation_to_tostring", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'convert_interpolation_to_tostring',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -9,7 +9,10 @@ use crate::frontend::DarkluaResult;
 use crate::nodes::{Arguments, Block, FunctionCall};
 use crate::process::{DefaultVisitor, IdentifierTracker, NodeProcessor, NodeVisitor};
 use crate::rules::require::{is_require_call, PathRequireMode};
-use crate::rules::{Context, RuleConfiguration, RuleConfigurationError, RuleProperties};
+use crate::rules::{
+    Context, RuleConfiguration, RuleConfigurationError, RuleProperties, RulePropertyDescription,
+    RulePropertyType,
+};
 
 use instance_path::InstancePath;
 pub use roblox_index_style::RobloxIndexStyle;
@@ -206,6 +209,13 @@ impl RuleConfiguration for ConvertRequire {
     fn serialize_to_properties(&self) -> RuleProperties {
         RuleProperties::new()
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("current", RulePropertyType::RequireMode).required(),
+            RulePropertyDescription::new("target", RulePropertyType::RequireMode).required(),
+        ]
+    }
 }
 
 #[cfg(test)]
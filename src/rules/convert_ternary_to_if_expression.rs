@@ -0,0 +1,153 @@
+use crate::generator::LuaSyntax;
+use crate::nodes::{BinaryExpression, BinaryOperator, Block, Expression, IfExpression};
+use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+/// Pushes the operands of a chain of `or` operations, from left to right.
+fn collect_operands<'a>(expression: &'a Expression, operands: &mut Vec<&'a Expression>) {
+    match expression {
+        Expression::Binary(binary) if binary.operator() == BinaryOperator::Or => {
+            collect_operands(binary.left(), operands);
+            collect_operands(binary.right(), operands);
+        }
+        _ => operands.push(expression),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    /// `cond and a or b` only behaves like `if cond then a else b` when `a`
+    /// can't be falsy, otherwise `b` would be returned when `cond` is truthy
+    fn is_branch(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Binary(binary) if binary.operator() == BinaryOperator::And => {
+                self.evaluator.evaluate(binary.right()).is_truthy() == Some(true)
+            }
+            _ => false,
+        }
+    }
+
+    fn convert(&self, expression: &Expression) -> Option<Expression> {
+        let mut operands = Vec::new();
+        collect_operands(expression, &mut operands);
+
+        let branch_count = operands[..operands.len() - 1]
+            .iter()
+            .take_while(|operand| self.is_branch(operand))
+            .count();
+
+        if branch_count == 0 {
+            return None;
+        }
+
+        let else_result = operands
+            .split_off(branch_count)
+            .into_iter()
+            .cloned()
+            .reduce(|current, operand| {
+                BinaryExpression::new(BinaryOperator::Or, current, operand).into()
+            })?;
+
+        let mut branches = operands.into_iter().filter_map(|operand| match operand {
+            Expression::Binary(binary) => Some((binary.left().clone(), binary.right().clone())),
+            _ => None,
+        });
+
+        let (condition, result) = branches.next()?;
+
+        Some(
+            branches
+                .fold(
+                    IfExpression::new(condition, result, else_result),
+                    |if_expression, (condition, result)| {
+                        if_expression.with_branch(condition, result)
+                    },
+                )
+                .into(),
+        )
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Binary(binary) = expression {
+            if binary.operator() == BinaryOperator::Or {
+                if let Some(replace_with) = self.convert(expression) {
+                    *expression = replace_with;
+                }
+            }
+        }
+    }
+}
+
+pub const CONVERT_TERNARY_TO_IF_EXPRESSION_RULE_NAME: &str = "convert_ternary_to_if_expression";
+
+/// A rule that converts the `condition and a or b` idiom into an if expression
+/// when `a` is known to be truthy.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConvertTernaryToIfExpression {}
+
+impl FlawlessRule for ConvertTernaryToIfExpression {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        if !context.supports_syntax(LuaSyntax::IfExpression) {
+            return;
+        }
+
+        let mut processor = Processor::default();
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ConvertTernaryToIfExpression {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONVERT_TERNARY_TO_IF_EXPRESSION_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConvertTernaryToIfExpression {
+        ConvertTernaryToIfExpression::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_ternary_to_if_expression", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'convert_ternary_to_if_expression',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,194 @@
+use crate::generator::{DenseLuaGenerator, LuaGenerator, LuaSyntax};
+use crate::nodes::{
+    AssignStatement, BinaryOperator, Block, CompoundAssignStatement, CompoundAssignTokens,
+    CompoundOperator, Expression, Statement, Variable,
+};
+use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+fn get_compound_operator(operator: BinaryOperator) -> Option<CompoundOperator> {
+    Some(match operator {
+        BinaryOperator::Plus => CompoundOperator::Plus,
+        BinaryOperator::Minus => CompoundOperator::Minus,
+        BinaryOperator::Asterisk => CompoundOperator::Asterisk,
+        BinaryOperator::Slash => CompoundOperator::Slash,
+        BinaryOperator::DoubleSlash => CompoundOperator::DoubleSlash,
+        BinaryOperator::Percent => CompoundOperator::Percent,
+        BinaryOperator::Caret => CompoundOperator::Caret,
+        BinaryOperator::Concat => CompoundOperator::Concat,
+        _ => return None,
+    })
+}
+
+fn is_same_variable(variable: &Variable, expression: &Expression) -> bool {
+    let mut variable_generator = DenseLuaGenerator::default();
+    variable_generator.write_variable(variable);
+
+    let mut expression_generator = DenseLuaGenerator::default();
+    expression_generator.write_expression(expression);
+
+    variable_generator.into_string() == expression_generator.into_string()
+}
+
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Default for Processor {
+    fn default() -> Self {
+        Self {
+            // the indexing operations are still performed once each, only the expressions
+            // used to obtain the variable are evaluated once instead of twice
+            evaluator: Evaluator::default().assume_pure_metamethods(),
+        }
+    }
+}
+
+impl Processor {
+    fn convert(&self, assign: &AssignStatement) -> Option<CompoundAssignStatement> {
+        if assign.variables_len() != 1 || assign.values_len() != 1 {
+            return None;
+        }
+
+        let variable = assign.iter_variables().next()?;
+
+        let binary = match assign.iter_values().next()? {
+            Expression::Binary(binary) => binary,
+            _ => return None,
+        };
+
+        let operator = get_compound_operator(binary.operator())?;
+
+        if !is_same_variable(variable, binary.left())
+            || self
+                .evaluator
+                .has_side_effects(&Expression::from(variable.clone()))
+        {
+            return None;
+        }
+
+        let compound_assign =
+            CompoundAssignStatement::new(operator, variable.clone(), binary.right().clone());
+
+        Some(match assign.get_tokens() {
+            Some(tokens) => {
+                let mut operator_token = tokens.equal.clone();
+                operator_token.replace_with_content(operator.to_str());
+                compound_assign.with_tokens(CompoundAssignTokens {
+                    operator: operator_token,
+                })
+            }
+            None => compound_assign,
+        })
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_statement(&mut self, statement: &mut Statement) {
+        if let Statement::Assign(assign) = statement {
+            if let Some(compound_assign) = self.convert(assign) {
+                *statement = compound_assign.into();
+            }
+        }
+    }
+}
+
+pub const CONVERT_TO_COMPOUND_ASSIGNMENT_RULE_NAME: &str = "convert_to_compound_assignment";
+
+/// A rule that converts assignments like `var = var + value` into compound
+/// assignments (`var += value`). Since compound assignments are only available in
+/// Luau, the rule has no effect unless `target_luau` is enabled.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConvertToCompoundAssignment {
+    target_luau: bool,
+}
+
+impl FlawlessRule for ConvertToCompoundAssignment {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        if !self.target_luau || !context.supports_syntax(LuaSyntax::CompoundAssignment) {
+            return;
+        }
+
+        let mut processor = Processor::default();
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ConvertToCompoundAssignment {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "target_luau" => {
+                    self.target_luau = value.expect_bool(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONVERT_TO_COMPOUND_ASSIGNMENT_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.target_luau {
+            properties.insert(
+                "target_luau".to_owned(),
+                RulePropertyValue::Boolean(self.target_luau),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("target_luau", RulePropertyType::Boolean)
+                .with_default(false),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConvertToCompoundAssignment {
+        ConvertToCompoundAssignment::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_to_compound_assignment", rule);
+    }
+
+    #[test]
+    fn serialize_rule_targeting_luau() {
+        let rule: Box<dyn Rule> = Box::new(ConvertToCompoundAssignment { target_luau: true });
+
+        assert_json_snapshot!("convert_to_compound_assignment_targeting_luau", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'convert_to_compound_assignment',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
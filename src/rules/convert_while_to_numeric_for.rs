@@ -0,0 +1,384 @@
+use crate::nodes::{
+    AssignStatement, BinaryOperator, Block, CompoundAssignStatement, CompoundOperator, Expression,
+    FunctionExpression, FunctionStatement, LastStatement, LocalFunctionStatement,
+    NumericForStatement, Statement, TypedIdentifier, Variable,
+};
+use crate::process::processors::FindUsage;
+use crate::process::utils::MAX_EXACT_INTEGER;
+use crate::process::{
+    DefaultVisitor, Evaluator, LuaValue, NodeProcessor, NodeVisitor, ScopeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+fn is_identifier(expression: &Expression, name: &str) -> bool {
+    matches!(expression, Expression::Identifier(identifier) if identifier.get_name() == name)
+}
+
+fn is_variable(variable: &Variable, name: &str) -> bool {
+    matches!(variable, Variable::Identifier(identifier) if identifier.get_name() == name)
+}
+
+/// Returns true if one of the statements contains a `continue` statement that applies
+/// to the loop owning the statements.
+fn has_continue<'a>(mut statements: impl Iterator<Item = &'a Statement>) -> bool {
+    statements.any(|statement| match statement {
+        Statement::Do(do_statement) => block_has_continue(do_statement.get_block()),
+        Statement::If(if_statement) => {
+            if_statement
+                .iter_branches()
+                .any(|branch| block_has_continue(branch.get_block()))
+                || if_statement
+                    .get_else_block()
+                    .is_some_and(block_has_continue)
+        }
+        _ => false,
+    })
+}
+
+fn block_has_continue(block: &Block) -> bool {
+    matches!(block.get_last_statement(), Some(LastStatement::Continue(_)))
+        || has_continue(block.iter_statements())
+}
+
+/// Returns true if the statement declares a local variable with the given name.
+fn declares_local(statement: &Statement, name: &str) -> bool {
+    match statement {
+        Statement::LocalAssign(assign) => assign
+            .iter_variables()
+            .any(|variable| variable.get_name() == name),
+        Statement::LocalFunction(function) => function.get_name() == name,
+        _ => false,
+    }
+}
+
+/// Finds what prevents the counter from becoming the variable of a numeric `for`: an
+/// assignment to the counter, or a function that could capture it.
+struct FindUnsafeCounterUsage<'a> {
+    counter: &'a str,
+    found: bool,
+}
+
+impl<'a> FindUnsafeCounterUsage<'a> {
+    fn new(counter: &'a str) -> Self {
+        Self {
+            counter,
+            found: false,
+        }
+    }
+}
+
+impl NodeProcessor for FindUnsafeCounterUsage<'_> {
+    fn process_assign_statement(&mut self, assign: &mut AssignStatement) {
+        if assign
+            .iter_variables()
+            .any(|variable| is_variable(variable, self.counter))
+        {
+            self.found = true;
+        }
+    }
+
+    fn process_compound_assign_statement(&mut self, assign: &mut CompoundAssignStatement) {
+        if is_variable(assign.get_variable(), self.counter) {
+            self.found = true;
+        }
+    }
+
+    fn process_function_expression(&mut self, _: &mut FunctionExpression) {
+        self.found = true;
+    }
+
+    fn process_function_statement(&mut self, _: &mut FunctionStatement) {
+        self.found = true;
+    }
+
+    fn process_local_function_statement(&mut self, _: &mut LocalFunctionStatement) {
+        self.found = true;
+    }
+}
+
+#[derive(Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn get_integer(&self, expression: &Expression) -> Option<f64> {
+        if self.evaluator.has_side_effects(expression) {
+            return None;
+        }
+        match self.evaluator.evaluate(expression) {
+            LuaValue::Number(number)
+                if number.fract() == 0.0 && number.abs() <= MAX_EXACT_INTEGER =>
+            {
+                Some(number)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the counter variable, its initial value and the expression of that value
+    /// when the statement is a local assignment of a single integer constant.
+    fn get_counter<'a>(
+        &self,
+        statement: &'a Statement,
+    ) -> Option<(&'a TypedIdentifier, f64, &'a Expression)> {
+        let assign = match statement {
+            Statement::LocalAssign(assign)
+                if assign.variables_len() == 1 && assign.values_len() == 1 =>
+            {
+                assign
+            }
+            _ => return None,
+        };
+
+        let value = assign.iter_values().next()?;
+        let start = self.get_integer(value)?;
+
+        Some((assign.iter_variables().next()?, start, value))
+    }
+
+    /// Returns the last value of the counter for which the loop body runs, when the
+    /// statement is `if counter == limit then break end` (or a similar comparison).
+    fn get_last_value(&self, statement: &Statement, counter: &str, start: f64) -> Option<f64> {
+        let if_statement = match statement {
+            Statement::If(if_statement)
+                if if_statement.branch_count() == 1 && if_statement.get_else_block().is_none() =>
+            {
+                if_statement
+            }
+            _ => return None,
+        };
+
+        let branch = if_statement.iter_branches().next()?;
+        let block = branch.get_block();
+        if block.statements_len() != 0
+            || !matches!(block.get_last_statement(), Some(LastStatement::Break(_)))
+        {
+            return None;
+        }
+
+        let binary = match branch.get_condition() {
+            Expression::Binary(binary) => binary,
+            _ => return None,
+        };
+
+        let (operator, limit) = if is_identifier(binary.left(), counter) {
+            (binary.operator(), binary.right())
+        } else if is_identifier(binary.right(), counter) {
+            let operator = match binary.operator() {
+                BinaryOperator::Equal => BinaryOperator::Equal,
+                BinaryOperator::LowerOrEqualThan => BinaryOperator::GreaterOrEqualThan,
+                BinaryOperator::LowerThan => BinaryOperator::GreaterThan,
+                _ => return None,
+            };
+            (operator, binary.left())
+        } else {
+            return None;
+        };
+
+        let limit = self.get_integer(limit)?;
+
+        match operator {
+            // the counter never equals the limit when it starts above it
+            BinaryOperator::Equal if start <= limit => Some(limit - 1.0),
+            BinaryOperator::GreaterOrEqualThan => Some(limit - 1.0),
+            BinaryOperator::GreaterThan => Some(limit),
+            _ => None,
+        }
+    }
+
+    fn is_one(&self, expression: &Expression) -> bool {
+        self.get_integer(expression) == Some(1.0)
+    }
+
+    /// Returns true if the statement adds one to the counter.
+    fn is_increment(&self, statement: &Statement, counter: &str) -> bool {
+        match statement {
+            Statement::Assign(assign)
+                if assign.variables_len() == 1 && assign.values_len() == 1 =>
+            {
+                let (variable, value) =
+                    match (assign.iter_variables().next(), assign.iter_values().next()) {
+                        (Some(variable), Some(value)) => (variable, value),
+                        _ => return false,
+                    };
+
+                let binary = match value {
+                    Expression::Binary(binary) if binary.operator() == BinaryOperator::Plus => {
+                        binary
+                    }
+                    _ => return false,
+                };
+
+                is_variable(variable, counter)
+                    && ((is_identifier(binary.left(), counter) && self.is_one(binary.right()))
+                        || (self.is_one(binary.left()) && is_identifier(binary.right(), counter)))
+            }
+            Statement::CompoundAssign(assign) => {
+                assign.get_operator() == CompoundOperator::Plus
+                    && is_variable(assign.get_variable(), counter)
+                    && self.is_one(assign.get_value())
+            }
+            _ => false,
+        }
+    }
+
+    /// Converts the `while true` loop at the given index of the block into a numeric `for`
+    /// loop, when the loop is preceded by the declaration of its counter.
+    fn convert(&self, block: &mut Block, index: usize) -> Option<NumericForStatement> {
+        let mut statements = block.iter_statements().skip(index - 1);
+        let (counter, start, start_expression) = self.get_counter(statements.next()?)?;
+
+        let while_statement = match statements.next()? {
+            Statement::While(while_statement)
+                if matches!(while_statement.get_condition(), Expression::True(_)) =>
+            {
+                while_statement
+            }
+            _ => return None,
+        };
+
+        let name = counter.get_name();
+        let body = while_statement.get_block();
+        let body_length = body.statements_len();
+
+        if body.get_last_statement().is_some() || body_length < 2 {
+            return None;
+        }
+
+        let mut body_statements = body.iter_statements();
+        let last_value = self.get_last_value(body_statements.next()?, name, start)?;
+
+        if !self.is_increment(body.iter_statements().last()?, name) {
+            return None;
+        }
+
+        let mut inner_statements: Vec<_> = body_statements.take(body_length - 2).cloned().collect();
+
+        if has_continue(inner_statements.iter())
+            || inner_statements
+                .iter()
+                .any(|statement| declares_local(statement, name))
+        {
+            return None;
+        }
+
+        let mut find_unsafe_usage = FindUnsafeCounterUsage::new(name);
+        for statement in inner_statements.iter_mut() {
+            DefaultVisitor::visit_statement(statement, &mut find_unsafe_usage);
+        }
+        if find_unsafe_usage.found {
+            return None;
+        }
+
+        let counter = counter.clone();
+        let start_expression = start_expression.clone();
+
+        // the counter keeps its value after the loop, but the `for` loop variable does
+        // not exist outside of the loop
+        let mut find_usage = FindUsage::new(counter.get_name());
+        let used_after_loop = block
+            .iter_mut_statements()
+            .skip(index + 1)
+            .any(|statement| {
+                ScopeVisitor::visit_statement(statement, &mut find_usage);
+                find_usage.has_found_usage()
+            })
+            || block.mutate_last_statement().is_some_and(|last_statement| {
+                ScopeVisitor::visit_last_statement(last_statement, &mut find_usage);
+                find_usage.has_found_usage()
+            });
+
+        if used_after_loop {
+            return None;
+        }
+
+        Some(NumericForStatement::new(
+            counter,
+            start_expression,
+            last_value,
+            None,
+            Block::new(inner_statements, None),
+        ))
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_block(&mut self, block: &mut Block) {
+        let mut index = 1;
+
+        while index < block.statements_len() {
+            if let Some(for_statement) = self.convert(block, index) {
+                block.remove_statement(index);
+                block.remove_statement(index - 1);
+                block.insert_statement(index - 1, for_statement);
+            } else {
+                index += 1;
+            }
+        }
+    }
+}
+
+pub const CONVERT_WHILE_TO_NUMERIC_FOR_RULE_NAME: &str = "convert_while_to_numeric_for";
+
+/// A rule that converts `while true` loops driven by a counter into numeric `for` loops,
+/// when the number of iterations is known.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConvertWhileToNumericFor {}
+
+impl FlawlessRule for ConvertWhileToNumericFor {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ConvertWhileToNumericFor {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        CONVERT_WHILE_TO_NUMERIC_FOR_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ConvertWhileToNumericFor {
+        ConvertWhileToNumericFor::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_while_to_numeric_for", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'convert_while_to_numeric_for',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,377 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+
+use crate::generator::{DenseLuaGenerator, LuaGenerator};
+use crate::nodes::{
+    Block, Expression, FunctionExpression, Identifier, LocalAssignStatement,
+    LocalFunctionStatement, Type, TypeDeclarationStatement,
+};
+use crate::process::{
+    IdentifierTracker, NodePostProcessor, NodePostVisitor, NodeProcessor, NodeVisitor, Scope,
+    ScopePostVisitor, ScopeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+const DEFAULT_MINIMUM_OCCURRENCES: usize = 2;
+const SHARED_FUNCTION_NAME: &str = "FN";
+
+/// Finds the identifiers referenced in a function that are not defined by the
+/// function itself.
+#[derive(Default)]
+struct FindFreeIdentifiers {
+    identifier_tracker: IdentifierTracker,
+    free_identifiers: Vec<String>,
+    has_types: bool,
+}
+
+impl Deref for FindFreeIdentifiers {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for FindFreeIdentifiers {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for FindFreeIdentifiers {
+    fn process_variable_expression(&mut self, variable: &mut Identifier) {
+        let name = variable.get_name();
+        if !self.is_identifier_used(name) {
+            self.free_identifiers.push(name.to_owned());
+        }
+    }
+
+    fn process_type(&mut self, _: &mut Type) {
+        self.has_types = true;
+    }
+
+    fn process_type_declaration(&mut self, _: &mut TypeDeclarationStatement) {
+        self.has_types = true;
+    }
+}
+
+/// Collects every identifier declared or referenced, to make sure the shared
+/// functions are assigned to names that do not conflict with existing variables.
+#[derive(Default)]
+struct CollectIdentifiers {
+    identifiers: HashSet<String>,
+}
+
+impl NodeProcessor for CollectIdentifiers {
+    fn process_variable_expression(&mut self, variable: &mut Identifier) {
+        self.identifiers.insert(variable.get_name().to_owned());
+    }
+}
+
+impl Scope for CollectIdentifiers {
+    fn push(&mut self) {}
+
+    fn pop(&mut self) {}
+
+    fn insert(&mut self, identifier: &mut String) {
+        self.identifiers.insert(identifier.to_owned());
+    }
+
+    fn insert_self(&mut self) {}
+
+    fn insert_local(&mut self, identifier: &mut String, _value: Option<&mut Expression>) {
+        self.identifiers.insert(identifier.to_owned());
+    }
+
+    fn insert_local_function(&mut self, function: &mut LocalFunctionStatement) {
+        self.identifiers
+            .insert(function.get_identifier().get_name().to_owned());
+    }
+}
+
+/// Computes a key identifying the function structurally (without its tokens) if
+/// the function can be moved to the beginning of the file. A function can be moved
+/// only when it does not capture any local variable.
+fn get_function_key(
+    function: &mut FunctionExpression,
+    identifier_tracker: &IdentifierTracker,
+) -> Option<String> {
+    let mut find_free_identifiers = FindFreeIdentifiers::default();
+    ScopeVisitor::visit_function_expression(function, &mut find_free_identifiers);
+
+    if find_free_identifiers.has_types
+        || find_free_identifiers
+            .free_identifiers
+            .iter()
+            .any(|identifier| identifier_tracker.is_identifier_used(identifier))
+    {
+        return None;
+    }
+
+    let mut generator = DenseLuaGenerator::default();
+    generator.write_function(function);
+    Some(generator.into_string())
+}
+
+#[derive(Default)]
+struct FunctionCollector {
+    identifier_tracker: IdentifierTracker,
+    // the key of each function expression, in the order they are visited
+    function_keys: Vec<Option<String>>,
+}
+
+impl Deref for FunctionCollector {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for FunctionCollector {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for FunctionCollector {}
+
+impl NodePostProcessor for FunctionCollector {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Function(function) = expression {
+            let key = get_function_key(function, &self.identifier_tracker);
+            self.function_keys.push(key);
+        }
+    }
+}
+
+struct FunctionReplacer {
+    identifier_tracker: IdentifierTracker,
+    function_keys: std::vec::IntoIter<Option<String>>,
+    shared_keys: HashSet<String>,
+    names: IdentifierTracker,
+    shared_name_prefix: String,
+    shared_names: HashMap<String, String>,
+    shared_functions: Vec<(String, FunctionExpression)>,
+}
+
+impl FunctionReplacer {
+    fn get_shared_name(&mut self, key: String, function: &FunctionExpression) -> String {
+        if let Some(name) = self.shared_names.get(&key) {
+            return name.clone();
+        }
+
+        let name = self
+            .names
+            .generate_identifier_with_prefix(self.shared_name_prefix.as_str());
+        self.shared_functions.push((name.clone(), function.clone()));
+        self.shared_names.insert(key, name.clone());
+        name
+    }
+}
+
+impl Deref for FunctionReplacer {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for FunctionReplacer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for FunctionReplacer {}
+
+impl NodePostProcessor for FunctionReplacer {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Function(function) = expression {
+            let key = match self.function_keys.next().flatten() {
+                Some(key) if self.shared_keys.contains(&key) => key,
+                _ => return,
+            };
+
+            let name = self.get_shared_name(key, function);
+            *expression = Expression::identifier(name);
+        }
+    }
+}
+
+pub const DEDUPLICATE_FUNCTIONS_RULE_NAME: &str = "deduplicate_functions";
+
+/// A rule that moves identical function expressions into a shared local variable
+/// defined at the beginning of the file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeduplicateFunctions {
+    minimum_occurrences: usize,
+}
+
+impl Default for DeduplicateFunctions {
+    fn default() -> Self {
+        Self {
+            minimum_occurrences: DEFAULT_MINIMUM_OCCURRENCES,
+        }
+    }
+}
+
+impl FlawlessRule for DeduplicateFunctions {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut collector = FunctionCollector::default();
+        ScopePostVisitor::visit_block(block, &mut collector);
+
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        for key in collector.function_keys.iter().flatten() {
+            *occurrences.entry(key.as_str()).or_default() += 1;
+        }
+
+        let shared_keys: HashSet<String> = occurrences
+            .into_iter()
+            .filter(|(_, count)| *count >= self.minimum_occurrences)
+            .map(|(key, _)| key.to_owned())
+            .collect();
+
+        if shared_keys.is_empty() {
+            return;
+        }
+
+        let mut collect_identifiers = CollectIdentifiers::default();
+        ScopeVisitor::visit_block(block, &mut collect_identifiers);
+
+        let mut names = IdentifierTracker::new();
+        names.push();
+        for identifier in collect_identifiers.identifiers.iter() {
+            names.insert(&mut identifier.clone());
+        }
+
+        let mut replacer = FunctionReplacer {
+            identifier_tracker: IdentifierTracker::default(),
+            function_keys: collector.function_keys.into_iter(),
+            shared_keys,
+            names,
+            shared_name_prefix: format!(
+                "{}{}",
+                context.generated_identifier_prefix(),
+                SHARED_FUNCTION_NAME
+            ),
+            shared_names: HashMap::new(),
+            shared_functions: Vec::new(),
+        };
+        ScopePostVisitor::visit_block(block, &mut replacer);
+
+        for (index, (name, function)) in replacer.shared_functions.into_iter().enumerate() {
+            block.insert_statement(
+                index,
+                LocalAssignStatement::from_variable(name).with_value(function),
+            );
+        }
+    }
+}
+
+impl RuleConfiguration for DeduplicateFunctions {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "minimum_occurrences" => {
+                    let minimum_occurrences = value.expect_usize(&key)?;
+                    if minimum_occurrences < 2 {
+                        return Err(RuleConfigurationError::UnexpectedValue {
+                            property: key,
+                            message: format!(
+                                "the minimum number of occurrences must be at least 2 (got {})",
+                                minimum_occurrences
+                            ),
+                        });
+                    }
+                    self.minimum_occurrences = minimum_occurrences;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        DEDUPLICATE_FUNCTIONS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.minimum_occurrences != DEFAULT_MINIMUM_OCCURRENCES {
+            properties.insert(
+                "minimum_occurrences".to_owned(),
+                RulePropertyValue::Usize(self.minimum_occurrences),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("minimum_occurrences", RulePropertyType::Usize)
+                .with_default(DEFAULT_MINIMUM_OCCURRENCES),
+        ]
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> DeduplicateFunctions {
+        DeduplicateFunctions::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_deduplicate_functions", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_minimum_occurrences() {
+        let rule: Box<dyn Rule> = Box::new(DeduplicateFunctions {
+            minimum_occurrences: 3,
+        });
+
+        assert_json_snapshot!("deduplicate_functions_with_minimum_occurrences", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'deduplicate_functions',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    #[test]
+    fn configure_with_minimum_occurrences_below_two_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'deduplicate_functions',
+            minimum_occurrences: 1,
+        }"#,
+        );
+        insta::assert_snapshot!(result.unwrap_err().to_string(), @"unexpected value for field 'minimum_occurrences': the minimum number of occurrences must be at least 2 (got 1)");
+    }
+}
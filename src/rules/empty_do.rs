@@ -1,5 +1,5 @@
 use crate::nodes::{Block, Statement};
-use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::process::{DefaultPostVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
 };
@@ -7,23 +7,16 @@ use crate::rules::{
 use super::verify_no_rule_properties;
 
 #[derive(Debug, Default)]
-struct EmptyDoFilter {
-    mutated: bool,
-}
+struct EmptyDoFilter;
 
-impl EmptyDoFilter {
-    pub fn has_mutated(&self) -> bool {
-        self.mutated
-    }
-}
+impl NodeProcessor for EmptyDoFilter {}
 
-impl NodeProcessor for EmptyDoFilter {
-    fn process_block(&mut self, block: &mut Block) {
+impl NodePostProcessor for EmptyDoFilter {
+    // nested blocks are processed first, so a do statement that only contained
+    // empty do statements is already empty when its parent block is filtered
+    fn process_after_block(&mut self, block: &mut Block) {
         block.filter_statements(|statement| match statement {
-            Statement::Do(do_statement) => {
-                self.mutated = do_statement.get_block().is_empty();
-                !self.mutated
-            }
+            Statement::Do(do_statement) => !do_statement.get_block().is_empty(),
             _ => true,
         });
     }
@@ -31,19 +24,15 @@ impl NodeProcessor for EmptyDoFilter {
 
 pub const REMOVE_EMPTY_DO_RULE_NAME: &str = "remove_empty_do";
 
-/// A rule that removes empty do statements.
+/// A rule that removes empty do statements, including do statements that only
+/// contain other empty do statements.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct RemoveEmptyDo {}
 
 impl FlawlessRule for RemoveEmptyDo {
     fn flawless_process(&self, block: &mut Block, _: &Context) {
-        loop {
-            let mut processor = EmptyDoFilter::default();
-            DefaultVisitor::visit_block(block, &mut processor);
-            if !processor.has_mutated() {
-                break;
-            }
-        }
+        let mut processor = EmptyDoFilter;
+        DefaultPostVisitor::visit_block(block, &mut processor);
     }
 }
 
@@ -0,0 +1,281 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    Arguments, Block, Expression, FieldExpression, FunctionCall, Prefix, StringExpression,
+    TupleArguments,
+};
+use crate::process::utils::is_valid_identifier;
+use crate::process::{IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+use super::remove_call_match::get_function_path;
+
+const ERROR_FUNCTION_NAME: &str = "error";
+const ASSERT_FUNCTION_NAME: &str = "assert";
+
+/// Returns the position of the message in the arguments of the given function: the
+/// message of `assert` comes after the asserted value.
+fn get_message_index(function: &str) -> usize {
+    if function == ASSERT_FUNCTION_NAME {
+        1
+    } else {
+        0
+    }
+}
+
+fn create_lookup_prefix(lookup_function: &str) -> Prefix {
+    let mut names = lookup_function.split('.');
+    let root = Prefix::from_name(names.next().unwrap_or(lookup_function));
+
+    names.fold(root, |prefix, field| {
+        FieldExpression::new(prefix, field).into()
+    })
+}
+
+struct Processor<'a, 'b, 'resources, 'code> {
+    identifier_tracker: IdentifierTracker,
+    functions: &'a [String],
+    lookup_function: Option<&'a str>,
+    context: &'a Context<'b, 'resources, 'code>,
+}
+
+impl<'a, 'b, 'resources, 'code> Processor<'a, 'b, 'resources, 'code> {
+    fn new(
+        functions: &'a [String],
+        lookup_function: Option<&'a str>,
+        context: &'a Context<'b, 'resources, 'code>,
+    ) -> Self {
+        Self {
+            identifier_tracker: Default::default(),
+            functions,
+            lookup_function,
+            context,
+        }
+    }
+
+    fn get_function(&self, call: &FunctionCall) -> Option<String> {
+        if call.get_method().is_some() {
+            return None;
+        }
+
+        let (root, path) = get_function_path(call.get_prefix())?;
+
+        if self.is_identifier_used(root) || !self.functions.contains(&path) {
+            return None;
+        }
+
+        Some(path)
+    }
+
+    fn create_lookup(&self, message: &str) -> Option<Expression> {
+        self.lookup_function.map(|lookup_function| {
+            FunctionCall::from_prefix(create_lookup_prefix(lookup_function))
+                .with_argument(StringExpression::from_value(message))
+                .into()
+        })
+    }
+}
+
+impl Deref for Processor<'_, '_, '_, '_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor<'_, '_, '_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor<'_, '_, '_, '_> {
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        let function = match self.get_function(call) {
+            Some(function) => function,
+            None => return,
+        };
+
+        let message_index = get_message_index(&function);
+
+        let message = match call.get_arguments() {
+            Arguments::Tuple(tuple) => match tuple.iter_values().nth(message_index) {
+                Some(Expression::String(string)) => string.get_value().to_owned(),
+                _ => return,
+            },
+            Arguments::String(string) if message_index == 0 => string.get_value().to_owned(),
+            Arguments::String(_) | Arguments::Table(_) => return,
+        };
+
+        if let Some(lookup) = self.create_lookup(&message) {
+            match call.mutate_arguments() {
+                Arguments::Tuple(tuple) => {
+                    if let Some(value) = tuple.iter_mut_values().nth(message_index) {
+                        *value = lookup;
+                    }
+                }
+                arguments => {
+                    *arguments = TupleArguments::default().with_argument(lookup).into();
+                }
+            }
+        }
+
+        self.context.add_extracted_message(function, message);
+    }
+}
+
+pub const EXTRACT_MESSAGES_RULE_NAME: &str = "extract_messages";
+
+/// A rule that collects the string literals passed as messages to `error` and `assert`
+/// (or other configured functions), and optionally replaces them with calls to a lookup
+/// function.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExtractMessages {
+    functions: Vec<String>,
+    lookup_function: Option<String>,
+}
+
+impl Default for ExtractMessages {
+    fn default() -> Self {
+        Self {
+            functions: vec![
+                ERROR_FUNCTION_NAME.to_owned(),
+                ASSERT_FUNCTION_NAME.to_owned(),
+            ],
+            lookup_function: None,
+        }
+    }
+}
+
+impl ExtractMessages {
+    fn has_default_functions(&self) -> bool {
+        self.functions == [ERROR_FUNCTION_NAME, ASSERT_FUNCTION_NAME]
+    }
+}
+
+impl FlawlessRule for ExtractMessages {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor =
+            Processor::new(&self.functions, self.lookup_function.as_deref(), context);
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ExtractMessages {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "functions" => {
+                    self.functions = value.expect_string_list(&key)?;
+                }
+                "lookup_function" => {
+                    let lookup_function = value.expect_string(&key)?;
+
+                    if !lookup_function.split('.').all(is_valid_identifier) {
+                        return Err(RuleConfigurationError::UnexpectedValue {
+                            property: key,
+                            message: format!(
+                                "invalid function name `{}`, expected an identifier or \
+                                identifiers separated by dots",
+                                lookup_function
+                            ),
+                        });
+                    }
+
+                    self.lookup_function = Some(lookup_function);
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        EXTRACT_MESSAGES_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if !self.has_default_functions() {
+            properties.insert(
+                "functions".to_owned(),
+                RulePropertyValue::StringList(self.functions.clone()),
+            );
+        }
+
+        if let Some(lookup_function) = &self.lookup_function {
+            properties.insert("lookup_function".to_owned(), lookup_function.into());
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("functions", RulePropertyType::StringList)
+                .with_default(vec![ERROR_FUNCTION_NAME, ASSERT_FUNCTION_NAME]),
+            RulePropertyDescription::new("lookup_function", RulePropertyType::String),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ExtractMessages {
+        ExtractMessages::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_extract_messages", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_lookup_function() {
+        let rule: Box<dyn Rule> = Box::new(ExtractMessages {
+            lookup_function: Some("i18n.get".to_owned()),
+            ..Default::default()
+        });
+
+        assert_json_snapshot!("extract_messages_with_lookup_function", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'extract_messages',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    #[test]
+    fn configure_with_invalid_lookup_function_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'extract_messages',
+            lookup_function: "i18n:get",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'lookup_function': invalid function name `i18n:get`, \
+            expected an identifier or identifiers separated by dots"
+        );
+    }
+}
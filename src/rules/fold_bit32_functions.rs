@@ -0,0 +1,298 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Arguments, Block, Expression, FunctionCall};
+use crate::process::utils::MAX_EXACT_INTEGER;
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::remove_call_match::get_function_path;
+use super::verify_no_rule_properties;
+
+const BIT32_LIBRARY_NAME: &str = "bit32";
+
+const BIT_COUNT: i64 = 32;
+
+/// Converts a number to an unsigned 32-bit integer, wrapping around like `bit32` does.
+/// Numbers with a fractional part are not converted, since Lua versions do not agree on
+/// how to round them.
+fn to_unsigned(value: f64) -> Option<u32> {
+    if value.fract() == 0.0 && value.abs() <= MAX_EXACT_INTEGER {
+        Some(value as i64 as u32)
+    } else {
+        None
+    }
+}
+
+/// Converts a number used as a bit count (a displacement, a field or a width).
+fn to_bit_count(value: f64) -> Option<i64> {
+    if value.fract() == 0.0 && value.abs() <= i32::MAX as f64 {
+        Some(value as i64)
+    } else {
+        None
+    }
+}
+
+fn shift(value: u32, displacement: i64) -> u32 {
+    if displacement <= -BIT_COUNT || displacement >= BIT_COUNT {
+        0
+    } else if displacement < 0 {
+        value >> -displacement
+    } else {
+        value << displacement
+    }
+}
+
+fn arithmetic_shift(value: u32, displacement: i64) -> u32 {
+    if displacement < 0 || value & (1 << (BIT_COUNT - 1)) == 0 {
+        shift(value, -displacement)
+    } else if displacement >= BIT_COUNT {
+        u32::MAX
+    } else {
+        ((value as i32) >> displacement) as u32
+    }
+}
+
+fn rotate(value: u32, displacement: i64) -> u32 {
+    value.rotate_left((displacement & (BIT_COUNT - 1)) as u32)
+}
+
+/// Returns the mask of a field of `bit32.extract` and `bit32.replace`, or `None` if the
+/// call would throw an error.
+fn field_mask(field: f64, width: f64) -> Option<(u32, u32)> {
+    let field = to_bit_count(field)?;
+    let width = to_bit_count(width)?;
+
+    if field < 0 || width <= 0 || field + width > BIT_COUNT {
+        return None;
+    }
+
+    Some((field as u32, (u32::MAX >> (BIT_COUNT - width)) << field))
+}
+
+fn fold_unsigned(arguments: &[f64], initial: u32, operation: fn(u32, u32) -> u32) -> Option<u32> {
+    arguments.iter().try_fold(initial, |result, value| {
+        Some(operation(result, to_unsigned(*value)?))
+    })
+}
+
+/// Computes the result of a `bit32` library function. Returns `None` when the function
+/// is unknown, does not return a number, or when the call would throw an error.
+fn compute_bit32_function(path: &str, arguments: &[f64]) -> Option<u32> {
+    match (path, arguments) {
+        ("bit32.band", _) => fold_unsigned(arguments, u32::MAX, |a, b| a & b),
+        ("bit32.bor", _) => fold_unsigned(arguments, 0, |a, b| a | b),
+        ("bit32.bxor", _) => fold_unsigned(arguments, 0, |a, b| a ^ b),
+        ("bit32.bnot", [value]) => Some(!to_unsigned(*value)?),
+        ("bit32.lshift", [value, displacement]) => {
+            Some(shift(to_unsigned(*value)?, to_bit_count(*displacement)?))
+        }
+        ("bit32.rshift", [value, displacement]) => {
+            Some(shift(to_unsigned(*value)?, -to_bit_count(*displacement)?))
+        }
+        ("bit32.arshift", [value, displacement]) => Some(arithmetic_shift(
+            to_unsigned(*value)?,
+            to_bit_count(*displacement)?,
+        )),
+        ("bit32.lrotate", [value, displacement]) => {
+            Some(rotate(to_unsigned(*value)?, to_bit_count(*displacement)?))
+        }
+        ("bit32.rrotate", [value, displacement]) => {
+            Some(rotate(to_unsigned(*value)?, -to_bit_count(*displacement)?))
+        }
+        ("bit32.extract", [value, field]) => compute_bit32_function(path, &[*value, *field, 1.0]),
+        ("bit32.extract", [value, field, width]) => {
+            let (field, mask) = field_mask(*field, *width)?;
+            Some((to_unsigned(*value)? & mask) >> field)
+        }
+        ("bit32.replace", [value, replacement, field]) => {
+            compute_bit32_function(path, &[*value, *replacement, *field, 1.0])
+        }
+        ("bit32.replace", [value, replacement, field, width]) => {
+            let (field, mask) = field_mask(*field, *width)?;
+            Some((to_unsigned(*value)? & !mask) | ((to_unsigned(*replacement)? << field) & mask))
+        }
+        ("bit32.countlz", [value]) => Some(to_unsigned(*value)?.leading_zeros()),
+        ("bit32.countrz", [value]) => Some(to_unsigned(*value)?.trailing_zeros()),
+        ("bit32.byteswap", [value]) => Some(to_unsigned(*value)?.swap_bytes()),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl Processor {
+    fn fold(&self, call: &FunctionCall) -> Option<u32> {
+        if call.get_method().is_some() {
+            return None;
+        }
+
+        let (root, path) = get_function_path(call.get_prefix())?;
+
+        if root != BIT32_LIBRARY_NAME || self.is_identifier_used(root) {
+            return None;
+        }
+
+        let arguments = match call.get_arguments() {
+            Arguments::Tuple(tuple) => tuple
+                .iter_values()
+                .map(|value| {
+                    if self.evaluator.has_side_effects(value) {
+                        return None;
+                    }
+                    match self.evaluator.evaluate(value) {
+                        LuaValue::Number(number) => Some(number),
+                        _ => None,
+                    }
+                })
+                .collect::<Option<Vec<_>>>()?,
+            Arguments::String(_) | Arguments::Table(_) => return None,
+        };
+
+        compute_bit32_function(&path, &arguments)
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(value) = self.fold(call) {
+                *expression = Expression::from(value as f64);
+            }
+        }
+    }
+}
+
+pub const FOLD_BIT32_FUNCTIONS_RULE_NAME: &str = "fold_bit32_functions";
+
+/// A rule that replaces calls to `bit32` library functions with constant number
+/// arguments with the computed result.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldBit32Functions {}
+
+impl FlawlessRule for FoldBit32Functions {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldBit32Functions {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_BIT32_FUNCTIONS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldBit32Functions {
+        FoldBit32Functions::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_bit32_functions", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_bit32_functions',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    #[test]
+    fn negative_numbers_wrap_around() {
+        assert_eq!(to_unsigned(-1.0), Some(u32::MAX));
+    }
+
+    #[test]
+    fn numbers_above_32_bits_wrap_around() {
+        assert_eq!(to_unsigned(4_294_967_298.0), Some(2));
+    }
+
+    #[test]
+    fn arshift_negative_value_fills_with_ones() {
+        assert_eq!(
+            compute_bit32_function("bit32.arshift", &[-256.0, 4.0]),
+            Some(0xFFFF_FFF0)
+        );
+    }
+
+    #[test]
+    fn arshift_negative_value_by_32_bits() {
+        assert_eq!(
+            compute_bit32_function("bit32.arshift", &[-1.0, 32.0]),
+            Some(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn arshift_with_negative_displacement_shifts_left() {
+        assert_eq!(
+            compute_bit32_function("bit32.arshift", &[-1.0, -4.0]),
+            Some(0xFFFF_FFF0)
+        );
+    }
+
+    #[test]
+    fn lrotate_with_negative_displacement() {
+        assert_eq!(
+            compute_bit32_function("bit32.lrotate", &[1.0, -1.0]),
+            Some(0x8000_0000)
+        );
+    }
+
+    #[test]
+    fn extract_with_field_out_of_bounds() {
+        assert_eq!(
+            compute_bit32_function("bit32.extract", &[1.0, 30.0, 4.0]),
+            None
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::nodes::{Block, Expression, TableEntry, TableExpression, UnaryOperator};
+use crate::process::{
+    DefaultPostVisitor, Evaluator, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+#[derive(Debug, Clone, Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn fold(&self, expression: &Expression) -> Option<usize> {
+        if self.evaluator.has_side_effects(expression) {
+            return None;
+        }
+
+        match expression {
+            Expression::Table(table) => self.get_array_length(table),
+            Expression::Parenthese(parenthese) => self.fold(parenthese.inner_expression()),
+            _ => match self.evaluator.evaluate(expression) {
+                LuaValue::String(string) => Some(string.len()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the length of a table that only contains array values. The border
+    /// of a table is only known when none of its values can be nil.
+    fn get_array_length(&self, table: &TableExpression) -> Option<usize> {
+        let mut length = 0;
+
+        for entry in table.iter_entries() {
+            let value = match entry {
+                TableEntry::Value(value) => value,
+                TableEntry::Field(_) | TableEntry::Index(_) => return None,
+            };
+
+            if self.evaluator.can_return_multiple_values(value) {
+                return None;
+            }
+
+            match self.evaluator.evaluate(value) {
+                LuaValue::Nil | LuaValue::Unknown => return None,
+                _ => {}
+            }
+
+            length += 1;
+        }
+
+        Some(length)
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Unary(unary) = expression {
+            if unary.operator() == UnaryOperator::Length {
+                if let Some(length) = self.fold(unary.get_expression()) {
+                    *expression = Expression::from(length);
+                }
+            }
+        }
+    }
+}
+
+pub const FOLD_LENGTH_OPERATOR_RULE_NAME: &str = "fold_length_operator";
+
+/// A rule that replaces the length operator (`#`) applied on constant strings and
+/// array-only table literals with the computed length.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldLengthOperator {}
+
+impl FlawlessRule for FoldLengthOperator {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        DefaultPostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldLengthOperator {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_LENGTH_OPERATOR_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldLengthOperator {
+        FoldLengthOperator::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_length_operator", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_length_operator',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::nodes::{BinaryExpression, BinaryOperator, Block, Expression};
+use crate::process::{
+    DefaultPostVisitor, Evaluator, NodePostProcessor, NodePostVisitor, NodeProcessor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+#[derive(Debug, Clone, Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn fold(&self, binary: &BinaryExpression) -> Option<Expression> {
+        let left = binary.left();
+        let is_truthy = self.evaluator.evaluate(left).is_truthy()?;
+
+        // the right operand is only evaluated when the left operand does not
+        // short-circuit the expression, so it can always be dropped. The left
+        // operand is always evaluated, so it can only be dropped when it does
+        // not have side effects
+        let keep_left = match binary.operator() {
+            BinaryOperator::And => !is_truthy,
+            BinaryOperator::Or => is_truthy,
+            _ => return None,
+        };
+
+        let value = if keep_left {
+            left
+        } else if self.evaluator.has_side_effects(left) {
+            return None;
+        } else {
+            binary.right()
+        };
+
+        if self.evaluator.can_return_multiple_values(value) {
+            Some(value.clone().in_parentheses())
+        } else {
+            Some(value.clone())
+        }
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Binary(binary) = expression {
+            if let Some(replace_with) = self.fold(binary) {
+                *expression = replace_with;
+            }
+        }
+    }
+}
+
+pub const FOLD_LOGICAL_OPERATORS_RULE_NAME: &str = "fold_logical_operators";
+
+/// A rule that replaces `and` and `or` expressions with one of their operands when
+/// the truthiness of the left operand is known.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldLogicalOperators {}
+
+impl FlawlessRule for FoldLogicalOperators {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        DefaultPostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldLogicalOperators {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_LOGICAL_OPERATORS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldLogicalOperators {
+        FoldLogicalOperators::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_logical_operators", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_logical_operators',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,183 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Arguments, Block, Expression, FunctionCall};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::remove_call_match::get_function_path;
+use super::verify_no_rule_properties;
+
+const MATH_LIBRARY_NAME: &str = "math";
+
+/// Computes the result of a `math` library function. Only functions available in every
+/// Lua version and that return an exact result are computed. Returns `None` when the
+/// function is unknown or when the call would throw an error.
+fn compute_math_function(path: &str, arguments: &[f64]) -> Option<f64> {
+    match (path, arguments) {
+        ("math.abs", [value]) => Some(value.abs()),
+        ("math.ceil", [value]) => Some(value.ceil()),
+        ("math.floor", [value]) => Some(value.floor()),
+        ("math.sqrt", [value]) => Some(value.sqrt()),
+        // Lua 5.4 throws an error when both arguments are integers and the divisor is zero
+        ("math.fmod", [value, divisor]) if *divisor != 0.0 => Some(value % divisor),
+        // these functions do not use `f64::max` and `f64::min` to match how Lua handles
+        // NaN values: the first value is kept unless another value compares greater
+        ("math.max", [first, rest @ ..]) => {
+            Some(
+                rest.iter()
+                    .fold(*first, |max, value| if *value > max { *value } else { max }),
+            )
+        }
+        ("math.min", [first, rest @ ..]) => {
+            Some(
+                rest.iter()
+                    .fold(*first, |min, value| if *value < min { *value } else { min }),
+            )
+        }
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl Processor {
+    fn fold(&self, call: &FunctionCall) -> Option<f64> {
+        if call.get_method().is_some() {
+            return None;
+        }
+
+        let (root, path) = get_function_path(call.get_prefix())?;
+
+        if root != MATH_LIBRARY_NAME || self.is_identifier_used(root) {
+            return None;
+        }
+
+        let arguments = match call.get_arguments() {
+            Arguments::Tuple(tuple) => tuple
+                .iter_values()
+                .map(|value| {
+                    if self.evaluator.has_side_effects(value) {
+                        return None;
+                    }
+                    match self.evaluator.evaluate(value) {
+                        LuaValue::Number(number) => Some(number),
+                        _ => None,
+                    }
+                })
+                .collect::<Option<Vec<_>>>()?,
+            Arguments::String(_) | Arguments::Table(_) => return None,
+        };
+
+        compute_math_function(&path, &arguments)
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(value) = self.fold(call) {
+                *expression = Expression::from(value);
+            }
+        }
+    }
+}
+
+pub const FOLD_MATH_FUNCTIONS_RULE_NAME: &str = "fold_math_functions";
+
+/// A rule that replaces calls to `math` library functions with constant number
+/// arguments with the computed result.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldMathFunctions {}
+
+impl FlawlessRule for FoldMathFunctions {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldMathFunctions {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_MATH_FUNCTIONS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldMathFunctions {
+        FoldMathFunctions::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_math_functions", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_math_functions',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    #[test]
+    fn max_keeps_first_nan_value() {
+        assert!(compute_math_function("math.max", &[f64::NAN, 1.0])
+            .unwrap()
+            .is_nan());
+    }
+
+    #[test]
+    fn max_skips_nan_value_after_first_value() {
+        assert_eq!(
+            compute_math_function("math.max", &[1.0, f64::NAN]),
+            Some(1.0)
+        );
+    }
+}
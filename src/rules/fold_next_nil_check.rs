@@ -0,0 +1,239 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    Arguments, BinaryOperator, Block, Expression, FunctionCall, Prefix, TableEntry, TableExpression,
+};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+const NEXT_FUNCTION: &str = "next";
+
+fn get_table_argument(expression: &Expression) -> Option<&TableExpression> {
+    match expression {
+        Expression::Table(table) => Some(table),
+        Expression::Parenthese(parenthese) => get_table_argument(parenthese.inner_expression()),
+        _ => None,
+    }
+}
+
+/// The known state of a value stored in a table literal.
+enum EntryValue {
+    Nil,
+    NotNil,
+    Unknown,
+}
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn get_entry_value(&self, value: &Expression) -> EntryValue {
+        if self.evaluator.can_return_multiple_values(value) {
+            return EntryValue::Unknown;
+        }
+        match self.evaluator.evaluate(value) {
+            LuaValue::Nil => EntryValue::Nil,
+            LuaValue::Unknown => EntryValue::Unknown,
+            _ => EntryValue::NotNil,
+        }
+    }
+
+    /// Returns whether the table built by the given literal is empty, when it can be
+    /// known. Entries assigned to `nil` do not add anything to the table.
+    fn is_table_empty(&self, table: &TableExpression) -> Option<bool> {
+        if self
+            .evaluator
+            .has_side_effects(&Expression::from(table.clone()))
+        {
+            return None;
+        }
+
+        let mut has_unknown_entry = false;
+
+        for entry in table.iter_entries() {
+            let value = match entry {
+                TableEntry::Field(field) => self.get_entry_value(field.get_value()),
+                TableEntry::Index(index) => {
+                    match self.evaluator.evaluate(index.get_key()) {
+                        LuaValue::Number(number) if number.is_nan() => return None,
+                        // a `nil` key throws an error when the table is built
+                        LuaValue::Nil | LuaValue::Unknown => return None,
+                        _ => {}
+                    }
+                    self.get_entry_value(index.get_value())
+                }
+                TableEntry::Value(value) => self.get_entry_value(value),
+            };
+
+            match value {
+                EntryValue::Nil => {}
+                EntryValue::NotNil => return Some(false),
+                EntryValue::Unknown => has_unknown_entry = true,
+            }
+        }
+
+        if has_unknown_entry {
+            None
+        } else {
+            Some(true)
+        }
+    }
+
+    fn is_nil(&self, expression: &Expression) -> bool {
+        !self.evaluator.has_side_effects(expression)
+            && self.evaluator.evaluate(expression) == LuaValue::Nil
+    }
+
+    /// Returns whether `next(value)` returns `nil`, when the call uses the global
+    /// `next` function on a table literal.
+    fn evaluate_next_call_is_nil(&self, expression: &Expression) -> Option<bool> {
+        let call = match expression {
+            Expression::Call(call) => call,
+            Expression::Parenthese(parenthese) => {
+                return self.evaluate_next_call_is_nil(parenthese.inner_expression())
+            }
+            _ => return None,
+        };
+
+        if !self.is_global_next(call) {
+            return None;
+        }
+
+        match call.get_arguments() {
+            Arguments::Table(table) => self.is_table_empty(table),
+            Arguments::Tuple(tuple) if tuple.len() == 1 => tuple
+                .iter_values()
+                .next()
+                .and_then(get_table_argument)
+                .and_then(|table| self.is_table_empty(table)),
+            Arguments::Tuple(_) | Arguments::String(_) => None,
+        }
+    }
+
+    fn is_global_next(&self, call: &FunctionCall) -> bool {
+        call.get_method().is_none()
+            && matches!(
+                call.get_prefix(),
+                Prefix::Identifier(identifier)
+                    if identifier.get_name() == NEXT_FUNCTION
+                        && !self.is_identifier_used(NEXT_FUNCTION)
+            )
+    }
+
+    fn fold(&self, expression: &Expression) -> Option<bool> {
+        let binary = match expression {
+            Expression::Binary(binary) => binary,
+            _ => return None,
+        };
+
+        let is_equal = match binary.operator() {
+            BinaryOperator::Equal => true,
+            BinaryOperator::NotEqual => false,
+            _ => return None,
+        };
+
+        let is_nil = if self.is_nil(binary.right()) {
+            self.evaluate_next_call_is_nil(binary.left())
+        } else if self.is_nil(binary.left()) {
+            self.evaluate_next_call_is_nil(binary.right())
+        } else {
+            None
+        }?;
+
+        Some(is_nil == is_equal)
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Some(value) = self.fold(expression) {
+            *expression = Expression::from(value);
+        }
+    }
+}
+
+pub const FOLD_NEXT_NIL_CHECK_RULE_NAME: &str = "fold_next_nil_check";
+
+/// A rule that replaces emptiness checks (`next(t) == nil`) on table literals with
+/// their result.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldNextNilCheck {}
+
+impl FlawlessRule for FoldNextNilCheck {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldNextNilCheck {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_NEXT_NIL_CHECK_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldNextNilCheck {
+        FoldNextNilCheck::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_next_nil_check", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_next_nil_check',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,278 @@
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    Arguments, Block, Expression, FunctionCall, LastStatement, LocalAssignStatement, Prefix,
+    ReturnStatement, TableEntry, TableExpression, TupleArguments,
+};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodeProcessor, NodeVisitor, ScopeVisitor,
+};
+use crate::rules::{
+    verify_no_rule_properties, Context, FlawlessRule, RuleConfiguration, RuleConfigurationError,
+    RuleProperties,
+};
+
+const SELECT_FUNCTION: &str = "select";
+
+enum SelectResult {
+    Count(usize),
+    Values(Vec<Expression>),
+}
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn is_select_function(&self, call: &FunctionCall) -> bool {
+        call.get_method().is_none()
+            && matches!(
+                call.get_prefix(),
+                Prefix::Identifier(identifier)
+                    if identifier.get_name() == SELECT_FUNCTION
+                        && !self.is_identifier_used(SELECT_FUNCTION)
+            )
+    }
+
+    fn fold(&self, call: &FunctionCall) -> Option<SelectResult> {
+        if !self.is_select_function(call) {
+            return None;
+        }
+
+        let mut arguments = match call.get_arguments() {
+            Arguments::Tuple(tuple) => tuple.iter_values(),
+            _ => return None,
+        };
+
+        let index = arguments.next()?;
+        let values: Vec<_> = arguments.collect();
+
+        // the number of values is only known when there is no `...` and when the
+        // last value is not a call that can return multiple values
+        if values
+            .iter()
+            .any(|value| matches!(value, Expression::VariableArguments(_)))
+            || matches!(values.last(), Some(Expression::Call(_)))
+            || self.evaluator.has_side_effects(index)
+        {
+            return None;
+        }
+
+        let start = match self.evaluator.evaluate(index) {
+            LuaValue::String(string) if string == "#" => {
+                if values
+                    .iter()
+                    .any(|value| self.evaluator.has_side_effects(value))
+                {
+                    return None;
+                }
+                return Some(SelectResult::Count(values.len()));
+            }
+            LuaValue::Number(number) if number.fract() == 0.0 => {
+                let count = values.len() as f64;
+                if number >= 1.0 {
+                    (number - 1.0).min(count) as usize
+                } else if number < 0.0 && -number <= count {
+                    (count + number) as usize
+                } else {
+                    // `select` throws an error when the index is out of range
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+
+        // the values before the selected ones are evaluated but discarded
+        if values
+            .iter()
+            .take(start)
+            .any(|value| self.evaluator.has_side_effects(value))
+        {
+            return None;
+        }
+
+        Some(SelectResult::Values(
+            values.into_iter().skip(start).cloned().collect(),
+        ))
+    }
+
+    /// Returns the values selected by the last expression of a list, when that
+    /// expression is a call to `select` that can be folded.
+    fn fold_last_values(&self, last: Option<&Expression>) -> Option<Vec<Expression>> {
+        match last? {
+            Expression::Call(call) => match self.fold(call)? {
+                SelectResult::Values(values) => Some(values),
+                SelectResult::Count(_) => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            match self.fold(call) {
+                Some(SelectResult::Count(count)) => {
+                    *expression = Expression::from(count);
+                }
+                // a single value can replace the call in any context
+                Some(SelectResult::Values(mut values)) if values.len() == 1 => {
+                    *expression = values.pop().expect("one value");
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn process_last_statement(&mut self, statement: &mut LastStatement) {
+        if let LastStatement::Return(statement) = statement {
+            if let Some(values) = self.fold_last_values(statement.iter_expressions().last()) {
+                let tokens = statement.get_tokens().cloned();
+
+                let mut expressions: Vec<_> =
+                    mem::take(statement).into_iter_expressions().collect();
+                expressions.pop();
+                expressions.extend(values);
+
+                let length = expressions.len();
+                let mut new_statement = ReturnStatement::new(expressions);
+                if let Some(mut tokens) = tokens {
+                    tokens.commas.truncate(length.saturating_sub(1));
+                    new_statement.set_tokens(tokens);
+                }
+                *statement = new_statement;
+            }
+        }
+    }
+
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        if let Arguments::Tuple(tuple) = call.mutate_arguments() {
+            if let Some(values) = self.fold_last_values(tuple.iter_values().last()) {
+                let tokens = tuple.get_tokens().cloned();
+
+                let mut arguments = mem::take(tuple).to_expressions();
+                arguments.pop();
+                arguments.extend(values);
+
+                let length = arguments.len();
+                let mut new_tuple = arguments.into_iter().collect::<TupleArguments>();
+                if let Some(mut tokens) = tokens {
+                    tokens.commas.truncate(length.saturating_sub(1));
+                    new_tuple.set_tokens(tokens);
+                }
+                *tuple = new_tuple;
+            }
+        }
+    }
+
+    fn process_local_assign_statement(&mut self, assign: &mut LocalAssignStatement) {
+        if let Some(values) = self.fold_last_values(assign.last_value()) {
+            let mut values = values.into_iter();
+
+            match values.next() {
+                Some(first) => {
+                    if let Some(last) = assign.iter_mut_values().last() {
+                        *last = first;
+                    }
+                    for value in values {
+                        assign.push_value(value);
+                    }
+                }
+                None => {
+                    assign.pop_value();
+                }
+            }
+        }
+    }
+
+    fn process_table_expression(&mut self, table: &mut TableExpression) {
+        let last_value = match table.iter_entries().last() {
+            Some(TableEntry::Value(value)) => Some(value),
+            _ => None,
+        };
+
+        if let Some(values) = self.fold_last_values(last_value) {
+            let entries = table.mutate_entries();
+            entries.pop();
+            entries.extend(values.into_iter().map(TableEntry::Value));
+        }
+    }
+}
+
+pub const FOLD_SELECT_RULE_NAME: &str = "fold_select";
+
+/// A rule that replaces calls to `select` with constant arguments by the selected
+/// values or by the number of values.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldSelect {}
+
+impl FlawlessRule for FoldSelect {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldSelect {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_SELECT_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldSelect {
+        FoldSelect::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_select", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_select',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,246 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Arguments, Block, Expression, FunctionCall, Prefix, StringExpression};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+const STRING_LIBRARY: &str = "string";
+const CHAR_FUNCTION: &str = "char";
+const BYTE_FUNCTION: &str = "byte";
+
+/// Converts a string position to a byte index, following how Lua handles negative
+/// positions (counted from the end of the string).
+fn relative_position(position: i64, length: usize) -> i64 {
+    if position >= 0 {
+        position
+    } else if position.unsigned_abs() as usize > length {
+        0
+    } else {
+        length as i64 + position + 1
+    }
+}
+
+/// Returns the byte selected by `string.byte(string, start, end)`, or `None` if the call
+/// does not return exactly one value.
+fn get_single_byte(string: &str, start: i64, end: Option<i64>) -> Option<u8> {
+    let bytes = string.as_bytes();
+    let length = bytes.len();
+
+    let start = relative_position(start, length);
+    let end = end
+        .map(|end| relative_position(end, length))
+        .unwrap_or(start)
+        .min(length as i64);
+    let start = start.max(1);
+
+    if start == end {
+        bytes.get(start as usize - 1).copied()
+    } else {
+        None
+    }
+}
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn is_string_function(&self, prefix: &Prefix, function_name: &str) -> bool {
+        match prefix {
+            Prefix::Field(field) => {
+                field.get_field().get_name() == function_name
+                    && matches!(
+                        field.get_prefix(),
+                        Prefix::Identifier(identifier)
+                            if identifier.get_name() == STRING_LIBRARY
+                                && !self.is_identifier_used(STRING_LIBRARY)
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    fn evaluate_integer(&self, expression: &Expression) -> Option<i64> {
+        if self.evaluator.has_side_effects(expression) {
+            return None;
+        }
+
+        match self.evaluator.evaluate(expression) {
+            LuaValue::Number(value) if value.fract() == 0.0 && value.abs() <= i32::MAX as f64 => {
+                Some(value as i64)
+            }
+            _ => None,
+        }
+    }
+
+    /// Computes `string.char(...)` when all the arguments are bytes that form a
+    /// valid UTF-8 string.
+    fn fold_char(&self, arguments: &[&Expression]) -> Option<String> {
+        let bytes = arguments
+            .iter()
+            .map(|argument| {
+                self.evaluate_integer(argument)
+                    .filter(|value| (0..=255).contains(value))
+                    .map(|value| value as u8)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        String::from_utf8(bytes).ok()
+    }
+
+    /// Computes `string.byte(string, start, end)` when it returns a single value.
+    fn fold_byte(&self, string: &Expression, positions: &[&Expression]) -> Option<u8> {
+        if self.evaluator.has_side_effects(string) {
+            return None;
+        }
+
+        let string = match self.evaluator.evaluate(string) {
+            LuaValue::String(string) => string,
+            _ => return None,
+        };
+
+        match positions {
+            [] => get_single_byte(&string, 1, None),
+            [start] => get_single_byte(&string, self.evaluate_integer(start)?, None),
+            [start, end] => get_single_byte(
+                &string,
+                self.evaluate_integer(start)?,
+                Some(self.evaluate_integer(end)?),
+            ),
+            _ => None,
+        }
+    }
+
+    fn fold(&self, call: &FunctionCall) -> Option<Expression> {
+        let arguments: Vec<_> = match call.get_arguments() {
+            Arguments::Tuple(tuple) => tuple.iter_values().collect(),
+            _ => return None,
+        };
+
+        match call.get_method() {
+            Some(method) => {
+                if method.get_name() != BYTE_FUNCTION {
+                    return None;
+                }
+
+                let string = match call.get_prefix() {
+                    Prefix::Parenthese(parenthese) => parenthese.inner_expression(),
+                    _ => return None,
+                };
+
+                self.fold_byte(string, &arguments)
+                    .map(|byte| Expression::from(byte as usize))
+            }
+            None => {
+                let prefix = call.get_prefix();
+
+                if self.is_string_function(prefix, CHAR_FUNCTION) {
+                    self.fold_char(&arguments)
+                        .map(|string| StringExpression::from_value(string).into())
+                } else if self.is_string_function(prefix, BYTE_FUNCTION) {
+                    let (string, positions) = arguments.split_first()?;
+
+                    self.fold_byte(string, positions)
+                        .map(|byte| Expression::from(byte as usize))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(value) = self.fold(call) {
+                *expression = value;
+            }
+        }
+    }
+}
+
+pub const FOLD_STRING_CHAR_AND_BYTE_RULE_NAME: &str = "fold_string_char_and_byte";
+
+/// A rule that replaces calls to `string.char` and `string.byte` with constant
+/// arguments by their result.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldStringCharAndByte {}
+
+impl FlawlessRule for FoldStringCharAndByte {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldStringCharAndByte {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_STRING_CHAR_AND_BYTE_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldStringCharAndByte {
+        FoldStringCharAndByte::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_string_char_and_byte", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_string_char_and_byte',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
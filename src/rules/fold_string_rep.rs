@@ -0,0 +1,231 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Arguments, Block, Expression, FunctionCall, Prefix, StringExpression};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+const STRING_LIBRARY: &str = "string";
+const REP_FUNCTION: &str = "rep";
+const DEFAULT_MAXIMUM_LENGTH: usize = 256;
+
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+    maximum_length: usize,
+}
+
+impl Processor {
+    fn new(maximum_length: usize) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            evaluator: Evaluator::default(),
+            maximum_length,
+        }
+    }
+
+    /// Returns the string and the count expressions when the call is `string.rep(s, n)`
+    /// or `(s):rep(n)`.
+    fn get_rep_arguments<'a>(
+        &self,
+        call: &'a FunctionCall,
+    ) -> Option<(&'a Expression, &'a Expression)> {
+        let arguments = match call.get_arguments() {
+            Arguments::Tuple(tuple) => tuple,
+            _ => return None,
+        };
+
+        match call.get_method() {
+            Some(method) => {
+                if method.get_name() != REP_FUNCTION || arguments.len() != 1 {
+                    return None;
+                }
+
+                let string = match call.get_prefix() {
+                    Prefix::Parenthese(parenthese) => parenthese.inner_expression(),
+                    _ => return None,
+                };
+
+                Some((string, arguments.iter_values().next()?))
+            }
+            None => {
+                if arguments.len() != 2 || !self.is_string_rep_function(call.get_prefix()) {
+                    return None;
+                }
+
+                let mut values = arguments.iter_values();
+                Some((values.next()?, values.next()?))
+            }
+        }
+    }
+
+    fn is_string_rep_function(&self, prefix: &Prefix) -> bool {
+        match prefix {
+            Prefix::Field(field) => {
+                field.get_field().get_name() == REP_FUNCTION
+                    && matches!(
+                        field.get_prefix(),
+                        Prefix::Identifier(identifier)
+                            if identifier.get_name() == STRING_LIBRARY
+                                && !self.is_identifier_used(STRING_LIBRARY)
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    fn fold(&self, call: &FunctionCall) -> Option<String> {
+        let (string, count) = self.get_rep_arguments(call)?;
+
+        if self.evaluator.has_side_effects(string) || self.evaluator.has_side_effects(count) {
+            return None;
+        }
+
+        let string = match self.evaluator.evaluate(string) {
+            LuaValue::String(string) => string,
+            _ => return None,
+        };
+
+        let count = match self.evaluator.evaluate(count) {
+            LuaValue::Number(count) if count >= 0.0 && count.fract() == 0.0 => count,
+            _ => return None,
+        };
+
+        if count * (string.len() as f64) > self.maximum_length as f64 {
+            return None;
+        }
+
+        Some(string.repeat(count as usize))
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(value) = self.fold(call) {
+                *expression = StringExpression::from_value(value).into();
+            }
+        }
+    }
+}
+
+pub const FOLD_STRING_REP_RULE_NAME: &str = "fold_string_rep";
+
+/// A rule that replaces calls to `string.rep` with constant arguments by the
+/// resulting string.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FoldStringRep {
+    maximum_length: usize,
+}
+
+impl Default for FoldStringRep {
+    fn default() -> Self {
+        Self {
+            maximum_length: DEFAULT_MAXIMUM_LENGTH,
+        }
+    }
+}
+
+impl FlawlessRule for FoldStringRep {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::new(self.maximum_length);
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldStringRep {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "maximum_length" => {
+                    self.maximum_length = value.expect_usize(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_STRING_REP_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.maximum_length != DEFAULT_MAXIMUM_LENGTH {
+            properties.insert(
+                "maximum_length".to_owned(),
+                RulePropertyValue::Usize(self.maximum_length),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("maximum_length", RulePropertyType::Usize)
+                .with_default(DEFAULT_MAXIMUM_LENGTH),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldStringRep {
+        FoldStringRep::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_string_rep", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_maximum_length() {
+        let rule: Box<dyn Rule> = Box::new(FoldStringRep { maximum_length: 10 });
+
+        assert_json_snapshot!("fold_string_rep_with_maximum_length", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_string_rep',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,203 @@
+use crate::nodes::{
+    Block, Expression, FieldExpression, IndexExpression, Prefix, TableEntry, TableExpression,
+};
+use crate::process::{
+    DefaultPostVisitor, Evaluator, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+#[derive(Debug, Clone, Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn fold_field(&self, field: &FieldExpression) -> Option<Expression> {
+        let key = LuaValue::String(field.get_field().get_name().to_owned());
+        self.fold(field.get_prefix(), key)
+    }
+
+    fn fold_index(&self, index: &IndexExpression) -> Option<Expression> {
+        if self.evaluator.has_side_effects(index.get_index()) {
+            return None;
+        }
+        let key = self.evaluator.evaluate(index.get_index());
+        self.fold(index.get_prefix(), key)
+    }
+
+    fn fold(&self, prefix: &Prefix, key: LuaValue) -> Option<Expression> {
+        let table = match prefix {
+            Prefix::Parenthese(parenthese) => match parenthese.inner_expression() {
+                Expression::Table(table) => table,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        if !is_valid_key(&key) || self.has_side_effects(table) {
+            return None;
+        }
+
+        self.find_value(table, &key).cloned()
+    }
+
+    fn has_side_effects(&self, table: &TableExpression) -> bool {
+        table.iter_entries().any(|entry| match entry {
+            TableEntry::Field(field) => self.evaluator.has_side_effects(field.get_value()),
+            TableEntry::Index(index) => {
+                self.evaluator.has_side_effects(index.get_key())
+                    || self.evaluator.has_side_effects(index.get_value())
+            }
+            TableEntry::Value(value) => self.evaluator.has_side_effects(value),
+        })
+    }
+
+    /// Returns the value associated with the given key, if it is the only entry
+    /// that can be associated with that key.
+    fn find_value<'a>(&self, table: &'a TableExpression, key: &LuaValue) -> Option<&'a Expression> {
+        let mut found = None;
+        let mut array_index = 0;
+        let last_index = table.len().saturating_sub(1);
+
+        for (index, entry) in table.iter_entries().enumerate() {
+            let (entry_key, value) = match entry {
+                TableEntry::Field(field) => (
+                    LuaValue::String(field.get_field().get_name().to_owned()),
+                    field.get_value(),
+                ),
+                TableEntry::Index(index_entry) => {
+                    let entry_key = self.evaluator.evaluate(index_entry.get_key());
+                    if !is_valid_key(&entry_key) {
+                        return None;
+                    }
+                    (entry_key, index_entry.get_value())
+                }
+                TableEntry::Value(value) => {
+                    // the last value of a table expands to all the values returned
+                    // by a call or `...`, which can define any array index
+                    if index == last_index && self.evaluator.can_return_multiple_values(value) {
+                        if matches!(key, LuaValue::Number(_)) {
+                            return None;
+                        }
+                        continue;
+                    }
+                    array_index += 1;
+                    (LuaValue::Number(array_index as f64), value)
+                }
+            };
+
+            if entry_key == *key {
+                if found.is_some() {
+                    return None;
+                }
+                found = Some(value);
+            }
+        }
+
+        found
+    }
+}
+
+fn is_valid_key(key: &LuaValue) -> bool {
+    match key {
+        LuaValue::String(_) | LuaValue::True | LuaValue::False => true,
+        LuaValue::Number(value) => !value.is_nan(),
+        LuaValue::Function | LuaValue::Nil | LuaValue::Table | LuaValue::Unknown => false,
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        let value = match expression {
+            Expression::Field(field) => self.fold_field(field),
+            Expression::Index(index) => self.fold_index(index),
+            _ => None,
+        };
+
+        if let Some(value) = value {
+            *expression = if self.evaluator.can_return_multiple_values(&value) {
+                value.in_parentheses()
+            } else {
+                value
+            };
+        }
+    }
+
+    fn process_after_prefix_expression(&mut self, prefix: &mut Prefix) {
+        let value = match prefix {
+            Prefix::Field(field) => self.fold_field(field),
+            Prefix::Index(index) => self.fold_index(index),
+            Prefix::Call(_) | Prefix::Identifier(_) | Prefix::Parenthese(_) => None,
+        };
+
+        if let Some(value) = value {
+            *prefix = Prefix::from(value);
+        }
+    }
+}
+
+pub const FOLD_TABLE_ACCESS_RULE_NAME: &str = "fold_table_access";
+
+/// A rule that replaces field and index accesses on constant table literals
+/// with the accessed value.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldTableAccess {}
+
+impl FlawlessRule for FoldTableAccess {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        DefaultPostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldTableAccess {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_TABLE_ACCESS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldTableAccess {
+        FoldTableAccess::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_table_access", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_table_access',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
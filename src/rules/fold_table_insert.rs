@@ -0,0 +1,282 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    Arguments, Block, Expression, LocalAssignStatement, Prefix, Statement, TableEntry,
+    TableExpression,
+};
+use crate::process::processors::FindVariables;
+use crate::process::{
+    DefaultVisitor, Evaluator, IdentifierTracker, LuaValue, NodeProcessor, NodeVisitor,
+    ScopeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+const TABLE_LIBRARY_NAME: &str = "table";
+const INSERT_FUNCTION_NAME: &str = "insert";
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl Processor {
+    fn is_known_non_nil(&self, expression: &Expression) -> bool {
+        !matches!(
+            self.evaluator.evaluate(expression),
+            LuaValue::Nil | LuaValue::Unknown
+        )
+    }
+
+    /// Returns the name of the local variable when the statement declares a single
+    /// variable assigned to a table where the length of the array part is known.
+    fn get_foldable_table<'a>(&self, statement: &'a Statement) -> Option<&'a str> {
+        let assign = match statement {
+            Statement::LocalAssign(assign) => assign,
+            _ => return None,
+        };
+
+        if assign.variables_len() != 1 || assign.values_len() != 1 {
+            return None;
+        }
+
+        match assign.last_value() {
+            Some(Expression::Table(table))
+                if table.iter_entries().all(|entry| match entry {
+                    TableEntry::Field(_) => true,
+                    TableEntry::Index(_) => false,
+                    TableEntry::Value(value) => self.is_known_non_nil(value),
+                }) =>
+            {
+                assign
+                    .iter_variables()
+                    .next()
+                    .map(|variable| variable.get_name().as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the inserted value if the statement is `table.insert(table_name, value)`
+    /// and if the value does not reference the table.
+    fn get_inserted_value(&self, statement: &Statement, table_name: &str) -> Option<Expression> {
+        let call = match statement {
+            Statement::Call(call) if call.get_method().is_none() => call,
+            _ => return None,
+        };
+
+        match call.get_prefix() {
+            Prefix::Field(field) if field.get_field().get_name() == INSERT_FUNCTION_NAME => {
+                if !matches!(
+                    field.get_prefix(),
+                    Prefix::Identifier(identifier) if identifier.get_name() == TABLE_LIBRARY_NAME
+                ) {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+
+        let mut values = match call.get_arguments() {
+            Arguments::Tuple(tuple) if tuple.len() == 2 => tuple.iter_values(),
+            _ => return None,
+        };
+
+        match values.next() {
+            Some(Expression::Identifier(identifier)) if identifier.get_name() == table_name => {}
+            _ => return None,
+        }
+
+        let mut value = values.next()?.clone();
+
+        let mut find_table = FindVariables::new(table_name);
+        DefaultVisitor::visit_expression(&mut value, &mut find_table);
+
+        if find_table.has_found_usage() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    fn collect_inserted_values(
+        &self,
+        block: &Block,
+        index: usize,
+        table_name: &str,
+    ) -> Vec<Expression> {
+        let mut values = Vec::new();
+
+        for statement in block.iter_statements().skip(index + 1) {
+            if let Some(value) = self.get_inserted_value(statement, table_name) {
+                let is_known_non_nil = self.is_known_non_nil(&value);
+
+                values.push(value);
+
+                // inserting `nil` does not grow the array part, so the position of
+                // the following values would not match the table constructor
+                if !is_known_non_nil {
+                    break;
+                }
+            } else {
+                break;
+            }
+        }
+
+        values
+    }
+
+    fn declares_table_library(&self, statement: &Statement) -> bool {
+        match statement {
+            Statement::LocalAssign(assign) => assign
+                .iter_variables()
+                .any(|variable| variable.get_name() == TABLE_LIBRARY_NAME),
+            Statement::LocalFunction(function) => function.get_name() == TABLE_LIBRARY_NAME,
+            _ => false,
+        }
+    }
+}
+
+fn can_return_multiple_values(expression: &Expression) -> bool {
+    match expression {
+        Expression::Call(_) | Expression::VariableArguments(_) => true,
+        Expression::TypeCast(type_cast) => can_return_multiple_values(type_cast.get_expression()),
+        _ => false,
+    }
+}
+
+fn append_values(assign: &mut LocalAssignStatement, values: Vec<Expression>) {
+    if let Some(Expression::Table(table)) = assign.iter_mut_values().next() {
+        append_table_values(table, values);
+    }
+}
+
+fn append_table_values(table: &mut TableExpression, values: Vec<Expression>) {
+    let entries = table.mutate_entries();
+
+    for value in values {
+        let value = if can_return_multiple_values(&value) {
+            value.in_parentheses()
+        } else {
+            value
+        };
+        entries.push(TableEntry::Value(value));
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_block(&mut self, block: &mut Block) {
+        if self.is_identifier_used(TABLE_LIBRARY_NAME) {
+            return;
+        }
+
+        let mut index = 0;
+
+        while index < block.statements_len() {
+            let statement = block
+                .iter_statements()
+                .nth(index)
+                .expect("statement index should be in bounds");
+
+            if self.declares_table_library(statement) {
+                return;
+            }
+
+            let values = self
+                .get_foldable_table(statement)
+                .map(|table_name| self.collect_inserted_values(block, index, table_name))
+                .unwrap_or_default();
+
+            if !values.is_empty() {
+                for _ in 0..values.len() {
+                    block.remove_statement(index + 1);
+                }
+
+                if let Some(Statement::LocalAssign(assign)) = block.iter_mut_statements().nth(index)
+                {
+                    append_values(assign, values);
+                }
+            }
+
+            index += 1;
+        }
+    }
+}
+
+pub const FOLD_TABLE_INSERT_RULE_NAME: &str = "fold_table_insert";
+
+/// A rule that moves `table.insert` calls following a table constructor into the
+/// constructor.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldTableInsert {}
+
+impl FlawlessRule for FoldTableInsert {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldTableInsert {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_TABLE_INSERT_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldTableInsert {
+        FoldTableInsert::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_table_insert", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_table_insert',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,225 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Block, Expression, FunctionCall, Prefix, StringExpression};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+const TOSTRING_FUNCTION: &str = "tostring";
+const TONUMBER_FUNCTION: &str = "tonumber";
+
+/// Integers below this limit are converted to strings the same way by every Lua
+/// version (Lua formats numbers with 14 significant digits).
+const MAX_STRING_INTEGER: f64 = 1e14;
+
+/// Returns true if the string cannot be converted by `tonumber` in any Lua version.
+/// Strings that only contain characters found in decimal, hexadecimal or special
+/// numbers (like `inf` or `nan`) are not considered, since some Lua versions may
+/// convert them differently.
+fn is_never_a_number(string: &str) -> bool {
+    string.trim().is_empty()
+        || string.chars().any(|character| {
+            !(character.is_ascii_hexdigit()
+                || character.is_ascii_whitespace()
+                || matches!(
+                    character,
+                    'x' | 'X'
+                        | 'p'
+                        | 'P'
+                        | 'n'
+                        | 'N'
+                        | 'i'
+                        | 'I'
+                        | 't'
+                        | 'T'
+                        | 'y'
+                        | 'Y'
+                        | '+'
+                        | '-'
+                        | '.'
+                        | '_'
+                        | '\u{B}'
+                ))
+        })
+}
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn is_global_function(&self, prefix: &Prefix, function_name: &str) -> bool {
+        matches!(
+            prefix,
+            Prefix::Identifier(identifier)
+                if identifier.get_name() == function_name
+                    && !self.is_identifier_used(function_name)
+        )
+    }
+
+    fn evaluate_constant(&self, expression: &Expression) -> LuaValue {
+        if self.evaluator.has_side_effects(expression) {
+            LuaValue::Unknown
+        } else {
+            self.evaluator.evaluate(expression)
+        }
+    }
+
+    /// Computes `tostring(value)` for values that are converted the same way by
+    /// every Lua version.
+    fn fold_tostring(&self, value: &Expression) -> Option<Expression> {
+        let string = match self.evaluate_constant(value) {
+            LuaValue::True => "true".to_owned(),
+            LuaValue::False => "false".to_owned(),
+            LuaValue::Nil => "nil".to_owned(),
+            LuaValue::String(string) => string,
+            LuaValue::Number(number)
+                if number.fract() == 0.0
+                    && number.abs() < MAX_STRING_INTEGER
+                    && !(number == 0.0 && number.is_sign_negative()) =>
+            {
+                match LuaValue::Number(number).string_coercion() {
+                    LuaValue::String(string) => string,
+                    _ => return None,
+                }
+            }
+            LuaValue::Number(_) | LuaValue::Function | LuaValue::Table | LuaValue::Unknown => {
+                return None
+            }
+        };
+
+        Some(StringExpression::from_value(string).into())
+    }
+
+    /// Computes `tonumber(value)` when the result does not depend on the Lua version.
+    fn fold_tonumber(&self, value: &Expression) -> Option<Expression> {
+        match self.evaluate_constant(value) {
+            LuaValue::Number(number) => Some(Expression::from(number)),
+            LuaValue::String(string) => match LuaValue::String(string).number_coercion() {
+                LuaValue::Number(number) => Some(Expression::from(number)),
+                LuaValue::String(string) if is_never_a_number(&string) => Some(Expression::nil()),
+                _ => None,
+            },
+            LuaValue::True | LuaValue::False | LuaValue::Nil => Some(Expression::nil()),
+            LuaValue::Function | LuaValue::Table | LuaValue::Unknown => None,
+        }
+    }
+
+    fn fold(&self, call: &FunctionCall) -> Option<Expression> {
+        if call.get_method().is_some() {
+            return None;
+        }
+
+        let arguments = call.get_arguments().clone().to_expressions();
+
+        // calls with a base argument for `tonumber` are not converted
+        let value = match arguments.as_slice() {
+            [value] => value,
+            _ => return None,
+        };
+
+        let prefix = call.get_prefix();
+
+        if self.is_global_function(prefix, TOSTRING_FUNCTION) {
+            self.fold_tostring(value)
+        } else if self.is_global_function(prefix, TONUMBER_FUNCTION) {
+            self.fold_tonumber(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(value) = self.fold(call) {
+                *expression = value;
+            }
+        }
+    }
+}
+
+pub const FOLD_TOSTRING_AND_TONUMBER_RULE_NAME: &str = "fold_tostring_and_tonumber";
+
+/// A rule that replaces calls to `tostring` and `tonumber` with a constant argument
+/// by their result.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldTostringAndTonumber {}
+
+impl FlawlessRule for FoldTostringAndTonumber {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldTostringAndTonumber {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_TOSTRING_AND_TONUMBER_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldTostringAndTonumber {
+        FoldTostringAndTonumber::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_tostring_and_tonumber", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_tostring_and_tonumber',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
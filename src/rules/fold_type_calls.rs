@@ -0,0 +1,149 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Block, Expression, FunctionCall, Prefix, StringExpression};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+const TYPE_FUNCTION: &str = "type";
+
+/// Returns the string that `type` returns for the value, or `None` if the value is unknown.
+fn get_type_name(value: LuaValue) -> Option<&'static str> {
+    match value {
+        LuaValue::True | LuaValue::False => Some("boolean"),
+        LuaValue::Function => Some("function"),
+        LuaValue::Nil => Some("nil"),
+        LuaValue::Number(_) => Some("number"),
+        LuaValue::String(_) => Some("string"),
+        LuaValue::Table => Some("table"),
+        LuaValue::Unknown => None,
+    }
+}
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn fold(&self, call: &FunctionCall) -> Option<Expression> {
+        if call.get_method().is_some() {
+            return None;
+        }
+
+        match call.get_prefix() {
+            Prefix::Identifier(identifier)
+                if identifier.get_name() == TYPE_FUNCTION
+                    && !self.is_identifier_used(TYPE_FUNCTION) => {}
+            _ => return None,
+        }
+
+        let arguments = call.get_arguments().clone().to_expressions();
+
+        // `type` throws an error when called without arguments
+        let value = match arguments.as_slice() {
+            [value] => value,
+            _ => return None,
+        };
+
+        if self.evaluator.has_side_effects(value) {
+            return None;
+        }
+
+        get_type_name(self.evaluator.evaluate(value))
+            .map(|type_name| StringExpression::from_value(type_name).into())
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(value) = self.fold(call) {
+                *expression = value;
+            }
+        }
+    }
+}
+
+pub const FOLD_TYPE_CALLS_RULE_NAME: &str = "fold_type_calls";
+
+/// A rule that replaces calls to `type` with a constant argument by the name of the
+/// type of the argument.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FoldTypeCalls {}
+
+impl FlawlessRule for FoldTypeCalls {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for FoldTypeCalls {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        FOLD_TYPE_CALLS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> FoldTypeCalls {
+        FoldTypeCalls::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_fold_type_calls", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'fold_type_calls',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use crate::generator::{DenseLuaGenerator, LuaGenerator};
+use crate::nodes::{
+    BinaryOperator, Block, Expression, Identifier, LastStatement, LocalAssignStatement, Prefix,
+    Statement,
+};
+use crate::process::utils::IdentifierGenerator;
+use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+fn get_key(expression: &Expression) -> String {
+    let mut generator = DenseLuaGenerator::default();
+    generator.write_expression(expression);
+    generator.into_string()
+}
+
+/// Returns the expression of field and index prefixes.
+fn get_prefix_expression(prefix: &Prefix) -> Option<Expression> {
+    match prefix {
+        Prefix::Field(field) => Some(Expression::from(field.as_ref().clone())),
+        Prefix::Index(index) => Some(Expression::from(index.as_ref().clone())),
+        Prefix::Call(_) | Prefix::Identifier(_) | Prefix::Parenthese(_) => None,
+    }
+}
+
+/// Collects the field and index expressions of a statement. The statement can not
+/// be processed if it contains anything that is not always evaluated exactly once
+/// (like the right side of `and` or the body of a function) or a function call that
+/// could change the collected values.
+struct ExpressionCollector {
+    evaluator: Evaluator,
+    expressions: Vec<(String, Expression)>,
+    can_hoist: bool,
+}
+
+impl Default for ExpressionCollector {
+    fn default() -> Self {
+        Self {
+            evaluator: Evaluator::default().assume_pure_metamethods(),
+            expressions: Vec::new(),
+            can_hoist: true,
+        }
+    }
+}
+
+impl ExpressionCollector {
+    fn push(&mut self, expression: Expression) {
+        if self.evaluator.has_side_effects(&expression) {
+            self.can_hoist = false;
+        } else {
+            self.expressions.push((get_key(&expression), expression));
+        }
+    }
+
+    /// Returns the largest expression that appears more than once.
+    fn into_repeated_expression(self) -> Option<(String, Expression)> {
+        if !self.can_hoist {
+            return None;
+        }
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (key, _) in self.expressions.iter() {
+            *counts.entry(key.as_str()).or_default() += 1;
+        }
+
+        let mut repeated: Option<&(String, Expression)> = None;
+        for entry in self.expressions.iter() {
+            if counts[entry.0.as_str()] < 2 {
+                continue;
+            }
+            match repeated {
+                Some(current) if current.0.len() >= entry.0.len() => {}
+                _ => repeated = Some(entry),
+            }
+        }
+
+        repeated.cloned()
+    }
+}
+
+impl NodeProcessor for ExpressionCollector {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::Call(_) | Expression::Function(_) | Expression::If(_) => {
+                self.can_hoist = false;
+            }
+            Expression::Binary(binary)
+                if matches!(binary.operator(), BinaryOperator::And | BinaryOperator::Or) =>
+            {
+                self.can_hoist = false;
+            }
+            Expression::Field(_) | Expression::Index(_) => self.push(expression.clone()),
+            _ => {}
+        }
+    }
+
+    fn process_prefix_expression(&mut self, prefix: &mut Prefix) {
+        if let Prefix::Call(_) = prefix {
+            self.can_hoist = false;
+        } else if let Some(expression) = get_prefix_expression(prefix) {
+            self.push(expression);
+        }
+    }
+}
+
+/// Replaces every field or index expression matching the given key with an identifier.
+struct ReplaceExpression<'a> {
+    key: &'a str,
+    identifier: &'a str,
+}
+
+impl NodeProcessor for ReplaceExpression<'_> {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if matches!(expression, Expression::Field(_) | Expression::Index(_))
+            && get_key(expression) == self.key
+        {
+            *expression = Identifier::new(self.identifier).into();
+        }
+    }
+
+    fn process_prefix_expression(&mut self, prefix: &mut Prefix) {
+        if get_prefix_expression(prefix).is_some_and(|expression| get_key(&expression) == self.key)
+        {
+            *prefix = Identifier::new(self.identifier).into();
+        }
+    }
+}
+
+/// The statements where repeated expressions can be hoisted.
+enum HoistTarget<'a> {
+    Statement(&'a mut Statement),
+    LastStatement(&'a mut LastStatement),
+}
+
+impl HoistTarget<'_> {
+    fn is_supported(&self) -> bool {
+        match self {
+            Self::Statement(statement) => matches!(
+                statement,
+                Statement::Assign(_)
+                    | Statement::Call(_)
+                    | Statement::CompoundAssign(_)
+                    | Statement::LocalAssign(_)
+            ),
+            Self::LastStatement(last_statement) => {
+                matches!(last_statement, LastStatement::Return(_))
+            }
+        }
+    }
+
+    fn visit<T: NodeProcessor>(&mut self, processor: &mut T) {
+        match self {
+            Self::Statement(statement) => DefaultVisitor::visit_statement(statement, processor),
+            Self::LastStatement(last_statement) => {
+                DefaultVisitor::visit_last_statement(last_statement, processor)
+            }
+        }
+    }
+}
+
+struct Processor {
+    identifier_generator: IdentifierGenerator,
+    identifier_count: usize,
+}
+
+impl Processor {
+    fn new(identifier_generator: IdentifierGenerator) -> Self {
+        Self {
+            identifier_generator,
+            identifier_count: 0,
+        }
+    }
+
+    fn generate_identifier(&mut self) -> String {
+        self.identifier_count += 1;
+        self.identifier_generator
+            .generate(&format!("SHARED_{}", self.identifier_count))
+    }
+
+    /// Replaces the repeated expressions of a statement with new local variables, and
+    /// returns the local assignments to insert before the statement.
+    fn hoist(&mut self, mut target: HoistTarget) -> Vec<Statement> {
+        let mut hoisted = Vec::new();
+
+        if !target.is_supported() {
+            return hoisted;
+        }
+
+        loop {
+            let mut collector = ExpressionCollector::default();
+            target.visit(&mut collector);
+
+            let (key, expression) = match collector.into_repeated_expression() {
+                Some(repeated) => repeated,
+                None => break,
+            };
+
+            let identifier = self.generate_identifier();
+            target.visit(&mut ReplaceExpression {
+                key: &key,
+                identifier: &identifier,
+            });
+
+            hoisted.push(
+                LocalAssignStatement::from_variable(identifier)
+                    .with_value(expression)
+                    .into(),
+            );
+        }
+
+        hoisted
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_block(&mut self, block: &mut Block) {
+        let mut index = 0;
+
+        while index < block.statements_len() {
+            let hoisted = match block.iter_mut_statements().nth(index) {
+                Some(statement) => self.hoist(HoistTarget::Statement(statement)),
+                None => break,
+            };
+
+            for statement in hoisted {
+                block.insert_statement(index, statement);
+                index += 1;
+            }
+            index += 1;
+        }
+
+        if let Some(last_statement) = block.mutate_last_statement() {
+            for statement in self.hoist(HoistTarget::LastStatement(last_statement)) {
+                block.push_statement(statement);
+            }
+        }
+    }
+}
+
+pub const HOIST_COMMON_SUBEXPRESSIONS_RULE_NAME: &str = "hoist_common_subexpressions";
+
+/// A rule that stores field and index expressions repeated within a statement in a
+/// local variable assigned before the statement.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct HoistCommonSubexpressions {}
+
+impl FlawlessRule for HoistCommonSubexpressions {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = Processor::new(context.identifier_generator(block));
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for HoistCommonSubexpressions {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        HOIST_COMMON_SUBEXPRESSIONS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> HoistCommonSubexpressions {
+        HoistCommonSubexpressions::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_hoist_common_subexpressions", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'hoist_common_subexpressions',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
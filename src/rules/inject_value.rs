@@ -4,7 +4,7 @@ use crate::nodes::{
 use crate::process::{IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
-    RulePropertyValue,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
 };
 
 use std::{env, ops};
@@ -235,6 +235,14 @@ impl RuleConfiguration for InjectGlobalValue {
 
         rules
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("identifier", RulePropertyType::String).required(),
+            RulePropertyDescription::new("value", RulePropertyType::LuaValue),
+            RulePropertyDescription::new("env", RulePropertyType::String),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+use crate::nodes::{
+    Block, Expression, FieldExpression, Identifier, LastStatement, Prefix, Statement, TableEntry,
+};
+use crate::process::{
+    Evaluator, IdentifierTracker, NodePostProcessor, NodePostVisitor, NodeProcessor, NodeVisitor,
+    ScopePostVisitor, ScopeVisitor,
+};
+use crate::rules::require::{is_require_call, PathRequireMode};
+use crate::rules::{
+    Context, RequireMode, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult,
+    RuleProperties, RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+use crate::Parser;
+
+type Constants = HashMap<String, Expression>;
+
+/// Returns the constant fields of a module when its only statement is a return of a
+/// table where every entry is a field with a constant value.
+fn get_module_constants(module: &Block) -> Option<Constants> {
+    if module.statements_len() != 0 {
+        return None;
+    }
+
+    let table = match module.get_last_statement() {
+        Some(LastStatement::Return(statement)) if statement.len() == 1 => {
+            match statement.iter_expressions().next()? {
+                Expression::Table(table) => table,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    let evaluator = Evaluator::default();
+    let mut constants = Constants::new();
+
+    for entry in table.iter_entries() {
+        let field = match entry {
+            TableEntry::Field(field) => field,
+            TableEntry::Index(_) | TableEntry::Value(_) => return None,
+        };
+
+        if evaluator.has_side_effects(field.get_value()) {
+            return None;
+        }
+
+        let value = evaluator.evaluate(field.get_value()).to_expression()?;
+        constants.insert(field.get_field().get_name().to_owned(), value);
+    }
+
+    Some(constants)
+}
+
+/// Counts the references to a variable and the references that are reading one
+/// of the constants of the module. When replacing, these field accesses are
+/// replaced with the constant value.
+struct ConstantFieldProcessor<'a> {
+    identifier_tracker: IdentifierTracker,
+    variable: &'a str,
+    constants: &'a Constants,
+    replace: bool,
+    references: usize,
+    constant_references: usize,
+}
+
+impl<'a> ConstantFieldProcessor<'a> {
+    fn new(variable: &'a str, constants: &'a Constants, replace: bool) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            variable,
+            constants,
+            replace,
+            references: 0,
+            constant_references: 0,
+        }
+    }
+
+    fn get_constant(&self, field: &FieldExpression) -> Option<&'a Expression> {
+        match field.get_prefix() {
+            Prefix::Identifier(identifier)
+                if identifier.get_name() == self.variable
+                    && !self.is_identifier_used(self.variable) =>
+            {
+                self.constants.get(field.get_field().get_name())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Deref for ConstantFieldProcessor<'_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for ConstantFieldProcessor<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for ConstantFieldProcessor<'_> {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Field(field) = expression {
+            if let Some(constant) = self.get_constant(field) {
+                self.constant_references += 1;
+                if self.replace {
+                    *expression = constant.clone();
+                }
+            }
+        }
+    }
+
+    fn process_prefix_expression(&mut self, prefix: &mut Prefix) {
+        if let Prefix::Field(field) = prefix {
+            if let Some(constant) = self.get_constant(field) {
+                self.constant_references += 1;
+                if self.replace {
+                    *prefix = constant.clone().into();
+                }
+            }
+        }
+    }
+
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        if identifier.get_name() == self.variable && !self.is_identifier_used(self.variable) {
+            self.references += 1;
+        }
+    }
+}
+
+/// Visits the statements following the given index (and the last statement) of the
+/// block, which are the statements where the local variable is in scope.
+fn visit_following_statements(
+    block: &mut Block,
+    index: usize,
+    processor: &mut ConstantFieldProcessor,
+) {
+    for statement in block.iter_mut_statements().skip(index + 1) {
+        ScopeVisitor::visit_statement(statement, processor);
+    }
+    if let Some(last_statement) = block.mutate_last_statement() {
+        ScopeVisitor::visit_last_statement(last_statement, processor);
+    }
+}
+
+struct ConstantRequire {
+    index: usize,
+    variable: String,
+    constants: Constants,
+}
+
+struct Processor<'a, 'b, 'resources, 'code> {
+    identifier_tracker: IdentifierTracker,
+    require_mode: &'a PathRequireMode,
+    context: &'a Context<'b, 'resources, 'code>,
+    // for each block being visited, the index of the current statement and the
+    // local variables assigned to a constant module
+    blocks: Vec<(usize, Vec<ConstantRequire>)>,
+}
+
+impl<'a, 'b, 'resources, 'code> Processor<'a, 'b, 'resources, 'code> {
+    fn new(require_mode: &'a PathRequireMode, context: &'a Context<'b, 'resources, 'code>) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            require_mode,
+            context,
+            blocks: Vec::new(),
+        }
+    }
+
+    fn get_constant_require(&self, statement: &Statement) -> Option<(String, Constants)> {
+        let assign = match statement {
+            Statement::LocalAssign(assign)
+                if assign.variables_len() == 1 && assign.values_len() == 1 =>
+            {
+                assign
+            }
+            _ => return None,
+        };
+
+        let call = match assign.iter_values().next()? {
+            Expression::Call(call) if is_require_call(call, self) => call,
+            _ => return None,
+        };
+
+        let path = match self.require_mode.find_require(call, self.context) {
+            Ok(path) => path?,
+            Err(err) => {
+                log::trace!("unable to find required module: {}", err);
+                return None;
+            }
+        };
+
+        let constants = self.read_module_constants(&path)?;
+
+        let variable = assign.iter_variables().next()?.get_name().to_owned();
+
+        Some((variable, constants))
+    }
+
+    fn read_module_constants(&self, path: &Path) -> Option<Constants> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("lua") | Some("luau") => {}
+            _ => return None,
+        }
+
+        let content = self.context.resources().get(path).ok()?;
+        self.context.add_file_dependency(path.to_path_buf());
+
+        let module = Parser::default().parse(&content).ok()?;
+
+        get_module_constants(&module)
+    }
+}
+
+impl Deref for Processor<'_, '_, '_, '_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor<'_, '_, '_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor<'_, '_, '_, '_> {
+    fn process_block(&mut self, _: &mut Block) {
+        self.blocks.push((0, Vec::new()));
+    }
+
+    fn process_statement(&mut self, statement: &mut Statement) {
+        let constant_require = self.get_constant_require(statement);
+
+        if let Some((index, constant_requires)) = self.blocks.last_mut() {
+            if let Some((variable, constants)) = constant_require {
+                constant_requires.push(ConstantRequire {
+                    index: *index,
+                    variable,
+                    constants,
+                });
+            }
+            *index += 1;
+        }
+    }
+}
+
+impl NodePostProcessor for Processor<'_, '_, '_, '_> {
+    fn process_after_block(&mut self, block: &mut Block) {
+        let (_, constant_requires) = self.blocks.pop().unwrap_or_default();
+
+        let mut removed_statements = vec![false; block.statements_len()];
+
+        for constant_require in constant_requires.iter().rev() {
+            let mut usage = ConstantFieldProcessor::new(
+                &constant_require.variable,
+                &constant_require.constants,
+                false,
+            );
+            visit_following_statements(block, constant_require.index, &mut usage);
+
+            // the variable escapes when it is used in other ways than reading a constant
+            if usage.references != usage.constant_references {
+                continue;
+            }
+
+            let mut replace = ConstantFieldProcessor::new(
+                &constant_require.variable,
+                &constant_require.constants,
+                true,
+            );
+            visit_following_statements(block, constant_require.index, &mut replace);
+
+            removed_statements[constant_require.index] = true;
+        }
+
+        if removed_statements.contains(&true) {
+            let mut removed_statements = removed_statements.into_iter();
+            block.filter_statements(|_| !removed_statements.next().unwrap_or_default());
+        }
+    }
+}
+
+pub const INLINE_CONSTANT_REQUIRE_RULE_NAME: &str = "inline_constant_require";
+
+/// A rule that replaces fields of required modules that only return a table of
+/// constants with the constant values.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InlineConstantRequire {
+    require_mode: PathRequireMode,
+}
+
+impl Rule for InlineConstantRequire {
+    fn process(&self, block: &mut Block, context: &Context) -> RuleProcessResult {
+        let mut require_mode = self.require_mode.clone();
+        require_mode
+            .initialize(context)
+            .map_err(|err| err.to_string())?;
+
+        let mut processor = Processor::new(&require_mode, context);
+        ScopePostVisitor::visit_block(block, &mut processor);
+
+        Ok(())
+    }
+}
+
+impl RuleConfiguration for InlineConstantRequire {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "require_mode" => match value.expect_require_mode(&key)? {
+                    RequireMode::Path(require_mode) => {
+                        self.require_mode = require_mode;
+                    }
+                    RequireMode::Roblox(_) => {
+                        return Err(RuleConfigurationError::UnexpectedValue {
+                            property: key,
+                            message: "only the path require mode is supported".to_owned(),
+                        })
+                    }
+                },
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        INLINE_CONSTANT_REQUIRE_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.require_mode != PathRequireMode::default() {
+            properties.insert(
+                "require_mode".to_owned(),
+                RulePropertyValue::RequireMode(RequireMode::Path(self.require_mode.clone())),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("require_mode", RulePropertyType::RequireMode)
+                .with_default("path"),
+        ]
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> InlineConstantRequire {
+        InlineConstantRequire::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_inline_constant_require", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'inline_constant_require',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    #[test]
+    fn configure_with_roblox_require_mode_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'inline_constant_require',
+            require_mode: 'roblox',
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'require_mode': only the path require mode is supported"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::nodes::{Arguments, Block, Expression, FunctionCall, LastStatement, Prefix};
+use crate::process::{
+    DefaultPostVisitor, DefaultVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    NodeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+#[derive(Default)]
+struct FindVariableArguments {
+    found: bool,
+}
+
+impl NodeProcessor for FindVariableArguments {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::VariableArguments(_) = expression {
+            self.found = true;
+        }
+    }
+}
+
+fn uses_variable_arguments(expression: &Expression) -> bool {
+    let mut expression = expression.clone();
+    let mut find_variable_arguments = FindVariableArguments::default();
+    DefaultVisitor::visit_expression(&mut expression, &mut find_variable_arguments);
+    find_variable_arguments.found
+}
+
+/// Returns the expression returned by the function when the call is an immediately
+/// invoked function that only returns a single value.
+fn get_inlined_expression(call: &FunctionCall) -> Option<&Expression> {
+    if call.get_method().is_some() {
+        return None;
+    }
+
+    match call.get_arguments() {
+        Arguments::Tuple(tuple) if tuple.is_empty() => {}
+        _ => return None,
+    }
+
+    let function = match call.get_prefix() {
+        Prefix::Parenthese(parenthese) => match parenthese.inner_expression() {
+            Expression::Function(function) => function,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if function.has_parameters() {
+        return None;
+    }
+
+    let block = function.get_block();
+
+    if block.statements_len() != 0 {
+        return None;
+    }
+
+    let return_statement = match block.get_last_statement() {
+        Some(LastStatement::Return(return_statement)) if return_statement.len() == 1 => {
+            return_statement
+        }
+        _ => return None,
+    };
+
+    let expression = return_statement.iter_expressions().next()?;
+
+    if uses_variable_arguments(expression) {
+        None
+    } else {
+        Some(expression)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Processor;
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Call(call) = expression {
+            if let Some(inlined) = get_inlined_expression(call) {
+                *expression = inlined.clone();
+            }
+        }
+    }
+}
+
+pub const INLINE_IMMEDIATELY_INVOKED_FUNCTION_RULE_NAME: &str =
+    "inline_immediately_invoked_function";
+
+/// A rule that replaces calls to functions defined in place that only return a
+/// value with the returned value.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InlineImmediatelyInvokedFunction {}
+
+impl FlawlessRule for InlineImmediatelyInvokedFunction {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor;
+        DefaultPostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for InlineImmediatelyInvokedFunction {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        INLINE_IMMEDIATELY_INVOKED_FUNCTION_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> InlineImmediatelyInvokedFunction {
+        InlineImmediatelyInvokedFunction::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_inline_immediately_invoked_function", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'inline_immediately_invoked_function',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,217 @@
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    Arguments, Block, Expression, FunctionCall, Prefix, TableEntry, TableExpression, Token,
+    TupleArguments,
+};
+use crate::process::{
+    Evaluator, IdentifierTracker, LuaValue, NodeProcessor, NodeVisitor, ScopeVisitor,
+};
+use crate::rules::{
+    verify_no_rule_properties, Context, FlawlessRule, RuleConfiguration, RuleConfigurationError,
+    RuleProperties,
+};
+
+const TABLE_LIBRARY_NAME: &str = "table";
+const UNPACK_FUNCTION_NAME: &str = "unpack";
+
+#[derive(Default)]
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl Processor {
+    fn is_global(&self, identifier: &str, name: &str) -> bool {
+        identifier == name && !self.is_identifier_used(name)
+    }
+
+    /// Returns true if the call is `table.unpack(...)` or `unpack(...)`.
+    fn is_unpack_function(&self, call: &FunctionCall) -> bool {
+        if call.get_method().is_some() {
+            return false;
+        }
+
+        match call.get_prefix() {
+            Prefix::Identifier(identifier) => {
+                self.is_global(identifier.get_name(), UNPACK_FUNCTION_NAME)
+            }
+            Prefix::Field(field) => {
+                field.get_field().get_name() == UNPACK_FUNCTION_NAME
+                    && matches!(
+                        field.get_prefix(),
+                        Prefix::Identifier(identifier)
+                            if self.is_global(identifier.get_name(), TABLE_LIBRARY_NAME)
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the values of the table when the array part only contains values
+    /// that are known to be non-nil, so that its length is known.
+    fn get_array_values<'a>(&self, table: &'a TableExpression) -> Option<UnpackedTable<'a>> {
+        let values = table
+            .iter_entries()
+            .map(|entry| match entry {
+                TableEntry::Value(value)
+                    if !matches!(
+                        self.evaluator.evaluate(value),
+                        LuaValue::Nil | LuaValue::Unknown
+                    ) =>
+                {
+                    Some(value.clone())
+                }
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+
+        Some(UnpackedTable { table, values })
+    }
+
+    /// Returns the values of the table when the expression is a call to `unpack`
+    /// with only a constant array.
+    fn get_unpacked_table<'a>(&self, expression: &'a Expression) -> Option<UnpackedTable<'a>> {
+        let call = match expression {
+            Expression::Call(call) if self.is_unpack_function(call) => call,
+            _ => return None,
+        };
+
+        match call.get_arguments() {
+            Arguments::Table(table) => self.get_array_values(table),
+            Arguments::Tuple(tuple) if tuple.len() == 1 => match tuple.iter_values().next() {
+                Some(Expression::Table(table)) => self.get_array_values(table),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+struct UnpackedTable<'a> {
+    table: &'a TableExpression,
+    values: Vec<Expression>,
+}
+
+impl UnpackedTable<'_> {
+    /// Returns the separators between the values of the table, to use as commas
+    /// between the arguments.
+    fn get_separators(&self) -> Vec<Token> {
+        self.table
+            .get_tokens()
+            .map(|tokens| {
+                tokens
+                    .separators
+                    .iter()
+                    .take(self.values.len().saturating_sub(1))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        if let Arguments::Tuple(tuple) = call.mutate_arguments() {
+            let unpacked = match tuple.iter_values().last() {
+                Some(last) => self
+                    .get_unpacked_table(last)
+                    .map(|unpacked| (unpacked.get_separators(), unpacked.values)),
+                None => None,
+            };
+
+            if let Some((separators, values)) = unpacked {
+                let tokens = tuple.get_tokens().cloned();
+
+                let mut arguments = mem::take(tuple).to_expressions();
+                arguments.pop();
+                arguments.extend(values);
+
+                let length = arguments.len();
+                let mut new_tuple = arguments.into_iter().collect::<TupleArguments>();
+                if let Some(mut tokens) = tokens {
+                    tokens.commas.extend(separators);
+                    tokens.commas.truncate(length.saturating_sub(1));
+                    new_tuple.set_tokens(tokens);
+                }
+                *tuple = new_tuple;
+            }
+        }
+    }
+}
+
+pub const INLINE_TABLE_UNPACK_RULE_NAME: &str = "inline_table_unpack";
+
+/// A rule that replaces `table.unpack` (or `unpack`) calls on array constructors
+/// at the end of the arguments of a call with the values of the array.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InlineTableUnpack {}
+
+impl FlawlessRule for InlineTableUnpack {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for InlineTableUnpack {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        INLINE_TABLE_UNPACK_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> InlineTableUnpack {
+        InlineTableUnpack::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_inline_table_unpack", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'inline_table_unpack',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::{BTreeMap, HashSet};
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    AssignStatement, Block, CompoundAssignStatement, Expression, FunctionStatement, Identifier,
+    LocalAssignStatement, Variable,
+};
+use crate::process::utils::is_valid_identifier;
+use crate::process::{IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+const DEFAULT_MINIMUM_REFERENCES: usize = 2;
+
+/// Counts the references to each global variable and finds the globals that are
+/// assigned somewhere in the block.
+#[derive(Default)]
+struct GlobalCollector {
+    identifier_tracker: IdentifierTracker,
+    references: BTreeMap<String, usize>,
+    assigned: HashSet<String>,
+}
+
+impl GlobalCollector {
+    fn assign_variable(&mut self, variable: &Variable) {
+        if let Variable::Identifier(identifier) = variable {
+            self.assign_identifier(identifier);
+        }
+    }
+
+    fn assign_identifier(&mut self, identifier: &Identifier) {
+        let name = identifier.get_name();
+        if !self.is_identifier_used(name) {
+            self.assigned.insert(name.to_owned());
+        }
+    }
+}
+
+impl Deref for GlobalCollector {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for GlobalCollector {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for GlobalCollector {
+    fn process_assign_statement(&mut self, assign: &mut AssignStatement) {
+        for variable in assign.iter_variables() {
+            self.assign_variable(variable);
+        }
+    }
+
+    fn process_compound_assign_statement(&mut self, assign: &mut CompoundAssignStatement) {
+        self.assign_variable(assign.get_variable());
+    }
+
+    fn process_function_statement(&mut self, function: &mut FunctionStatement) {
+        let name = function.get_name();
+        if name.get_field_names().is_empty() && !name.has_method() {
+            self.assign_identifier(name.get_name());
+        }
+    }
+
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        let name = identifier.get_name();
+        if !self.is_identifier_used(name) {
+            *self.references.entry(name.to_owned()).or_default() += 1;
+        }
+    }
+}
+
+pub const LOCALIZE_GLOBALS_RULE_NAME: &str = "localize_globals";
+
+/// A rule that defines local variables at the beginning of the file for the
+/// globals that are referenced often (`local print = print`).
+#[derive(Debug, PartialEq, Eq)]
+pub struct LocalizeGlobals {
+    minimum_references: usize,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl Default for LocalizeGlobals {
+    fn default() -> Self {
+        Self {
+            minimum_references: DEFAULT_MINIMUM_REFERENCES,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+impl LocalizeGlobals {
+    fn can_localize(&self, global: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|name| name == global))
+            && !self.exclude.iter().any(|name| name == global)
+    }
+}
+
+fn expect_identifier_list(
+    key: &str,
+    value: RulePropertyValue,
+) -> Result<Vec<String>, RuleConfigurationError> {
+    let list = value.expect_string_list(key)?;
+
+    if let Some(invalid) = list.iter().find(|name| !is_valid_identifier(name)) {
+        return Err(RuleConfigurationError::UnexpectedValue {
+            property: key.to_owned(),
+            message: format!("invalid identifier `{}`", invalid),
+        });
+    }
+
+    Ok(list)
+}
+
+impl FlawlessRule for LocalizeGlobals {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut collector = GlobalCollector::default();
+        ScopeVisitor::visit_block(block, &mut collector);
+
+        let GlobalCollector {
+            references,
+            assigned,
+            ..
+        } = collector;
+
+        let globals: Vec<_> = references
+            .into_iter()
+            .filter(|(global, references)| {
+                *references >= self.minimum_references
+                    && !assigned.contains(global)
+                    && self.can_localize(global)
+            })
+            .map(|(global, _)| global)
+            .collect();
+
+        for (index, global) in globals.into_iter().enumerate() {
+            let value = Expression::identifier(&global);
+            block.insert_statement(
+                index,
+                LocalAssignStatement::from_variable(global).with_value(value),
+            );
+        }
+    }
+}
+
+impl RuleConfiguration for LocalizeGlobals {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "minimum_references" => {
+                    let minimum_references = value.expect_usize(&key)?;
+                    if minimum_references == 0 {
+                        return Err(RuleConfigurationError::UnexpectedValue {
+                            property: key,
+                            message: "the minimum number of references must be at least 1"
+                                .to_owned(),
+                        });
+                    }
+                    self.minimum_references = minimum_references;
+                }
+                "include" => {
+                    self.include = expect_identifier_list(&key, value)?;
+                }
+                "exclude" => {
+                    self.exclude = expect_identifier_list(&key, value)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        LOCALIZE_GLOBALS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.minimum_references != DEFAULT_MINIMUM_REFERENCES {
+            properties.insert(
+                "minimum_references".to_owned(),
+                RulePropertyValue::Usize(self.minimum_references),
+            );
+        }
+
+        if !self.include.is_empty() {
+            properties.insert(
+                "include".to_owned(),
+                RulePropertyValue::StringList(self.include.clone()),
+            );
+        }
+
+        if !self.exclude.is_empty() {
+            properties.insert(
+                "exclude".to_owned(),
+                RulePropertyValue::StringList(self.exclude.clone()),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("minimum_references", RulePropertyType::Usize)
+                .with_default(DEFAULT_MINIMUM_REFERENCES),
+            RulePropertyDescription::new("include", RulePropertyType::StringList)
+                .with_default(Vec::<String>::new()),
+            RulePropertyDescription::new("exclude", RulePropertyType::StringList)
+                .with_default(Vec::<String>::new()),
+        ]
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        // a global changed by another script after the local is created would not
+        // be seen by this file anymore
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> LocalizeGlobals {
+        LocalizeGlobals::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_localize_globals", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_properties() {
+        let rule: Box<dyn Rule> = Box::new(LocalizeGlobals {
+            minimum_references: 3,
+            include: vec!["print".to_owned(), "math".to_owned()],
+            exclude: vec!["game".to_owned()],
+        });
+
+        assert_json_snapshot!("localize_globals_with_properties", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'localize_globals',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    #[test]
+    fn configure_with_invalid_identifier_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'localize_globals',
+            include: ["math.floor"],
+        }"#,
+        );
+        insta::assert_snapshot!(result.unwrap_err().to_string(), @"unexpected value for field 'include': invalid identifier `math.floor`");
+    }
+
+    #[test]
+    fn configure_with_zero_minimum_references_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'localize_globals',
+            minimum_references: 0,
+        }"#,
+        );
+        insta::assert_snapshot!(result.unwrap_err().to_string(), @"unexpected value for field 'minimum_references': the minimum number of references must be at least 1");
+    }
+}
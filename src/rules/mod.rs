@@ -3,14 +3,41 @@
 mod append_text_comment;
 pub mod bundle;
 mod call_parens;
+mod collapse_else_if;
 mod compute_expression;
 mod configuration_error;
+mod configuration_schema;
+mod convert_concat_to_interpolated_string;
+mod convert_concat_to_table_concat;
 mod convert_index_to_field;
+mod convert_interpolation_to_tostring;
 mod convert_require;
+mod convert_ternary_to_if_expression;
+mod convert_to_compound_assignment;
+mod convert_while_to_numeric_for;
+mod deduplicate_functions;
 mod empty_do;
+mod extract_messages;
 mod filter_early_return;
+mod fold_bit32_functions;
+mod fold_length_operator;
+mod fold_logical_operators;
+mod fold_math_functions;
+mod fold_next_nil_check;
+mod fold_select;
+mod fold_string_char_and_byte;
+mod fold_string_rep;
+mod fold_table_access;
+mod fold_table_insert;
+mod fold_tostring_and_tonumber;
+mod fold_type_calls;
 mod group_local;
+mod hoist_common_subexpressions;
 mod inject_value;
+mod inline_constant_require;
+mod inline_immediately_invoked_function;
+mod inline_table_unpack;
+mod localize_globals;
 mod method_def;
 mod no_local_function;
 mod remove_assertions;
@@ -19,31 +46,69 @@ mod remove_comments;
 mod remove_compound_assign;
 mod remove_continue;
 mod remove_debug_profiling;
+mod remove_empty_if_branches;
 mod remove_floor_division;
 mod remove_if_expression;
 mod remove_interpolated_string;
 mod remove_nil_declarations;
+mod remove_redundant_return;
 mod remove_spaces;
 mod remove_types;
+mod remove_unused_parameters;
+mod remove_unused_pure_call;
 mod remove_unused_variable;
 mod rename_variables;
+mod reorder_local_assignments;
 mod replace_referenced_tokens;
 pub(crate) mod require;
 mod rule_property;
 mod shift_token_line;
+mod shorten_number_literals;
+mod shorten_string_escapes;
+mod simplify_boolean_comparison;
+mod simplify_boolean_if_expression;
+mod sort_table_fields;
+mod unroll_ipairs_loop;
 mod unused_if_branch;
 mod unused_while;
 
 pub use append_text_comment::*;
 pub use call_parens::*;
+pub use collapse_else_if::*;
 pub use compute_expression::*;
 pub use configuration_error::RuleConfigurationError;
+pub use configuration_schema::{configuration_schema, RulePropertyDescription, RulePropertyType};
+pub use convert_concat_to_interpolated_string::*;
+pub use convert_concat_to_table_concat::*;
 pub use convert_index_to_field::*;
+pub use convert_interpolation_to_tostring::*;
 pub use convert_require::*;
+pub use convert_ternary_to_if_expression::*;
+pub use convert_to_compound_assignment::*;
+pub use convert_while_to_numeric_for::*;
+pub use deduplicate_functions::*;
 pub use empty_do::*;
+pub use extract_messages::*;
 pub use filter_early_return::*;
+pub use fold_bit32_functions::*;
+pub use fold_length_operator::*;
+pub use fold_logical_operators::*;
+pub use fold_math_functions::*;
+pub use fold_next_nil_check::*;
+pub use fold_select::*;
+pub use fold_string_char_and_byte::*;
+pub use fold_string_rep::*;
+pub use fold_table_access::*;
+pub use fold_table_insert::*;
+pub use fold_tostring_and_tonumber::*;
+pub use fold_type_calls::*;
 pub use group_local::*;
+pub use hoist_common_subexpressions::*;
 pub use inject_value::*;
+pub use inline_constant_require::*;
+pub use inline_immediately_invoked_function::*;
+pub use inline_table_unpack::*;
+pub use localize_globals::*;
 pub use method_def::*;
 pub use no_local_function::*;
 pub use remove_assertions::*;
@@ -51,21 +116,34 @@ pub use remove_comments::*;
 pub use remove_compound_assign::*;
 pub use remove_continue::*;
 pub use remove_debug_profiling::*;
+pub use remove_empty_if_branches::*;
 pub use remove_floor_division::*;
 pub use remove_if_expression::*;
 pub use remove_interpolated_string::*;
 pub use remove_nil_declarations::*;
+pub use remove_redundant_return::*;
 pub use remove_spaces::*;
 pub use remove_types::*;
+pub use remove_unused_parameters::*;
+pub use remove_unused_pure_call::*;
 pub use remove_unused_variable::*;
 pub use rename_variables::*;
+pub use reorder_local_assignments::*;
 pub(crate) use replace_referenced_tokens::*;
 pub use rule_property::*;
 pub(crate) use shift_token_line::*;
+pub use shorten_number_literals::*;
+pub use shorten_string_escapes::*;
+pub use simplify_boolean_comparison::*;
+pub use simplify_boolean_if_expression::*;
+pub use sort_table_fields::*;
+pub use unroll_ipairs_loop::*;
 pub use unused_if_branch::*;
 pub use unused_while::*;
 
+use crate::generator::{LuaSyntax, LuaVersion};
 use crate::nodes::Block;
+use crate::process::utils::{IdentifierGenerator, DEFAULT_GENERATED_IDENTIFIER_PREFIX};
 use crate::Resources;
 
 use serde::de::{self, MapAccess, Visitor};
@@ -83,6 +161,8 @@ pub struct ContextBuilder<'a, 'resources, 'code> {
     original_code: &'code str,
     blocks: HashMap<PathBuf, &'a Block>,
     project_location: Option<PathBuf>,
+    generated_identifier_prefix: Option<String>,
+    target: Option<LuaVersion>,
 }
 
 impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
@@ -97,6 +177,8 @@ impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
             original_code,
             blocks: Default::default(),
             project_location: None,
+            generated_identifier_prefix: None,
+            target: None,
         }
     }
 
@@ -105,6 +187,18 @@ impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
         self
     }
 
+    /// Sets the prefix of the identifiers generated by rules (`__DARKLUA_` by default).
+    pub fn with_generated_identifier_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.generated_identifier_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the version of Lua that the processed code must run on.
+    pub fn with_target(mut self, target: LuaVersion) -> Self {
+        self.target = Some(target);
+        self
+    }
+
     pub fn build(self) -> Context<'a, 'resources, 'code> {
         Context {
             path: self.path,
@@ -112,7 +206,10 @@ impl<'a, 'resources, 'code> ContextBuilder<'a, 'resources, 'code> {
             original_code: self.original_code,
             blocks: self.blocks,
             project_location: self.project_location,
+            generated_identifier_prefix: self.generated_identifier_prefix,
+            target: self.target,
             dependencies: Default::default(),
+            extracted_messages: Default::default(),
         }
     }
 
@@ -129,18 +226,25 @@ pub struct Context<'a, 'resources, 'code> {
     original_code: &'code str,
     blocks: HashMap<PathBuf, &'a Block>,
     project_location: Option<PathBuf>,
+    generated_identifier_prefix: Option<String>,
+    target: Option<LuaVersion>,
     dependencies: std::cell::RefCell<Vec<PathBuf>>,
+    extracted_messages: std::cell::RefCell<Vec<(String, String)>>,
 }
 
 impl Context<'_, '_, '_> {
+    /// Returns the block of a file that was requested by [`Rule::require_content`].
     pub fn block(&self, path: impl AsRef<Path>) -> Option<&Block> {
         self.blocks.get(path.as_ref()).copied()
     }
 
+    /// Returns the path of the file currently being processed.
     pub fn current_path(&self) -> &Path {
         self.path.as_ref()
     }
 
+    /// Registers a file that the processed file depends on. In watch mode, the processed file
+    /// will be processed again when one of its dependencies changes.
     pub fn add_file_dependency(&self, path: PathBuf) {
         if let Ok(mut dependencies) = self.dependencies.try_borrow_mut() {
             log::trace!("add file dependency {}", path.display());
@@ -150,6 +254,51 @@ impl Context<'_, '_, '_> {
         }
     }
 
+    /// Registers a message passed to a function of the processed file. The messages can be
+    /// obtained after processing with
+    /// [`WorkerTree::extracted_messages`](crate::WorkerTree::extracted_messages).
+    pub(crate) fn add_extracted_message(
+        &self,
+        function: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        if let Ok(mut messages) = self.extracted_messages.try_borrow_mut() {
+            messages.push((function.into(), message.into()));
+        } else {
+            log::warn!("unable to submit extracted message (internal error)");
+        }
+    }
+
+    /// Returns the function and the message of each message registered by rules.
+    pub(crate) fn take_extracted_messages(&self) -> Vec<(String, String)> {
+        self.extracted_messages.take()
+    }
+
+    /// Returns the prefix of the identifiers generated by rules.
+    pub fn generated_identifier_prefix(&self) -> &str {
+        self.generated_identifier_prefix
+            .as_deref()
+            .unwrap_or(DEFAULT_GENERATED_IDENTIFIER_PREFIX)
+    }
+
+    /// Returns the version of Lua that the processed code must run on, if one is configured.
+    pub fn target(&self) -> Option<LuaVersion> {
+        self.target
+    }
+
+    /// Returns true if the processed code can contain the given syntax. Without a target
+    /// version, any syntax is allowed.
+    pub fn supports_syntax(&self, syntax: LuaSyntax) -> bool {
+        self.target.map_or(true, |target| target.supports(syntax))
+    }
+
+    /// Creates a generator for the names of the variables that a rule adds to the block.
+    /// The names start with the configured prefix and never collide with an identifier
+    /// of the block.
+    pub(crate) fn identifier_generator(&self, block: &mut Block) -> IdentifierGenerator {
+        IdentifierGenerator::new(self.generated_identifier_prefix(), block)
+    }
+
     pub fn into_dependencies(self) -> impl Iterator<Item = PathBuf> {
         self.dependencies.into_inner().into_iter()
     }
@@ -176,12 +325,59 @@ impl Context<'_, '_, '_> {
     }
 }
 
+/// The result of [`Rule::process`]. The error string is reported to the user along with the
+/// name of the rule and the path of the processed file.
 pub type RuleProcessResult = Result<(), String>;
 
 /// Defines an interface that will be used to mutate blocks and how to serialize and deserialize
 /// the rule configuration.
+///
+/// This trait can be implemented outside of darklua to create custom rules. These rules can then
+/// be added to a [`Configuration`](crate::Configuration) using
+/// [`with_rule`](crate::Configuration::with_rule). Since custom rules are unknown to the rule
+/// deserializer, they can only be added from code and not from a configuration file.
+///
+/// Rules that can not fail can implement [`FlawlessRule`] instead of this trait.
+///
+/// ```
+/// # use darklua_core::nodes::Block;
+/// # use darklua_core::rules::{
+/// #     Context, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult,
+/// #     RuleProperties,
+/// # };
+/// #[derive(Debug)]
+/// struct RejectEmptyFiles;
+///
+/// impl Rule for RejectEmptyFiles {
+///     fn process(&self, block: &mut Block, _context: &Context) -> RuleProcessResult {
+///         if block.is_empty() {
+///             Err("file is empty".to_owned())
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// impl RuleConfiguration for RejectEmptyFiles {
+///     fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+///         match properties.into_keys().next() {
+///             Some(key) => Err(RuleConfigurationError::UnexpectedProperty(key)),
+///             None => Ok(()),
+///         }
+///     }
+///
+///     fn get_name(&self) -> &'static str {
+///         "reject_empty_files"
+///     }
+///
+///     fn serialize_to_properties(&self) -> RuleProperties {
+///         RuleProperties::new()
+///     }
+/// }
+/// ```
 pub trait Rule: RuleConfiguration + fmt::Debug {
-    /// This method should mutate the given block to apply the rule
+    /// This method should mutate the given block to apply the rule. When an error is returned,
+    /// the remaining rules are not applied to the file.
     fn process(&self, block: &mut Block, context: &Context) -> RuleProcessResult;
 
     /// Return the list of paths to Lua files that is necessary to apply this rule. This will load
@@ -191,6 +387,8 @@ pub trait Rule: RuleConfiguration + fmt::Debug {
     }
 }
 
+/// Defines how a rule is configured and how its configuration is serialized. Every [`Rule`] must
+/// implement this trait.
 pub trait RuleConfiguration {
     /// The rule deserializer will construct the default rule and then send the properties through
     /// this method to modify the behavior of the rule.
@@ -204,9 +402,23 @@ pub trait RuleConfiguration {
     fn has_properties(&self) -> bool {
         !self.serialize_to_properties().is_empty()
     }
+    /// Describes the properties accepted by the `configure` method. This is used to generate
+    /// the [configuration schema](configuration_schema()).
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        Vec::new()
+    }
+    /// Returns `false` if the rule relies on assumptions about the code that can change its
+    /// behavior in some edge cases. These rules are skipped when the configuration enables
+    /// the safe mode.
+    fn is_semantics_preserving(&self) -> bool {
+        true
+    }
 }
 
+/// A simpler version of [`Rule`] for rules that can not fail. Any type implementing this trait,
+/// [`RuleConfiguration`] and [`Debug`](fmt::Debug) also implements [`Rule`].
 pub trait FlawlessRule {
+    /// This method should mutate the given block to apply the rule.
     fn flawless_process(&self, block: &mut Block, context: &Context);
 }
 
@@ -241,13 +453,22 @@ pub fn get_default_rules() -> Vec<Box<dyn Rule>> {
 pub fn get_all_rule_names() -> Vec<&'static str> {
     vec![
         APPEND_TEXT_COMMENT_RULE_NAME,
+        COLLAPSE_ELSE_IF_RULE_NAME,
         COMPUTE_EXPRESSIONS_RULE_NAME,
         CONVERT_INDEX_TO_FIELD_RULE_NAME,
+        CONVERT_INTERPOLATION_TO_TOSTRING_RULE_NAME,
         CONVERT_LOCAL_FUNCTION_TO_ASSIGN_RULE_NAME,
         CONVERT_REQUIRE_RULE_NAME,
+        CONVERT_TO_COMPOUND_ASSIGNMENT_RULE_NAME,
+        DEDUPLICATE_FUNCTIONS_RULE_NAME,
         FILTER_AFTER_EARLY_RETURN_RULE_NAME,
+        FOLD_LOGICAL_OPERATORS_RULE_NAME,
+        FOLD_STRING_REP_RULE_NAME,
+        FOLD_TABLE_INSERT_RULE_NAME,
         GROUP_LOCAL_ASSIGNMENT_RULE_NAME,
         INJECT_GLOBAL_VALUE_RULE_NAME,
+        INLINE_CONSTANT_REQUIRE_RULE_NAME,
+        INLINE_IMMEDIATELY_INVOKED_FUNCTION_RULE_NAME,
         REMOVE_ASSERTIONS_RULE_NAME,
         REMOVE_COMMENTS_RULE_NAME,
         REMOVE_COMPOUND_ASSIGNMENT_RULE_NAME,
@@ -260,11 +481,39 @@ pub fn get_all_rule_names() -> Vec<&'static str> {
         REMOVE_SPACES_RULE_NAME,
         REMOVE_TYPES_RULE_NAME,
         REMOVE_UNUSED_IF_BRANCH_RULE_NAME,
+        REMOVE_UNUSED_PURE_CALL_RULE_NAME,
         REMOVE_UNUSED_VARIABLE_RULE_NAME,
         REMOVE_UNUSED_WHILE_RULE_NAME,
         RENAME_VARIABLES_RULE_NAME,
         REMOVE_IF_EXPRESSION_RULE_NAME,
         REMOVE_CONTINUE_RULE_NAME,
+        SIMPLIFY_BOOLEAN_COMPARISON_RULE_NAME,
+        SHORTEN_NUMBER_LITERALS_RULE_NAME,
+        LOCALIZE_GLOBALS_RULE_NAME,
+        FOLD_SELECT_RULE_NAME,
+        INLINE_TABLE_UNPACK_RULE_NAME,
+        REORDER_LOCAL_ASSIGNMENTS_RULE_NAME,
+        CONVERT_CONCAT_TO_TABLE_CONCAT_RULE_NAME,
+        FOLD_LENGTH_OPERATOR_RULE_NAME,
+        REMOVE_EMPTY_IF_BRANCHES_RULE_NAME,
+        CONVERT_TERNARY_TO_IF_EXPRESSION_RULE_NAME,
+        FOLD_TABLE_ACCESS_RULE_NAME,
+        SHORTEN_STRING_ESCAPES_RULE_NAME,
+        SIMPLIFY_BOOLEAN_IF_EXPRESSION_RULE_NAME,
+        FOLD_MATH_FUNCTIONS_RULE_NAME,
+        FOLD_STRING_CHAR_AND_BYTE_RULE_NAME,
+        REMOVE_UNUSED_PARAMETERS_RULE_NAME,
+        FOLD_TOSTRING_AND_TONUMBER_RULE_NAME,
+        CONVERT_CONCAT_TO_INTERPOLATED_STRING_RULE_NAME,
+        SORT_TABLE_FIELDS_RULE_NAME,
+        FOLD_NEXT_NIL_CHECK_RULE_NAME,
+        UNROLL_IPAIRS_LOOP_RULE_NAME,
+        HOIST_COMMON_SUBEXPRESSIONS_RULE_NAME,
+        REMOVE_REDUNDANT_RETURN_RULE_NAME,
+        CONVERT_WHILE_TO_NUMERIC_FOR_RULE_NAME,
+        FOLD_BIT32_FUNCTIONS_RULE_NAME,
+        EXTRACT_MESSAGES_RULE_NAME,
+        FOLD_TYPE_CALLS_RULE_NAME,
     ]
 }
 
@@ -274,15 +523,30 @@ impl FromStr for Box<dyn Rule> {
     fn from_str(string: &str) -> Result<Self, Self::Err> {
         let rule: Box<dyn Rule> = match string {
             APPEND_TEXT_COMMENT_RULE_NAME => Box::<AppendTextComment>::default(),
+            COLLAPSE_ELSE_IF_RULE_NAME => Box::<CollapseElseIf>::default(),
             COMPUTE_EXPRESSIONS_RULE_NAME => Box::<ComputeExpression>::default(),
             CONVERT_INDEX_TO_FIELD_RULE_NAME => Box::<ConvertIndexToField>::default(),
+            CONVERT_INTERPOLATION_TO_TOSTRING_RULE_NAME => {
+                Box::<ConvertInterpolationToToString>::default()
+            }
             CONVERT_LOCAL_FUNCTION_TO_ASSIGN_RULE_NAME => {
                 Box::<ConvertLocalFunctionToAssign>::default()
             }
             CONVERT_REQUIRE_RULE_NAME => Box::<ConvertRequire>::default(),
+            CONVERT_TO_COMPOUND_ASSIGNMENT_RULE_NAME => {
+                Box::<ConvertToCompoundAssignment>::default()
+            }
+            DEDUPLICATE_FUNCTIONS_RULE_NAME => Box::<DeduplicateFunctions>::default(),
             FILTER_AFTER_EARLY_RETURN_RULE_NAME => Box::<FilterAfterEarlyReturn>::default(),
+            FOLD_LOGICAL_OPERATORS_RULE_NAME => Box::<FoldLogicalOperators>::default(),
+            FOLD_STRING_REP_RULE_NAME => Box::<FoldStringRep>::default(),
+            FOLD_TABLE_INSERT_RULE_NAME => Box::<FoldTableInsert>::default(),
             GROUP_LOCAL_ASSIGNMENT_RULE_NAME => Box::<GroupLocalAssignment>::default(),
             INJECT_GLOBAL_VALUE_RULE_NAME => Box::<InjectGlobalValue>::default(),
+            INLINE_CONSTANT_REQUIRE_RULE_NAME => Box::<InlineConstantRequire>::default(),
+            INLINE_IMMEDIATELY_INVOKED_FUNCTION_RULE_NAME => {
+                Box::<InlineImmediatelyInvokedFunction>::default()
+            }
             REMOVE_ASSERTIONS_RULE_NAME => Box::<RemoveAssertions>::default(),
             REMOVE_COMMENTS_RULE_NAME => Box::<RemoveComments>::default(),
             REMOVE_COMPOUND_ASSIGNMENT_RULE_NAME => Box::<RemoveCompoundAssignment>::default(),
@@ -296,11 +560,47 @@ impl FromStr for Box<dyn Rule> {
             REMOVE_SPACES_RULE_NAME => Box::<RemoveSpaces>::default(),
             REMOVE_TYPES_RULE_NAME => Box::<RemoveTypes>::default(),
             REMOVE_UNUSED_IF_BRANCH_RULE_NAME => Box::<RemoveUnusedIfBranch>::default(),
+            REMOVE_UNUSED_PURE_CALL_RULE_NAME => Box::<RemoveUnusedPureCall>::default(),
             REMOVE_UNUSED_VARIABLE_RULE_NAME => Box::<RemoveUnusedVariable>::default(),
             REMOVE_UNUSED_WHILE_RULE_NAME => Box::<RemoveUnusedWhile>::default(),
             RENAME_VARIABLES_RULE_NAME => Box::<RenameVariables>::default(),
             REMOVE_IF_EXPRESSION_RULE_NAME => Box::<RemoveIfExpression>::default(),
             REMOVE_CONTINUE_RULE_NAME => Box::<RemoveContinue>::default(),
+            SIMPLIFY_BOOLEAN_COMPARISON_RULE_NAME => Box::<SimplifyBooleanComparison>::default(),
+            SHORTEN_NUMBER_LITERALS_RULE_NAME => Box::<ShortenNumberLiterals>::default(),
+            LOCALIZE_GLOBALS_RULE_NAME => Box::<LocalizeGlobals>::default(),
+            FOLD_SELECT_RULE_NAME => Box::<FoldSelect>::default(),
+            INLINE_TABLE_UNPACK_RULE_NAME => Box::<InlineTableUnpack>::default(),
+            REORDER_LOCAL_ASSIGNMENTS_RULE_NAME => Box::<ReorderLocalAssignments>::default(),
+            CONVERT_CONCAT_TO_TABLE_CONCAT_RULE_NAME => {
+                Box::<ConvertConcatToTableConcat>::default()
+            }
+            FOLD_LENGTH_OPERATOR_RULE_NAME => Box::<FoldLengthOperator>::default(),
+            REMOVE_EMPTY_IF_BRANCHES_RULE_NAME => Box::<RemoveEmptyIfBranches>::default(),
+            CONVERT_TERNARY_TO_IF_EXPRESSION_RULE_NAME => {
+                Box::<ConvertTernaryToIfExpression>::default()
+            }
+            FOLD_TABLE_ACCESS_RULE_NAME => Box::<FoldTableAccess>::default(),
+            SHORTEN_STRING_ESCAPES_RULE_NAME => Box::<ShortenStringEscapes>::default(),
+            SIMPLIFY_BOOLEAN_IF_EXPRESSION_RULE_NAME => {
+                Box::<SimplifyBooleanIfExpression>::default()
+            }
+            FOLD_MATH_FUNCTIONS_RULE_NAME => Box::<FoldMathFunctions>::default(),
+            FOLD_STRING_CHAR_AND_BYTE_RULE_NAME => Box::<FoldStringCharAndByte>::default(),
+            REMOVE_UNUSED_PARAMETERS_RULE_NAME => Box::<RemoveUnusedParameters>::default(),
+            FOLD_TOSTRING_AND_TONUMBER_RULE_NAME => Box::<FoldTostringAndTonumber>::default(),
+            CONVERT_CONCAT_TO_INTERPOLATED_STRING_RULE_NAME => {
+                Box::<ConvertConcatToInterpolatedString>::default()
+            }
+            SORT_TABLE_FIELDS_RULE_NAME => Box::<SortTableFields>::default(),
+            FOLD_NEXT_NIL_CHECK_RULE_NAME => Box::<FoldNextNilCheck>::default(),
+            UNROLL_IPAIRS_LOOP_RULE_NAME => Box::<UnrollIpairsLoop>::default(),
+            HOIST_COMMON_SUBEXPRESSIONS_RULE_NAME => Box::<HoistCommonSubexpressions>::default(),
+            REMOVE_REDUNDANT_RETURN_RULE_NAME => Box::<RemoveRedundantReturn>::default(),
+            CONVERT_WHILE_TO_NUMERIC_FOR_RULE_NAME => Box::<ConvertWhileToNumericFor>::default(),
+            FOLD_BIT32_FUNCTIONS_RULE_NAME => Box::<FoldBit32Functions>::default(),
+            EXTRACT_MESSAGES_RULE_NAME => Box::<ExtractMessages>::default(),
+            FOLD_TYPE_CALLS_RULE_NAME => Box::<FoldTypeCalls>::default(),
             _ => return Err(format!("invalid rule name: {}", string)),
         };
 
@@ -1,53 +1,99 @@
 use crate::nodes::{
-    Block, FunctionExpression, LocalAssignStatement, LocalFunctionStatement, Statement,
+    AssignStatement, Block, FunctionExpression, LocalAssignStatement, LocalFunctionStatement,
+    Statement, Variable,
 };
 use crate::process::{processors::FindVariables, DefaultVisitor, NodeProcessor, NodeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
 };
 
-use serde::ser::{Serialize, Serializer};
 use std::mem;
 
-use super::verify_no_rule_properties;
-
-struct Processor;
+fn take_function_expression(local_function: &mut LocalFunctionStatement) -> FunctionExpression {
+    let mut function_expression = FunctionExpression::default();
+    function_expression.set_variadic(local_function.is_variadic());
+    mem::swap(
+        function_expression.mutate_block(),
+        local_function.mutate_block(),
+    );
+    mem::swap(
+        function_expression.mutate_parameters(),
+        local_function.mutate_parameters(),
+    );
+    function_expression
+}
 
-impl Processor {
-    fn convert(&self, local_function: &mut LocalFunctionStatement) -> Statement {
-        let mut function_expression = FunctionExpression::default();
-        function_expression.set_variadic(local_function.is_variadic());
-        mem::swap(
-            function_expression.mutate_block(),
-            local_function.mutate_block(),
-        );
-        mem::swap(
-            function_expression.mutate_parameters(),
-            local_function.mutate_parameters(),
-        );
+fn is_recursive(local_function: &mut LocalFunctionStatement) -> bool {
+    let name = local_function.get_name().to_owned();
 
-        LocalAssignStatement::from_variable(local_function.get_name())
-            .with_value(function_expression)
-            .into()
+    if local_function.has_parameter(&name) {
+        false
+    } else {
+        let mut find_usage = FindVariables::new(&name);
+        DefaultVisitor::visit_block(local_function.mutate_block(), &mut find_usage);
+        find_usage.has_found_usage()
     }
 }
 
+/// Replaces a recursive local function with the declaration of its name, and returns the
+/// assignment of the function to that name.
+fn split_recursive_function(statement: &mut Statement) -> Option<AssignStatement> {
+    let (name, function_expression) = match statement {
+        Statement::LocalFunction(local_function) => {
+            if !is_recursive(local_function) {
+                return None;
+            }
+            (
+                local_function.get_name().to_owned(),
+                take_function_expression(local_function),
+            )
+        }
+        _ => return None,
+    };
+
+    *statement = LocalAssignStatement::from_variable(name.as_str()).into();
+
+    Some(AssignStatement::from_variable(
+        Variable::new(name),
+        function_expression,
+    ))
+}
+
+struct Processor {
+    convert_recursive_functions: bool,
+}
+
 impl NodeProcessor for Processor {
+    fn process_block(&mut self, block: &mut Block) {
+        if !self.convert_recursive_functions {
+            return;
+        }
+
+        let mut index = 0;
+
+        while index < block.statements_len() {
+            let assign = block
+                .iter_mut_statements()
+                .nth(index)
+                .and_then(split_recursive_function);
+
+            if let Some(assign) = assign {
+                block.insert_statement(index + 1, assign);
+                index += 2;
+            } else {
+                index += 1;
+            }
+        }
+    }
+
     fn process_statement(&mut self, statement: &mut Statement) {
         if let Statement::LocalFunction(local_function) = statement {
-            let name = local_function.get_name().to_owned();
-
-            if local_function.has_parameter(&name) {
-                let mut assign = self.convert(local_function);
+            if !is_recursive(local_function) {
+                let mut assign = LocalAssignStatement::from_variable(local_function.get_name())
+                    .with_value(take_function_expression(local_function))
+                    .into();
                 mem::swap(statement, &mut assign)
-            } else {
-                let mut find_usage = FindVariables::new(&name);
-                DefaultVisitor::visit_block(local_function.mutate_block(), &mut find_usage);
-
-                if !find_usage.has_found_usage() {
-                    let mut assign = self.convert(local_function);
-                    mem::swap(statement, &mut assign)
-                }
             }
         };
     }
@@ -56,19 +102,32 @@ impl NodeProcessor for Processor {
 pub const CONVERT_LOCAL_FUNCTION_TO_ASSIGN_RULE_NAME: &str = "convert_local_function_to_assign";
 
 /// Convert local function statements into local assignements when the function is not recursive.
+/// When `convert_recursive_functions` is enabled, recursive functions are converted into a
+/// local declaration followed by an assignment.
 #[derive(Debug, Default, PartialEq, Eq)]
-pub struct ConvertLocalFunctionToAssign {}
+pub struct ConvertLocalFunctionToAssign {
+    convert_recursive_functions: bool,
+}
 
 impl FlawlessRule for ConvertLocalFunctionToAssign {
     fn flawless_process(&self, block: &mut Block, _: &Context) {
-        let mut processor = Processor;
+        let mut processor = Processor {
+            convert_recursive_functions: self.convert_recursive_functions,
+        };
         DefaultVisitor::visit_block(block, &mut processor);
     }
 }
 
 impl RuleConfiguration for ConvertLocalFunctionToAssign {
     fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
-        verify_no_rule_properties(&properties)?;
+        for (key, value) in properties {
+            match key.as_str() {
+                "convert_recursive_functions" => {
+                    self.convert_recursive_functions = value.expect_bool(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
 
         Ok(())
     }
@@ -78,13 +137,23 @@ impl RuleConfiguration for ConvertLocalFunctionToAssign {
     }
 
     fn serialize_to_properties(&self) -> RuleProperties {
-        RuleProperties::new()
+        let mut properties = RuleProperties::new();
+
+        if self.convert_recursive_functions {
+            properties.insert(
+                "convert_recursive_functions".to_owned(),
+                RulePropertyValue::Boolean(self.convert_recursive_functions),
+            );
+        }
+
+        properties
     }
-}
 
-impl Serialize for ConvertLocalFunctionToAssign {
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.serialize_str(CONVERT_LOCAL_FUNCTION_TO_ASSIGN_RULE_NAME)
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("convert_recursive_functions", RulePropertyType::Boolean)
+                .with_default(false),
+        ]
     }
 }
 
@@ -102,7 +171,21 @@ mod test {
 
     #[test]
     fn serialize_default_rule() {
-        assert_json_snapshot!("default_convert_local_function_to_assign", new_rule());
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_convert_local_function_to_assign", rule);
+    }
+
+    #[test]
+    fn serialize_rule_converting_recursive_functions() {
+        let rule: Box<dyn Rule> = Box::new(ConvertLocalFunctionToAssign {
+            convert_recursive_functions: true,
+        });
+
+        assert_json_snapshot!(
+            "convert_local_function_to_assign_converting_recursive_functions",
+            rule
+        );
     }
 
     #[test]
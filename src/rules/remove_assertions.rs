@@ -1,50 +1,68 @@
-use std::collections::HashMap;
 use std::iter::{self, FromIterator};
 
+use indexmap::IndexMap;
+
 use crate::nodes::{Block, Expression, FunctionCall, Prefix, TupleArguments};
 use crate::process::{IdentifierTracker, NodeVisitor, ScopeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
 };
 
-use super::remove_call_match::{CallMatch, RemoveFunctionCallProcessor};
+use super::remove_call_match::{get_function_path, CallMatch, RemoveFunctionCallProcessor};
 
 const ASSERT_FUNCTION_NAME: &str = "assert";
 
 pub const REMOVE_ASSERTIONS_RULE_NAME: &str = "remove_assertions";
 
-/// A rule that removes `assert` calls.
+/// A rule that removes `assert` calls (or calls to other assertion functions).
 #[derive(Debug, PartialEq, Eq)]
 pub struct RemoveAssertions {
     preserve_args_side_effects: bool,
+    functions: Vec<String>,
 }
 
 impl Default for RemoveAssertions {
     fn default() -> Self {
         Self {
             preserve_args_side_effects: true,
+            functions: vec![ASSERT_FUNCTION_NAME.to_owned()],
         }
     }
 }
 
-struct AssertMatcher;
+impl RemoveAssertions {
+    fn has_default_functions(&self) -> bool {
+        self.functions.len() == 1 && self.functions[0] == ASSERT_FUNCTION_NAME
+    }
+}
+
+struct AssertMatcher<'a> {
+    functions: &'a [String],
+}
 
-impl CallMatch<()> for AssertMatcher {
+impl CallMatch<()> for AssertMatcher<'_> {
     fn matches(&self, identifiers: &IdentifierTracker, prefix: &Prefix) -> bool {
-        if identifiers.is_identifier_used(ASSERT_FUNCTION_NAME) {
-            return false;
+        match get_function_path(prefix) {
+            Some((root, path)) => {
+                !identifiers.is_identifier_used(root) && self.functions.contains(&path)
+            }
+            None => false,
         }
+    }
 
-        match prefix {
-            Prefix::Identifier(identifier) => identifier.get_name() == ASSERT_FUNCTION_NAME,
-            _ => false,
-        }
+    fn can_remove_from_expression(&self, call: &FunctionCall) -> bool {
+        // only the result of the `assert` function is known
+        matches!(
+            call.get_prefix(),
+            Prefix::Identifier(identifier) if identifier.get_name() == ASSERT_FUNCTION_NAME
+        )
     }
 
     fn compute_result(
         &self,
         call: &FunctionCall,
-        mappings: &HashMap<&'static str, String>,
+        mappings: &IndexMap<&'static str, String>,
     ) -> Option<Expression> {
         let expressions = call.get_arguments().clone().to_expressions();
 
@@ -73,9 +91,14 @@ impl CallMatch<()> for AssertMatcher {
 }
 
 impl FlawlessRule for RemoveAssertions {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
-        let mut processor =
-            RemoveFunctionCallProcessor::new(self.preserve_args_side_effects, AssertMatcher);
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = RemoveFunctionCallProcessor::new(
+            self.preserve_args_side_effects,
+            AssertMatcher {
+                functions: &self.functions,
+            },
+            context.identifier_generator(block),
+        );
         ScopeVisitor::visit_block(block, &mut processor);
 
         if let Some(statement) = processor.extract_reserved_globals() {
@@ -91,6 +114,9 @@ impl RuleConfiguration for RemoveAssertions {
                 "preserve_arguments_side_effects" => {
                     self.preserve_args_side_effects = value.expect_bool(&key)?;
                 }
+                "functions" => {
+                    self.functions = value.expect_string_list(&key)?;
+                }
                 _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
             }
         }
@@ -109,8 +135,27 @@ impl RuleConfiguration for RemoveAssertions {
             properties.insert("preserve_arguments_side_effects".to_owned(), false.into());
         }
 
+        if !self.has_default_functions() {
+            properties.insert(
+                "functions".to_owned(),
+                RulePropertyValue::StringList(self.functions.clone()),
+            );
+        }
+
         properties
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new(
+                "preserve_arguments_side_effects",
+                RulePropertyType::Boolean,
+            )
+            .with_default(true),
+            RulePropertyDescription::new("functions", RulePropertyType::StringList)
+                .with_default(vec![ASSERT_FUNCTION_NAME]),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -135,11 +180,22 @@ mod test {
     fn serialize_rule_without_side_effects() {
         let rule: Box<dyn Rule> = Box::new(RemoveAssertions {
             preserve_args_side_effects: false,
+            ..Default::default()
         });
 
         assert_json_snapshot!("remove_assertions_without_side_effects", rule);
     }
 
+    #[test]
+    fn serialize_rule_with_functions() {
+        let rule: Box<dyn Rule> = Box::new(RemoveAssertions {
+            functions: vec!["assert".to_owned(), "debug.assert".to_owned()],
+            ..Default::default()
+        });
+
+        assert_json_snapshot!("remove_assertions_with_functions", rule);
+    }
+
     #[test]
     fn configure_with_extra_field_error() {
         let result = json5::from_str::<Box<dyn Rule>>(
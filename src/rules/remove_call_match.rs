@@ -1,10 +1,12 @@
-use std::collections::HashMap;
 use std::{iter, ops};
 
+use indexmap::IndexMap;
+
 use crate::nodes::{
     Arguments, DoStatement, Expression, FunctionCall, Identifier, LocalAssignStatement, Prefix,
     Statement, TableEntry, TypedIdentifier,
 };
+use crate::process::utils::IdentifierGenerator;
 use crate::process::{Evaluator, IdentifierTracker, NodeProcessor};
 use crate::utils::{expressions_as_expression, expressions_as_statement};
 
@@ -14,7 +16,7 @@ pub(crate) trait CallMatch<T> {
     fn compute_result(
         &self,
         _call: &FunctionCall,
-        _mappings: &HashMap<&'static str, String>,
+        _mappings: &IndexMap<&'static str, String>,
     ) -> Option<Expression> {
         None
     }
@@ -22,13 +24,86 @@ pub(crate) trait CallMatch<T> {
     fn reserve_globals(&self) -> impl Iterator<Item = &'static str> {
         iter::empty()
     }
+
+    /// Returns false when a matching call used as an expression must be kept,
+    /// because its result can not be computed.
+    fn can_remove_from_expression(&self, _call: &FunctionCall) -> bool {
+        true
+    }
+}
+
+/// Returns the identifier at the root of the prefix and the path to the function
+/// (like `math.floor`).
+pub(crate) fn get_function_path(prefix: &Prefix) -> Option<(&str, String)> {
+    match prefix {
+        Prefix::Identifier(identifier) => {
+            let name = identifier.get_name();
+            Some((name.as_str(), name.to_owned()))
+        }
+        Prefix::Field(field) => {
+            let (root, mut path) = get_function_path(field.get_prefix())?;
+            path.push('.');
+            path.push_str(field.get_field().get_name());
+            Some((root, path))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the expressions from the arguments of a call that have side effects.
+pub(crate) fn preserve_arguments_side_effects(
+    evaluator: &Evaluator,
+    arguments: &Arguments,
+) -> Vec<Expression> {
+    match arguments {
+        Arguments::Tuple(tuple) => tuple
+            .iter_values()
+            .filter(|value| evaluator.has_side_effects(value))
+            .cloned()
+            .collect(),
+        Arguments::Table(table) => {
+            let mut expressions = Vec::new();
+
+            for entry in table.iter_entries() {
+                match entry {
+                    TableEntry::Field(field) => {
+                        let expression = field.get_value();
+                        if evaluator.has_side_effects(expression) {
+                            expressions.push(expression.clone());
+                        }
+                    }
+                    TableEntry::Index(index) => {
+                        let key = index.get_key();
+                        let value = index.get_value();
+
+                        if evaluator.has_side_effects(key) {
+                            expressions.push(key.clone());
+                        }
+                        if evaluator.has_side_effects(value) {
+                            expressions.push(value.clone());
+                        }
+                    }
+                    TableEntry::Value(value) => {
+                        if evaluator.has_side_effects(value) {
+                            expressions.push(value.clone());
+                        }
+                    }
+                }
+            }
+
+            expressions
+        }
+        Arguments::String(_) => Vec::new(),
+    }
 }
 
-#[derive(Default)]
 pub(crate) struct RemoveFunctionCallProcessor<Args, T: CallMatch<Args>> {
     identifier_tracker: IdentifierTracker,
-    global_mappings: HashMap<&'static str, String>,
+    // the reserved globals are declared in the order they were reserved, so that the
+    // generated code does not depend on the iteration order of a hash map
+    global_mappings: IndexMap<&'static str, String>,
     global_counter: u32,
+    identifier_generator: IdentifierGenerator,
     evaluator: Evaluator,
     preserve_args_side_effects: bool,
     matcher: T,
@@ -54,11 +129,16 @@ where
 }
 
 impl<Args, T: CallMatch<Args>> RemoveFunctionCallProcessor<Args, T> {
-    pub(crate) fn new(preserve_args_side_effects: bool, matcher: T) -> Self {
+    pub(crate) fn new(
+        preserve_args_side_effects: bool,
+        matcher: T,
+        identifier_generator: IdentifierGenerator,
+    ) -> Self {
         Self {
             identifier_tracker: Default::default(),
             global_mappings: Default::default(),
             global_counter: 0,
+            identifier_generator,
             evaluator: Default::default(),
             preserve_args_side_effects,
             matcher,
@@ -67,7 +147,7 @@ impl<Args, T: CallMatch<Args>> RemoveFunctionCallProcessor<Args, T> {
     }
 
     pub(crate) fn extract_reserved_globals(&mut self) -> Option<Statement> {
-        let (variables, values) = self.global_mappings.drain().fold(
+        let (variables, values) = self.global_mappings.drain(..).fold(
             (Vec::new(), Vec::new()),
             |(mut variables, mut values), (global, reserved_name)| {
                 variables.push(TypedIdentifier::new(reserved_name));
@@ -83,52 +163,10 @@ impl<Args, T: CallMatch<Args>> RemoveFunctionCallProcessor<Args, T> {
         }
     }
 
-    fn preserve_side_effects(&self, arguments: &Arguments) -> Vec<Expression> {
-        match arguments {
-            Arguments::Tuple(tuple) => tuple
-                .iter_values()
-                .filter(|value| self.evaluator.has_side_effects(value))
-                .cloned()
-                .collect(),
-            Arguments::Table(table) => {
-                let mut expressions = Vec::new();
-
-                for entry in table.iter_entries() {
-                    match entry {
-                        TableEntry::Field(field) => {
-                            let expression = field.get_value();
-                            if self.evaluator.has_side_effects(expression) {
-                                expressions.push(expression.clone());
-                            }
-                        }
-                        TableEntry::Index(index) => {
-                            let key = index.get_key();
-                            let value = index.get_value();
-
-                            if self.evaluator.has_side_effects(key) {
-                                expressions.push(key.clone());
-                            }
-                            if self.evaluator.has_side_effects(value) {
-                                expressions.push(value.clone());
-                            }
-                        }
-                        TableEntry::Value(value) => {
-                            if self.evaluator.has_side_effects(value) {
-                                expressions.push(value.clone());
-                            }
-                        }
-                    }
-                }
-
-                expressions
-            }
-            Arguments::String(_) => Vec::new(),
-        }
-    }
-
     fn get_reserved_global(&mut self) -> String {
         self.global_counter += 1;
-        format!("__DARKLUA_REMOVE_CALL_RESERVED_{}", self.global_counter)
+        self.identifier_generator
+            .generate(&format!("REMOVE_CALL_RESERVED_{}", self.global_counter))
     }
 }
 
@@ -155,7 +193,10 @@ impl<Args, T: CallMatch<Args>> NodeProcessor for RemoveFunctionCallProcessor<Arg
                     .matches(&self.identifier_tracker, call.get_prefix())
             {
                 *statement = if self.preserve_args_side_effects {
-                    expressions_as_statement(self.preserve_side_effects(call.get_arguments()))
+                    expressions_as_statement(preserve_arguments_side_effects(
+                        &self.evaluator,
+                        call.get_arguments(),
+                    ))
                 } else {
                     DoStatement::default().into()
                 };
@@ -169,6 +210,7 @@ impl<Args, T: CallMatch<Args>> NodeProcessor for RemoveFunctionCallProcessor<Arg
                 && self
                     .matcher
                     .matches(&self.identifier_tracker, call.get_prefix())
+                && self.matcher.can_remove_from_expression(call)
             {
                 let insert_globals = self
                     .matcher
@@ -188,7 +230,10 @@ impl<Args, T: CallMatch<Args>> NodeProcessor for RemoveFunctionCallProcessor<Arg
                     *expression = result;
                 } else {
                     *expression = if self.preserve_args_side_effects {
-                        expressions_as_expression(self.preserve_side_effects(call.get_arguments()))
+                        expressions_as_expression(preserve_arguments_side_effects(
+                            &self.evaluator,
+                            call.get_arguments(),
+                        ))
                     } else {
                         Expression::nil()
                     };
@@ -4,6 +4,7 @@ use crate::nodes::*;
 use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType,
 };
 
 #[derive(Debug, Default)]
@@ -78,6 +79,11 @@ impl NodeProcessor for RemoveCommentProcessor {
         type_declaration.clear_comments();
     }
 
+    fn process_type_function(&mut self, type_function: &mut TypeFunctionStatement) {
+        type_function.clear_comments();
+        DefaultVisitor::visit_block(type_function.mutate_block(), self);
+    }
+
     fn process_expression(&mut self, expression: &mut Expression) {
         match expression {
             Expression::False(token)
@@ -230,27 +236,27 @@ impl NodeProcessor for RemoveCommentProcessor {
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct FilterCommentProcessor<'a> {
+/// A processor that only removes the comments for which the `keep` function
+/// returns false.
+pub(crate) struct FilterCommentProcessor<'a, F> {
     original_code: &'a str,
-    except: &'a Vec<Regex>,
+    keep: F,
 }
 
-impl<'a> FilterCommentProcessor<'a> {
-    pub(crate) fn new(original_code: &'a str, except: &'a Vec<Regex>) -> Self {
+impl<'a, F: Fn(&str) -> bool> FilterCommentProcessor<'a, F> {
+    pub(crate) fn new(original_code: &'a str, keep: F) -> Self {
         Self {
             original_code,
-            except,
+            keep,
         }
     }
 
     fn ignore_trivia(&self, trivia: &Trivia) -> bool {
-        let content = trivia.read(self.original_code);
-        self.except.iter().any(|pattern| pattern.is_match(content))
+        (self.keep)(trivia.read(self.original_code))
     }
 }
 
-impl NodeProcessor for FilterCommentProcessor<'_> {
+impl<F: Fn(&str) -> bool> NodeProcessor for FilterCommentProcessor<'_, F> {
     fn process_block(&mut self, block: &mut Block) {
         block.filter_comments(|trivia| self.ignore_trivia(trivia));
     }
@@ -322,6 +328,11 @@ impl NodeProcessor for FilterCommentProcessor<'_> {
         type_declaration.filter_comments(|trivia| self.ignore_trivia(trivia));
     }
 
+    fn process_type_function(&mut self, type_function: &mut TypeFunctionStatement) {
+        type_function.filter_comments(|trivia| self.ignore_trivia(trivia));
+        DefaultVisitor::visit_block(type_function.mutate_block(), self);
+    }
+
     fn process_expression(&mut self, expression: &mut Expression) {
         match expression {
             Expression::False(token)
@@ -488,7 +499,10 @@ impl FlawlessRule for RemoveComments {
             let mut processor = RemoveCommentProcessor::default();
             DefaultVisitor::visit_block(block, &mut processor);
         } else {
-            let mut processor = FilterCommentProcessor::new(context.original_code(), &self.except);
+            let mut processor =
+                FilterCommentProcessor::new(context.original_code(), |content: &str| {
+                    self.except.iter().any(|pattern| pattern.is_match(content))
+                });
             DefaultVisitor::visit_block(block, &mut processor);
         }
     }
@@ -515,6 +529,13 @@ impl RuleConfiguration for RemoveComments {
     fn serialize_to_properties(&self) -> RuleProperties {
         RuleProperties::new()
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("except", RulePropertyType::StringList)
+                .with_default(Vec::<String>::new()),
+        ]
+    }
 }
 
 #[cfg(test)]
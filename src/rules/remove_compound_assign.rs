@@ -2,26 +2,96 @@ use std::ops::{Deref, DerefMut};
 
 use crate::nodes::{
     AssignStatement, BinaryExpression, Block, CompoundAssignStatement, DoStatement, Expression,
-    FieldExpression, IndexExpression, LocalAssignStatement, Prefix, Statement, Variable,
+    FieldExpression, IndexExpression, InterpolationSegment, LocalAssignStatement, Prefix,
+    Statement, Variable,
+};
+use crate::process::{
+    DefaultVisitor, Evaluator, IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor,
 };
-use crate::process::{DefaultVisitor, IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
 };
 
 use super::{verify_no_rule_properties, RemoveCommentProcessor, RemoveWhitespacesProcessor};
 
+/// Returns true if evaluating the expression more than once could produce
+/// different values, because it contains a function call or a table or function
+/// constructor.
+fn may_create_new_value(expression: &Expression) -> bool {
+    match expression {
+        Expression::Table(_) | Expression::Function(_) | Expression::Call(_) => true,
+        Expression::False(_)
+        | Expression::Identifier(_)
+        | Expression::Nil(_)
+        | Expression::Number(_)
+        | Expression::String(_)
+        | Expression::True(_)
+        | Expression::VariableArguments(_) => false,
+        Expression::Binary(binary) => {
+            may_create_new_value(binary.left()) || may_create_new_value(binary.right())
+        }
+        Expression::Field(field) => prefix_may_create_new_value(field.get_prefix()),
+        Expression::If(if_expression) => {
+            may_create_new_value(if_expression.get_condition())
+                || may_create_new_value(if_expression.get_result())
+                || if_expression.iter_branches().any(|branch| {
+                    may_create_new_value(branch.get_condition())
+                        || may_create_new_value(branch.get_result())
+                })
+                || may_create_new_value(if_expression.get_else_result())
+        }
+        Expression::Index(index) => {
+            prefix_may_create_new_value(index.get_prefix())
+                || may_create_new_value(index.get_index())
+        }
+        Expression::InterpolatedString(interpolated_string) => interpolated_string
+            .iter_segments()
+            .any(|segment| match segment {
+                InterpolationSegment::String(_) => false,
+                InterpolationSegment::Value(value) => may_create_new_value(value.get_expression()),
+            }),
+        Expression::Parenthese(parenthese) => may_create_new_value(parenthese.inner_expression()),
+        Expression::TypeCast(type_cast) => may_create_new_value(type_cast.get_expression()),
+        Expression::Unary(unary) => may_create_new_value(unary.get_expression()),
+    }
+}
+
+fn prefix_may_create_new_value(prefix: &Prefix) -> bool {
+    match prefix {
+        Prefix::Identifier(_) => false,
+        Prefix::Call(_) => true,
+        Prefix::Field(field) => prefix_may_create_new_value(field.get_prefix()),
+        Prefix::Index(index) => {
+            prefix_may_create_new_value(index.get_prefix())
+                || may_create_new_value(index.get_index())
+        }
+        Prefix::Parenthese(parenthese) => may_create_new_value(parenthese.inner_expression()),
+    }
+}
+
 struct Processor {
     identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
     remove_comments: RemoveCommentProcessor,
     remove_spaces: RemoveWhitespacesProcessor,
+    variable_prefix: String,
 }
 
 impl Processor {
+    fn new(identifier_prefix: &str) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::new(),
+            evaluator: Evaluator::default(),
+            remove_comments: RemoveCommentProcessor::default(),
+            remove_spaces: RemoveWhitespacesProcessor::default(),
+            variable_prefix: format!("{}VAR", identifier_prefix),
+        }
+    }
+
     #[inline]
     fn generate_variable(&mut self) -> String {
         self.identifier_tracker
-            .generate_identifier_with_prefix("__DARKLUA_VAR")
+            .generate_identifier_with_prefix(self.variable_prefix.as_str())
     }
 
     fn simplify_prefix(&self, prefix: &Prefix) -> Option<Prefix> {
@@ -46,63 +116,31 @@ impl Processor {
         }
     }
 
+    fn needs_variable(&self, expression: &Expression) -> bool {
+        self.evaluator.has_side_effects(expression) || may_create_new_value(expression)
+    }
+
+    fn prefix_needs_variable(&self, prefix: &Prefix) -> bool {
+        match prefix {
+            Prefix::Identifier(_) => false,
+            Prefix::Call(_) | Prefix::Field(_) | Prefix::Index(_) | Prefix::Parenthese(_) => {
+                self.needs_variable(&Expression::from(prefix.clone()))
+            }
+        }
+    }
+
     fn replace_with(&mut self, assignment: &CompoundAssignStatement) -> Option<Statement> {
         match assignment.get_variable() {
             Variable::Index(index) => {
-                let prefix_assignment = match index.get_prefix() {
-                    Prefix::Identifier(_) => None,
-                    Prefix::Parenthese(parenthese)
-                        if matches!(
-                            parenthese.inner_expression(),
-                            Expression::False(_)
-                                | Expression::Identifier(_)
-                                | Expression::Number(_)
-                                | Expression::Nil(_)
-                                | Expression::String(_)
-                                | Expression::True(_)
-                                | Expression::VariableArguments(_)
-                        ) =>
-                    {
-                        None
-                    }
-                    Prefix::Call(_)
-                    | Prefix::Field(_)
-                    | Prefix::Index(_)
-                    | Prefix::Parenthese(_) => Some(self.generate_variable()),
+                let prefix_assignment = if self.prefix_needs_variable(index.get_prefix()) {
+                    Some(self.generate_variable())
+                } else {
+                    None
                 };
-                let index_assignment = match index.get_index() {
-                    Expression::False(_)
-                    | Expression::Identifier(_)
-                    | Expression::Number(_)
-                    | Expression::Nil(_)
-                    | Expression::InterpolatedString(_)
-                    | Expression::String(_)
-                    | Expression::True(_)
-                    | Expression::VariableArguments(_) => None,
-                    Expression::Parenthese(parenthese)
-                        if matches!(
-                            parenthese.inner_expression(),
-                            Expression::False(_)
-                                | Expression::Identifier(_)
-                                | Expression::Number(_)
-                                | Expression::Nil(_)
-                                | Expression::String(_)
-                                | Expression::True(_)
-                                | Expression::VariableArguments(_)
-                        ) =>
-                    {
-                        None
-                    }
-                    Expression::Binary(_)
-                    | Expression::Call(_)
-                    | Expression::Field(_)
-                    | Expression::Function(_)
-                    | Expression::If(_)
-                    | Expression::Index(_)
-                    | Expression::Parenthese(_)
-                    | Expression::Table(_)
-                    | Expression::TypeCast(_)
-                    | Expression::Unary(_) => Some(self.generate_variable()),
+                let index_assignment = if self.needs_variable(index.get_index()) {
+                    Some(self.generate_variable())
+                } else {
+                    None
                 };
 
                 match (prefix_assignment, index_assignment) {
@@ -154,24 +192,10 @@ impl Processor {
             }
             Variable::Field(field) => match field.get_prefix() {
                 Prefix::Identifier(_) => None,
-                Prefix::Parenthese(parenthese)
-                    if matches!(
-                        parenthese.inner_expression(),
-                        Expression::False(_)
-                            | Expression::Identifier(_)
-                            | Expression::Number(_)
-                            | Expression::Nil(_)
-                            | Expression::String(_)
-                            | Expression::True(_)
-                            | Expression::VariableArguments(_)
-                    ) =>
-                {
-                    let new_prefix =
-                        if let Expression::Identifier(identifier) = parenthese.inner_expression() {
-                            Prefix::from(identifier.clone())
-                        } else {
-                            parenthese.clone().into()
-                        };
+                prefix if !self.prefix_needs_variable(prefix) => {
+                    let new_prefix = self
+                        .simplify_prefix(prefix)
+                        .unwrap_or_else(|| prefix.clone());
                     let new_variable = FieldExpression::new(new_prefix, field.get_field().clone());
 
                     Some(self.create_new_assignment_with_variable(
@@ -180,19 +204,15 @@ impl Processor {
                         Some(new_variable.into()),
                     ))
                 }
-                Prefix::Call(_) | Prefix::Field(_) | Prefix::Index(_) | Prefix::Parenthese(_) => {
+                prefix => {
                     let identifier = self.generate_variable();
                     let new_variable = FieldExpression::new(
                         Prefix::from_name(&identifier),
                         field.get_field().clone(),
                     );
 
-                    let assign = LocalAssignStatement::from_variable(identifier).with_value(
-                        match field.get_prefix().clone() {
-                            Prefix::Parenthese(parenthese) => parenthese.into_inner_expression(),
-                            prefix => prefix.into(),
-                        },
-                    );
+                    let assign = LocalAssignStatement::from_variable(identifier)
+                        .with_value(self.remove_parentheses(prefix.clone()));
 
                     Some(self.create_do_assignment(assignment, assign, new_variable))
                 }
@@ -256,16 +276,6 @@ impl Processor {
     }
 }
 
-impl Default for Processor {
-    fn default() -> Self {
-        Self {
-            identifier_tracker: IdentifierTracker::new(),
-            remove_comments: RemoveCommentProcessor::default(),
-            remove_spaces: RemoveWhitespacesProcessor::default(),
-        }
-    }
-}
-
 impl Deref for Processor {
     type Target = IdentifierTracker;
 
@@ -298,15 +308,19 @@ pub const REMOVE_COMPOUND_ASSIGNMENT_RULE_NAME: &str = "remove_compound_assignme
 pub struct RemoveCompoundAssignment {}
 
 impl RemoveCompoundAssignment {
-    pub(crate) fn replace_compound_assignment(&self, statement: &mut Statement) {
-        let mut processor = Processor::default();
+    pub(crate) fn replace_compound_assignment(
+        &self,
+        statement: &mut Statement,
+        identifier_prefix: &str,
+    ) {
+        let mut processor = Processor::new(identifier_prefix);
         ScopeVisitor::visit_statement(statement, &mut processor);
     }
 }
 
 impl FlawlessRule for RemoveCompoundAssignment {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
-        let mut processor = Processor::default();
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = Processor::new(context.generated_identifier_prefix());
         ScopeVisitor::visit_block(block, &mut processor);
     }
 }
@@ -2,57 +2,102 @@ use std::fmt::Debug;
 use std::mem;
 
 use crate::nodes::{
-    AssignStatement, Block, GenericForStatement, Identifier, IfStatement, LastStatement,
-    LocalAssignStatement, NumericForStatement, RepeatStatement, UnaryExpression, UnaryOperator,
-    WhileStatement,
+    AssignStatement, Block, Expression, GenericForStatement, Identifier, IfStatement,
+    LastStatement, LocalAssignStatement, NumericForStatement, RepeatStatement, Statement,
+    UnaryExpression, UnaryOperator, Variable, WhileStatement,
+};
+use crate::process::utils::IdentifierGenerator;
+use crate::process::{
+    DefaultPostVisitor, DefaultVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    NodeVisitor,
 };
-use crate::process::{DefaultPostVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor};
 use crate::rules::{Context, RuleConfiguration, RuleConfigurationError, RuleProperties};
 
 use super::{verify_no_rule_properties, FlawlessRule};
 
-#[derive(Default)]
 struct Processor {
     loop_stack: Vec<Option<LoopData>>,
     loop_identifier_count: u16,
+    identifier_generator: IdentifierGenerator,
 }
 
 struct LoopData {
     has_continue_statement: bool,
-    loop_break_id: u16,
+    identifier: Identifier,
+    until_identifier: Identifier,
 }
 
 impl LoopData {
-    fn new(loop_break_id: u16) -> Self {
-        Self {
-            has_continue_statement: false,
-            loop_break_id,
-        }
+    fn get_identifier(&self) -> Identifier {
+        self.identifier.clone()
     }
 
-    fn get_identifier(&self) -> Identifier {
-        Identifier::new(format!("__DARKLUA_CONTINUE_{}", self.loop_break_id))
+    fn get_until_identifier(&self) -> Identifier {
+        self.until_identifier.clone()
     }
 }
 
 impl Processor {
+    fn new(identifier_generator: IdentifierGenerator) -> Self {
+        Self {
+            loop_stack: Vec::new(),
+            loop_identifier_count: 0,
+            identifier_generator,
+        }
+    }
+
     fn push_loop(&mut self) {
         self.loop_identifier_count += 1;
-        self.loop_stack
-            .push(Some(LoopData::new(self.loop_identifier_count)));
+        let loop_data = LoopData {
+            has_continue_statement: false,
+            identifier: Identifier::new(
+                self.identifier_generator
+                    .generate(&format!("CONTINUE_{}", self.loop_identifier_count)),
+            ),
+            until_identifier: Identifier::new(
+                self.identifier_generator
+                    .generate(&format!("REPEAT_UNTIL_{}", self.loop_identifier_count)),
+            ),
+        };
+        self.loop_stack.push(Some(loop_data));
     }
 
     fn push_no_loop(&mut self) {
         self.loop_stack.push(None);
     }
 
-    fn wrap_loop_block_if_needed(&mut self, block: &mut Block) {
+    /// Wraps the loop block in a `repeat ... until true` loop so that `continue`
+    /// statements can be replaced with `break` statements. For `repeat` loops, the
+    /// condition is given so that it can be evaluated inside the wrapper, where
+    /// the locals of the loop block are still in scope.
+    fn wrap_loop_block_if_needed(
+        &mut self,
+        block: &mut Block,
+        until_condition: Option<&mut Expression>,
+    ) {
         if let Some(loop_data) = self.loop_stack.pop().flatten() {
             if !loop_data.has_continue_statement {
                 return;
             }
             let mut current_loop_block = mem::take(block);
 
+            let until_identifier = until_condition.map(|condition| {
+                let until_identifier = loop_data.get_until_identifier();
+
+                let mut processor = AssignUntilCondition {
+                    continue_identifier: loop_data.get_identifier(),
+                    until_identifier: until_identifier.clone(),
+                    condition: mem::replace(condition, until_identifier.clone().into()),
+                };
+                DefaultVisitor::visit_block(&mut current_loop_block, &mut processor);
+
+                if current_loop_block.get_last_statement().is_none() {
+                    current_loop_block.push_statement(processor.create_assignment());
+                }
+
+                until_identifier
+            });
+
             if current_loop_block.get_last_statement().is_none() {
                 current_loop_block.push_statement(AssignStatement::from_variable(
                     loop_data.get_identifier(),
@@ -60,22 +105,69 @@ impl Processor {
                 ));
             }
 
-            let new_block = Block::default()
-                .with_statement(
-                    LocalAssignStatement::from_variable(loop_data.get_identifier())
-                        .with_value(false),
-                )
-                .with_statement(RepeatStatement::new(current_loop_block, true))
-                .with_statement(IfStatement::create(
-                    UnaryExpression::new(UnaryOperator::Not, loop_data.get_identifier()),
-                    LastStatement::Break(None),
-                ));
+            let mut new_block = Block::default().with_statement(
+                LocalAssignStatement::from_variable(loop_data.get_identifier()).with_value(false),
+            );
+
+            if let Some(until_identifier) = until_identifier {
+                new_block.push_statement(LocalAssignStatement::from_variable(until_identifier));
+            }
+
+            new_block.push_statement(RepeatStatement::new(current_loop_block, true));
+            new_block.push_statement(IfStatement::create(
+                UnaryExpression::new(UnaryOperator::Not, loop_data.get_identifier()),
+                LastStatement::Break(None),
+            ));
 
             *block = new_block;
         }
     }
 }
 
+/// Assigns the condition of a `repeat` loop before each `break` statement that
+/// replaced a `continue` statement.
+struct AssignUntilCondition {
+    continue_identifier: Identifier,
+    until_identifier: Identifier,
+    condition: Expression,
+}
+
+impl AssignUntilCondition {
+    fn create_assignment(&self) -> AssignStatement {
+        AssignStatement::from_variable(self.until_identifier.clone(), self.condition.clone())
+    }
+
+    fn is_continue_assignment(&self, statement: &Statement) -> bool {
+        match statement {
+            Statement::Assign(assign) => matches!(
+                assign.get_variables().as_slice(),
+                [Variable::Identifier(identifier)]
+                    if identifier.get_name() == self.continue_identifier.get_name()
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl NodeProcessor for AssignUntilCondition {
+    fn process_block(&mut self, block: &mut Block) {
+        if !matches!(block.get_last_statement(), Some(LastStatement::Break(_))) {
+            return;
+        }
+
+        // the continue variable is assigned right before the `break` statement
+        if block
+            .iter_statements()
+            .last()
+            .filter(|statement| self.is_continue_assignment(statement))
+            .is_some()
+        {
+            let index = block.statements_len() - 1;
+            block.insert_statement(index, self.create_assignment());
+        }
+    }
+}
+
 impl NodeProcessor for Processor {
     fn process_generic_for_statement(&mut self, _: &mut GenericForStatement) {
         self.push_loop();
@@ -138,19 +230,20 @@ impl NodeProcessor for Processor {
 
 impl NodePostProcessor for Processor {
     fn process_after_generic_for_statement(&mut self, statement: &mut GenericForStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        self.wrap_loop_block_if_needed(statement.mutate_block(), None);
     }
 
     fn process_after_numeric_for_statement(&mut self, statement: &mut NumericForStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        self.wrap_loop_block_if_needed(statement.mutate_block(), None);
     }
 
     fn process_after_repeat_statement(&mut self, statement: &mut RepeatStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        let (block, condition) = statement.mutate_block_and_condition();
+        self.wrap_loop_block_if_needed(block, Some(condition));
     }
 
     fn process_after_while_statement(&mut self, statement: &mut WhileStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        self.wrap_loop_block_if_needed(statement.mutate_block(), None);
     }
 
     fn process_after_function_statement(&mut self, _: &mut crate::nodes::FunctionStatement) {
@@ -169,8 +262,8 @@ pub const REMOVE_CONTINUE_RULE_NAME: &str = "remove_continue";
 pub struct RemoveContinue {}
 
 impl FlawlessRule for RemoveContinue {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
-        let mut processor = Processor::default();
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = Processor::new(context.identifier_generator(block));
         DefaultPostVisitor::visit_block(block, &mut processor);
     }
 }
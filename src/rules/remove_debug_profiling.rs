@@ -2,6 +2,7 @@ use crate::nodes::{Block, Prefix};
 use crate::process::{IdentifierTracker, NodeVisitor, ScopeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType,
 };
 
 use super::remove_call_match::RemoveFunctionCallProcessor;
@@ -43,9 +44,12 @@ fn should_remove_call(identifiers: &IdentifierTracker, prefix: &Prefix) -> bool
 }
 
 impl FlawlessRule for RemoveDebugProfiling {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
-        let mut processor =
-            RemoveFunctionCallProcessor::new(self.preserve_args_side_effects, should_remove_call);
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = RemoveFunctionCallProcessor::new(
+            self.preserve_args_side_effects,
+            should_remove_call,
+            context.identifier_generator(block),
+        );
         ScopeVisitor::visit_block(block, &mut processor);
 
         if let Some(statement) = processor.extract_reserved_globals() {
@@ -81,6 +85,14 @@ impl RuleConfiguration for RemoveDebugProfiling {
 
         properties
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![RulePropertyDescription::new(
+            "preserve_arguments_side_effects",
+            RulePropertyType::Boolean,
+        )
+        .with_default(true)]
+    }
 }
 
 #[cfg(test)]
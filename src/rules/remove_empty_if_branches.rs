@@ -0,0 +1,164 @@
+use std::mem;
+
+use crate::nodes::{BinaryExpression, BinaryOperator, Block, Expression, IfStatement, Statement};
+use crate::process::{
+    DefaultPostVisitor, Evaluator, NodePostProcessor, NodePostVisitor, NodeProcessor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+use crate::utils::expressions_as_statement;
+
+use super::verify_no_rule_properties;
+
+enum FilterResult {
+    Keep,
+    Remove,
+    Replace(Statement),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn simplify_if_statement(&self, if_statement: &mut IfStatement) -> FilterResult {
+        if let Some(else_block) = if_statement.get_else_block() {
+            if else_block.is_empty() {
+                if_statement.take_else_block();
+            }
+        }
+
+        if if_statement.get_else_block().is_some() {
+            return FilterResult::Keep;
+        }
+
+        // an empty branch prevents the next branches from running when its
+        // condition is truthy, so only the empty branches at the end can be
+        // removed
+        let branches = if_statement.mutate_branches();
+        while branches.len() > 1 {
+            let last = branches.last().expect("branches should not be empty");
+
+            if last.get_block().is_empty() && !self.evaluator.has_side_effects(last.get_condition())
+            {
+                branches.pop();
+            } else {
+                break;
+            }
+        }
+
+        if if_statement
+            .iter_branches()
+            .any(|branch| !branch.get_block().is_empty())
+        {
+            return FilterResult::Keep;
+        }
+
+        let conditions = if_statement
+            .mutate_branches()
+            .drain(..)
+            .map(|mut branch| mem::replace(branch.mutate_condition(), Expression::nil()))
+            .collect::<Vec<_>>();
+
+        if conditions
+            .iter()
+            .all(|condition| !self.evaluator.has_side_effects(condition))
+        {
+            return FilterResult::Remove;
+        }
+
+        // the conditions are evaluated until one of them is truthy, which is
+        // exactly how a chain of `or` operations evaluates its operands
+        let condition = conditions
+            .into_iter()
+            .reduce(|current, condition| {
+                BinaryExpression::new(BinaryOperator::Or, current, condition).into()
+            })
+            .expect("if statement should have at least one branch");
+
+        FilterResult::Replace(expressions_as_statement(vec![condition]))
+    }
+}
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_block(&mut self, block: &mut Block) {
+        block.filter_mut_statements(|statement| {
+            if let Statement::If(if_statement) = statement {
+                match self.simplify_if_statement(if_statement) {
+                    FilterResult::Keep => true,
+                    FilterResult::Remove => false,
+                    FilterResult::Replace(new_statement) => {
+                        *statement = new_statement;
+                        true
+                    }
+                }
+            } else {
+                true
+            }
+        });
+    }
+}
+
+pub const REMOVE_EMPTY_IF_BRANCHES_RULE_NAME: &str = "remove_empty_if_branches";
+
+/// A rule that removes empty `else` blocks and empty `elseif` branches, and removes
+/// if statements that do not contain any code.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemoveEmptyIfBranches {}
+
+impl FlawlessRule for RemoveEmptyIfBranches {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        DefaultPostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for RemoveEmptyIfBranches {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REMOVE_EMPTY_IF_BRANCHES_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> RemoveEmptyIfBranches {
+        RemoveEmptyIfBranches::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_remove_empty_if_branches", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_empty_if_branches',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -12,6 +12,7 @@ use crate::rules::{
 
 struct RemoveFloorDivisionProcessor {
     math_floor_identifier: String,
+    identifier_prefix: String,
     define_math_floor: bool,
     identifier_tracker: IdentifierTracker,
 }
@@ -34,9 +35,10 @@ const DEFAULT_MATH_LIBRARY: &str = "math";
 const DEFAULT_MATH_FLOOR_NAME: &str = "floor";
 
 impl RemoveFloorDivisionProcessor {
-    fn new(math_floor_identifier: impl Into<String>) -> Self {
+    fn new(math_floor_identifier: impl Into<String>, identifier_prefix: impl Into<String>) -> Self {
         Self {
             math_floor_identifier: math_floor_identifier.into(),
+            identifier_prefix: identifier_prefix.into(),
             define_math_floor: false,
             identifier_tracker: Default::default(),
         }
@@ -64,7 +66,8 @@ impl NodeProcessor for RemoveFloorDivisionProcessor {
             Statement::CompoundAssign(assign_statement)
                 if assign_statement.get_operator() == CompoundOperator::DoubleSlash =>
             {
-                RemoveCompoundAssignment::default().replace_compound_assignment(statement);
+                RemoveCompoundAssignment::default()
+                    .replace_compound_assignment(statement, &self.identifier_prefix);
             }
             _ => {}
         }
@@ -90,16 +93,19 @@ pub const REMOVE_FLOOR_DIVISION_RULE_NAME: &str = "remove_floor_division";
 pub struct RemoveFloorDivision {}
 
 impl FlawlessRule for RemoveFloorDivision {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
-        const MATH_FLOOR_IDENTIFIER: &str = "__DARKLUA_MATH_FLOOR";
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let math_floor_identifier = context.identifier_generator(block).generate("MATH_FLOOR");
 
-        let mut processor = RemoveFloorDivisionProcessor::new(MATH_FLOOR_IDENTIFIER);
+        let mut processor = RemoveFloorDivisionProcessor::new(
+            &math_floor_identifier,
+            context.generated_identifier_prefix(),
+        );
         ScopeVisitor::visit_block(block, &mut processor);
 
         if processor.define_math_floor {
             block.insert_statement(
                 0,
-                LocalAssignStatement::from_variable(MATH_FLOOR_IDENTIFIER).with_value(
+                LocalAssignStatement::from_variable(math_floor_identifier).with_value(
                     FieldExpression::new(
                         Prefix::from_name(DEFAULT_MATH_LIBRARY),
                         DEFAULT_MATH_FLOOR_NAME,
@@ -1,6 +1,6 @@
 use crate::nodes::{
     BinaryExpression, BinaryOperator, Block, Expression, IndexExpression, TableEntry,
-    TableExpression,
+    TableExpression, UnaryExpression, UnaryOperator,
 };
 use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
 use crate::rules::{
@@ -26,24 +26,46 @@ impl Processor {
         .into()
     }
 
+    fn is_truthy(&self, expression: &Expression) -> bool {
+        self.evaluator
+            .evaluate(expression)
+            .is_truthy()
+            .unwrap_or_default()
+    }
+
+    fn negate(&self, expression: Expression) -> Expression {
+        match expression {
+            Expression::Unary(unary) if unary.operator() == UnaryOperator::Not => {
+                unary.get_expression().clone()
+            }
+            _ => UnaryExpression::new(UnaryOperator::Not, expression).into(),
+        }
+    }
+
+    /// Converts a branch using the shortest form that gives the same result:
+    /// - `condition and result or else_result` when `result` is always truthy
+    /// - `not condition and else_result or result` when `else_result` is always truthy
+    /// - `(condition and { result } or { else_result })[1]` otherwise
     fn convert_if_branch(
         &self,
         condition: Expression,
         result: Expression,
         else_result: Expression,
     ) -> Expression {
-        if self
-            .evaluator
-            .evaluate(&result)
-            .is_truthy()
-            .unwrap_or_default()
-        {
+        if self.is_truthy(&result) {
             BinaryExpression::new(
                 BinaryOperator::Or,
                 BinaryExpression::new(BinaryOperator::And, condition, result),
                 else_result,
             )
             .into()
+        } else if self.is_truthy(&else_result) {
+            BinaryExpression::new(
+                BinaryOperator::Or,
+                BinaryExpression::new(BinaryOperator::And, self.negate(condition), else_result),
+                result,
+            )
+            .into()
         } else {
             IndexExpression::new(
                 Expression::from(BinaryExpression::new(
@@ -65,7 +87,10 @@ impl Processor {
 impl NodeProcessor for Processor {
     fn process_expression(&mut self, expression: &mut Expression) {
         if let Expression::If(if_expression) = expression {
-            let else_result = if_expression.iter_branches().fold(
+            // the last branch is converted first, since it becomes the else
+            // result of the previous branch
+            let branches: Vec<_> = if_expression.iter_branches().collect();
+            let else_result = branches.into_iter().rev().fold(
                 if_expression.get_else_result().clone(),
                 |else_result, branch| {
                     self.convert_if_branch(
@@ -87,7 +112,8 @@ impl NodeProcessor for Processor {
 
 pub const REMOVE_IF_EXPRESSION_RULE_NAME: &str = "remove_if_expression";
 
-/// A rule that removes trailing `nil` in local assignments.
+/// A rule that replaces if expressions with equivalent expressions that can be used
+/// in Lua versions without if expressions.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct RemoveIfExpression {}
 
@@ -8,6 +8,7 @@ use crate::nodes::{
 use crate::process::{IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -164,14 +165,15 @@ pub struct RemoveInterpolatedString {
 }
 
 impl FlawlessRule for RemoveInterpolatedString {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
-        const STRING_FORMAT_IDENTIFIER: &str = "__DARKLUA_STR_FMT";
-        const TOSTRING_IDENTIFIER: &str = "__DARKLUA_TO_STR";
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut identifier_generator = context.identifier_generator(block);
+        let string_format_identifier = identifier_generator.generate("STR_FMT");
+        let tostring_identifier = identifier_generator.generate("TO_STR");
 
         let mut processor = RemoveInterpolatedStringProcessor::new(
             self.strategy,
-            STRING_FORMAT_IDENTIFIER,
-            TOSTRING_IDENTIFIER,
+            &string_format_identifier,
+            &tostring_identifier,
         );
         ScopeVisitor::visit_block(block, &mut processor);
 
@@ -180,7 +182,7 @@ impl FlawlessRule for RemoveInterpolatedString {
             let mut values = Vec::new();
 
             if processor.define_string_format {
-                variables.push(TypedIdentifier::new(STRING_FORMAT_IDENTIFIER));
+                variables.push(TypedIdentifier::new(string_format_identifier));
                 values.push(
                     FieldExpression::new(
                         Prefix::from_name(DEFAULT_STRING_LIBRARY),
@@ -191,7 +193,7 @@ impl FlawlessRule for RemoveInterpolatedString {
             }
 
             if processor.define_tostring {
-                variables.push(TypedIdentifier::new(TOSTRING_IDENTIFIER));
+                variables.push(TypedIdentifier::new(tostring_identifier));
                 values.push(Identifier::new(DEFAULT_TOSTRING_IDENTIFIER).into());
             }
 
@@ -242,6 +244,14 @@ impl RuleConfiguration for RemoveInterpolatedString {
 
         properties
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![RulePropertyDescription::new(
+            "strategy",
+            RulePropertyType::Choice(&["string", "tostring"]),
+        )
+        .with_default("string")]
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,104 @@
+use crate::nodes::{
+    Block, FunctionExpression, FunctionStatement, LastStatement, LocalFunctionStatement,
+    TypeFunctionStatement,
+};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+/// Removes the last statement of a function body when it is a `return` without
+/// any value, since reaching the end of the function returns nothing too.
+fn remove_trailing_return(block: &mut Block) {
+    if matches!(
+        block.get_last_statement(),
+        Some(LastStatement::Return(statement)) if statement.is_empty()
+    ) {
+        block.take_last_statement();
+    }
+}
+
+#[derive(Debug, Default)]
+struct Processor;
+
+impl NodeProcessor for Processor {
+    fn process_function_expression(&mut self, function: &mut FunctionExpression) {
+        remove_trailing_return(function.mutate_block());
+    }
+
+    fn process_function_statement(&mut self, function: &mut FunctionStatement) {
+        remove_trailing_return(function.mutate_block());
+    }
+
+    fn process_local_function_statement(&mut self, function: &mut LocalFunctionStatement) {
+        remove_trailing_return(function.mutate_block());
+    }
+
+    fn process_type_function(&mut self, function: &mut TypeFunctionStatement) {
+        remove_trailing_return(function.mutate_block());
+    }
+}
+
+pub const REMOVE_REDUNDANT_RETURN_RULE_NAME: &str = "remove_redundant_return";
+
+/// A rule that removes `return` statements without values at the end of function
+/// bodies and at the end of the file.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemoveRedundantReturn {}
+
+impl FlawlessRule for RemoveRedundantReturn {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        remove_trailing_return(block);
+
+        let mut processor = Processor;
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for RemoveRedundantReturn {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REMOVE_REDUNDANT_RETURN_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> RemoveRedundantReturn {
+        RemoveRedundantReturn::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_remove_redundant_return", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_redundant_return',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
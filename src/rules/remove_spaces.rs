@@ -78,6 +78,11 @@ impl NodeProcessor for RemoveWhitespacesProcessor {
         type_declaration.clear_whitespaces();
     }
 
+    fn process_type_function(&mut self, type_function: &mut TypeFunctionStatement) {
+        type_function.clear_whitespaces();
+        DefaultVisitor::visit_block(type_function.mutate_block(), self);
+    }
+
     fn process_expression(&mut self, expression: &mut Expression) {
         match expression {
             Expression::False(token)
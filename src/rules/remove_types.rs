@@ -4,7 +4,7 @@ use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
 };
 
-use super::verify_no_rule_properties;
+use super::{verify_no_rule_properties, FilterCommentProcessor};
 
 #[derive(Default)]
 struct RemoveTypesProcessor {
@@ -13,7 +13,12 @@ struct RemoveTypesProcessor {
 
 impl NodeProcessor for RemoveTypesProcessor {
     fn process_block(&mut self, block: &mut Block) {
-        block.filter_statements(|statement| !matches!(statement, Statement::TypeDeclaration(_)));
+        block.filter_statements(|statement| {
+            !matches!(
+                statement,
+                Statement::TypeDeclaration(_) | Statement::TypeFunction(_)
+            )
+        });
     }
 
     fn process_local_assign_statement(&mut self, local_assign: &mut LocalAssignStatement) {
@@ -58,16 +63,29 @@ impl NodeProcessor for RemoveTypesProcessor {
     }
 }
 
+const MODE_DIRECTIVES: [&str; 3] = ["--!strict", "--!nonstrict", "--!nocheck"];
+
+fn is_mode_directive(comment: &str) -> bool {
+    MODE_DIRECTIVES.contains(&comment.trim_end())
+}
+
 pub const REMOVE_TYPES_RULE_NAME: &str = "remove_types";
 
-/// A rule that removes Luau types from all AST nodes.
+/// A rule that removes Luau types from all AST nodes. It also removes the Luau type
+/// checking mode comments (`--!strict`, `--!nonstrict` and `--!nocheck`).
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct RemoveTypes {}
 
 impl FlawlessRule for RemoveTypes {
-    fn flawless_process(&self, block: &mut Block, _: &Context) {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
         let mut processor = RemoveTypesProcessor::default();
         DefaultVisitor::visit_block(block, &mut processor);
+
+        let mut comment_processor =
+            FilterCommentProcessor::new(context.original_code(), |comment: &str| {
+                !is_mode_directive(comment)
+            });
+        DefaultVisitor::visit_block(block, &mut comment_processor);
     }
 }
 
@@ -89,7 +107,11 @@ impl RuleConfiguration for RemoveTypes {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::rules::Rule;
+    use crate::{
+        generator::{LuaGenerator, TokenBasedLuaGenerator},
+        rules::{ContextBuilder, Rule},
+        Parser, Resources,
+    };
 
     use insta::assert_json_snapshot;
 
@@ -114,4 +136,40 @@ mod test {
         );
         pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
     }
+
+    fn process_code(code: &str) -> String {
+        let mut block = Parser::default()
+            .preserve_tokens()
+            .parse(code)
+            .expect("unable to parse code");
+
+        new_rule().flawless_process(
+            &mut block,
+            &ContextBuilder::new(".", &Resources::from_memory(), code).build(),
+        );
+
+        let mut generator = TokenBasedLuaGenerator::new(code);
+        generator.write_block(&block);
+        generator.into_string()
+    }
+
+    #[test]
+    fn removes_strict_mode_comment_and_keeps_other_comments() {
+        let output = process_code(
+            "--!strict\n-- module comment\nlocal value: number = 1 -- one\nreturn value\n",
+        );
+
+        assert!(!output.contains("--!strict"), "{}", output);
+        assert!(output.contains("-- module comment"), "{}", output);
+        assert!(output.contains("-- one"), "{}", output);
+    }
+
+    #[test]
+    fn removes_nonstrict_and_nocheck_mode_comments() {
+        let output = process_code("--!nonstrict\n--!nocheck\n--!native\nreturn nil\n");
+
+        assert!(!output.contains("--!nonstrict"), "{}", output);
+        assert!(!output.contains("--!nocheck"), "{}", output);
+        assert!(output.contains("--!native"), "{}", output);
+    }
 }
@@ -0,0 +1,332 @@
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    Arguments, Block, Expression, FunctionCall, Identifier, LocalFunctionStatement, Prefix,
+    Statement, TupleArguments,
+};
+use crate::process::processors::FindUsage;
+use crate::process::{
+    DefaultVisitor, Evaluator, IdentifierTracker, NodeProcessor, NodeVisitor, Scope, ScopeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+/// Returns the number of parameters to keep, after removing the trailing parameters
+/// that are not referenced in the function body.
+fn get_kept_parameters_count(function: &mut LocalFunctionStatement) -> usize {
+    let mut kept = function.parameters_count();
+
+    if function.is_variadic() {
+        return kept;
+    }
+
+    while kept > 0 {
+        let name = function.get_parameters()[kept - 1].get_name().to_owned();
+        let mut find_usage = FindUsage::new(&name);
+        ScopeVisitor::visit_block(function.mutate_block(), &mut find_usage);
+
+        if find_usage.has_found_usage() {
+            break;
+        }
+        kept -= 1;
+    }
+
+    kept
+}
+
+fn is_call_to(call: &FunctionCall, name: &str, identifier_tracker: &IdentifierTracker) -> bool {
+    call.get_method().is_none()
+        && matches!(
+            call.get_prefix(),
+            Prefix::Identifier(identifier)
+                if identifier.get_name() == name && !identifier_tracker.is_identifier_used(name)
+        )
+}
+
+/// Verifies that every reference to a local function is a call from which the
+/// arguments given to the removed parameters can be dropped.
+struct CallAnalyzer<'a> {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+    name: &'a str,
+    parameters_count: usize,
+    kept_count: usize,
+    references: usize,
+    removable_calls: usize,
+}
+
+impl<'a> CallAnalyzer<'a> {
+    fn new(name: &'a str, parameters_count: usize, kept_count: usize) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            evaluator: Evaluator::default(),
+            name,
+            parameters_count,
+            kept_count,
+            references: 0,
+            removable_calls: 0,
+        }
+    }
+
+    fn can_remove_parameters(&self) -> bool {
+        self.references == self.removable_calls
+    }
+
+    fn can_remove_arguments(&self, arguments: &Arguments) -> bool {
+        match arguments {
+            Arguments::Tuple(tuple) => {
+                if tuple.len() <= self.parameters_count {
+                    if let Some(last) = tuple.iter_values().last() {
+                        // the values of the last argument would fill the removed parameters
+                        if self.evaluator.can_return_multiple_values(last) {
+                            return false;
+                        }
+                    }
+                }
+
+                tuple
+                    .iter_values()
+                    .skip(self.kept_count)
+                    .all(|value| !self.evaluator.has_side_effects(value))
+            }
+            Arguments::String(_) => true,
+            Arguments::Table(table) => {
+                self.kept_count > 0
+                    || !self
+                        .evaluator
+                        .has_side_effects(&Expression::from(table.clone()))
+            }
+        }
+    }
+}
+
+impl Deref for CallAnalyzer<'_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for CallAnalyzer<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for CallAnalyzer<'_> {
+    fn process_variable_expression(&mut self, variable: &mut Identifier) {
+        if variable.get_name() == self.name && !self.is_identifier_used(self.name) {
+            self.references += 1;
+        }
+    }
+
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        if is_call_to(call, self.name, &self.identifier_tracker)
+            && self.can_remove_arguments(call.get_arguments())
+        {
+            self.removable_calls += 1;
+        }
+    }
+}
+
+/// Removes the arguments given to the removed parameters of a local function.
+struct ArgumentRemover<'a> {
+    identifier_tracker: IdentifierTracker,
+    name: &'a str,
+    kept_count: usize,
+}
+
+impl<'a> ArgumentRemover<'a> {
+    fn new(name: &'a str, kept_count: usize) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            name,
+            kept_count,
+        }
+    }
+}
+
+impl Deref for ArgumentRemover<'_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for ArgumentRemover<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for ArgumentRemover<'_> {
+    fn process_function_call(&mut self, call: &mut FunctionCall) {
+        if !is_call_to(call, self.name, &self.identifier_tracker) {
+            return;
+        }
+
+        let arguments = call.mutate_arguments();
+        let arguments_count = match arguments {
+            Arguments::Tuple(tuple) => tuple.len(),
+            Arguments::String(_) | Arguments::Table(_) => 1,
+        };
+
+        if arguments_count <= self.kept_count {
+            return;
+        }
+
+        let tuple = TupleArguments::from(mem::take(arguments));
+        let tokens = tuple.get_tokens().cloned();
+        let mut values = tuple.to_expressions();
+        values.truncate(self.kept_count);
+
+        let length = values.len();
+        let mut new_tuple = values.into_iter().collect::<TupleArguments>();
+        if let Some(mut tokens) = tokens {
+            tokens.commas.truncate(length.saturating_sub(1));
+            new_tuple.set_tokens(tokens);
+        }
+        *arguments = Arguments::Tuple(new_tuple);
+    }
+}
+
+/// Visits the places where a local function can be referenced: its own body, the
+/// statements that follow it and the extra expression of the scope (like the
+/// condition of a repeat statement).
+fn visit_function_scope<T: NodeProcessor + Scope>(
+    block: &mut Block,
+    index: usize,
+    extra: Option<&mut Expression>,
+    processor: &mut T,
+) {
+    if let Some(Statement::LocalFunction(function)) = block.iter_mut_statements().nth(index) {
+        processor.push();
+        for parameter in function.iter_mut_parameters() {
+            processor.insert(parameter.mutate_name());
+        }
+        ScopeVisitor::visit_block(function.mutate_block(), processor);
+        processor.pop();
+    }
+
+    processor.push();
+    for statement in block.iter_mut_statements().skip(index + 1) {
+        ScopeVisitor::visit_statement(statement, processor);
+    }
+    if let Some(last_statement) = block.mutate_last_statement() {
+        ScopeVisitor::visit_last_statement(last_statement, processor);
+    }
+    if let Some(expression) = extra {
+        ScopeVisitor::visit_expression(expression, processor);
+    }
+    processor.pop();
+}
+
+#[derive(Default)]
+struct RemoveUnusedParametersProcessor {}
+
+impl NodeProcessor for RemoveUnusedParametersProcessor {
+    fn process_scope(&mut self, block: &mut Block, mut extra: Option<&mut Expression>) {
+        for index in 0..block.statements_len() {
+            let (name, parameters_count, kept_count) = match block.iter_mut_statements().nth(index)
+            {
+                Some(Statement::LocalFunction(function)) => (
+                    function.get_name().to_owned(),
+                    function.parameters_count(),
+                    get_kept_parameters_count(function),
+                ),
+                _ => continue,
+            };
+
+            if kept_count == parameters_count {
+                continue;
+            }
+
+            let mut analyzer = CallAnalyzer::new(&name, parameters_count, kept_count);
+            visit_function_scope(block, index, extra.as_deref_mut(), &mut analyzer);
+
+            if !analyzer.can_remove_parameters() {
+                continue;
+            }
+
+            let mut remover = ArgumentRemover::new(&name, kept_count);
+            visit_function_scope(block, index, extra.as_deref_mut(), &mut remover);
+
+            if let Some(Statement::LocalFunction(function)) = block.iter_mut_statements().nth(index)
+            {
+                function.mutate_parameters().truncate(kept_count);
+                if let Some(tokens) = function.mutate_tokens() {
+                    tokens
+                        .parameter_commas
+                        .truncate(kept_count.saturating_sub(1));
+                }
+            }
+        }
+    }
+}
+
+pub const REMOVE_UNUSED_PARAMETERS_RULE_NAME: &str = "remove_unused_parameters";
+
+/// A rule that removes the trailing unused parameters of local functions that are
+/// only called directly, along with the matching arguments at each call.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemoveUnusedParameters {}
+
+impl FlawlessRule for RemoveUnusedParameters {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = RemoveUnusedParametersProcessor::default();
+        processor.process_scope(block, None);
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for RemoveUnusedParameters {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REMOVE_UNUSED_PARAMETERS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> RemoveUnusedParameters {
+        RemoveUnusedParameters::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_remove_unused_parameters", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_unused_parameters',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
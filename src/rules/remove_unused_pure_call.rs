@@ -0,0 +1,229 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{Block, FunctionCall, Statement};
+use crate::process::{
+    Evaluator, IdentifierTracker, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    ScopePostVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+use crate::utils::expressions_as_statement;
+
+use super::remove_call_match::{get_function_path, preserve_arguments_side_effects};
+
+// `tostring` is only pure when the value has no `__tostring` metamethod with side
+// effects: the rule assumes that metamethods do not have side effects
+const DEFAULT_PURE_FUNCTIONS: [&str; 10] = [
+    "math.abs",
+    "math.ceil",
+    "math.floor",
+    "math.max",
+    "math.min",
+    "math.sqrt",
+    "tonumber",
+    "tostring",
+    "type",
+    "typeof",
+];
+
+struct Processor<'a> {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+    functions: &'a [String],
+    // for each block being visited, tells which statements should be removed
+    removed_statements: Vec<Vec<bool>>,
+}
+
+impl<'a> Processor<'a> {
+    fn new(functions: &'a [String]) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            evaluator: Evaluator::default(),
+            functions,
+            removed_statements: Vec::new(),
+        }
+    }
+
+    fn is_pure_call(&self, call: &FunctionCall) -> bool {
+        if call.get_method().is_some() {
+            return false;
+        }
+
+        match get_function_path(call.get_prefix()) {
+            Some((root, path)) => !self.is_identifier_used(root) && self.functions.contains(&path),
+            None => false,
+        }
+    }
+}
+
+impl Deref for Processor<'_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor<'_> {
+    fn process_block(&mut self, _: &mut Block) {
+        self.removed_statements.push(Vec::new());
+    }
+
+    fn process_statement(&mut self, statement: &mut Statement) {
+        let mut remove = false;
+
+        if let Statement::Call(call) = statement {
+            if self.is_pure_call(call) {
+                let side_effects =
+                    preserve_arguments_side_effects(&self.evaluator, call.get_arguments());
+
+                if side_effects.is_empty() {
+                    remove = true;
+                } else {
+                    *statement = expressions_as_statement(side_effects);
+                }
+            }
+        }
+
+        if let Some(removed_statements) = self.removed_statements.last_mut() {
+            removed_statements.push(remove);
+        }
+    }
+}
+
+impl NodePostProcessor for Processor<'_> {
+    fn process_after_block(&mut self, block: &mut Block) {
+        let removed_statements = self.removed_statements.pop().unwrap_or_default();
+
+        if removed_statements.contains(&true) {
+            let mut removed_statements = removed_statements.into_iter();
+            block.filter_statements(|_| !removed_statements.next().unwrap_or_default());
+        }
+    }
+}
+
+pub const REMOVE_UNUSED_PURE_CALL_RULE_NAME: &str = "remove_unused_pure_call";
+
+/// A rule that removes call statements to functions known to be pure, since their
+/// result is discarded.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RemoveUnusedPureCall {
+    functions: Vec<String>,
+}
+
+impl Default for RemoveUnusedPureCall {
+    fn default() -> Self {
+        Self {
+            functions: DEFAULT_PURE_FUNCTIONS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl RemoveUnusedPureCall {
+    fn has_default_functions(&self) -> bool {
+        self.functions.len() == DEFAULT_PURE_FUNCTIONS.len()
+            && DEFAULT_PURE_FUNCTIONS
+                .iter()
+                .all(|function| self.functions.iter().any(|value| value == function))
+    }
+}
+
+impl FlawlessRule for RemoveUnusedPureCall {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::new(&self.functions);
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for RemoveUnusedPureCall {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "functions" => {
+                    self.functions = value.expect_string_list(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REMOVE_UNUSED_PURE_CALL_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if !self.has_default_functions() {
+            properties.insert(
+                "functions".to_owned(),
+                RulePropertyValue::StringList(self.functions.clone()),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("functions", RulePropertyType::StringList)
+                .with_default(&DEFAULT_PURE_FUNCTIONS[..]),
+        ]
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> RemoveUnusedPureCall {
+        RemoveUnusedPureCall::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_remove_unused_pure_call", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_functions() {
+        let rule: Box<dyn Rule> = Box::new(RemoveUnusedPureCall {
+            functions: vec!["string.len".to_owned()],
+        });
+
+        assert_json_snapshot!("remove_unused_pure_call_with_functions", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_unused_pure_call',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -5,11 +5,12 @@ mod rename_processor;
 use rename_processor::RenameProcessor;
 
 use crate::nodes::Block;
+use crate::process::processors::CollectGlobalNames;
 use crate::process::utils::is_valid_identifier;
 use crate::process::{DefaultVisitor, NodeVisitor, ScopeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
-    RulePropertyValue,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
 };
 
 use std::collections::HashSet;
@@ -103,8 +104,16 @@ impl FlawlessRule for RenameVariables {
             collect_functions.into()
         };
 
+        let mut collect_globals = CollectGlobalNames::default();
+        ScopeVisitor::visit_block(block, &mut collect_globals);
+        let used_globals: Vec<String> = collect_globals.into();
+
         let mut processor = RenameProcessor::new(
-            self.globals.clone().into_iter().chain(avoid_identifiers),
+            self.globals
+                .clone()
+                .into_iter()
+                .chain(avoid_identifiers)
+                .chain(used_globals),
             self.include_functions,
         );
         ScopeVisitor::visit_block(block, &mut processor);
@@ -152,6 +161,15 @@ impl RuleConfiguration for RenameVariables {
 
         properties
     }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("globals", RulePropertyType::StringList)
+                .with_default(vec!["$default"]),
+            RulePropertyDescription::new("include_functions", RulePropertyType::Boolean)
+                .with_default(false),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use crate::nodes::{Block, Identifier, LocalAssignStatement, Statement};
+use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    verify_no_rule_properties, Context, FlawlessRule, RuleConfiguration, RuleConfigurationError,
+    RuleProperties,
+};
+
+/// Collects the name of every variable read in the visited nodes (including the
+/// ones inside nested functions).
+#[derive(Default)]
+struct ReadVariables {
+    names: HashSet<String>,
+}
+
+impl NodeProcessor for ReadVariables {
+    fn process_variable_expression(&mut self, identifier: &mut Identifier) {
+        if !self.names.contains(identifier.get_name()) {
+            self.names.insert(identifier.get_name().to_owned());
+        }
+    }
+}
+
+struct Declaration {
+    statement: LocalAssignStatement,
+    declared: HashSet<String>,
+    read: HashSet<String>,
+}
+
+impl Declaration {
+    fn new(mut statement: LocalAssignStatement) -> Self {
+        let declared = statement
+            .iter_variables()
+            .map(|variable| variable.get_name().to_owned())
+            .collect();
+
+        let mut read_variables = ReadVariables::default();
+        for value in statement.iter_mut_values() {
+            DefaultVisitor::visit_expression(value, &mut read_variables);
+        }
+
+        Self {
+            statement,
+            declared,
+            read: read_variables.names,
+        }
+    }
+
+    /// Returns true if the order of the two declarations can not be changed: when
+    /// one of them reads a variable declared by the other or when they declare a
+    /// variable with the same name.
+    fn depends_on(&self, other: &Self) -> bool {
+        !self.read.is_disjoint(&other.declared)
+            || !other.read.is_disjoint(&self.declared)
+            || !self.declared.is_disjoint(&other.declared)
+    }
+}
+
+#[derive(Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    fn can_reorder(&self, statement: &Statement) -> bool {
+        match statement {
+            Statement::LocalAssign(assign) => assign
+                .iter_values()
+                .all(|value| !self.evaluator.has_side_effects(value)),
+            _ => false,
+        }
+    }
+
+    /// Sorts the declarations so that the ones that do not depend on each other
+    /// are next to each other. Each declaration is placed after all the
+    /// declarations it depends on, and declarations only move before the ones
+    /// they do not depend on.
+    fn reorder(&self, declarations: Vec<Declaration>) -> Vec<Statement> {
+        let mut levels: Vec<usize> = Vec::with_capacity(declarations.len());
+
+        for (index, declaration) in declarations.iter().enumerate() {
+            let level = declarations
+                .iter()
+                .take(index)
+                .zip(levels.iter())
+                .filter(|(previous, _)| declaration.depends_on(previous))
+                .map(|(_, level)| level + 1)
+                .max()
+                .unwrap_or(0);
+            levels.push(level);
+        }
+
+        let mut declarations: Vec<_> = levels.into_iter().zip(declarations).collect();
+        declarations.sort_by_key(|(level, _)| *level);
+
+        declarations
+            .into_iter()
+            .map(|(_, declaration)| declaration.statement.into())
+            .collect()
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_block(&mut self, block: &mut Block) {
+        let mut statements = Vec::with_capacity(block.statements_len());
+        let mut declarations = Vec::new();
+
+        for statement in block.take_statements() {
+            if self.can_reorder(&statement) {
+                if let Statement::LocalAssign(assign) = statement {
+                    declarations.push(Declaration::new(assign));
+                }
+            } else {
+                if !declarations.is_empty() {
+                    statements.extend(self.reorder(declarations));
+                    declarations = Vec::new();
+                }
+                statements.push(statement);
+            }
+        }
+
+        if !declarations.is_empty() {
+            statements.extend(self.reorder(declarations));
+        }
+
+        block.set_statements(statements);
+    }
+}
+
+pub const REORDER_LOCAL_ASSIGNMENTS_RULE_NAME: &str = "reorder_local_assignments";
+
+/// A rule that reorders consecutive local assignments that do not depend on each
+/// other, so that they can be grouped together.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReorderLocalAssignments {}
+
+impl FlawlessRule for ReorderLocalAssignments {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ReorderLocalAssignments {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        REORDER_LOCAL_ASSIGNMENTS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ReorderLocalAssignments {
+        ReorderLocalAssignments::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_reorder_local_assignments", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'reorder_local_assignments',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -86,6 +86,11 @@ impl NodeProcessor for Processor<'_> {
         type_declaration.replace_referenced_tokens(self.code);
     }
 
+    fn process_type_function(&mut self, type_function: &mut TypeFunctionStatement) {
+        type_function.replace_referenced_tokens(self.code);
+        DefaultVisitor::visit_block(type_function.mutate_block(), self);
+    }
+
     fn process_expression(&mut self, expression: &mut Expression) {
         match expression {
             Expression::False(token)
@@ -38,6 +38,14 @@ impl RulePropertyValue {
         }
     }
 
+    pub(crate) fn expect_usize(self, key: &str) -> Result<usize, RuleConfigurationError> {
+        if let Self::Usize(value) = self {
+            Ok(value)
+        } else {
+            Err(RuleConfigurationError::UsizeExpected(key.to_owned()))
+        }
+    }
+
     pub(crate) fn expect_string_list(
         self,
         key: &str,
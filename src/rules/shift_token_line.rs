@@ -86,6 +86,11 @@ impl NodeProcessor for ShiftTokenLineProcessor {
         type_declaration.shift_token_line(self.shift_amount);
     }
 
+    fn process_type_function(&mut self, type_function: &mut TypeFunctionStatement) {
+        type_function.shift_token_line(self.shift_amount);
+        DefaultVisitor::visit_block(type_function.mutate_block(), self);
+    }
+
     fn process_expression(&mut self, expression: &mut Expression) {
         match expression {
             Expression::False(token)
@@ -0,0 +1,184 @@
+use crate::generator::{DenseLuaGenerator, LuaGenerator, LuaVersion};
+use crate::nodes::{Block, Expression, NumberExpression};
+use crate::process::utils::MAX_EXACT_INTEGER;
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    verify_no_rule_properties, Context, FlawlessRule, RuleConfiguration, RuleConfigurationError,
+    RuleProperties,
+};
+
+fn generate_number(number: &NumberExpression) -> String {
+    let mut generator = DenseLuaGenerator::default();
+    generator.write_expression(&Expression::Number(number.clone()));
+    generator.into_string()
+}
+
+/// Returns the representations of the value that can be used in place of the
+/// number: the decimal form, the scientific form and the hexadecimal form for
+/// integers.
+fn get_candidates(value: f64) -> Vec<String> {
+    let mut candidates = vec![format!("{}", value), format!("{:e}", value)];
+
+    if value.fract() == 0.0 && value >= 0.0 && value < u64::MAX as f64 {
+        candidates.push(format!("0x{:x}", value as u64));
+    }
+
+    candidates
+}
+
+/// Returns true if Lua 5.3 and Lua 5.4 read the number literal as an integer
+/// instead of a float.
+fn is_integer_literal(code: &str) -> bool {
+    let code = code.to_ascii_lowercase();
+
+    match code.strip_prefix("0x") {
+        Some(hexadecimal) => !hexadecimal.contains(['.', 'p']),
+        None => !code.contains(['.', 'e']),
+    }
+}
+
+/// Finds the shortest number (as written by the generators) that evaluates to
+/// the exact same value. Returns the number with its generated code, only if it
+/// is shorter than the given number, which is written as `literal`.
+///
+/// When `preserve_subtype` is true, integers are only replaced with integers and
+/// floats with floats, since Lua 5.3 and Lua 5.4 make the difference between them
+/// (`1.0` is printed as `1.0` while `1` is printed as `1`).
+fn shorten(
+    number: &NumberExpression,
+    literal: &str,
+    preserve_subtype: bool,
+) -> Option<(NumberExpression, String)> {
+    let value = number.compute_value();
+
+    if !value.is_finite() {
+        return None;
+    }
+
+    let original_length = literal.len();
+    let is_integer = is_integer_literal(literal);
+
+    // integers larger than 2^53 are not represented exactly by the computed value
+    if preserve_subtype && is_integer && value.abs() > MAX_EXACT_INTEGER {
+        return None;
+    }
+
+    get_candidates(value)
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate: NumberExpression = candidate.parse().ok()?;
+            let code = generate_number(&candidate);
+
+            // the generated code must be parsed back to the same bits, which also
+            // takes care of preserving the sign of zero
+            let reparsed: NumberExpression = code.parse().ok()?;
+            if reparsed.compute_value().to_bits() != value.to_bits() {
+                return None;
+            }
+
+            Some((reparsed, code))
+        })
+        .filter(|(_, code)| !preserve_subtype || is_integer_literal(code) == is_integer)
+        .filter(|(_, code)| code.len() < original_length)
+        .min_by_key(|(_, code)| code.len())
+}
+
+#[derive(Debug)]
+struct Processor<'a> {
+    original_code: &'a str,
+    preserve_subtype: bool,
+}
+
+impl NodeProcessor for Processor<'_> {
+    fn process_number_expression(&mut self, number: &mut NumberExpression) {
+        // when tokens are preserved, the number is written as it appears in the code
+        let literal = match number.get_token() {
+            Some(token) => token.read(self.original_code).to_owned(),
+            None => generate_number(number),
+        };
+
+        if let Some((mut shortened, code)) = shorten(number, &literal, self.preserve_subtype) {
+            if let Some(token) = number.get_token() {
+                let mut token = token.clone();
+                token.replace_with_content(code);
+                shortened.set_token(token);
+            }
+            *number = shortened;
+        }
+    }
+}
+
+pub const SHORTEN_NUMBER_LITERALS_RULE_NAME: &str = "shorten_number_literals";
+
+/// A rule that rewrites number literals to their shortest equivalent form between
+/// the decimal, scientific and hexadecimal notations. Unless the target is Lua 5.1
+/// or Luau, where all numbers are floats, integers stay integers and floats stay
+/// floats.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ShortenNumberLiterals {}
+
+impl FlawlessRule for ShortenNumberLiterals {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = Processor {
+            original_code: context.original_code(),
+            preserve_subtype: !matches!(
+                context.target(),
+                Some(LuaVersion::Lua51) | Some(LuaVersion::Luau)
+            ),
+        };
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ShortenNumberLiterals {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        SHORTEN_NUMBER_LITERALS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ShortenNumberLiterals {
+        ShortenNumberLiterals::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_shorten_number_literals", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'shorten_number_literals',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    #[test]
+    fn negative_zero_is_not_shortened() {
+        let number = crate::nodes::DecimalNumber::new(-0.0).into();
+
+        assert_eq!(shorten(&number, &generate_number(&number), false), None);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::nodes::{Block, StringExpression};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    verify_no_rule_properties, Context, FlawlessRule, RuleConfiguration, RuleConfigurationError,
+    RuleProperties,
+};
+
+/// A part of the content of a quoted string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment<'a> {
+    /// Code that is kept as-is, like regular characters or unicode escapes.
+    Verbatim(&'a str),
+    /// A byte written with an escape sequence.
+    Byte(u8),
+}
+
+fn read_decimal_escape(content: &str) -> Option<(u8, usize)> {
+    let length = content
+        .bytes()
+        .take(3)
+        .take_while(u8::is_ascii_digit)
+        .count();
+
+    content[..length]
+        .parse::<u16>()
+        .ok()
+        .filter(|value| *value < 256)
+        .map(|value| (value as u8, length))
+}
+
+fn read_hexadecimal_escape(content: &str) -> Option<(u8, usize)> {
+    content
+        .get(..2)
+        .filter(|digits| digits.bytes().all(|digit| digit.is_ascii_hexdigit()))
+        .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+        .map(|value| (value, 2))
+}
+
+/// Splits the content of a quoted string (without its quotes) into segments. Returns
+/// `None` if the content contains a malformed escape sequence.
+fn read_segments(content: &str) -> Option<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut index = 0;
+
+    while let Some(escape_start) = content[index..].find('\\').map(|start| index + start) {
+        if escape_start > index {
+            segments.push(Segment::Verbatim(&content[index..escape_start]));
+        }
+
+        let escape_content = &content[escape_start + 1..];
+        let escape_character = escape_content.chars().next()?;
+
+        let (segment, length) = match escape_character {
+            'a' => (Segment::Byte(7), 1),
+            'b' => (Segment::Byte(8), 1),
+            't' => (Segment::Byte(9), 1),
+            'n' => (Segment::Byte(10), 1),
+            'v' => (Segment::Byte(11), 1),
+            'f' => (Segment::Byte(12), 1),
+            'r' => (Segment::Byte(13), 1),
+            '\\' | '"' | '\'' => (Segment::Byte(escape_character as u8), 1),
+            'x' => {
+                let (value, length) = read_hexadecimal_escape(&escape_content[1..])?;
+                (Segment::Byte(value), 1 + length)
+            }
+            digit if digit.is_ascii_digit() => {
+                let (value, length) = read_decimal_escape(escape_content)?;
+                (Segment::Byte(value), length)
+            }
+            'u' => {
+                let length = escape_content.find('}')? + 1;
+                (
+                    Segment::Verbatim(&content[escape_start..escape_start + 1 + length]),
+                    length,
+                )
+            }
+            _ => {
+                let length = escape_character.len_utf8();
+                (
+                    Segment::Verbatim(&content[escape_start..escape_start + 1 + length]),
+                    length,
+                )
+            }
+        };
+
+        segments.push(segment);
+        index = escape_start + 1 + length;
+    }
+
+    if index < content.len() {
+        segments.push(Segment::Verbatim(&content[index..]));
+    }
+
+    Some(segments)
+}
+
+fn write_byte(byte: u8, quote: char, next_is_digit: bool) -> String {
+    match byte {
+        7 => "\\a".to_owned(),
+        8 => "\\b".to_owned(),
+        9 => "\\t".to_owned(),
+        10 => "\\n".to_owned(),
+        11 => "\\v".to_owned(),
+        12 => "\\f".to_owned(),
+        13 => "\\r".to_owned(),
+        b'\\' => "\\\\".to_owned(),
+        _ if byte as char == quote => format!("\\{}", quote),
+        b' '..=b'~' => (byte as char).to_string(),
+        // a decimal escape followed by a digit would read that digit too
+        _ if next_is_digit => format!("\\{:03}", byte),
+        _ => format!("\\{}", byte),
+    }
+}
+
+/// Rewrites the escape sequences of a quoted string (including its quotes) to their
+/// shortest form. Returns `None` if the string is not a quoted string or if it
+/// contains a malformed escape sequence.
+fn shorten_escapes(string: &str) -> Option<String> {
+    let quote = string.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+
+    if string.len() < 2 || !string.ends_with(quote) {
+        return None;
+    }
+
+    let segments = read_segments(&string[1..string.len() - 1])?;
+
+    // segments are written from the end, so that a decimal escape can know if it
+    // is followed by a digit
+    let mut parts = Vec::with_capacity(segments.len());
+    let mut next_is_digit = false;
+
+    for segment in segments.into_iter().rev() {
+        let part = match segment {
+            Segment::Verbatim(content) => content.to_owned(),
+            Segment::Byte(byte) => write_byte(byte, quote, next_is_digit),
+        };
+        next_is_digit = part.starts_with(|c: char| c.is_ascii_digit());
+        parts.push(part);
+    }
+
+    let mut result = String::with_capacity(string.len());
+    result.push(quote);
+    result.extend(parts.into_iter().rev());
+    result.push(quote);
+
+    Some(result)
+}
+
+struct Processor<'a> {
+    original_code: &'a str,
+}
+
+impl NodeProcessor for Processor<'_> {
+    fn process_string_expression(&mut self, string: &mut StringExpression) {
+        if let Some(token) = string.get_token() {
+            let content = token.read(self.original_code);
+
+            if let Some(shortened) = shorten_escapes(content).filter(|new| new != content) {
+                let mut token = token.clone();
+                token.replace_with_content(shortened);
+                string.set_token(token);
+            }
+        }
+    }
+}
+
+pub const SHORTEN_STRING_ESCAPES_RULE_NAME: &str = "shorten_string_escapes";
+
+/// A rule that rewrites the escape sequences of quoted strings to their shortest
+/// form, without changing the bytes of the strings.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ShortenStringEscapes {}
+
+impl FlawlessRule for ShortenStringEscapes {
+    fn flawless_process(&self, block: &mut Block, context: &Context) {
+        let mut processor = Processor {
+            original_code: context.original_code(),
+        };
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for ShortenStringEscapes {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        SHORTEN_STRING_ESCAPES_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> ShortenStringEscapes {
+        ShortenStringEscapes::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_shorten_string_escapes", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'shorten_string_escapes',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+
+    macro_rules! test_shorten_escapes {
+        ($($name:ident ($input:literal) => $output:expr),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    pretty_assertions::assert_eq!(shorten_escapes($input).as_deref(), $output);
+                }
+            )*
+        };
+    }
+
+    test_shorten_escapes!(
+        decimal_escape_to_letter(r"'\097'") => Some("'a'"),
+        hexadecimal_escape_to_letter(r"'\x61'") => Some("'a'"),
+        control_character(r"'\001'") => Some(r"'\1'"),
+        control_character_followed_by_digit(r"'\0011'") => Some(r"'\0011'"),
+        control_character_followed_by_escaped_digit(r"'\001\049'") => Some(r"'\0011'"),
+        new_line_decimal_escape(r"'\010'") => Some(r"'\n'"),
+        same_quote(r"'\039'") => Some(r"'\''"),
+        other_quote(r"'\034'") => Some(r#"'"'"#),
+        same_double_quote(r#""\034""#) => Some(r#""\"""#),
+        backslash(r"'\092'") => Some(r"'\\'"),
+        extended_byte(r"'\xC8'") => Some(r"'\200'"),
+        unicode_escape_is_kept(r"'\u{48}\065'") => Some(r"'\u{48}A'"),
+        skip_whitespace_escape_is_kept("'a\\z  \\066'") => Some("'a\\z  B'"),
+        escaped_new_line_is_kept("'a\\\nb'") => Some("'a\\\nb'"),
+        long_string(r"[[\097]]") => None,
+        invalid_decimal_escape(r"'\256'") => None,
+    );
+}
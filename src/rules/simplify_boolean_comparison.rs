@@ -0,0 +1,349 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::nodes::{
+    BinaryOperator, Block, Expression, FunctionExpression, FunctionStatement, GenericForStatement,
+    IfExpression, IfStatement, LocalAssignStatement, LocalFunctionStatement, NumericForStatement,
+    RepeatStatement, Type, TypedIdentifier, UnaryExpression, UnaryOperator, WhileStatement,
+};
+use crate::process::{NodePostProcessor, NodePostVisitor, NodeProcessor, Scope, ScopePostVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+pub(crate) fn is_boolean_type(r#type: &Type) -> bool {
+    match r#type {
+        Type::Name(name) => {
+            name.get_type_name().get_name() == "boolean" && !name.has_type_parameters()
+        }
+        Type::True(_) | Type::False(_) => true,
+        Type::Parenthese(parenthese) => is_boolean_type(parenthese.get_inner_type()),
+        Type::Union(union) => union.iter_types().all(is_boolean_type),
+        _ => false,
+    }
+}
+
+fn is_boolean_identifier(identifier: &TypedIdentifier) -> bool {
+    identifier
+        .get_type()
+        .map(is_boolean_type)
+        .unwrap_or_default()
+}
+
+fn get_double_negation_value(expression: &Expression) -> Option<&Expression> {
+    match expression {
+        Expression::Unary(unary) if unary.operator() == UnaryOperator::Not => {
+            match unary.get_expression() {
+                Expression::Unary(inner) if inner.operator() == UnaryOperator::Not => {
+                    Some(inner.get_expression())
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Removes double negations from an expression where only the truthiness of the
+/// value matters, like the condition of an if statement or the operand of `not`.
+fn simplify_truthiness(expression: &mut Expression) {
+    if let Some(value) = get_double_negation_value(expression).cloned() {
+        *expression = value;
+        simplify_truthiness(expression);
+        return;
+    }
+
+    match expression {
+        Expression::Unary(unary) if unary.operator() == UnaryOperator::Not => {
+            simplify_truthiness(unary.mutate_expression());
+        }
+        Expression::Binary(binary)
+            if matches!(binary.operator(), BinaryOperator::And | BinaryOperator::Or) =>
+        {
+            simplify_truthiness(binary.mutate_left());
+            simplify_truthiness(binary.mutate_right());
+        }
+        Expression::Parenthese(parenthese) => {
+            simplify_truthiness(parenthese.mutate_inner_expression());
+        }
+        _ => {}
+    }
+}
+
+#[derive(Default)]
+struct Processor {
+    scopes: Vec<HashMap<String, bool>>,
+    // for each node declaring identifiers, a queue that tells if each identifier is
+    // known to be a boolean (in the order of the calls to `insert` or `insert_local`)
+    pending_identifiers: Vec<VecDeque<bool>>,
+}
+
+impl Processor {
+    fn push_pending_identifiers<'a>(
+        &mut self,
+        identifiers: impl Iterator<Item = &'a TypedIdentifier>,
+    ) {
+        let pending: VecDeque<_> = identifiers.map(is_boolean_identifier).collect();
+
+        if !pending.is_empty() {
+            self.pending_identifiers.push(pending);
+        }
+    }
+
+    fn pop_pending_identifier(&mut self) -> bool {
+        let pending = match self.pending_identifiers.last_mut() {
+            Some(pending) => pending,
+            None => return false,
+        };
+
+        let is_boolean = pending.pop_front().unwrap_or_default();
+
+        if pending.is_empty() {
+            self.pending_identifiers.pop();
+        }
+
+        is_boolean
+    }
+
+    fn insert_identifier(&mut self, identifier: &str, is_boolean: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(identifier.to_owned(), is_boolean);
+        }
+    }
+
+    fn is_boolean_identifier(&self, identifier: &str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(identifier))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn is_boolean(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::True(_) | Expression::False(_) => true,
+            Expression::Binary(binary) => match binary.operator() {
+                BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::LowerThan
+                | BinaryOperator::LowerOrEqualThan
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterOrEqualThan => true,
+                BinaryOperator::And | BinaryOperator::Or => {
+                    self.is_boolean(binary.left()) && self.is_boolean(binary.right())
+                }
+                _ => false,
+            },
+            Expression::Unary(unary) => matches!(unary.operator(), UnaryOperator::Not),
+            Expression::Parenthese(parenthese) => self.is_boolean(parenthese.inner_expression()),
+            Expression::TypeCast(type_cast) => {
+                is_boolean_type(type_cast.get_type()) || self.is_boolean(type_cast.get_expression())
+            }
+            Expression::If(if_expression) => {
+                self.is_boolean(if_expression.get_result())
+                    && if_expression
+                        .iter_branches()
+                        .all(|branch| self.is_boolean(branch.get_result()))
+                    && self.is_boolean(if_expression.get_else_result())
+            }
+            Expression::Identifier(identifier) => self.is_boolean_identifier(identifier.get_name()),
+            _ => false,
+        }
+    }
+
+    fn simplify_comparison(&self, expression: &Expression) -> Option<Expression> {
+        let binary = match expression {
+            Expression::Binary(binary) => binary,
+            _ => return None,
+        };
+
+        let is_equal = match binary.operator() {
+            BinaryOperator::Equal => true,
+            BinaryOperator::NotEqual => false,
+            _ => return None,
+        };
+
+        let (value, literal) = match (binary.left(), binary.right()) {
+            (value, Expression::True(_)) => (value, true),
+            (value, Expression::False(_)) => (value, false),
+            (Expression::True(_), value) => (value, true),
+            (Expression::False(_), value) => (value, false),
+            _ => return None,
+        };
+
+        if !self.is_boolean(value) {
+            return None;
+        }
+
+        if literal == is_equal {
+            Some(value.clone())
+        } else {
+            Some(UnaryExpression::new(UnaryOperator::Not, value.clone()).into())
+        }
+    }
+
+    fn simplify_double_negation(&self, expression: &Expression) -> Option<Expression> {
+        get_double_negation_value(expression)
+            .filter(|value| self.is_boolean(value))
+            .cloned()
+    }
+}
+
+impl Scope for Processor {
+    fn push(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn insert(&mut self, identifier: &mut String) {
+        let is_boolean = self.pop_pending_identifier();
+        self.insert_identifier(identifier, is_boolean);
+    }
+
+    fn insert_self(&mut self) {
+        self.insert_identifier("self", false);
+    }
+
+    fn insert_local(&mut self, identifier: &mut String, _value: Option<&mut Expression>) {
+        let is_boolean = self.pop_pending_identifier();
+        self.insert_identifier(identifier, is_boolean);
+    }
+
+    fn insert_local_function(&mut self, function: &mut LocalFunctionStatement) {
+        self.insert_identifier(function.get_name(), false);
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_local_assign_statement(&mut self, assign: &mut LocalAssignStatement) {
+        self.push_pending_identifiers(assign.iter_variables());
+    }
+
+    fn process_function_expression(&mut self, function: &mut FunctionExpression) {
+        self.push_pending_identifiers(function.iter_parameters());
+    }
+
+    fn process_function_statement(&mut self, function: &mut FunctionStatement) {
+        self.push_pending_identifiers(function.iter_parameters());
+    }
+
+    fn process_local_function_statement(&mut self, function: &mut LocalFunctionStatement) {
+        self.push_pending_identifiers(function.iter_parameters());
+    }
+
+    fn process_generic_for_statement(&mut self, generic_for: &mut GenericForStatement) {
+        self.push_pending_identifiers(generic_for.iter_identifiers());
+    }
+
+    fn process_numeric_for_statement(&mut self, numeric_for: &mut NumericForStatement) {
+        self.push_pending_identifiers(std::iter::once(numeric_for.get_identifier()));
+    }
+}
+
+impl NodePostProcessor for Processor {
+    fn process_after_if_statement(&mut self, if_statement: &mut IfStatement) {
+        for branch in if_statement.mutate_branches() {
+            simplify_truthiness(branch.mutate_condition());
+        }
+    }
+
+    fn process_after_while_statement(&mut self, while_statement: &mut WhileStatement) {
+        simplify_truthiness(while_statement.mutate_condition());
+    }
+
+    fn process_after_repeat_statement(&mut self, repeat: &mut RepeatStatement) {
+        simplify_truthiness(repeat.mutate_condition());
+    }
+
+    fn process_after_if_expression(&mut self, if_expression: &mut IfExpression) {
+        simplify_truthiness(if_expression.mutate_condition());
+        for branch in if_expression.iter_mut_branches() {
+            simplify_truthiness(branch.mutate_condition());
+        }
+    }
+
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Unary(unary) = expression {
+            if unary.operator() == UnaryOperator::Not {
+                simplify_truthiness(unary.mutate_expression());
+            }
+        }
+
+        if let Some(replace_with) = self
+            .simplify_comparison(expression)
+            .or_else(|| self.simplify_double_negation(expression))
+        {
+            *expression = replace_with;
+        }
+    }
+}
+
+pub const SIMPLIFY_BOOLEAN_COMPARISON_RULE_NAME: &str = "simplify_boolean_comparison";
+
+/// A rule that removes comparisons with `true` or `false` and double negations when the
+/// compared value is known to be a boolean. Double negations are also removed when only
+/// the truthiness of the value matters, like in conditions.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SimplifyBooleanComparison {}
+
+impl FlawlessRule for SimplifyBooleanComparison {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        ScopePostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for SimplifyBooleanComparison {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        SIMPLIFY_BOOLEAN_COMPARISON_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+
+    fn is_semantics_preserving(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> SimplifyBooleanComparison {
+        SimplifyBooleanComparison::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_simplify_boolean_comparison", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'simplify_boolean_comparison',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,186 @@
+use crate::nodes::{
+    BinaryOperator, Block, Expression, IfExpression, IfStatement, RepeatStatement, UnaryExpression,
+    UnaryOperator, WhileStatement,
+};
+use crate::process::{DefaultPostVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::simplify_boolean_comparison::is_boolean_type;
+use super::verify_no_rule_properties;
+
+/// Returns true if the expression always evaluates to `true` or `false`.
+fn is_boolean(expression: &Expression) -> bool {
+    match expression {
+        Expression::True(_) | Expression::False(_) => true,
+        Expression::Binary(binary) => match binary.operator() {
+            BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::LowerThan
+            | BinaryOperator::LowerOrEqualThan
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterOrEqualThan => true,
+            BinaryOperator::And | BinaryOperator::Or => {
+                is_boolean(binary.left()) && is_boolean(binary.right())
+            }
+            _ => false,
+        },
+        Expression::Unary(unary) => matches!(unary.operator(), UnaryOperator::Not),
+        Expression::Parenthese(parenthese) => is_boolean(parenthese.inner_expression()),
+        Expression::TypeCast(type_cast) => {
+            is_boolean_type(type_cast.get_type()) || is_boolean(type_cast.get_expression())
+        }
+        Expression::If(if_expression) => {
+            is_boolean(if_expression.get_result())
+                && if_expression
+                    .iter_branches()
+                    .all(|branch| is_boolean(branch.get_result()))
+                && is_boolean(if_expression.get_else_result())
+        }
+        _ => false,
+    }
+}
+
+/// Returns the condition of an if expression of the form `if condition then true else false`
+/// or `if condition then false else true`, with a boolean that is true for the second form.
+fn get_boolean_if_condition(expression: &Expression) -> Option<(&Expression, bool)> {
+    match expression {
+        Expression::If(if_expression) if !if_expression.has_elseif_branch() => {
+            match (if_expression.get_result(), if_expression.get_else_result()) {
+                (Expression::True(_), Expression::False(_)) => {
+                    Some((if_expression.get_condition(), false))
+                }
+                (Expression::False(_), Expression::True(_)) => {
+                    Some((if_expression.get_condition(), true))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Simplifies a boolean if expression. When `truthiness_only` is false, the expression
+/// is only replaced with its condition if the condition is known to be a boolean.
+fn simplify(expression: &Expression, truthiness_only: bool) -> Option<Expression> {
+    let (condition, negated) = get_boolean_if_condition(expression)?;
+
+    if negated {
+        Some(UnaryExpression::new(UnaryOperator::Not, condition.clone()).into())
+    } else if truthiness_only || is_boolean(condition) {
+        Some(condition.clone())
+    } else {
+        None
+    }
+}
+
+/// Simplifies an expression where only the truthiness of the value matters, like
+/// the condition of an if statement or the operand of `not`.
+fn simplify_condition(expression: &mut Expression) {
+    if let Expression::Parenthese(parenthese) = expression {
+        simplify_condition(parenthese.mutate_inner_expression());
+    } else if let Some(replace_with) = simplify(expression, true) {
+        *expression = replace_with;
+    }
+}
+
+#[derive(Debug, Default)]
+struct Processor;
+
+impl NodeProcessor for Processor {}
+
+impl NodePostProcessor for Processor {
+    fn process_after_if_statement(&mut self, if_statement: &mut IfStatement) {
+        for branch in if_statement.mutate_branches() {
+            simplify_condition(branch.mutate_condition());
+        }
+    }
+
+    fn process_after_while_statement(&mut self, while_statement: &mut WhileStatement) {
+        simplify_condition(while_statement.mutate_condition());
+    }
+
+    fn process_after_repeat_statement(&mut self, repeat: &mut RepeatStatement) {
+        simplify_condition(repeat.mutate_condition());
+    }
+
+    fn process_after_if_expression(&mut self, if_expression: &mut IfExpression) {
+        simplify_condition(if_expression.mutate_condition());
+        for branch in if_expression.iter_mut_branches() {
+            simplify_condition(branch.mutate_condition());
+        }
+    }
+
+    fn process_after_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Unary(unary) = expression {
+            if unary.operator() == UnaryOperator::Not {
+                simplify_condition(unary.mutate_expression());
+            }
+        }
+
+        if let Some(replace_with) = simplify(expression, false) {
+            *expression = replace_with;
+        }
+    }
+}
+
+pub const SIMPLIFY_BOOLEAN_IF_EXPRESSION_RULE_NAME: &str = "simplify_boolean_if_expression";
+
+/// A rule that replaces if expressions like `if condition then true else false` with
+/// their condition, and `if condition then false else true` with `not condition`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SimplifyBooleanIfExpression {}
+
+impl FlawlessRule for SimplifyBooleanIfExpression {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor;
+        DefaultPostVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for SimplifyBooleanIfExpression {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        SIMPLIFY_BOOLEAN_IF_EXPRESSION_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> SimplifyBooleanIfExpression {
+        SimplifyBooleanIfExpression::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_simplify_boolean_if_expression", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'simplify_boolean_if_expression',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
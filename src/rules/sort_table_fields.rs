@@ -0,0 +1,118 @@
+use crate::nodes::{Block, TableEntry, TableExpression};
+use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+};
+
+use super::verify_no_rule_properties;
+
+fn get_field_name(entry: &TableEntry) -> Option<&str> {
+    match entry {
+        TableEntry::Field(field) => Some(field.get_field().get_name()),
+        TableEntry::Index(_) | TableEntry::Value(_) => None,
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Processor {
+    evaluator: Evaluator,
+}
+
+impl Processor {
+    /// Returns `true` when the entries of the table can be sorted without changing the
+    /// resulting table: every entry must be a field entry with a unique name and no value
+    /// can have side effects, so that the evaluation order of the values does not matter.
+    fn can_sort(&self, table: &TableExpression) -> bool {
+        let mut names = Vec::with_capacity(table.len());
+
+        for entry in table.iter_entries() {
+            match entry {
+                TableEntry::Field(field) => {
+                    if self.evaluator.has_side_effects(field.get_value()) {
+                        return false;
+                    }
+                    names.push(field.get_field().get_name());
+                }
+                TableEntry::Index(_) | TableEntry::Value(_) => return false,
+            }
+        }
+
+        if names.windows(2).all(|pair| pair[0] < pair[1]) {
+            // the fields are already sorted
+            return false;
+        }
+
+        names.sort_unstable();
+        names.windows(2).all(|pair| pair[0] != pair[1])
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_table_expression(&mut self, table: &mut TableExpression) {
+        if self.can_sort(table) {
+            table
+                .mutate_entries()
+                .sort_by(|a, b| get_field_name(a).cmp(&get_field_name(b)));
+        }
+    }
+}
+
+pub const SORT_TABLE_FIELDS_RULE_NAME: &str = "sort_table_fields";
+
+/// A rule that sorts the field entries of table constructors by their name, so that
+/// equal tables written in different orders produce the same code.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SortTableFields {}
+
+impl FlawlessRule for SortTableFields {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::default();
+        DefaultVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for SortTableFields {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        verify_no_rule_properties(&properties)?;
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        SORT_TABLE_FIELDS_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        RuleProperties::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> SortTableFields {
+        SortTableFields::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_sort_table_fields", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'sort_table_fields',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -0,0 +1,372 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::nodes::{
+    Arguments, AssignStatement, Block, CompoundAssignStatement, DoStatement, Expression,
+    FunctionCall, GenericForStatement, LastStatement, ParentheseExpression, Prefix, Statement,
+    TableEntry, TableExpression, Variable,
+};
+use crate::process::{
+    DefaultVisitor, Evaluator, IdentifierTracker, LuaValue, NodeProcessor, NodeVisitor,
+    ScopeVisitor,
+};
+use crate::rules::{
+    Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
+    RulePropertyDescription, RulePropertyType, RulePropertyValue,
+};
+
+use super::unused_if_branch::can_splice;
+
+const IPAIRS_FUNCTION: &str = "ipairs";
+const DEFAULT_MAXIMUM_LENGTH: usize = 4;
+
+fn get_table_argument(expression: &Expression) -> Option<&TableExpression> {
+    match expression {
+        Expression::Table(table) => Some(table),
+        Expression::Parenthese(parenthese) => get_table_argument(parenthese.inner_expression()),
+        _ => None,
+    }
+}
+
+/// Returns true if the block contains a `break` or `continue` statement that exits
+/// the loop owning the block.
+fn has_loop_exit(block: &Block) -> bool {
+    matches!(
+        block.get_last_statement(),
+        Some(LastStatement::Break(_)) | Some(LastStatement::Continue(_))
+    ) || block.iter_statements().any(|statement| match statement {
+        Statement::Do(do_statement) => has_loop_exit(do_statement.get_block()),
+        Statement::If(if_statement) => {
+            if_statement
+                .iter_branches()
+                .any(|branch| has_loop_exit(branch.get_block()))
+                || if_statement.get_else_block().is_some_and(has_loop_exit)
+        }
+        _ => false,
+    })
+}
+
+/// Finds assignments to any of the given identifiers.
+struct FindAssignment<'a> {
+    names: &'a [String],
+    found: bool,
+}
+
+impl<'a> FindAssignment<'a> {
+    fn new(names: &'a [String]) -> Self {
+        Self {
+            names,
+            found: false,
+        }
+    }
+
+    fn check_variable(&mut self, variable: &Variable) {
+        if let Variable::Identifier(identifier) = variable {
+            if self.names.iter().any(|name| name == identifier.get_name()) {
+                self.found = true;
+            }
+        }
+    }
+}
+
+impl NodeProcessor for FindAssignment<'_> {
+    fn process_assign_statement(&mut self, assign: &mut AssignStatement) {
+        for variable in assign.get_variables() {
+            self.check_variable(variable);
+        }
+    }
+
+    fn process_compound_assign_statement(&mut self, assign: &mut CompoundAssignStatement) {
+        self.check_variable(assign.get_variable());
+    }
+}
+
+/// Replaces the references to the loop variables with their value.
+struct ReplaceLoopVariables {
+    identifier_tracker: IdentifierTracker,
+    variables: Vec<(String, Expression)>,
+}
+
+impl ReplaceLoopVariables {
+    fn get_value(&self, name: &str) -> Option<Expression> {
+        if self.is_identifier_used(name) {
+            return None;
+        }
+        self.variables
+            .iter()
+            .find(|(variable, _)| variable == name)
+            .map(|(_, value)| value.clone())
+    }
+}
+
+impl Deref for ReplaceLoopVariables {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for ReplaceLoopVariables {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for ReplaceLoopVariables {
+    fn process_expression(&mut self, expression: &mut Expression) {
+        if let Expression::Identifier(identifier) = expression {
+            if let Some(value) = self.get_value(identifier.get_name()) {
+                *expression = value;
+            }
+        }
+    }
+
+    fn process_prefix_expression(&mut self, prefix: &mut Prefix) {
+        if let Prefix::Identifier(identifier) = prefix {
+            if let Some(value) = self.get_value(identifier.get_name()) {
+                *prefix = ParentheseExpression::new(value).into();
+            }
+        }
+    }
+}
+
+struct Processor {
+    identifier_tracker: IdentifierTracker,
+    evaluator: Evaluator,
+    maximum_length: usize,
+}
+
+impl Processor {
+    fn new(maximum_length: usize) -> Self {
+        Self {
+            identifier_tracker: IdentifierTracker::default(),
+            evaluator: Evaluator::default(),
+            maximum_length,
+        }
+    }
+
+    fn is_global_ipairs(&self, call: &FunctionCall) -> bool {
+        call.get_method().is_none()
+            && matches!(
+                call.get_prefix(),
+                Prefix::Identifier(identifier)
+                    if identifier.get_name() == IPAIRS_FUNCTION
+                        && !self.is_identifier_used(IPAIRS_FUNCTION)
+            )
+    }
+
+    /// Returns the values of the array literal given to `ipairs`. Each value must be
+    /// a constant different from `nil`, since it gets copied for each reference.
+    fn get_iterated_values(&self, for_statement: &GenericForStatement) -> Option<Vec<Expression>> {
+        let call = match for_statement.get_expressions().as_slice() {
+            [Expression::Call(call)] if self.is_global_ipairs(call) => call,
+            _ => return None,
+        };
+
+        let table = match call.get_arguments() {
+            Arguments::Table(table) => table,
+            Arguments::Tuple(tuple) if tuple.len() == 1 => {
+                get_table_argument(tuple.iter_values().next()?)?
+            }
+            Arguments::Tuple(_) | Arguments::String(_) => return None,
+        };
+
+        if table.len() > self.maximum_length {
+            return None;
+        }
+
+        table
+            .iter_entries()
+            .map(|entry| match entry {
+                TableEntry::Value(value) if !self.evaluator.has_side_effects(value) => {
+                    match self.evaluator.evaluate(value) {
+                        LuaValue::Nil => None,
+                        value => value.to_expression(),
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn unroll(&self, for_statement: &GenericForStatement) -> Option<DoStatement> {
+        if for_statement.identifiers_len() > 2 {
+            return None;
+        }
+
+        let values = self.get_iterated_values(for_statement)?;
+
+        let block = for_statement.get_block();
+
+        if has_loop_exit(block) {
+            return None;
+        }
+
+        let names: Vec<_> = for_statement
+            .iter_identifiers()
+            .map(|identifier| identifier.get_name().to_owned())
+            .collect();
+
+        let mut find_assignment = FindAssignment::new(&names);
+        let mut body = block.clone();
+        DefaultVisitor::visit_block(&mut body, &mut find_assignment);
+        if find_assignment.found {
+            return None;
+        }
+
+        let splice = can_splice(&body);
+        let mut unrolled_block = Block::default();
+
+        for (index, value) in values.into_iter().enumerate() {
+            let mut variables = Vec::with_capacity(2);
+            let mut names = names.iter();
+            if let Some(index_name) = names.next() {
+                variables.push((index_name.clone(), Expression::from(index + 1)));
+            }
+            if let Some(value_name) = names.next() {
+                variables.push((value_name.clone(), value));
+            }
+
+            let mut iteration_block = body.clone();
+            let mut replace_variables = ReplaceLoopVariables {
+                identifier_tracker: IdentifierTracker::default(),
+                variables,
+            };
+            ScopeVisitor::visit_block(&mut iteration_block, &mut replace_variables);
+
+            if splice {
+                for statement in iteration_block.take_statements() {
+                    unrolled_block.push_statement(statement);
+                }
+            } else {
+                unrolled_block.push_statement(DoStatement::new(iteration_block));
+            }
+        }
+
+        Some(DoStatement::new(unrolled_block))
+    }
+}
+
+impl Deref for Processor {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for Processor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for Processor {
+    fn process_statement(&mut self, statement: &mut Statement) {
+        if let Statement::GenericFor(for_statement) = statement {
+            if let Some(unrolled) = self.unroll(for_statement) {
+                *statement = unrolled.into();
+            }
+        }
+    }
+}
+
+pub const UNROLL_IPAIRS_LOOP_RULE_NAME: &str = "unroll_ipairs_loop";
+
+/// A rule that unrolls generic `for` loops iterating with `ipairs` over small array
+/// literals of constant values.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnrollIpairsLoop {
+    maximum_length: usize,
+}
+
+impl Default for UnrollIpairsLoop {
+    fn default() -> Self {
+        Self {
+            maximum_length: DEFAULT_MAXIMUM_LENGTH,
+        }
+    }
+}
+
+impl FlawlessRule for UnrollIpairsLoop {
+    fn flawless_process(&self, block: &mut Block, _: &Context) {
+        let mut processor = Processor::new(self.maximum_length);
+        ScopeVisitor::visit_block(block, &mut processor);
+    }
+}
+
+impl RuleConfiguration for UnrollIpairsLoop {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match key.as_str() {
+                "maximum_length" => {
+                    self.maximum_length = value.expect_usize(&key)?;
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        UNROLL_IPAIRS_LOOP_RULE_NAME
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+
+        if self.maximum_length != DEFAULT_MAXIMUM_LENGTH {
+            properties.insert(
+                "maximum_length".to_owned(),
+                RulePropertyValue::Usize(self.maximum_length),
+            );
+        }
+
+        properties
+    }
+
+    fn describe_properties(&self) -> Vec<RulePropertyDescription> {
+        vec![
+            RulePropertyDescription::new("maximum_length", RulePropertyType::Usize)
+                .with_default(DEFAULT_MAXIMUM_LENGTH),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rule;
+
+    use insta::assert_json_snapshot;
+
+    fn new_rule() -> UnrollIpairsLoop {
+        UnrollIpairsLoop::default()
+    }
+
+    #[test]
+    fn serialize_default_rule() {
+        let rule: Box<dyn Rule> = Box::new(new_rule());
+
+        assert_json_snapshot!("default_unroll_ipairs_loop", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_maximum_length() {
+        let rule: Box<dyn Rule> = Box::new(UnrollIpairsLoop { maximum_length: 10 });
+
+        assert_json_snapshot!("unroll_ipairs_loop_with_maximum_length", rule);
+    }
+
+    #[test]
+    fn configure_with_extra_field_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'unroll_ipairs_loop',
+            prop: "something",
+        }"#,
+        );
+        pretty_assertions::assert_eq!(result.unwrap_err().to_string(), "unexpected field 'prop'");
+    }
+}
@@ -9,7 +9,23 @@ use super::verify_no_rule_properties;
 enum FilterResult {
     Keep,
     Remove,
-    Replace(Statement),
+    Replace(Block),
+}
+
+/// Returns true if the statements of the block can be moved into the parent block
+/// without a `do` statement: the block must not declare anything (local variables,
+/// local functions or types) and must not end with a last statement (like a `return`).
+pub(crate) fn can_splice(block: &Block) -> bool {
+    block.get_last_statement().is_none()
+        && block.iter_statements().all(|statement| {
+            !matches!(
+                statement,
+                Statement::LocalAssign(_)
+                    | Statement::LocalFunction(_)
+                    | Statement::TypeDeclaration(_)
+                    | Statement::TypeFunction(_)
+            )
+        })
 }
 
 #[derive(Debug, Clone, Default)]
@@ -60,13 +76,13 @@ impl IfFilter {
                 if block_replacer.is_empty() {
                     FilterResult::Remove
                 } else {
-                    FilterResult::Replace(DoStatement::new(block_replacer).into())
+                    FilterResult::Replace(block_replacer)
                 }
             } else if let Some(else_block) = if_statement.take_else_block() {
                 if else_block.is_empty() {
                     FilterResult::Remove
                 } else {
-                    FilterResult::Replace(DoStatement::new(else_block).into())
+                    FilterResult::Replace(else_block)
                 }
             } else {
                 FilterResult::Remove
@@ -174,20 +190,40 @@ impl IfFilter {
 
 impl NodeProcessor for IfFilter {
     fn process_block(&mut self, block: &mut Block) {
-        block.filter_mut_statements(|statement| {
-            if let Statement::If(if_statement) = statement {
-                match self.simplify_if_statement(if_statement) {
-                    FilterResult::Keep => true,
-                    FilterResult::Remove => false,
-                    FilterResult::Replace(new_statement) => {
-                        *statement = new_statement;
-                        true
+        let mut index = 0;
+
+        while index < block.statements_len() {
+            let result = match block.iter_mut_statements().nth(index) {
+                Some(Statement::If(if_statement)) => self.simplify_if_statement(if_statement),
+                _ => FilterResult::Keep,
+            };
+
+            match result {
+                FilterResult::Keep => {
+                    index += 1;
+                }
+                FilterResult::Remove => {
+                    block.remove_statement(index);
+                }
+                FilterResult::Replace(mut new_block) => {
+                    if can_splice(&new_block) {
+                        block.remove_statement(index);
+                        // the inserted statements are processed on the next iterations,
+                        // since they can also be if statements that can be simplified
+                        for (offset, statement) in
+                            new_block.take_statements().into_iter().enumerate()
+                        {
+                            block.insert_statement(index + offset, statement);
+                        }
+                    } else {
+                        if let Some(statement) = block.iter_mut_statements().nth(index) {
+                            *statement = DoStatement::new(new_block).into();
+                        }
+                        index += 1;
                     }
                 }
-            } else {
-                true
             }
-        });
+        }
     }
 
     fn process_expression(&mut self, expression: &mut Expression) {
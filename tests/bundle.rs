@@ -637,5 +637,170 @@ data:
 
             process_main_with_error(&resources, "two_different_direct_cycles");
         }
+
+        #[test]
+        fn three_modules_cycle_lists_modules_in_order() {
+            let resources = memory_resources!(
+                "src/a.lua" => "return require('./b')",
+                "src/b.lua" => "return require('./c')",
+                "src/c.lua" => "return require('./a')",
+                "src/main.lua" => "local value = require('./a.lua')",
+                ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+            );
+
+            let errors = process(
+                &resources,
+                Options::new("src/main.lua").with_output("out.lua"),
+            )
+            .unwrap()
+            .result()
+            .unwrap_err();
+
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].to_string().replace('\\', "/").contains(
+                "cyclic require detected with `src/a.lua` > `src/b.lua` > `src/c.lua` > `src/a.lua`"
+            ));
+        }
+    }
+}
+
+mod incremental {
+    use darklua_core::WorkerTree;
+
+    use super::*;
+
+    fn process_tree(worker_tree: &mut WorkerTree, resources: &Resources) {
+        let options = Options::new("src").with_output("out");
+        worker_tree.collect_work(resources, &options).unwrap();
+        worker_tree.process(resources, options).unwrap();
+        assert_eq!(worker_tree.total_errors(), 0);
+    }
+
+    #[test]
+    fn leaf_change_only_processes_dependent_entry() {
+        let resources = memory_resources!(
+            "src/app.lua" => "local leaf = require('../lib/leaf.lua')\nreturn leaf",
+            "src/other.lua" => "return 'other'",
+            "lib/leaf.lua" => "return 'leaf v1'",
+            ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+        );
+
+        let mut worker_tree = WorkerTree::default();
+        process_tree(&mut worker_tree, &resources);
+
+        assert!(resources.get("out/app.lua").unwrap().contains("leaf v1"));
+
+        // replace the output of the unrelated entry to detect if it gets written again
+        resources
+            .write("out/other.lua", "-- not processed")
+            .unwrap();
+        resources.write("lib/leaf.lua", "return 'leaf v2'").unwrap();
+
+        worker_tree.source_changed("lib/leaf.lua");
+        process_tree(&mut worker_tree, &resources);
+
+        assert!(resources.get("out/app.lua").unwrap().contains("leaf v2"));
+        pretty_assertions::assert_eq!(resources.get("out/other.lua").unwrap(), "-- not processed");
+    }
+
+    #[test]
+    fn leaf_change_processes_transitive_dependents() {
+        let resources = memory_resources!(
+            "src/app.lua" => "local mid = require('./mid.lua')\nreturn mid",
+            "src/mid.lua" => "return require('./leaf.lua')",
+            "src/leaf.lua" => "return 'leaf v1'",
+            "src/other.lua" => "return 'other'",
+            ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+        );
+
+        let mut worker_tree = WorkerTree::default();
+        process_tree(&mut worker_tree, &resources);
+
+        resources
+            .write("out/other.lua", "-- not processed")
+            .unwrap();
+        resources.write("src/leaf.lua", "return 'leaf v2'").unwrap();
+
+        worker_tree.source_changed("src/leaf.lua");
+        process_tree(&mut worker_tree, &resources);
+
+        assert!(resources.get("out/app.lua").unwrap().contains("leaf v2"));
+        assert!(resources.get("out/mid.lua").unwrap().contains("leaf v2"));
+        assert!(resources.get("out/leaf.lua").unwrap().contains("leaf v2"));
+        pretty_assertions::assert_eq!(resources.get("out/other.lua").unwrap(), "-- not processed");
+    }
+
+    #[test]
+    fn leaf_change_does_not_process_entry_that_stopped_requiring_it() {
+        let resources = memory_resources!(
+            "src/app.lua" => "local leaf = require('../lib/leaf.lua')\nreturn leaf",
+            "lib/leaf.lua" => "return 'leaf v1'",
+            ".darklua.json" => DARKLUA_BUNDLE_ONLY_READABLE_CONFIG,
+        );
+
+        let mut worker_tree = WorkerTree::default();
+        process_tree(&mut worker_tree, &resources);
+
+        resources.write("src/app.lua", "return 'app'").unwrap();
+        worker_tree.source_changed("src/app.lua");
+        process_tree(&mut worker_tree, &resources);
+
+        assert_eq!(worker_tree.iter_external_dependencies().count(), 0);
+
+        resources.write("out/app.lua", "-- not processed").unwrap();
+        resources.write("lib/leaf.lua", "return 'leaf v2'").unwrap();
+
+        worker_tree.source_changed("lib/leaf.lua");
+        process_tree(&mut worker_tree, &resources);
+
+        pretty_assertions::assert_eq!(resources.get("out/app.lua").unwrap(), "-- not processed");
+    }
+}
+
+#[cfg(feature = "zip")]
+mod zip_archive {
+    use std::io::{Cursor, Write};
+
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    fn create_archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+        for (path, content) in files {
+            writer
+                .start_file(*path, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn bundle_modules_from_zip_archive() {
+        let archive = create_archive(&[
+            (".darklua.json", DARKLUA_BUNDLE_ONLY_READABLE_CONFIG),
+            (
+                "src/main.lua",
+                "local value = require('./lib/value')\nreturn value",
+            ),
+            ("src/lib/value.lua", "return 'value from archive'"),
+        ]);
+        let resources = Resources::from_zip_archive(archive).unwrap();
+
+        process(
+            &resources,
+            Options::new("src/main.lua").with_output("out.lua"),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        let main = resources.get("out.lua").unwrap();
+
+        assert!(main.contains("value from archive"));
+        assert!(!main.contains("require("));
     }
 }
@@ -0,0 +1,211 @@
+mod utils;
+
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use darklua_core::{
+    nodes::{Block, Identifier},
+    process,
+    process::{IdentifierTracker, NodeProcessor, NodeVisitor, ScopeVisitor},
+    rules::{
+        Context, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult,
+        RuleProperties, RulePropertyValue,
+    },
+    Configuration, GeneratorParameters, Options,
+};
+
+use pretty_assertions::assert_eq;
+
+use utils::memory_resources;
+
+struct GlobalRenamer<'a> {
+    identifier_tracker: IdentifierTracker,
+    global: &'a str,
+    new_name: &'a str,
+    renamed: usize,
+}
+
+impl Deref for GlobalRenamer<'_> {
+    type Target = IdentifierTracker;
+
+    fn deref(&self) -> &Self::Target {
+        &self.identifier_tracker
+    }
+}
+
+impl DerefMut for GlobalRenamer<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.identifier_tracker
+    }
+}
+
+impl NodeProcessor for GlobalRenamer<'_> {
+    fn process_variable_expression(&mut self, variable: &mut Identifier) {
+        if variable.get_name() == self.global && !self.is_identifier_used(self.global) {
+            variable.set_name(self.new_name);
+            self.renamed += 1;
+        }
+    }
+}
+
+/// A custom rule that renames every reference to a global variable and counts
+/// how many references were renamed.
+#[derive(Debug, Default)]
+struct RenameGlobal {
+    global: String,
+    new_name: String,
+    renamed: Rc<Cell<usize>>,
+}
+
+impl Rule for RenameGlobal {
+    fn process(&self, block: &mut Block, _: &Context) -> RuleProcessResult {
+        if self.global.is_empty() || self.new_name.is_empty() {
+            return Err("the global and its new name must be provided".to_owned());
+        }
+
+        let mut processor = GlobalRenamer {
+            identifier_tracker: IdentifierTracker::new(),
+            global: &self.global,
+            new_name: &self.new_name,
+            renamed: 0,
+        };
+        ScopeVisitor::visit_block(block, &mut processor);
+
+        self.renamed.set(self.renamed.get() + processor.renamed);
+        Ok(())
+    }
+}
+
+impl RuleConfiguration for RenameGlobal {
+    fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+        for (key, value) in properties {
+            match (key.as_str(), value) {
+                ("global", RulePropertyValue::String(global)) => self.global = global,
+                ("new_name", RulePropertyValue::String(new_name)) => self.new_name = new_name,
+                ("global" | "new_name", _) => {
+                    return Err(RuleConfigurationError::StringExpected(key))
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> &'static str {
+        "rename_global"
+    }
+
+    fn serialize_to_properties(&self) -> RuleProperties {
+        let mut properties = RuleProperties::new();
+        properties.insert(
+            "global".to_owned(),
+            RulePropertyValue::String(self.global.clone()),
+        );
+        properties.insert(
+            "new_name".to_owned(),
+            RulePropertyValue::String(self.new_name.clone()),
+        );
+        properties
+    }
+}
+
+fn new_rename_global(global: &str, new_name: &str) -> RenameGlobal {
+    let mut rule = RenameGlobal::default();
+    let mut properties = RuleProperties::new();
+    properties.insert(
+        "global".to_owned(),
+        RulePropertyValue::String(global.to_owned()),
+    );
+    properties.insert(
+        "new_name".to_owned(),
+        RulePropertyValue::String(new_name.to_owned()),
+    );
+    rule.configure(properties).unwrap();
+    rule
+}
+
+#[test]
+fn custom_rule_renames_and_counts_global_references() {
+    let resources = memory_resources!(
+        "src/a.lua" => "print('a') local function f(print) print('b') end return print",
+        "src/b.lua" => "local print = print print('c')",
+    );
+
+    let rule = new_rename_global("print", "log");
+    let renamed = Rc::clone(&rule.renamed);
+    let rule: Box<dyn Rule> = Box::new(rule);
+
+    let configuration = Configuration::empty()
+        .with_generator(GeneratorParameters::default_dense())
+        .with_rule(rule);
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(renamed.get(), 3);
+    assert_eq!(
+        resources.get("src/a.lua").unwrap(),
+        "log('a')local function f(print)print('b')end return log"
+    );
+    assert_eq!(
+        resources.get("src/b.lua").unwrap(),
+        "local print=log print('c')"
+    );
+}
+
+#[test]
+fn custom_rule_error_is_reported() {
+    let resources = memory_resources!(
+        "src/test.lua" => "print('a')",
+    );
+
+    let rule: Box<dyn Rule> = Box::<RenameGlobal>::default();
+
+    let errors = process(
+        &resources,
+        Options::new("src").with_configuration(Configuration::empty().with_rule(rule)),
+    )
+    .unwrap()
+    .result()
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(resources.get("src/test.lua").unwrap(), "print('a')");
+}
+
+#[test]
+fn custom_rule_configuration_rejects_unknown_property() {
+    let mut rule = RenameGlobal::default();
+    let mut properties = RuleProperties::new();
+    properties.insert("prop".to_owned(), RulePropertyValue::Boolean(true));
+
+    assert_eq!(
+        rule.configure(properties),
+        Err(RuleConfigurationError::UnexpectedProperty(
+            "prop".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn custom_rule_serializes_with_its_properties() {
+    let rule: Box<dyn Rule> = Box::new(new_rename_global("print", "log"));
+
+    let value: serde_json::Value = serde_json::to_value(&rule).unwrap();
+
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "rule": "rename_global",
+            "global": "print",
+            "new_name": "log",
+        })
+    );
+}
@@ -0,0 +1,190 @@
+// this test file verifies that processing the same input with the same configuration
+// always produces byte-identical output, so that generated files produce clean diffs
+
+use darklua_core::{process, Configuration, Options, Resources};
+
+mod utils;
+
+use utils::memory_resources;
+
+/// The number of times each configuration is processed. Hash maps are seeded
+/// differently every time they are created, so running the process multiple times
+/// gives a chance to any dependency on their iteration order to show up.
+const RUN_COUNT: usize = 8;
+
+const MAIN_CODE: &str = r#"
+local utils = require("./utils")
+local values = require("./values")
+
+local function onEvent(event, ...)
+    assert(event, "event expected", ...)
+    assert(type(event.name) == "string", "name expected")
+    print(string.format("%s: %d", event.name, event.count))
+    print(string.upper(event.name), string.lower(event.name))
+end
+
+local callbacks = {
+    first = function(value) return value + 1 end,
+    second = function(value) return value + 1 end,
+    third = function(value) return value * 2 end,
+}
+
+for name, callback in pairs(callbacks) do
+    local total = 0
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            continue
+        end
+        total += callback(i)
+    end
+    onEvent({ name = name, count = total })
+end
+
+return utils.merge(values, { zeta = 1, alpha = 2, gamma = 3, beta = 4 })
+"#;
+
+const UTILS_CODE: &str = r#"
+local utils = {}
+
+function utils.merge(left: { [string]: any }, right: { [string]: any }): { [string]: any }
+    local result = {}
+    for key, value in pairs(left) do
+        result[key] = value
+    end
+    for key, value in pairs(right) do
+        result[key] = value
+    end
+    return result
+end
+
+function utils.average(list)
+    local sum = 0
+    for _, value in ipairs(list) do
+        sum = sum + value
+    end
+    return sum // #list, math.floor(sum / #list), math.max(sum, 0)
+end
+
+return utils
+"#;
+
+const VALUES_CODE: &str = r#"
+local config = { debug = false, level = 3, name = "values" }
+
+local function describe(object)
+    return object.config.name .. object.config.name .. tostring(object.config.level)
+end
+
+return {
+    describe = describe,
+    label = if config.debug then "debug" else "release",
+    message = `{config.name} at level {config.level}`,
+    config = config,
+}
+"#;
+
+const OUTPUT_FILES: [&str; 3] = ["out/main.lua", "out/utils.lua", "out/values.lua"];
+
+fn create_resources() -> Resources {
+    memory_resources!(
+        "src/main.lua" => MAIN_CODE,
+        "src/utils.lua" => UTILS_CODE,
+        "src/values.lua" => VALUES_CODE,
+    )
+}
+
+fn process_outputs(
+    configuration: &str,
+    options: fn() -> Options,
+    output_files: &[&str],
+) -> Vec<String> {
+    let resources = create_resources();
+    let configuration: Configuration = json5::from_str(configuration).unwrap();
+
+    process(&resources, options().with_configuration(configuration))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    output_files
+        .iter()
+        .map(|path| resources.get(path).unwrap())
+        .collect()
+}
+
+fn assert_deterministic_output(
+    configuration: &str,
+    options: fn() -> Options,
+    output_files: &[&str],
+) {
+    let expected = process_outputs(configuration, options, output_files);
+
+    for _ in 1..RUN_COUNT {
+        pretty_assertions::assert_eq!(
+            process_outputs(configuration, options, output_files),
+            expected
+        );
+    }
+}
+
+fn process_directory() -> Options {
+    Options::new("src").with_output("out")
+}
+
+#[test]
+fn default_rules_produce_identical_output() {
+    assert_deterministic_output("{}", process_directory, &OUTPUT_FILES);
+}
+
+#[test]
+fn default_rules_with_dense_generator_produce_identical_output() {
+    assert_deterministic_output("{ generator: 'dense' }", process_directory, &OUTPUT_FILES);
+}
+
+#[test]
+fn default_rules_with_readable_generator_produce_identical_output() {
+    assert_deterministic_output(
+        "{ generator: 'readable' }",
+        process_directory,
+        &OUTPUT_FILES,
+    );
+}
+
+#[test]
+fn rules_adding_declarations_produce_identical_output() {
+    assert_deterministic_output(
+        r#"{
+            rules: [
+                'remove_types',
+                'remove_continue',
+                'remove_compound_assignment',
+                'remove_floor_division',
+                'remove_if_expression',
+                'remove_interpolated_string',
+                'remove_assertions',
+                'deduplicate_functions',
+                'hoist_common_subexpressions',
+                { rule: 'localize_globals', minimum_references: 1 },
+                'sort_table_fields',
+                'group_local_assignment',
+                'rename_variables',
+            ],
+            generator: 'dense',
+        }"#,
+        process_directory,
+        &OUTPUT_FILES,
+    );
+}
+
+#[test]
+fn bundle_produces_identical_output() {
+    assert_deterministic_output(
+        r#"{
+            rules: ['remove_types', 'remove_interpolated_string', 'remove_continue'],
+            generator: 'readable',
+            bundle: { require_mode: 'path' },
+        }"#,
+        || Options::new("src/main.lua").with_output("out.lua"),
+        &["out.lua"],
+    );
+}
@@ -1,6 +1,17 @@
 mod utils;
 
-use darklua_core::{process, Options, Resources};
+use std::{path::PathBuf, time::Duration};
+
+use darklua_core::{
+    collect_requires,
+    generator::LuaVersion,
+    process,
+    rules::{
+        bundle::BundleRequireMode, ConvertTernaryToIfExpression, LocalizeGlobals, RemoveEmptyDo,
+        RemoveFloorDivision, RemoveNilDeclaration, Rule, SimplifyBooleanComparison,
+    },
+    BundleConfiguration, Configuration, GeneratorParameters, Options, Parser, Resources,
+};
 
 use pretty_assertions::assert_eq;
 
@@ -172,105 +183,1549 @@ fn use_default_json5_config_in_place() {
     assert_eq!(resources.get("src/test.lua").unwrap(), "return 'Hello'");
 }
 
-mod errors {
-    use std::path::{Path, PathBuf};
+#[test]
+fn append_source_map_comment_from_config() {
+    let resources = memory_resources!(
+        "src/test.lua" => "return _G.VALUE",
+        ".darklua.json5" => "{ rules: [], source_map_url: '{file}.map' }",
+    );
 
-    use darklua_core::{
-        nodes::Block,
-        rules::{
-            Context, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult,
-            RuleProperties,
-        },
-        Configuration, WorkerTree,
-    };
+    process(&resources, Options::new("src").with_output("output"))
+        .unwrap()
+        .result()
+        .unwrap();
 
-    use super::*;
+    let output = resources.get("output/test.lua").unwrap();
 
-    fn assert_errors(snapshot_name: &'static str, resources: &Resources, options: Options) {
-        let errors = process(resources, options)
-            .map_err(|err| vec![err])
-            .and_then(WorkerTree::result)
-            .unwrap_err();
+    assert_eq!(
+        output,
+        "return _G.VALUE\n--# sourceMappingURL=test.lua.map\n"
+    );
+    darklua_core::Parser::default()
+        .parse(&output)
+        .expect("output should be valid Lua");
+}
 
-        let errors_display = errors
-            .into_iter()
-            .map(|err| format!("- {}", err).replace('\\', "/"))
-            .collect::<Vec<_>>()
-            .join("\n");
-        insta::assert_snapshot!(snapshot_name, errors_display);
-    }
+#[test]
+fn preserve_license_header_comment_with_dense_generator() {
+    let resources = memory_resources!(
+        "src/test.lua" => "--[[\n    Copyright (c) 2024 Example\n]]\n-- helper module\nlocal value = 1 -- Copyright\nreturn value\n",
+        ".darklua.json5" => "{ generator: 'dense', rules: ['remove_comments'], preserve_header_comments: ['Copyright'] }",
+    );
 
-    #[test]
-    fn snapshot_simple_cyclic_work_error() {
-        let resources = memory_resources!(
-            "src/a.lua" => "return 'module a'",
-            "src/b.lua" => "return 'module b'",
-        );
+    process(&resources, Options::new("src").with_output("output"))
+        .unwrap()
+        .result()
+        .unwrap();
 
-        #[derive(Debug)]
-        struct CustomRule;
+    let output = resources.get("output/test.lua").unwrap();
 
-        impl RuleConfiguration for CustomRule {
-            fn configure(
-                &mut self,
-                _properties: RuleProperties,
-            ) -> Result<(), RuleConfigurationError> {
-                Ok(())
-            }
+    assert_eq!(
+        output,
+        "--[[\n    Copyright (c) 2024 Example\n]]\nlocal value=1 return value"
+    );
+    darklua_core::Parser::default()
+        .parse(&output)
+        .expect("output should be valid Lua");
+}
 
-            fn get_name(&self) -> &'static str {
-                "custom-rule"
-            }
+#[test]
+fn preserve_header_comment_is_not_duplicated_with_retain_lines_generator() {
+    let resources = memory_resources!(
+        "src/test.lua" => "--!strict\n-- helper module\nreturn nil\n",
+        ".darklua.json5" => "{ rules: [], preserve_header_comments: ['^--!'] }",
+    );
 
-            fn serialize_to_properties(&self) -> RuleProperties {
-                Default::default()
-            }
-        }
+    process(&resources, Options::new("src").with_output("output"))
+        .unwrap()
+        .result()
+        .unwrap();
 
-        impl Rule for CustomRule {
-            fn process(&self, _: &mut Block, _: &Context) -> RuleProcessResult {
-                Ok(())
-            }
+    assert_eq!(
+        resources.get("output/test.lua").unwrap(),
+        "--!strict\n-- helper module\nreturn nil\n"
+    );
+}
 
-            fn require_content(&self, _: &Path, _: &Block) -> Vec<PathBuf> {
-                vec!["src/a.lua".into(), "src/b.lua".into()]
-            }
-        }
+#[test]
+fn processing_report_records_changed_rules() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
 
-        let rule: Box<dyn Rule> = Box::new(CustomRule);
+    let remove_empty_do: Box<dyn Rule> = Box::<RemoveEmptyDo>::default();
+    let remove_nil_declaration: Box<dyn Rule> = Box::<RemoveNilDeclaration>::default();
+    let configuration = Configuration::empty()
+        .with_rule(remove_empty_do)
+        .with_rule(remove_nil_declaration);
 
-        assert_errors(
-            "simple_cyclic_work_error",
-            &resources,
-            Options::new("src").with_configuration(Configuration::empty().with_rule(rule)),
-        );
-    }
+    let worker_tree = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(configuration)
+            .with_processing_report(),
+    )
+    .unwrap();
 
-    #[test]
-    fn snapshot_missing_configuration_file() {
-        let resources = memory_resources!(
-            "src/init.lua" => "return ''",
-        );
+    let report: Vec<_> = worker_tree
+        .processing_report()
+        .into_iter()
+        .map(|entry| {
+            (
+                entry.path().display().to_string().replace('\\', "/"),
+                entry.rule_name(),
+                entry.changed(),
+            )
+        })
+        .collect();
 
-        assert_errors(
-            "missing_configuration_file",
-            &resources,
-            Options::new("src").with_configuration_at("missing/config.json"),
-        );
-    }
+    assert_eq!(
+        report,
+        vec![
+            ("src/test.lua".to_owned(), "remove_empty_do", true),
+            ("src/test.lua".to_owned(), "remove_nil_declaration", false),
+        ]
+    );
+}
 
-    #[test]
-    fn snapshot_multiple_configuration_file_found() {
-        let resources = memory_resources!(
-            "src/init.lua" => "return ''",
-            ".darklua.json" => "{ rules: [] }",
-            ".darklua.json5" => "{ rules: [] }",
-        );
+#[test]
+fn extract_messages_replaces_messages_and_collects_catalog() {
+    let resources = memory_resources!(
+        "src/test.lua" => "assert(value, 'value expected')\nif value < 0 then error('negative value') end",
+    );
 
-        assert_errors(
-            "multiple_configuration_file_found",
-            &resources,
-            Options::new("src"),
-        );
-    }
+    let extract_messages = json5::from_str::<Box<dyn Rule>>(
+        "{ rule: 'extract_messages', lookup_function: 'i18n.get' }",
+    )
+    .unwrap();
+    let configuration = Configuration::empty().with_rule(extract_messages);
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap();
+
+    let messages: Vec<_> = worker_tree
+        .extracted_messages()
+        .into_iter()
+        .map(|entry| {
+            (
+                entry.path().display().to_string().replace('\\', "/"),
+                entry.function().to_owned(),
+                entry.message().to_owned(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "src/test.lua".to_owned(),
+                "assert".to_owned(),
+                "value expected".to_owned()
+            ),
+            (
+                "src/test.lua".to_owned(),
+                "error".to_owned(),
+                "negative value".to_owned()
+            ),
+        ]
+    );
+
+    worker_tree.result().unwrap();
+
+    let parser = Parser::default();
+    assert_eq!(
+        parser
+            .parse(&resources.get("src/test.lua").unwrap())
+            .unwrap(),
+        parser
+            .parse(
+                "assert(value, i18n.get('value expected'))\n\
+                if value < 0 then error(i18n.get('negative value')) end"
+            )
+            .unwrap()
+    );
+}
+
+#[test]
+fn safe_mode_skips_rules_that_may_change_behavior() {
+    let code = "local value: boolean = true\nreturn value == true";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+    );
+
+    let simplify_boolean_comparison: Box<dyn Rule> = Box::<SimplifyBooleanComparison>::default();
+    let configuration = Configuration::empty()
+        .with_rule(simplify_boolean_comparison)
+        .with_safe_mode();
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), code);
+}
+
+#[test]
+fn safe_mode_skips_localize_globals() {
+    let code = "print('a')\nprint('b')";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+    );
+
+    let localize_globals: Box<dyn Rule> = Box::<LocalizeGlobals>::default();
+    let configuration = Configuration::empty()
+        .with_rule(localize_globals)
+        .with_safe_mode();
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), code);
+}
+
+#[test]
+fn luau_only_rule_has_no_effect_with_lua51_target() {
+    let code = "return condition and 'a' or 'b'";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+    );
+
+    let convert_ternary: Box<dyn Rule> = Box::<ConvertTernaryToIfExpression>::default();
+    let configuration = Configuration::empty()
+        .with_rule(convert_ternary)
+        .with_target(LuaVersion::Lua51);
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), code);
+}
+
+#[test]
+fn luau_only_rule_applies_with_luau_target() {
+    let resources = memory_resources!(
+        "src/test.lua" => "return condition and 'a' or 'b'",
+    );
+
+    let convert_ternary: Box<dyn Rule> = Box::<ConvertTernaryToIfExpression>::default();
+    let configuration = Configuration::empty()
+        .with_rule(convert_ternary)
+        .with_target(LuaVersion::Luau);
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(
+        resources.get("src/test.lua").unwrap(),
+        "return if condition then 'a' else 'b'"
+    );
+}
+
+#[test]
+fn process_bitwise_operators_with_lua54_target() {
+    let code = "return a & b | c << 2";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+    );
+
+    let configuration = Configuration::empty().with_target(LuaVersion::Lua54);
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), code);
+}
+
+#[test]
+fn generating_if_expression_with_lua51_target_errors() {
+    let code = "return if condition then 'a' else 'b'";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+    );
+
+    let configuration = Configuration::empty().with_target(LuaVersion::Lua51);
+
+    let errors = process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0]
+            .to_string()
+            .ends_with("if expressions are not supported by Lua 5.1"),
+        "{}",
+        errors[0]
+    );
+    assert_eq!(resources.get("src/test.lua").unwrap(), code);
+}
+
+#[test]
+fn generated_identifiers_use_configured_prefix() {
+    let resources = memory_resources!(
+        "src/test.lua" => "local math = {}\nreturn a // b",
+    );
+
+    let remove_floor_division: Box<dyn Rule> = Box::<RemoveFloorDivision>::default();
+    let configuration = Configuration::empty()
+        .with_rule(remove_floor_division)
+        .with_generator(GeneratorParameters::default_dense())
+        .with_generated_identifier_prefix("_D");
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    let output = resources.get("src/test.lua").unwrap();
+
+    assert!(
+        output.contains("local _DMATH_FLOOR=math.floor"),
+        "{}",
+        output
+    );
+    assert!(output.contains("_DMATH_FLOOR(a/b)"), "{}", output);
+    assert!(!output.contains("__DARKLUA_"), "{}", output);
+}
+
+#[test]
+fn generated_identifiers_with_configured_prefix_avoid_existing_names() {
+    let resources = memory_resources!(
+        "src/test.lua" => "local math = {}\nreturn a // b + _DMATH_FLOOR",
+    );
+
+    let remove_floor_division: Box<dyn Rule> = Box::<RemoveFloorDivision>::default();
+    let configuration = Configuration::empty()
+        .with_rule(remove_floor_division)
+        .with_generator(GeneratorParameters::default_dense())
+        .with_generated_identifier_prefix("_D");
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(configuration),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    let output = resources.get("src/test.lua").unwrap();
+
+    assert!(
+        output.contains("local _DMATH_FLOOR_1=math.floor"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("_DMATH_FLOOR_1(a/b)+_DMATH_FLOOR"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn timing_report_lists_applied_rules() {
+    let resources = memory_resources!(
+        "src/a.lua" => ANY_CODE,
+        "src/b.lua" => ANY_CODE,
+    );
+
+    let remove_empty_do: Box<dyn Rule> = Box::<RemoveEmptyDo>::default();
+    let remove_nil_declaration: Box<dyn Rule> = Box::<RemoveNilDeclaration>::default();
+    let configuration = Configuration::empty()
+        .with_rule(remove_empty_do)
+        .with_rule(remove_nil_declaration);
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(configuration)
+            .with_timing(),
+    )
+    .unwrap();
+
+    let report = worker_tree.timing_report();
+
+    let mut rule_names: Vec<_> = report.iter().map(|timing| timing.rule_name()).collect();
+    rule_names.sort();
+    assert_eq!(
+        rule_names,
+        vec!["remove_empty_do", "remove_nil_declaration"]
+    );
+
+    for timing in report.iter() {
+        assert_eq!(timing.file_count(), 2);
+        assert!(timing.total_duration() >= Duration::ZERO);
+    }
+    assert!(report
+        .windows(2)
+        .all(|timings| timings[0].total_duration() >= timings[1].total_duration()));
+
+    let entries = worker_tree.rule_timing_entries();
+    assert_eq!(entries.len(), 4);
+    for timing in report.iter() {
+        let total: Duration = entries
+            .iter()
+            .filter(|entry| entry.rule_name() == timing.rule_name())
+            .map(|entry| entry.duration())
+            .sum();
+        assert_eq!(total, timing.total_duration());
+    }
+}
+
+#[test]
+fn stats_records_sizes_and_node_counts() {
+    let code = "local value = 1 + 2\nif value > 2 then\n    print(value)\nend\nreturn value";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+    );
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .with_output("out")
+            .with_stats(),
+    )
+    .unwrap();
+
+    let statistics = worker_tree.file_statistics();
+    assert_eq!(statistics.len(), 1);
+
+    let file_statistics = statistics[0];
+    let output = resources.get("out/test.lua").unwrap();
+
+    assert_eq!(
+        file_statistics
+            .path()
+            .display()
+            .to_string()
+            .replace('\\', "/"),
+        "src/test.lua"
+    );
+    assert_eq!(file_statistics.input_size(), code.len());
+    assert_eq!(file_statistics.output_size(), output.len());
+    // the local assignment, the if statement, the call and the return statement
+    assert_eq!(file_statistics.statement_count(), 4);
+    // `1 + 2`, `1`, `2`, `value > 2`, `value`, `2`, `value` (argument) and `value` (return)
+    assert_eq!(file_statistics.expression_count(), 8);
+
+    worker_tree.result().unwrap();
+}
+
+#[test]
+fn stats_are_empty_when_not_enabled() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src").with_configuration(Configuration::empty()),
+    )
+    .unwrap();
+
+    assert!(worker_tree.file_statistics().is_empty());
+}
+
+#[test]
+fn timing_report_is_empty_when_not_enabled() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let worker_tree = process(&resources, Options::new("src")).unwrap();
+
+    assert!(worker_tree.timing_report().is_empty());
+    assert!(worker_tree.rule_timing_entries().is_empty());
+}
+
+#[test]
+fn processing_report_is_empty_when_not_enabled() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let worker_tree = process(&resources, Options::new("src")).unwrap();
+
+    assert!(worker_tree.processing_report().is_empty());
+}
+
+#[test]
+fn dry_run_does_not_write_files() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let remove_empty_do: Box<dyn Rule> = Box::<RemoveEmptyDo>::default();
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty().with_rule(remove_empty_do))
+            .dry_run(),
+    )
+    .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), ANY_CODE);
+
+    let entries = worker_tree.dry_run_entries();
+    assert_eq!(entries.len(), 1);
+
+    let entry = entries[0];
+    assert_eq!(
+        entry.path().display().to_string().replace('\\', "/"),
+        "src/test.lua"
+    );
+    assert_eq!(entry.original(), Some(ANY_CODE));
+    assert_eq!(entry.transformed(), ANY_CODE_DEFAULT_PROCESS);
+    assert!(entry.changed());
+}
+
+#[test]
+fn dry_run_with_output_does_not_create_files() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src").with_output("output").dry_run(),
+    )
+    .unwrap();
+
+    assert!(!resources.exists("output/test.lua").unwrap());
+
+    let entries = worker_tree.dry_run_entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].original(), None);
+    assert_eq!(entries[0].transformed(), ANY_CODE_DEFAULT_PROCESS);
+}
+
+#[test]
+fn dry_run_bundle_with_required_file_without_output() {
+    let resources = memory_resources!(
+        "src/main.lua" => "return require('./value')",
+        "src/value.lua" => "return 'ok' -- comment",
+        ".darklua.json5" => "{ rules: ['remove_comments'], bundle: { require_mode: 'path' } }",
+    );
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src").with_output("output").dry_run(),
+    )
+    .unwrap();
+
+    assert_eq!(worker_tree.total_errors(), 0);
+    assert!(!resources.exists("output/value.lua").unwrap());
+
+    let entries = worker_tree.dry_run_entries();
+    assert_eq!(entries.len(), 2);
+
+    let main_entry = entries
+        .iter()
+        .find(|entry| entry.path().ends_with("main.lua"))
+        .expect("main entry should exist");
+    assert!(main_entry.transformed().contains("'ok'"));
+    assert!(!main_entry.transformed().contains("comment"));
+}
+
+#[test]
+fn dry_run_entries_are_empty_when_not_enabled() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let worker_tree = process(&resources, Options::new("src")).unwrap();
+
+    assert!(worker_tree.dry_run_entries().is_empty());
+}
+
+#[test]
+fn manifest_lists_processed_files() {
+    let resources = memory_resources!(
+        "src/changed.lua" => ANY_CODE,
+        "src/unchanged.lua" => ANY_CODE_DEFAULT_PROCESS,
+    );
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_output("output")
+            .with_manifest("manifest.json"),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&resources.get("manifest.json").unwrap()).unwrap();
+
+    assert_eq!(
+        manifest,
+        serde_json::json!({
+            "version": 1,
+            "files": [
+                {
+                    "input": "src/changed.lua",
+                    "output": "output/changed.lua",
+                    "changed": true,
+                    "size": ANY_CODE_DEFAULT_PROCESS.len(),
+                },
+                {
+                    "input": "src/unchanged.lua",
+                    "output": "output/unchanged.lua",
+                    "changed": false,
+                    "size": ANY_CODE_DEFAULT_PROCESS.len(),
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn manifest_is_not_written_during_dry_run() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src").with_manifest("manifest.json").dry_run(),
+    )
+    .unwrap();
+
+    assert!(!resources.exists("manifest.json").unwrap());
+
+    let entries = worker_tree.manifest_entries();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].changed());
+}
+
+#[test]
+fn output_transform_prepends_banner() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+        "src/nested/other.lua" => ANY_CODE,
+    );
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_output("output")
+            .with_output_transform(|code, output| {
+                let file_name = output.file_name().unwrap().to_string_lossy();
+                format!("-- generated file: {}\n{}", file_name, code)
+            }),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(
+        resources.get("output/test.lua").unwrap(),
+        format!("-- generated file: test.lua\n{}", ANY_CODE_DEFAULT_PROCESS)
+    );
+    assert_eq!(
+        resources.get("output/nested/other.lua").unwrap(),
+        format!("-- generated file: other.lua\n{}", ANY_CODE_DEFAULT_PROCESS)
+    );
+}
+
+fn has_no_darklua_comment(_path: &std::path::Path, content: &str) -> bool {
+    !content
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .contains("--!nodarklua")
+}
+
+#[test]
+fn file_filter_skips_file_with_sentinel_comment() {
+    let skipped_code = "--!nodarklua\ndo end return true";
+    let resources = memory_resources!(
+        "src/skipped.lua" => skipped_code,
+        "src/test.lua" => ANY_CODE,
+    );
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_output("output")
+            .with_file_filter(has_no_darklua_comment),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert!(!resources.exists("output/skipped.lua").unwrap());
+    assert_eq!(
+        resources.get("output/test.lua").unwrap(),
+        ANY_CODE_DEFAULT_PROCESS
+    );
+}
+
+#[test]
+fn file_filter_skips_file_in_place() {
+    let skipped_code = "--!nodarklua\ndo end return true";
+    let resources = memory_resources!(
+        "src/skipped.lua" => skipped_code,
+        "src/test.lua" => ANY_CODE,
+    );
+
+    process(
+        &resources,
+        Options::new("src").with_file_filter(has_no_darklua_comment),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(resources.get("src/skipped.lua").unwrap(), skipped_code);
+    assert_eq!(
+        resources.get("src/test.lua").unwrap(),
+        ANY_CODE_DEFAULT_PROCESS
+    );
+}
+
+#[test]
+fn file_filter_copies_filtered_files() {
+    let skipped_code = "--!nodarklua\ndo end return true";
+    let resources = memory_resources!(
+        "src/skipped.lua" => skipped_code,
+        "src/test.lua" => ANY_CODE,
+    );
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_output("output")
+            .with_file_filter(has_no_darklua_comment)
+            .copy_filtered_files(),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(resources.get("output/skipped.lua").unwrap(), skipped_code);
+    assert_eq!(
+        resources.get("output/test.lua").unwrap(),
+        ANY_CODE_DEFAULT_PROCESS
+    );
+}
+
+#[test]
+fn file_filter_skipped_file_can_be_required() {
+    use darklua_core::{
+        nodes::Block,
+        rules::{
+            Context, RuleConfiguration, RuleConfigurationError, RuleProcessResult, RuleProperties,
+        },
+    };
+    use std::path::{Path, PathBuf};
+
+    #[derive(Debug)]
+    struct RequireSkippedFile;
+
+    impl RuleConfiguration for RequireSkippedFile {
+        fn configure(&mut self, _properties: RuleProperties) -> Result<(), RuleConfigurationError> {
+            Ok(())
+        }
+
+        fn get_name(&self) -> &'static str {
+            "require-skipped-file"
+        }
+
+        fn serialize_to_properties(&self) -> RuleProperties {
+            Default::default()
+        }
+    }
+
+    impl Rule for RequireSkippedFile {
+        fn process(&self, _: &mut Block, _: &Context) -> RuleProcessResult {
+            Ok(())
+        }
+
+        fn require_content(&self, current_source: &Path, _: &Block) -> Vec<PathBuf> {
+            if current_source.ends_with("test.lua") {
+                vec!["src/skipped.lua".into()]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    let resources = memory_resources!(
+        "src/skipped.lua" => "--!nodarklua\nreturn true",
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let rule: Box<dyn Rule> = Box::new(RequireSkippedFile);
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_output("output")
+            .with_configuration(Configuration::empty().with_rule(rule))
+            .with_file_filter(has_no_darklua_comment),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert!(!resources.exists("output/skipped.lua").unwrap());
+    assert!(resources.exists("output/test.lua").unwrap());
+}
+
+const BYTE_ORDER_MARK: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+fn process_with_end_of_file_options(
+    generator: GeneratorParameters,
+    update_options: impl FnOnce(Options) -> Options,
+) -> String {
+    let resources = memory_resources!(
+        "src/test.lua" => "local a = 1\nreturn a\n",
+    );
+
+    process(
+        &resources,
+        update_options(
+            Options::new("src")
+                .with_configuration(Configuration::empty().with_generator(generator)),
+        ),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    resources.get("src/test.lua").unwrap()
+}
+
+fn process_with_generator(generator: GeneratorParameters, code: &str) -> String {
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+    );
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(Configuration::empty().with_generator(generator)),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    resources.get("src/test.lua").unwrap()
+}
+
+#[test]
+fn retain_lines_generator_with_escaped_non_ascii() {
+    assert_eq!(
+        process_with_generator(
+            GeneratorParameters::default_retain_lines().with_escaped_non_ascii(),
+            "return 'café'",
+        ),
+        "return 'caf\\195\\169'"
+    );
+}
+
+#[test]
+fn format_generator_with_escaped_non_ascii() {
+    let output = process_with_generator(
+        GeneratorParameters::default_format().with_escaped_non_ascii(),
+        "return 'café'",
+    );
+
+    assert_eq!(output.trim_end(), "return 'caf\\195\\169'");
+}
+
+#[test]
+fn dense_generator_with_escaped_non_ascii() {
+    assert_eq!(
+        process_with_generator(
+            GeneratorParameters::default_dense().with_escaped_non_ascii(),
+            "return 'café'",
+        ),
+        "return'caf\\195\\169'"
+    );
+}
+
+#[test]
+fn readable_generator_with_escaped_non_ascii() {
+    let output = process_with_generator(
+        GeneratorParameters::default_readable().with_escaped_non_ascii(),
+        "return 'café'",
+    );
+
+    assert_eq!(output.trim_end(), "return 'caf\\195\\169'");
+}
+
+#[test]
+fn dense_generator_with_always_semicolons() {
+    assert_eq!(
+        process_with_generator(
+            GeneratorParameters::default_dense().with_always_semicolons(),
+            "local a = 1 return a",
+        ),
+        "local a=1;return a"
+    );
+}
+
+#[test]
+fn dense_generator_with_spaced_binary_operators() {
+    assert_eq!(
+        process_with_generator(
+            GeneratorParameters::default_dense().with_spaced_binary_operators(),
+            "return a + b",
+        ),
+        "return a + b"
+    );
+}
+
+#[test]
+fn readable_generator_with_aligned_table_entries() {
+    let output = process_with_generator(
+        GeneratorParameters::default_readable()
+            .with_column_span(10)
+            .with_aligned_table_entries(),
+        "return { a = 1, value = 2 }",
+    );
+
+    assert!(output.contains("a     = 1,"), "{}", output);
+    assert!(output.contains("value = 2,"), "{}", output);
+}
+
+#[test]
+fn readable_generator_without_trailing_commas() {
+    let output = process_with_generator(
+        GeneratorParameters::default_readable()
+            .with_column_span(10)
+            .without_trailing_commas(),
+        "return { a = 1, value = 2 }",
+    );
+
+    assert!(output.contains("a = 1,"), "{}", output);
+    assert!(output.contains("value = 2\n"), "{}", output);
+}
+
+#[test]
+fn output_does_not_start_with_byte_order_mark_by_default() {
+    let code =
+        process_with_end_of_file_options(GeneratorParameters::default_dense(), |options| options);
+
+    assert!(!code.as_bytes().starts_with(BYTE_ORDER_MARK));
+}
+
+#[test]
+fn output_starts_with_byte_order_mark() {
+    let code = process_with_end_of_file_options(GeneratorParameters::default_dense(), |options| {
+        options.with_byte_order_mark()
+    });
+
+    assert!(code.as_bytes().starts_with(BYTE_ORDER_MARK));
+    assert_eq!(
+        &code.as_bytes()[BYTE_ORDER_MARK.len()..],
+        b"local a=1 return a"
+    );
+}
+
+#[test]
+fn output_keeps_generated_end_of_file_by_default() {
+    assert_eq!(
+        process_with_end_of_file_options(GeneratorParameters::default_dense(), |options| options),
+        "local a=1 return a"
+    );
+    assert_eq!(
+        process_with_end_of_file_options(GeneratorParameters::default_readable(), |options| {
+            options
+        }),
+        "local a = 1\n\nreturn a\n"
+    );
+}
+
+#[test]
+fn output_ends_with_final_new_line() {
+    assert_eq!(
+        process_with_end_of_file_options(GeneratorParameters::default_dense(), |options| {
+            options.with_final_new_line(true)
+        }),
+        "local a=1 return a\n"
+    );
+    assert_eq!(
+        process_with_end_of_file_options(GeneratorParameters::default_readable(), |options| {
+            options.with_final_new_line(true)
+        }),
+        "local a = 1\n\nreturn a\n"
+    );
+}
+
+#[test]
+fn output_ends_without_final_new_line() {
+    assert_eq!(
+        process_with_end_of_file_options(GeneratorParameters::default_dense(), |options| {
+            options.with_final_new_line(false)
+        }),
+        "local a=1 return a"
+    );
+    assert_eq!(
+        process_with_end_of_file_options(GeneratorParameters::default_readable(), |options| {
+            options.with_final_new_line(false)
+        }),
+        "local a = 1\n\nreturn a"
+    );
+}
+
+#[test]
+fn output_with_byte_order_mark_and_final_new_line() {
+    let code = process_with_end_of_file_options(GeneratorParameters::default_dense(), |options| {
+        options.with_byte_order_mark().with_final_new_line(true)
+    });
+
+    assert_eq!(code, "\u{FEFF}local a=1 return a\n");
+}
+
+#[test]
+fn output_with_byte_order_mark_and_without_final_new_line() {
+    let code =
+        process_with_end_of_file_options(GeneratorParameters::default_readable(), |options| {
+            options.with_byte_order_mark().with_final_new_line(false)
+        });
+
+    assert_eq!(code, "\u{FEFF}local a = 1\n\nreturn a");
+}
+
+#[test]
+fn dry_run_entry_includes_end_of_file_options() {
+    let resources = memory_resources!(
+        "src/test.lua" => ANY_CODE,
+    );
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src")
+            .with_byte_order_mark()
+            .with_final_new_line(true)
+            .dry_run(),
+    )
+    .unwrap();
+
+    let entries = worker_tree.dry_run_entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        entries[0].transformed(),
+        format!("\u{FEFF}{}\n", ANY_CODE_DEFAULT_PROCESS)
+    );
+}
+
+#[test]
+fn generator_override_for_glob_pattern() {
+    let resources = memory_resources!(
+        "src/core/init.lua" => "local values = { 1, 2 }\nreturn values",
+        "src/plugin/init.lua" => "local values = { 1, 2 }\nreturn values",
+    );
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .with_generator_override_for("src/core/**", GeneratorParameters::default_dense())
+            .with_generator_override_for("src/plugin/**", GeneratorParameters::default_readable()),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(
+        resources.get("src/core/init.lua").unwrap(),
+        "local values={1,2}return values"
+    );
+    assert_eq!(
+        resources.get("src/plugin/init.lua").unwrap(),
+        "local values = {1, 2}\n\nreturn values\n"
+    );
+}
+
+#[test]
+fn generator_override_for_glob_pattern_uses_first_match() {
+    let resources = memory_resources!(
+        "src/init.lua" => "local values = { 1, 2 }\nreturn values",
+    );
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .with_generator_override_for("src/*.lua", GeneratorParameters::default_dense())
+            .with_generator_override_for("src/**", GeneratorParameters::default_readable()),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(
+        resources.get("src/init.lua").unwrap(),
+        "local values={1,2}return values"
+    );
+}
+
+#[test]
+fn format_generator_keeps_comments() {
+    let resources = memory_resources!(
+        "src/test.lua" => "-- leading\nlocal a=1 -- trailing\nreturn   a",
+    );
+
+    process(
+        &resources,
+        Options::new("src").with_configuration(
+            Configuration::empty().with_generator(GeneratorParameters::default_format()),
+        ),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(
+        resources.get("src/test.lua").unwrap(),
+        "-- leading\nlocal a = 1 -- trailing\nreturn a\n"
+    );
+}
+
+mod errors {
+    use std::path::{Path, PathBuf};
+
+    use darklua_core::{
+        nodes::Block,
+        rules::{
+            Context, Rule, RuleConfiguration, RuleConfigurationError, RuleProcessResult,
+            RuleProperties,
+        },
+        Configuration, DarkluaError, ErrorCategory, ErrorLocation, WorkerTree,
+    };
+
+    use super::*;
+
+    fn assert_errors(snapshot_name: &'static str, resources: &Resources, options: Options) {
+        let errors = process(resources, options)
+            .map_err(|err| vec![err])
+            .and_then(WorkerTree::result)
+            .unwrap_err();
+
+        let errors_display = errors
+            .into_iter()
+            .map(|err| format!("- {}", err).replace('\\', "/"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        insta::assert_snapshot!(snapshot_name, errors_display);
+    }
+
+    #[test]
+    fn count_errors_by_category() {
+        let resources = memory_resources!(
+            "src/broken.lua" => "returnone",
+            "src/main.lua" => "local library = require('./unknown.lua')",
+            "src/valid.lua" => "return nil",
+            ".darklua.json5" => "{ rules: [], bundle: { require_mode: 'path' } }",
+        );
+
+        let worker_tree = process(&resources, Options::new("src")).unwrap();
+
+        pretty_assertions::assert_eq!(worker_tree.total_errors(), 2);
+        pretty_assertions::assert_eq!(
+            worker_tree.error_counts(),
+            vec![(ErrorCategory::Parser, 1), (ErrorCategory::Bundle, 1)]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    fn get_single_error(resources: &Resources, options: Options) -> DarkluaError {
+        let mut errors = process(resources, options)
+            .map_err(|err| vec![err])
+            .and_then(WorkerTree::result)
+            .unwrap_err();
+
+        pretty_assertions::assert_eq!(errors.len(), 1);
+        errors.pop().unwrap()
+    }
+
+    #[test]
+    fn parser_error_location() {
+        let resources = memory_resources!(
+            "src/broken.lua" => "local a = 1\nlocal b = = 2\n",
+            ".darklua.json5" => "{ rules: [] }",
+        );
+
+        let error = get_single_error(&resources, Options::new("src"));
+        let location: ErrorLocation = error.location().expect("error should have a location");
+
+        pretty_assertions::assert_eq!(location.path(), Path::new("src/broken.lua"));
+        pretty_assertions::assert_eq!(location.line(), 2);
+        pretty_assertions::assert_eq!(location.column(), 9);
+    }
+
+    #[test]
+    fn parser_error_location_keeps_display() {
+        let resources = memory_resources!(
+            "src/broken.lua" => "returnone",
+            ".darklua.json5" => "{ rules: [] }",
+        );
+
+        let error = get_single_error(&resources, Options::new("src"));
+
+        assert!(error.location().is_some());
+        assert!(error
+            .to_string()
+            .starts_with("unable to parse `src/broken.lua`: "));
+    }
+
+    #[test]
+    fn configuration_error_has_no_location() {
+        let resources = memory_resources!(
+            "src/test.lua" => "return nil",
+            ".darklua.json5" => "{ rules: [], preserve_header_comments: ['(oops'] }",
+        );
+
+        let error = get_single_error(&resources, Options::new("src"));
+
+        assert!(error.location().is_none());
+    }
+
+    #[test]
+    fn snapshot_invalid_preserve_header_comments_pattern() {
+        let resources = memory_resources!(
+            "src/test.lua" => "return nil",
+            ".darklua.json5" => "{ rules: [], preserve_header_comments: ['(oops'] }",
+        );
+
+        assert_errors(
+            "invalid_preserve_header_comments_pattern",
+            &resources,
+            Options::new("src"),
+        );
+    }
+
+    #[test]
+    fn snapshot_simple_cyclic_work_error() {
+        let resources = memory_resources!(
+            "src/a.lua" => "return 'module a'",
+            "src/b.lua" => "return 'module b'",
+        );
+
+        #[derive(Debug)]
+        struct CustomRule;
+
+        impl RuleConfiguration for CustomRule {
+            fn configure(
+                &mut self,
+                _properties: RuleProperties,
+            ) -> Result<(), RuleConfigurationError> {
+                Ok(())
+            }
+
+            fn get_name(&self) -> &'static str {
+                "custom-rule"
+            }
+
+            fn serialize_to_properties(&self) -> RuleProperties {
+                Default::default()
+            }
+        }
+
+        impl Rule for CustomRule {
+            fn process(&self, _: &mut Block, _: &Context) -> RuleProcessResult {
+                Ok(())
+            }
+
+            fn require_content(&self, _: &Path, _: &Block) -> Vec<PathBuf> {
+                vec!["src/a.lua".into(), "src/b.lua".into()]
+            }
+        }
+
+        let rule: Box<dyn Rule> = Box::new(CustomRule);
+
+        assert_errors(
+            "simple_cyclic_work_error",
+            &resources,
+            Options::new("src").with_configuration(Configuration::empty().with_rule(rule)),
+        );
+    }
+
+    #[test]
+    fn snapshot_missing_configuration_file() {
+        let resources = memory_resources!(
+            "src/init.lua" => "return ''",
+        );
+
+        assert_errors(
+            "missing_configuration_file",
+            &resources,
+            Options::new("src").with_configuration_at("missing/config.json"),
+        );
+    }
+
+    #[test]
+    fn snapshot_multiple_configuration_file_found() {
+        let resources = memory_resources!(
+            "src/init.lua" => "return ''",
+            ".darklua.json" => "{ rules: [] }",
+            ".darklua.json5" => "{ rules: [] }",
+        );
+
+        assert_errors(
+            "multiple_configuration_file_found",
+            &resources,
+            Options::new("src"),
+        );
+    }
+
+    #[test]
+    fn snapshot_invalid_generator_override_pattern() {
+        let resources = memory_resources!(
+            "src/init.lua" => "return ''",
+        );
+
+        assert_errors(
+            "invalid_generator_override_pattern",
+            &resources,
+            Options::new("src")
+                .with_generator_override_for("src/**/[", GeneratorParameters::default_dense()),
+        );
+    }
+
+    #[test]
+    fn snapshot_format_generator_with_rules() {
+        let resources = memory_resources!(
+            "src/init.lua" => "return ''",
+        );
+
+        assert_errors(
+            "format_generator_with_rules",
+            &resources,
+            Options::new("src").with_generator_override(GeneratorParameters::default_format()),
+        );
+    }
+}
+
+#[test]
+fn concatenate_files_in_path_order() {
+    let resources = memory_resources!(
+        "src/b.lua" => "local value = 'b'\nprint(value)",
+        "src/c/d.lua" => "local value = 'd'\nprint(value)",
+        "src/a.lua" => "local value = 'a'\nprint(value)",
+    );
+
+    process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .with_output("out.lua")
+            .concatenate(),
+    )
+    .unwrap()
+    .result()
+    .unwrap();
+
+    assert_eq!(
+        resources.get("out.lua").unwrap(),
+        "do\nlocal value = 'a'\nprint(value)\nend\n\
+        do\nlocal value = 'b'\nprint(value)\nend\n\
+        do\nlocal value = 'd'\nprint(value)\nend\n"
+    );
+}
+
+#[test]
+fn concatenate_file_with_return_errors() {
+    let resources = memory_resources!(
+        "src/a.lua" => "print('a')",
+        "src/b.lua" => "local value = 'b'\nreturn value",
+    );
+
+    let errors = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .with_output("out.lua")
+            .concatenate(),
+    )
+    .unwrap()
+    .result()
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(resources.get("out.lua").is_err());
+}
+
+#[test]
+fn concatenate_file_with_exported_type_errors() {
+    let resources = memory_resources!(
+        "src/a.lua" => "print('a')",
+        "src/b.lua" => "export type Value = string\nlocal value: Value = 'b'",
+    );
+
+    let errors = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .with_output("out.lua")
+            .concatenate(),
+    )
+    .unwrap()
+    .result()
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(resources.get("out.lua").is_err());
+}
+
+#[test]
+fn concatenate_without_output_errors() {
+    let resources = memory_resources!(
+        "src/a.lua" => "print('a')",
+    );
+
+    let error = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .concatenate(),
+    )
+    .unwrap_err();
+
+    assert_eq!(error.category(), darklua_core::ErrorCategory::Configuration);
+}
+
+#[test]
+fn concatenate_with_dry_run_and_manifest() {
+    let resources = memory_resources!(
+        "src/a.lua" => "print('a')",
+        "src/b.lua" => "print('b')",
+    );
+
+    let worker_tree = process(
+        &resources,
+        Options::new("src")
+            .with_configuration(Configuration::empty())
+            .with_output("out.lua")
+            .with_manifest("manifest.json")
+            .concatenate()
+            .dry_run(),
+    )
+    .unwrap();
+
+    assert!(!resources.exists("out.lua").unwrap());
+
+    let entries = worker_tree.dry_run_entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path().display().to_string(), "out.lua");
+    assert_eq!(entries[0].original(), None);
+    assert_eq!(
+        entries[0].transformed(),
+        "do\nprint('a')\nend\ndo\nprint('b')\nend\n"
+    );
+
+    let manifest_entries = worker_tree.manifest_entries();
+    assert_eq!(manifest_entries.len(), 1);
+    assert_eq!(manifest_entries[0].input().display().to_string(), "src");
+    assert_eq!(
+        manifest_entries[0].output().display().to_string(),
+        "out.lua"
+    );
+}
+
+#[test]
+fn concatenate_after_processing_files_in_place() {
+    let resources = memory_resources!(
+        "src/a.lua" => "print('a')",
+        "src/b.lua" => "print('b')",
+    );
+
+    let mut worker_tree = darklua_core::WorkerTree::default();
+
+    let options = Options::new("src").with_configuration(Configuration::empty());
+    worker_tree.collect_work(&resources, &options).unwrap();
+    worker_tree.process(&resources, options).unwrap();
+
+    let options = Options::new("src")
+        .with_configuration(Configuration::empty())
+        .with_output("out.lua")
+        .concatenate();
+    worker_tree.collect_work(&resources, &options).unwrap();
+    worker_tree.process(&resources, options).unwrap();
+
+    assert_eq!(
+        resources.get("out.lua").unwrap(),
+        "do\nprint('a')\nend\ndo\nprint('b')\nend\n"
+    );
+}
+
+#[test]
+fn collect_requires_returns_resolved_paths() {
+    let resources = memory_resources!(
+        "src/main.lua" => "local a = require('./a')\nlocal lib = require('./lib')\nreturn a(lib)",
+        "src/a.lua" => "return function() end",
+        "src/lib/init.lua" => "return {}",
+    );
+
+    let requires = collect_requires(
+        &resources,
+        "src/main.lua".as_ref(),
+        &BundleConfiguration::new(BundleRequireMode::default()),
+    )
+    .unwrap();
+
+    assert_eq!(
+        requires,
+        vec![
+            PathBuf::from("src/a.lua"),
+            PathBuf::from("src/lib/init.lua")
+        ]
+    );
+}
+
+#[test]
+fn collect_requires_with_missing_module_errors() {
+    let resources = memory_resources!(
+        "src/main.lua" => "local a = require('./a')",
+    );
+
+    let result = collect_requires(
+        &resources,
+        "src/main.lua".as_ref(),
+        &BundleConfiguration::new(BundleRequireMode::default()),
+    );
+
+    assert!(result.is_err());
 }
@@ -0,0 +1,50 @@
+use darklua_core::rules::{CollapseElseIf, Rule};
+
+test_rule!(
+    collapse_else_if,
+    CollapseElseIf::default(),
+    nested_if("if a then else if b then end end") => "if a then elseif b then end",
+    nested_if_with_else("if a then x() else if b then y() else z() end end")
+        => "if a then x() elseif b then y() else z() end",
+    nested_if_with_elseif("if a then else if b then x() elseif c then y() end end")
+        => "if a then elseif b then x() elseif c then y() end",
+    outer_elseif_with_nested_if("if a then x() elseif b then y() else if c then z() end end")
+        => "if a then x() elseif b then y() elseif c then z() end",
+    two_levels("if a then else if b then else if c then end end end")
+        => "if a then elseif b then elseif c then end",
+    nested_in_function("local function f() if a then else if b then end end end")
+        => "local function f() if a then elseif b then end end",
+);
+
+test_rule_snapshot!(
+    collapse_else_if,
+    CollapseElseIf::default(),
+    single_level("if a then\n    x()\nelse\n    if b then\n        y()\n    else\n        z()\n    end\nend\n"),
+    two_levels("if a then\n    x()\nelse\n    if b then\n        y()\n    else\n        if c then\n            z()\n        elseif d then\n            w()\n        end\n    end\nend\n"),
+    extra_statements_in_else("if a then\n    x()\nelse\n    local value = compute()\n    if value then\n        y()\n    end\nend\n"),
+);
+
+test_rule_without_effects!(
+    CollapseElseIf::default(),
+    else_without_if("if a then else x() end"),
+    statement_before_nested_if("if a then else x() if b then end end"),
+    statement_after_nested_if("if a then else if b then end x() end"),
+    nested_if_with_return("if a then else if b then end return end"),
+    nested_if_in_branch("if a then if b then end end"),
+    nested_if_in_do("if a then else do if b then end end end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'collapse_else_if',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'collapse_else_if'").unwrap();
+}
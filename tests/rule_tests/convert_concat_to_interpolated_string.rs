@@ -0,0 +1,77 @@
+use darklua_core::rules::{ConvertConcatToInterpolatedString, Rule};
+
+fn rule_targeting_luau() -> Box<dyn Rule> {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_concat_to_interpolated_string',
+        target_luau: true,
+    }"#,
+    )
+    .unwrap()
+}
+
+test_rule!(
+    convert_concat_to_interpolated_string,
+    rule_targeting_luau(),
+    string_and_identifier("return 'hello ' .. name") => "return `hello {name}`",
+    identifier_and_string("return name .. '!'") => "return `{name}!`",
+    mixed_chain("return 'a' .. x .. 'b' .. y") => "return `a{x}b{y}`",
+    consecutive_strings("return 'a' .. 'b' .. x") => "return `ab{x}`",
+    arithmetic_operand("return 'total: ' .. count + 1") => "return `total: {count + 1}`",
+    call_operand("return 'value: ' .. getValue()") => "return `value: {getValue()}`",
+    parenthese_operand("return 'value: ' .. (a or b)") => "return `value: {a or b}`",
+    interpolated_string_operand("return `a{x}` .. y") => "return `a{x}{y}`",
+    opening_brace("return '{' .. x") => "return `\\{{x}`",
+    backtick("return '`' .. x") => "return `\\`{x}`",
+    nested_chain_in_parentheses("return ('a' .. x) .. y") => "return (`a{x}`) .. y",
+    chain_in_call_argument("print('count: ' .. count)") => "print(`count: {count}`)",
+);
+
+test_rule_without_effects!(
+    rule_targeting_luau(),
+    only_strings("return 'a' .. 'b'"),
+    without_strings("return a .. b"),
+    addition("return 'a' + b"),
+);
+
+test_rule_without_effects!(
+    ConvertConcatToInterpolatedString::default(),
+    not_targeting_luau("return 'a' .. b"),
+);
+
+mod convert_concat_to_interpolated_string_snapshots {
+    use super::*;
+
+    snapshot_rule_with_generator!(
+        rule_targeting_luau(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        mixed_chain,
+        "local label = 'item ' .. index .. ': ' .. item.name .. ' (' .. count * 2 .. ')'\n"
+    );
+
+    snapshot_rule_with_generator!(
+        rule_targeting_luau(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        brace_escaping,
+        "local message = '{' .. name .. '} `quoted`'\n"
+    );
+}
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_concat_to_interpolated_string',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'convert_concat_to_interpolated_string'").unwrap();
+}
@@ -0,0 +1,84 @@
+use darklua_core::rules::{ConvertConcatToTableConcat, Rule};
+
+test_rule!(
+    convert_concat_to_table_concat,
+    ConvertConcatToTableConcat::default(),
+    long_string_chain("return 'a' .. 'b' .. 'c' .. 'd' .. 'e' .. 'f' .. 'g' .. 'h' .. 'i' .. 'j'")
+        => "return table.concat({ 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j' })",
+    long_variable_chain("return a .. b .. c .. d .. e .. f .. g .. h .. i .. j .. k")
+        => "return table.concat({ a, b, c, d, e, f, g, h, i, j, k })",
+);
+
+test_rule!(
+    convert_concat_to_table_concat_with_minimum_operands,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_concat_to_table_concat',
+        minimum_operands: 4,
+    }"#,
+    )
+    .unwrap(),
+    four_operands("return a .. b .. c .. d") => "return table.concat({ a, b, c, d })",
+    keeps_evaluation_order("return first() .. second() .. third() .. fourth")
+        => "return table.concat({ first(), second(), third(), fourth })",
+    last_operand_is_call("return a .. b .. c .. call()") => "return table.concat({ a, b, c, (call()) })",
+    last_operand_is_variable_arguments("return a .. b .. c .. ...") => "return table.concat({ a, b, c, (...) })",
+    parenthese_operand_is_kept("return a .. b .. (c .. d) .. e")
+        => "return table.concat({ a, b, (c .. d), e })",
+    nested_chain_in_call("return a .. b .. c .. f(w .. x .. y .. z)")
+        => "return table.concat({ a, b, c, (f(table.concat({ w, x, y, z }))) })",
+    chain_in_local_assign("local message = 'Hello ' .. name .. ', you have ' .. count_text")
+        => "local message = table.concat({ 'Hello ', name, ', you have ', count_text })",
+    end_of_chain_after_number("return 1 .. a .. b .. c .. d")
+        => "return 1 .. table.concat({ a, b, c, d })",
+);
+
+test_rule_snapshot!(
+    convert_concat_to_table_concat,
+    ConvertConcatToTableConcat::default(),
+    long_string_chain(
+        "local message = 'Hello' .. ' ' .. name .. ', ' .. 'you have ' .. count .. ' new ' .. 'messages' .. ' from ' .. sender .. '.'\nprint(message)\n"
+    ),
+    mixed_number_chain_bail(
+        "local message = 'Hello' .. ' ' .. name .. ', ' .. 'you have ' .. 3 .. ' new ' .. 'messages' .. ' from ' .. sender\nprint(message)\n"
+    ),
+);
+
+test_rule_without_effects!(
+    ConvertConcatToTableConcat::default(),
+    short_chain("return a .. b .. c"),
+    chain_below_default_minimum("return a .. b .. c .. d .. e .. f .. g .. h .. i"),
+);
+
+test_rule_without_effects!(
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_concat_to_table_concat',
+        minimum_operands: 4,
+    }"#,
+    )
+    .unwrap(),
+    number_operand("return a .. b .. 1 .. c"),
+    computed_number_operand("return a .. b .. (2 + 3) .. c"),
+    arithmetic_operand("return a .. b .. c .. d + 1"),
+    length_operand("return a .. b .. c .. #list"),
+    negative_operand("return a .. b .. c .. -value"),
+    or_with_number_operand("return a .. b .. c .. (value or 0)"),
+    if_expression_with_number_operand("return a .. b .. c .. (if value then 'x' else 0)"),
+    table_is_local("local table = {} return a .. b .. c .. d"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_concat_to_table_concat',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'convert_concat_to_table_concat'").unwrap();
+}
@@ -0,0 +1,41 @@
+use darklua_core::rules::{ConvertInterpolationToToString, Rule};
+
+test_rule!(
+    convert_interpolation_to_tostring,
+    ConvertInterpolationToToString::default(),
+    single_identifier("return `{object}`") => "return tostring(object)",
+    single_call("return `{getValue()}`") => "return tostring(getValue())",
+    single_binary_expression("return `{a + b}`") => "return tostring(a + b)",
+    single_value_as_argument("print(`{value}`)") => "print(tostring(value))",
+    nested_single_value("return `{`{object}`}`") => "return tostring(tostring(object))",
+    single_value_inside_multiple_segments("return `value: {`{object}`}`")
+        => "return `value: {tostring(object)}`",
+    single_value_after_tostring_scope("do local tostring end return `{object}`")
+        => "do local tostring end return tostring(object)",
+);
+
+test_rule_without_effects!(
+    ConvertInterpolationToToString::default(),
+    empty_string("return ``"),
+    only_text("return `abc`"),
+    text_before_value("return `-{object}`"),
+    text_after_value("return `{object}-`"),
+    two_values("return `{a}{b}`"),
+    shadowed_tostring("local tostring = function() end return `{object}`"),
+    tostring_parameter("local function f(tostring) return `{object}` end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_interpolation_to_tostring',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'convert_interpolation_to_tostring'").unwrap();
+}
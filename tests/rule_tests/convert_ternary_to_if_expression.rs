@@ -0,0 +1,61 @@
+use darklua_core::rules::{ConvertTernaryToIfExpression, Rule};
+
+test_rule!(
+    convert_ternary_to_if_expression,
+    ConvertTernaryToIfExpression::default(),
+    string_value("return condition and 'yes' or 'no'") => "return if condition then 'yes' else 'no'",
+    number_value("return condition and 1 or 2") => "return if condition then 1 else 2",
+    zero_value("return condition and 0 or fallback") => "return if condition then 0 else fallback",
+    true_value("return condition and true or value") => "return if condition then true else value",
+    table_value("return condition and {} or nil") => "return if condition then {} else nil",
+    function_value("return condition and function() end or default")
+        => "return if condition then function() end else default",
+    call_condition("return isReady() and 'ready' or 'waiting'")
+        => "return if isReady() then 'ready' else 'waiting'",
+    comparison_condition("return value > 0 and 'positive' or 'negative'")
+        => "return if value > 0 then 'positive' else 'negative'",
+    nested_in_else("return a and 'a' or b and 'b' or 'c'")
+        => "return if a then 'a' elseif b then 'b' else 'c'",
+    in_parentheses("return (condition and 'yes' or 'no') .. '!'")
+        => "return (if condition then 'yes' else 'no') .. '!'",
+    partial_chain("return a and 'a' or b and c or 'd'")
+        => "return if a then 'a' else b and c or 'd'",
+    in_local_assign("local label = enabled and 'on' or 'off'")
+        => "local label = if enabled then 'on' else 'off'",
+);
+
+test_rule_snapshot!(
+    convert_ternary_to_if_expression,
+    ConvertTernaryToIfExpression::default(),
+    provably_truthy_value("local label = isEnabled and 'enabled' or 'disabled'\nprint(label)\n"),
+    possibly_falsy_value_preserved(
+        "local value = useCache and cache[key] or compute(key)\nprint(value)\n"
+    ),
+);
+
+test_rule_without_effects!(
+    ConvertTernaryToIfExpression::default(),
+    identifier_value("return condition and value or default"),
+    false_value("return condition and false or true"),
+    nil_value("return condition and nil or default"),
+    call_value("return condition and compute() or default"),
+    field_value("return condition and object.value or default"),
+    only_and("return condition and 'yes'"),
+    only_or("return value or 'no'"),
+    and_in_parentheses_right("return condition or (a and 'b')"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_ternary_to_if_expression',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'convert_ternary_to_if_expression'").unwrap();
+}
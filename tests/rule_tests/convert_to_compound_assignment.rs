@@ -0,0 +1,87 @@
+use darklua_core::rules::{ConvertToCompoundAssignment, Rule};
+
+test_rule!(
+    convert_to_compound_assignment,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_to_compound_assignment',
+        target_luau: true,
+    }"#,
+    )
+    .unwrap(),
+    add("x = x + 1") => "x += 1",
+    subtract("x = x - 1") => "x -= 1",
+    multiply("x = x * 2") => "x *= 2",
+    divide("x = x / 2") => "x /= 2",
+    floor_divide("x = x // 2") => "x //= 2",
+    modulo("x = x % 2") => "x %= 2",
+    power("x = x ^ 2") => "x ^= 2",
+    concat("x = x .. 'a'") => "x ..= 'a'",
+    concat_chain("x = x .. 'a' .. 'b'") => "x ..= 'a' .. 'b'",
+    power_chain("x = x ^ 2 ^ 3") => "x ^= 2 ^ 3",
+    add_product("x = x + a * b") => "x += a * b",
+    subtract_parenthese("x = x - (a - b)") => "x -= (a - b)",
+    field("object.count = object.count + 1") => "object.count += 1",
+    nested_field("a.b.c = a.b.c * 2") => "a.b.c *= 2",
+    index_with_string("t['key'] = t['key'] + 1") => "t['key'] += 1",
+    index_with_identifier("t[key] = t[key] + 1") => "t[key] += 1",
+    nested_block("do x = x + 1 end") => "do x += 1 end",
+    function_block("local function f() count = count + 1 end") => "local function f() count += 1 end",
+);
+
+test_rule_snapshot!(
+    convert_to_compound_assignment,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_to_compound_assignment',
+        target_luau: true,
+    }"#,
+    )
+    .unwrap(),
+    identifier_case("local count = 0\ncount = count + 1\nprint(count)\n"),
+    field_case("local object = { value = 'a' }\nobject.value = object.value .. 'b'\n"),
+    side_effect_index_bail("t[f()] = t[f()] + 1\n"),
+);
+
+test_rule_without_effects!(
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_to_compound_assignment',
+        target_luau: true,
+    }"#,
+    )
+    .unwrap(),
+    different_variable("x = y + 1"),
+    variable_on_right("x = 1 + x"),
+    left_associative_chain("x = x - a - b"),
+    comparison("x = x == 1"),
+    logical_and("x = x and y"),
+    logical_or("x = x or y"),
+    multiple_variables("x, y = x + 1, y + 1"),
+    multiple_values("x = x + 1, 2"),
+    parenthese_variable("x = (x) + 1"),
+    call_in_index("t[f()] = t[f()] + 1"),
+    call_in_prefix("f().x = f().x + 1"),
+    different_index("t[a] = t[b] + 1"),
+    different_field("t.a = t.b + 1"),
+);
+
+test_rule_without_effects!(
+    ConvertToCompoundAssignment::default(),
+    without_target_luau("x = x + 1"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_to_compound_assignment',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'convert_to_compound_assignment'").unwrap();
+}
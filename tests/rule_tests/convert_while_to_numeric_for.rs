@@ -0,0 +1,85 @@
+use darklua_core::rules::{ConvertWhileToNumericFor, Rule};
+
+test_rule!(
+    convert_while_to_numeric_for,
+    ConvertWhileToNumericFor::default(),
+    equal_limit("local i = 0 while true do if i == 3 then break end print(i) i = i + 1 end")
+        => "for i = 0, 2 do print(i) end",
+    greater_or_equal_limit("local i = 1 while true do if i >= 10 then break end print(i) i = i + 1 end")
+        => "for i = 1, 9 do print(i) end",
+    greater_limit("local i = 1 while true do if i > 10 then break end print(i) i = i + 1 end")
+        => "for i = 1, 10 do print(i) end",
+    limit_on_the_left("local i = 1 while true do if 10 < i then break end print(i) i = i + 1 end")
+        => "for i = 1, 10 do print(i) end",
+    limit_on_the_left_lower_or_equal("local i = 1 while true do if 10 <= i then break end print(i) i = i + 1 end")
+        => "for i = 1, 9 do print(i) end",
+    equal_limit_on_the_left("local i = 0 while true do if 3 == i then break end print(i) i = i + 1 end")
+        => "for i = 0, 2 do print(i) end",
+    compound_increment("local i = 0 while true do if i == 3 then break end print(i) i += 1 end")
+        => "for i = 0, 2 do print(i) end",
+    increment_with_one_on_the_left("local i = 0 while true do if i == 3 then break end print(i) i = 1 + i end")
+        => "for i = 0, 2 do print(i) end",
+    computed_limit("local i = 0 while true do if i == 2 * 4 then break end print(i) i = i + 1 end")
+        => "for i = 0, 7 do print(i) end",
+    negative_start("local i = -2 while true do if i == 0 then break end print(i) i = i + 1 end")
+        => "for i = -2, -1 do print(i) end",
+    start_above_greater_or_equal_limit("local i = 5 while true do if i >= 3 then break end print(i) i = i + 1 end")
+        => "for i = 5, 2 do print(i) end",
+    empty_body("local i = 0 while true do if i == 3 then break end i = i + 1 end")
+        => "for i = 0, 2 do end",
+    body_with_break("local i = 0 while true do if i == 3 then break end if found(i) then break end i = i + 1 end")
+        => "for i = 0, 2 do if found(i) then break end end",
+    continue_in_nested_loop("local i = 0 while true do if i == 3 then break end for _ = 1, 2 do continue end i = i + 1 end")
+        => "for i = 0, 2 do for _ = 1, 2 do continue end end",
+    statements_around("print('start') local i = 0 while true do if i == 3 then break end print(i) i = i + 1 end print('end')")
+        => "print('start') for i = 0, 2 do print(i) end print('end')",
+    nested_in_function("local function f() local i = 0 while true do if i == 3 then break end print(i) i = i + 1 end end")
+        => "local function f() for i = 0, 2 do print(i) end end",
+    nested_loops("local i = 0 while true do if i == 2 then break end local j = 0 while true do if j == 2 then break end print(i, j) j = j + 1 end i = i + 1 end")
+        => "for i = 0, 1 do for j = 0, 1 do print(i, j) end end",
+    counter_shadowed_after_loop("local i = 0 while true do if i == 3 then break end print(i) i = i + 1 end local i = 1 print(i)")
+        => "for i = 0, 2 do print(i) end local i = 1 print(i)",
+);
+
+test_rule_without_effects!(
+    ConvertWhileToNumericFor::default(),
+    unknown_limit("local i = 0 while true do if i == n then break end print(i) i = i + 1 end"),
+    unknown_start("local i = n while true do if i == 3 then break end print(i) i = i + 1 end"),
+    decimal_start("local i = 0.5 while true do if i >= 3 then break end print(i) i = i + 1 end"),
+    start_above_equal_limit("local i = 5 while true do if i == 3 then break end print(i) i = i + 1 end"),
+    lower_than_limit("local i = 0 while true do if i < 3 then break end print(i) i = i + 1 end"),
+    not_equal_limit("local i = 0 while true do if i ~= 3 then break end print(i) i = i + 1 end"),
+    missing_increment("local i = 0 while true do if i == 3 then break end print(i) end"),
+    increment_by_two("local i = 0 while true do if i == 4 then break end print(i) i = i + 2 end"),
+    decrement("local i = 3 while true do if i == 0 then break end print(i) i = i - 1 end"),
+    break_check_not_first("local i = 0 while true do print(i) if i == 3 then break end i = i + 1 end"),
+    break_check_with_else("local i = 0 while true do if i == 3 then break else print(i) end i = i + 1 end"),
+    break_check_with_statements("local i = 0 while true do if i == 3 then print(i) break end i = i + 1 end"),
+    condition_not_true("local i = 0 while running do if i == 3 then break end print(i) i = i + 1 end"),
+    counter_assigned_in_body("local i = 0 while true do if i == 3 then break end i = 2 print(i) i = i + 1 end"),
+    counter_assigned_in_nested_block("local i = 0 while true do if i == 3 then break end if x then i = 2 end i = i + 1 end"),
+    counter_declared_in_body("local i = 0 while true do if i == 3 then break end local i = 'a' print(i) i = i + 1 end"),
+    closure_in_body("local i = 0 while true do if i == 3 then break end call(function() return i end) i = i + 1 end"),
+    continue_in_body("local i = 0 while true do if i == 3 then break end if skip(i) then continue end i = i + 1 end"),
+    counter_used_after_loop("local i = 0 while true do if i == 3 then break end print(i) i = i + 1 end print(i)"),
+    counter_assigned_after_loop("local i = 0 while true do if i == 3 then break end print(i) i = i + 1 end i = 0"),
+    counter_returned_after_loop("local function f() local i = 0 while true do if i == 3 then break end print(i) i = i + 1 end return i end"),
+    multiple_variables("local i, j = 0, 0 while true do if i == 3 then break end print(i) i = i + 1 end"),
+    loop_not_after_counter("local i = 0 print(i) while true do if i == 3 then break end print(i) i = i + 1 end"),
+    loop_with_return("local function f() local i = 0 while true do if i == 3 then break end i = i + 1 return end end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_while_to_numeric_for',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'convert_while_to_numeric_for'").unwrap();
+}
@@ -0,0 +1,91 @@
+use darklua_core::rules::{DeduplicateFunctions, Rule};
+
+test_rule!(
+    deduplicate_functions,
+    DeduplicateFunctions::default(),
+    two_empty_functions("local a = function() end local b = function() end")
+        => "local __DARKLUA_FN = function() end local a = __DARKLUA_FN local b = __DARKLUA_FN",
+    functions_with_different_trivia(
+        "local a = function(value) return value end\nlocal b = function( value )\n\treturn  value\nend"
+    ) => "local __DARKLUA_FN = function(value) return value end local a = __DARKLUA_FN local b = __DARKLUA_FN",
+    functions_using_globals(
+        "call(function(...) return print(...) end) call(function(...) return print(...) end)"
+    ) => "local __DARKLUA_FN = function(...) return print(...) end call(__DARKLUA_FN) call(__DARKLUA_FN)",
+    functions_in_nested_blocks(
+        "do call(function(a, b) return a + b end) end while true do call(function(a, b) return a + b end) end"
+    ) => "local __DARKLUA_FN = function(a, b) return a + b end do call(__DARKLUA_FN) end while true do call(__DARKLUA_FN) end",
+    nested_functions(
+        "local a = function() return function() end end local b = function() return function() end end"
+    ) => "local __DARKLUA_FN = function() end local __DARKLUA_FN0 = function() return __DARKLUA_FN end local a = __DARKLUA_FN0 local b = __DARKLUA_FN0",
+    generated_name_avoids_existing_variables(
+        "local __DARKLUA_FN = 1 call(function() end, function() end)"
+    ) => "local __DARKLUA_FN0 = function() end local __DARKLUA_FN = 1 call(__DARKLUA_FN0, __DARKLUA_FN0)",
+    functions_with_local_variables(
+        "call(function() local value = get() return value end) call(function() local value = get() return value end)"
+    ) => "local __DARKLUA_FN = function() local value = get() return value end call(__DARKLUA_FN) call(__DARKLUA_FN)",
+);
+
+test_rule_snapshot!(
+    deduplicate_functions,
+    DeduplicateFunctions::default(),
+    callbacks_used_in_module(
+        "local module = {}\n\nfunction module.filter(list)\n\tlocal result = {}\n\tfor _, value in list do\n\t\tif (function(v) return v ~= nil end)(value) then\n\t\t\ttable.insert(result, value)\n\t\tend\n\tend\n\treturn result\nend\n\nmodule.isValid = function(v) return v ~= nil end\n\nreturn module\n"
+    ),
+);
+
+test_rule!(
+    deduplicate_functions_with_minimum_occurrences,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'deduplicate_functions',
+        minimum_occurrences: 3,
+    }"#,
+    )
+    .unwrap(),
+    three_functions("call(function() end) call(function() end) call(function() end)")
+        => "local __DARKLUA_FN = function() end call(__DARKLUA_FN) call(__DARKLUA_FN) call(__DARKLUA_FN)",
+);
+
+test_rule_without_effects!(
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'deduplicate_functions',
+        minimum_occurrences: 3,
+    }"#,
+    )
+    .unwrap(),
+    two_functions_below_minimum("call(function() end) call(function() end)"),
+);
+
+test_rule_without_effects!(
+    DeduplicateFunctions::default(),
+    single_function("local a = function() end"),
+    different_functions("local a = function() return 1 end local b = function() return 2 end"),
+    functions_capturing_different_locals(
+        "local a = 1 local f = function() return a end do local a = 2 local g = function() return a end end"
+    ),
+    functions_capturing_same_local("local a = 1 local f = function() return a end local g = function() return a end"),
+    functions_capturing_parameter(
+        "local function make(value) return function() return value end, function() return value end end"
+    ),
+    functions_assigning_to_upvalue("local count = 0 call(function() count = count + 1 end) call(function() count = count + 1 end)"),
+    functions_capturing_self(
+        "function object:method() call(function() return self end) call(function() return self end) end"
+    ),
+    functions_with_types("call(function(a: number) end) call(function(a: number) end)"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'deduplicate_functions',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'deduplicate_functions'").unwrap();
+}
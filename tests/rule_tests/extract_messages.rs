@@ -0,0 +1,73 @@
+use darklua_core::rules::{ExtractMessages, Rule};
+
+test_rule!(
+    extract_messages_with_lookup_function,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'extract_messages',
+        lookup_function: 'translate',
+    }"#,
+    )
+    .unwrap(),
+    error_message("error('oops')") => "error(translate('oops'))",
+    error_message_with_level("error('oops', 2)") => "error(translate('oops'), 2)",
+    error_with_string_argument("error 'oops'") => "error(translate('oops'))",
+    assert_message("assert(value, 'value expected')") => "assert(value, translate('value expected'))",
+    error_in_function("local function fail() error('oops') end") => "local function fail() error(translate('oops')) end",
+    assert_as_expression("local value = assert(get(), 'value expected')")
+        => "local value = assert(get(), translate('value expected'))",
+);
+
+test_rule!(
+    extract_messages_with_library_lookup_function,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'extract_messages',
+        functions: ['log.warn'],
+        lookup_function: 'i18n.messages.get',
+    }"#,
+    )
+    .unwrap(),
+    custom_function_message("log.warn('careful')") => "log.warn(i18n.messages.get('careful'))",
+);
+
+test_rule_without_effects!(
+    ExtractMessages::default(),
+    error_message("error('oops')"),
+    assert_message("assert(value, 'value expected')"),
+);
+
+test_rule_without_effects!(
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'extract_messages',
+        lookup_function: 'translate',
+    }"#,
+    )
+    .unwrap(),
+    error_with_variable_message("error(message)"),
+    error_with_interpolated_string("error(`oops {value}`)"),
+    error_with_concatenated_message("error('oops: ' .. value)"),
+    error_with_table("error({ message = 'oops' })"),
+    assert_without_message("assert(value)"),
+    assert_with_string_argument("assert 'value'"),
+    local_error_function("local error = print error('oops')"),
+    error_parameter("local function run(error) error('oops') end"),
+    method_call("error:report('oops')"),
+    function_not_configured("print('oops')"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'extract_messages',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'extract_messages'").unwrap();
+}
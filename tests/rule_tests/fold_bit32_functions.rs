@@ -0,0 +1,78 @@
+use darklua_core::rules::{FoldBit32Functions, Rule};
+
+test_rule!(
+    fold_bit32_functions,
+    FoldBit32Functions::default(),
+    band("return bit32.band(0xF0, 0x3C)") => "return 48",
+    band_without_arguments("return bit32.band()") => "return 4294967295",
+    band_with_negative_number("return bit32.band(-1, 255)") => "return 255",
+    band_with_number_above_32_bits("return bit32.band(4294967298, 3)") => "return 2",
+    bor("return bit32.bor(1, 2, 4)") => "return 7",
+    bor_without_arguments("return bit32.bor()") => "return 0",
+    bxor("return bit32.bxor(5, 3)") => "return 6",
+    bxor_many_arguments("return bit32.bxor(0xFF, 0x0F, 1)") => "return 241",
+    bnot("return bit32.bnot(0)") => "return 4294967295",
+    lshift("return bit32.lshift(1, 4)") => "return 16",
+    lshift_overflow("return bit32.lshift(0xFFFFFFFF, 4)") => "return 4294967280",
+    lshift_to_last_bit("return bit32.lshift(3, 31)") => "return 2147483648",
+    lshift_by_32_bits("return bit32.lshift(1, 32)") => "return 0",
+    lshift_with_negative_displacement("return bit32.lshift(16, -2)") => "return 4",
+    rshift("return bit32.rshift(-1, 28)") => "return 15",
+    rshift_above_32_bits("return bit32.rshift(256, 40)") => "return 0",
+    arshift_negative_value("return bit32.arshift(-256, 4)") => "return 4294967280",
+    arshift_positive_value("return bit32.arshift(256, 4)") => "return 16",
+    lrotate("return bit32.lrotate(0x80000001, 1)") => "return 3",
+    lrotate_by_32_bits("return bit32.lrotate(6, 32)") => "return 6",
+    rrotate("return bit32.rrotate(1, 1)") => "return 2147483648",
+    extract("return bit32.extract(0xF0, 4, 4)") => "return 15",
+    extract_without_width("return bit32.extract(5, 2)") => "return 1",
+    replace("return bit32.replace(0, 7, 4, 3)") => "return 112",
+    replace_truncates_value("return bit32.replace(0, 0xFF, 0, 4)") => "return 15",
+    countlz("return bit32.countlz(1)") => "return 31",
+    countrz("return bit32.countrz(8)") => "return 3",
+    countrz_of_zero("return bit32.countrz(0)") => "return 32",
+    byteswap("return bit32.byteswap(0x12345678)") => "return 2018915346",
+    computed_argument("return bit32.band(2 * 3, 3)") => "return 2",
+    nested_calls("return bit32.bor(bit32.lshift(1, 2), 1)") => "return 5",
+    bit32_shadowed_in_other_scope("do local bit32 = {} end return bit32.bnot(0)")
+        => "do local bit32 = {} end return 4294967295",
+);
+
+test_rule_without_effects!(
+    FoldBit32Functions::default(),
+    unknown_argument("return bit32.band(x, 1)"),
+    call_argument("return bit32.band(getValue(), 1)"),
+    string_argument("return bit32.band('1', 2)"),
+    decimal_argument("return bit32.band(1.5, 1)"),
+    infinite_argument("return bit32.band(1/0, 1)"),
+    decimal_displacement("return bit32.lshift(1, 0.5)"),
+    missing_argument("return bit32.bnot()"),
+    missing_displacement("return bit32.lshift(1)"),
+    extra_argument("return bit32.bnot(1, 2)"),
+    extract_field_out_of_bounds("return bit32.extract(1, 30, 4)"),
+    extract_negative_field("return bit32.extract(1, -1)"),
+    extract_zero_width("return bit32.extract(1, 0, 0)"),
+    btest_returns_boolean("return bit32.btest(1, 1)"),
+    unknown_bit32_function("return bit32.unknown(1)"),
+    other_library("return math.abs(-1)"),
+    method_call("return bit32:band(1, 1)"),
+    local_bit32_variable(
+        "local bit32 = { band = function() return 0 end } return bit32.band(1, 1)"
+    ),
+    bit32_parameter("local function compute(bit32) return bit32.band(1, 1) end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_bit32_functions',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_bit32_functions'").unwrap();
+}
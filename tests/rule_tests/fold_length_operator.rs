@@ -0,0 +1,54 @@
+use darklua_core::rules::{FoldLengthOperator, Rule};
+
+test_rule!(
+    fold_length_operator,
+    FoldLengthOperator::default(),
+    empty_string("return #''") => "return 0",
+    string("return #'hello'") => "return 5",
+    string_with_escape("return #'a\\n'") => "return 2",
+    string_with_multibyte_characters("return #'é'") => "return 2",
+    concatenated_strings("return #('ab' .. 'cd')") => "return 4",
+    string_in_parentheses("return #('hello')") => "return 5",
+    empty_table("return #{}") => "return 0",
+    array_of_numbers("return #{ 1, 2, 3 }") => "return 3",
+    array_of_mixed_values("return #{ 'a', true, false, {}, function() end }") => "return 5",
+    table_in_parentheses("return #({ 1, 2 })") => "return 2",
+    nested_length("return #{ #'abc', #{} }") => "return 2",
+    length_in_arithmetic("local n = #'abc' + 1") => "local n = 3 + 1",
+);
+
+test_rule_snapshot!(
+    fold_length_operator,
+    FoldLengthOperator::default(),
+    string_length("local name = 'darklua'\nlocal length = #'darklua'\nprint(name, length)\n"),
+    array_literal_length("local count = #{ 'a', 'b', 'c', 'd' }\nprint(count)\n"),
+    mixed_table_bail("local count = #{ 'a', 'b', key = 'value' }\nprint(count)\n"),
+);
+
+test_rule_without_effects!(
+    FoldLengthOperator::default(),
+    identifier("return #list"),
+    table_with_field("return #{ 1, 2, key = 3 }"),
+    table_with_index("return #{ 1, 2, [3] = 3 }"),
+    table_with_nil_value("return #{ 1, nil, 3 }"),
+    table_with_unknown_value("return #{ 1, value, 3 }"),
+    table_ending_with_call("return #{ 1, call() }"),
+    table_ending_with_variable_arguments("return #{ 1, ... }"),
+    table_with_call_value("return #{ call(), 2 }"),
+    number("return #1"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_length_operator',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_length_operator'").unwrap();
+}
@@ -0,0 +1,62 @@
+use darklua_core::rules::{FoldLogicalOperators, Rule};
+
+test_rule!(
+    fold_logical_operators,
+    FoldLogicalOperators::default(),
+    true_and_identifier("return true and x") => "return x",
+    false_or_identifier("return false or x") => "return x",
+    nil_or_identifier("return nil or x") => "return x",
+    number_and_identifier("return 1 and x") => "return x",
+    false_and_identifier("return false and x") => "return false",
+    nil_and_identifier("return nil and x") => "return nil",
+    true_or_identifier("return true or x") => "return true",
+    string_or_identifier("return 'abc' or x") => "return 'abc'",
+    false_and_call("return false and sideEffect()") => "return false",
+    true_or_call("return true or sideEffect()") => "return true",
+    true_and_call("return true and call()") => "return (call())",
+    false_or_variable_arguments("return false or ...") => "return (...)",
+    nested_expressions("return true and (false or x)") => "return (x)",
+    nested_left_operand("return (true and false) or x") => "return x",
+    chained_and("return true and true and x") => "return x",
+    in_condition("if true and x then end") => "if x then end",
+    table_or_empty_table_default("return { 1 } or {}") => "return { 1 }",
+    string_or_number_default("return 'abc' or 0") => "return 'abc'",
+    nil_or_empty_table_default("return nil or {}") => "return {}",
+    false_or_number_default("return false or 0") => "return 0",
+    table_with_call_or_default("return { call() } or {}") => "return { call() }",
+);
+
+test_rule_snapshot!(
+    fold_logical_operators,
+    FoldLogicalOperators::default(),
+    true_and_x("local value = true and x\n"),
+    false_or_x("local value = false or x\n"),
+    false_and_side_effect("local value = false and sideEffect()\n"),
+    true_or_side_effect("local value = true or sideEffect()\n"),
+);
+
+test_rule_without_effects!(
+    FoldLogicalOperators::default(),
+    unknown_left_operand("return x and y"),
+    call_left_operand("return call() or y"),
+    left_operand_with_side_effects("return { call() } and x"),
+    other_binary_operator("return true == x"),
+    unknown_or_empty_table_default("return x or {}"),
+    unknown_or_number_default("return n or 0"),
+    call_or_empty_table_default("return call() or {}"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_logical_operators',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_logical_operators'").unwrap();
+}
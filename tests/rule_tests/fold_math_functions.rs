@@ -0,0 +1,53 @@
+use darklua_core::rules::{FoldMathFunctions, Rule};
+
+test_rule!(
+    fold_math_functions,
+    FoldMathFunctions::default(),
+    floor("return math.floor(3.7)") => "return 3",
+    floor_negative("return math.floor(-3.2)") => "return -4",
+    ceil("return math.ceil(3.2)") => "return 4",
+    abs("return math.abs(-4)") => "return 4",
+    sqrt("return math.sqrt(16)") => "return 4",
+    sqrt_of_negative_number("return math.sqrt(-1)") => "return 0/0",
+    abs_of_negative_infinity("return math.abs(-1/0)") => "return 1/0",
+    fmod("return math.fmod(7, 3)") => "return 1",
+    fmod_negative("return math.fmod(-7, 3)") => "return -1",
+    max("return math.max(1, 5, 2)") => "return 5",
+    max_with_one_argument("return math.max(8)") => "return 8",
+    min("return math.min(3, -2, 7)") => "return -2",
+    computed_argument("return math.floor(7 / 2)") => "return 3",
+    nested_calls("return math.floor(math.sqrt(17))") => "return 4",
+    in_binary_expression("local value = math.max(1, 2) + x") => "local value = 2 + x",
+    math_shadowed_in_other_scope("do local math = {} end return math.abs(-1)") => "do local math = {} end return 1",
+);
+
+test_rule_without_effects!(
+    FoldMathFunctions::default(),
+    unknown_argument("return math.floor(x)"),
+    call_argument("return math.floor(getValue())"),
+    string_argument("return math.floor('3.7')"),
+    max_without_arguments("return math.max()"),
+    fmod_by_zero("return math.fmod(1, 0)"),
+    missing_argument("return math.abs()"),
+    extra_argument("return math.abs(-1, 2)"),
+    unknown_math_function("return math.random(10)"),
+    other_library("return string.len(3)"),
+    method_call("return math:floor(1.5)"),
+    local_math_variable("local math = { floor = function() return 0 end } return math.floor(1.5)"),
+    math_parameter("local function compute(math) return math.floor(1.5) end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_math_functions',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_math_functions'").unwrap();
+}
@@ -0,0 +1,55 @@
+use darklua_core::rules::{FoldNextNilCheck, Rule};
+
+test_rule!(
+    fold_next_nil_check,
+    FoldNextNilCheck::default(),
+    empty_table("return next({}) == nil") => "return true",
+    empty_table_not_equal("return next({}) ~= nil") => "return false",
+    empty_table_nil_on_left("return nil == next({})") => "return true",
+    empty_table_nil_on_left_not_equal("return nil ~= next({})") => "return false",
+    empty_table_call("return next{} == nil") => "return true",
+    empty_table_in_parentheses("return next(({})) == nil") => "return true",
+    next_call_in_parentheses("return (next({})) == nil") => "return true",
+    table_with_nil_values("return next({ nil, key = nil }) == nil") => "return true",
+    array_table("return next({ 1, 2 }) == nil") => "return false",
+    array_table_not_equal("return next({ 1, 2 }) ~= nil") => "return true",
+    array_table_nil_on_left("return nil == next({ 'a' })") => "return false",
+    field_table("return next({ key = true }) == nil") => "return false",
+    index_table("return next({ ['key'] = 0 }) == nil") => "return false",
+    table_with_unknown_and_constant_values("return next({ value, 1 }) == nil") => "return false",
+    nested_table_value("return next({ {} }) == nil") => "return false",
+    in_if_condition("if next({}) == nil then print('empty') end")
+        => "if true then print('empty') end",
+);
+
+test_rule_without_effects!(
+    FoldNextNilCheck::default(),
+    identifier("return next(t) == nil"),
+    field_expression("return next(object.list) == nil"),
+    table_with_unknown_value("return next({ value }) == nil"),
+    table_with_call("return next({ call() }) == nil"),
+    table_with_variable_arguments("return next({ ... }) == nil"),
+    table_with_nil_key("return next({ [nil] = 1 }) == nil"),
+    table_with_unknown_key("return next({ [key] = 1 }) == nil"),
+    next_with_two_arguments("return next({}, nil) == nil"),
+    next_is_local("local next = function() return 1 end return next({}) == nil"),
+    next_method("return object:next({}) == nil"),
+    compare_with_false("return next({}) == false"),
+    compare_with_unknown("return next({}) == value"),
+    less_than_operator("return next({ 1 }) < nil"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_next_nil_check',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_next_nil_check'").unwrap();
+}
@@ -0,0 +1,59 @@
+use darklua_core::rules::{FoldSelect, Rule};
+
+test_rule!(
+    fold_select,
+    FoldSelect::default(),
+    select_second_value("return select(2, x, y, z)") => "return y, z",
+    select_first_value("return select(1, x, y)") => "return x, y",
+    select_last_value("local value = select(3, x, y, z)") => "local value = z",
+    select_negative_index("return select(-1, x, y, z)") => "return z",
+    select_negative_index_suffix("return select(-2, x, y, z)") => "return y, z",
+    select_index_past_values("return select(4, x, y, z)") => "return",
+    select_index_past_values_in_call("print('a', select(3, x, y))") => "print('a')",
+    select_index_past_values_in_local_assign("local a, b = select(5, x)") => "local a, b",
+    select_count("return select('#', x, y)") => "return 2",
+    select_count_without_values("return select('#')") => "return 0",
+    select_count_in_expression("local n = select('#', a, b, c) + 1") => "local n = 3 + 1",
+    select_in_call_arguments("print(select(2, 'a', 'b', 'c'))") => "print('b', 'c')",
+    select_in_table("local list = { 1, select(2, x, y, z) }") => "local list = { 1, y, z }",
+    select_in_local_assign("local a, b = select(2, x, y, z)") => "local a, b = y, z",
+    select_single_value_in_binary("return select(2, x, y) + 1") => "return y + 1",
+    select_with_computed_index("return select(1 + 1, x, y, z)") => "return y, z",
+    select_keeps_discarded_values_without_side_effects("return select(3, 'a', 1, x)") => "return x",
+    select_keeps_selected_call("return select(2, x, call(), y)") => "return call(), y",
+);
+
+test_rule_without_effects!(
+    FoldSelect::default(),
+    select_variable_arguments("return select(2, ...)"),
+    select_count_variable_arguments("return select('#', ...)"),
+    select_values_before_variable_arguments("return select(2, x, ...)"),
+    select_ending_with_call("return select(2, x, call())"),
+    select_count_ending_with_call("return select('#', x, call())"),
+    select_unknown_index("return select(n, x, y)"),
+    select_zero_index("return select(0, x, y)"),
+    select_negative_index_out_of_range("return select(-3, x, y)"),
+    select_decimal_index("return select(1.5, x, y)"),
+    select_invalid_string_index("return select('a', x, y)"),
+    select_discards_call("return select(2, call(), x)"),
+    select_count_with_call("return select('#', call(), x)"),
+    select_multiple_values_in_binary("return select(1, x, y) + 1"),
+    shadowed_select("local select = function() end return select(2, x, y)"),
+    select_method_call("return object:select(2, x, y)"),
+    select_field_call("return object.select(2, x, y)"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_select',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_select'").unwrap();
+}
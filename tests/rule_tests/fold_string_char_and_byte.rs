@@ -0,0 +1,69 @@
+use darklua_core::rules::{FoldStringCharAndByte, Rule};
+
+test_rule!(
+    fold_string_char_and_byte,
+    FoldStringCharAndByte::default(),
+    char_single_argument("return string.char(65)") => "return 'A'",
+    char_multiple_arguments("return string.char(104, 105)") => "return 'hi'",
+    char_without_arguments("return string.char()") => "return ''",
+    char_computed_argument("return string.char(60 + 5)") => "return 'A'",
+    char_new_line("return string.char(10)") => "return '\\n'",
+    char_utf8_sequence("return string.char(195, 169)") => "return 'é'",
+    byte_single_character("return string.byte('A')") => "return 65",
+    byte_with_position("return string.byte('abc', 2)") => "return 98",
+    byte_with_negative_position("return string.byte('abc', -1)") => "return 99",
+    byte_with_range_of_one("return string.byte('abc', 2, 2)") => "return 98",
+    byte_with_range_past_the_end("return string.byte('abc', 3, 10)") => "return 99",
+    byte_of_multibyte_character("return string.byte('é')") => "return 195",
+    byte_method_call("return ('A'):byte()") => "return 65",
+    byte_method_call_with_position("return ('abc'):byte(3)") => "return 99",
+    nested_calls("return string.char(string.byte('a') + 1)") => "return 'b'",
+);
+
+test_rule_snapshot!(
+    fold_string_char_and_byte,
+    FoldStringCharAndByte::default(),
+    char_multiple_arguments_form("local greeting = string.char(104, 105)\nprint(greeting)\n"),
+    byte_single_character_form("local code = string.byte('A')\nprint(code)\n"),
+    char_out_of_range_bail(
+        "local invalid = string.char(104, 256)\nlocal valid = string.char(72)\n"
+    ),
+);
+
+test_rule_without_effects!(
+    FoldStringCharAndByte::default(),
+    char_out_of_range("return string.char(256)"),
+    char_negative_argument("return string.char(-1)"),
+    char_decimal_argument("return string.char(65.5)"),
+    char_string_argument("return string.char('65')"),
+    char_unknown_argument("return string.char(code)"),
+    char_invalid_utf8("return string.char(255)"),
+    byte_range("return string.byte('abc', 1, 3)"),
+    byte_reversed_range("return string.byte('abc', 3, 1)"),
+    byte_empty_string("return string.byte('')"),
+    byte_position_out_of_bounds("return string.byte('abc', 4)"),
+    byte_position_zero("return string.byte('abc', 0)"),
+    byte_decimal_position("return string.byte('abc', 1.5)"),
+    byte_unknown_string("return string.byte(value)"),
+    byte_number_argument("return string.byte(5)"),
+    byte_too_many_arguments("return string.byte('a', 1, 1, 1)"),
+    byte_method_on_identifier("return value:byte()"),
+    char_method("return ('a'):char()"),
+    string_is_local("local string = {} return string.char(65)"),
+    other_string_function("return string.upper('a')"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_string_char_and_byte',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_string_char_and_byte'").unwrap();
+}
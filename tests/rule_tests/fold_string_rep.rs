@@ -0,0 +1,69 @@
+use darklua_core::rules::{FoldStringRep, Rule};
+
+test_rule!(
+    fold_string_rep,
+    FoldStringRep::default(),
+    dotted_call("return string.rep('-', 5)") => "return '-----'",
+    method_call("return ('ab'):rep(3)") => "return 'ababab'",
+    zero_count("return string.rep('-', 0)") => "return ''",
+    empty_string("return string.rep('', 10)") => "return ''",
+    concatenated_string("return string.rep('a' .. 'b', 2)") => "return 'abab'",
+    computed_count("return string.rep('-', 2 + 1)") => "return '---'",
+    nested_call("return string.rep(string.rep('a', 2), 2)") => "return 'aaaa'",
+    result_concatenated("return string.rep('=', 2) .. '>'") => "return '==' .. '>'",
+);
+
+test_rule_snapshot!(
+    fold_string_rep,
+    FoldStringRep::default(),
+    method_call_form("local separator = ('-'):rep(20)\nprint(separator)\n"),
+    dotted_form("local indent = string.rep(' ', 4)\nprint(indent .. 'value')\n"),
+    zero_count_form("local empty = string.rep('abc', 0)\n"),
+    size_cap_bail("local line = string.rep('=', 1000)\nlocal short = string.rep('=', 10)\n"),
+);
+
+test_rule!(
+    fold_string_rep_with_maximum_length,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_string_rep',
+        maximum_length: 4,
+    }"#,
+    )
+    .unwrap(),
+    at_maximum_length("return string.rep('ab', 2)") => "return 'abab'",
+    over_maximum_length("return string.rep('ab', 3)") => "return string.rep('ab', 3)",
+);
+
+test_rule_without_effects!(
+    FoldStringRep::default(),
+    unknown_string("return string.rep(value, 2)"),
+    unknown_count("return string.rep('-', count)"),
+    negative_count("return string.rep('-', -1)"),
+    decimal_count("return string.rep('-', 1.5)"),
+    number_as_string("return string.rep(5, 2)"),
+    count_as_string("return string.rep('-', '2')"),
+    with_separator("return string.rep('-', 2, ',')"),
+    missing_count("return string.rep('-')"),
+    method_with_separator("return ('-'):rep(2, ',')"),
+    string_is_local("local string = {} return string.rep('-', 2)"),
+    method_on_identifier("return value:rep(2)"),
+    other_method("return ('-'):upper()"),
+    other_string_function("return string.sub('abc', 1, 2)"),
+    too_long("return string.rep('-', 257)"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_string_rep',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_string_rep'").unwrap();
+}
@@ -0,0 +1,64 @@
+use darklua_core::rules::{FoldTableAccess, Rule};
+
+test_rule!(
+    fold_table_access,
+    FoldTableAccess::default(),
+    field_access("return ({ a = 1, b = 2 }).a") => "return 1",
+    index_access_with_string("return ({ a = 1, b = 2 })['b']") => "return 2",
+    index_access_with_index_entry("return ({ ['key'] = true }).key") => "return true",
+    index_access_with_number_key("return ({ [2] = 'two' })[2]") => "return 'two'",
+    array_access("return ({ 'a', 'b', 'c' })[2]") => "return 'b'",
+    array_access_with_field_entries("return ({ 'a', x = 1, 'b' })[2]") => "return 'b'",
+    array_access_with_float_key("return ({ 'a', 'b' })[2.0]") => "return 'b'",
+    boolean_key("return ({ [true] = 'yes' })[true]") => "return 'yes'",
+    nil_value("return ({ a = nil }).a") => "return nil",
+    table_value("return ({ a = { b = 1 } }).a") => "return { b = 1 }",
+    nested_access("return ({ a = { b = 1 } }).a.b") => "return 1",
+    function_value("return ({ a = function() end }).a") => "return function() end",
+    variable_value("return ({ a = value }).a") => "return value",
+    variable_arguments_value("return ({ a = ... }).a") => "return (...)",
+    field_on_last_variable_arguments("return ({ a = 1, ... }).a") => "return 1",
+    access_as_call_prefix("({ run = print }).run('hello')") => "print('hello')",
+    access_as_method_prefix("return ({ value = object }).value:method()") => "return object:method()",
+    access_as_assignment_prefix("({ value = object }).value.field = true") => "object.field = true",
+);
+
+test_rule_snapshot!(
+    fold_table_access,
+    FoldTableAccess::default(),
+    present_key("local color = ({ red = 0xFF0000, green = 0x00FF00, blue = 0x0000FF }).green\nprint(color)\n"),
+    absent_key_bail("local color = ({ red = 0xFF0000, green = 0x00FF00 }).blue\nprint(color)\n"),
+    side_effect_value_bail("local value = ({ a = 1, b = compute() }).a\nprint(value)\n"),
+);
+
+test_rule_without_effects!(
+    FoldTableAccess::default(),
+    absent_key("return ({ a = 1 }).b"),
+    identifier_table("return object.a"),
+    table_without_parentheses_prefix("return call().a"),
+    side_effect_value("return ({ a = 1, b = call() }).a"),
+    side_effect_key("return ({ a = 1, [call()] = 2 }).a"),
+    side_effect_index("return ({ a = 1 })[call()]"),
+    unknown_key("return ({ a = 1 })[key]"),
+    unknown_entry_key("return ({ a = 1, [key] = 2 }).a"),
+    duplicated_key("return ({ a = 1, a = 2 }).a"),
+    array_index_and_explicit_key("return ({ 'a', [1] = 'b' })[1]"),
+    array_access_after_variable_arguments("return ({ 'a', ... })[2]"),
+    assign_to_table_field("({ a = value }).a = 2"),
+    array_access_after_call("return ({ 'a', call() })[1]"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_table_access',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_table_access'").unwrap();
+}
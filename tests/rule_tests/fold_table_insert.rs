@@ -0,0 +1,75 @@
+use darklua_core::rules::{FoldTableInsert, Rule};
+
+test_rule!(
+    fold_table_insert,
+    FoldTableInsert::default(),
+    single_insert_in_empty_table("local t = {} table.insert(t, 1)") => "local t = { 1 }",
+    multiple_inserts_in_empty_table("local t = {} table.insert(t, 1) table.insert(t, 'a') table.insert(t, true)")
+        => "local t = { 1, 'a', true }",
+    insert_in_non_empty_table("local t = { 1, 2 } table.insert(t, 3)") => "local t = { 1, 2, 3 }",
+    insert_in_table_with_field("local t = { name = value } table.insert(t, 1)")
+        => "local t = { name = value, 1 }",
+    insert_identifier("local t = {} table.insert(t, value)") => "local t = { value }",
+    insert_call_keeps_single_value("local t = {} table.insert(t, call())") => "local t = { (call()) }",
+    insert_variadic_keeps_single_value("local t = {} table.insert(t, ...)") => "local t = { (...) }",
+    insert_values_with_side_effects_in_order("local t = {} table.insert(t, 1) table.insert(t, call())")
+        => "local t = { 1, (call()) }",
+    stop_after_possibly_nil_value("local t = {} table.insert(t, value) table.insert(t, 2)")
+        => "local t = { value } table.insert(t, 2)",
+    stop_at_insert_in_other_table("local t = {} table.insert(t, 1) table.insert(other, 2) table.insert(t, 3)")
+        => "local t = { 1 } table.insert(other, 2) table.insert(t, 3)",
+    stop_at_other_statement("local t = {} table.insert(t, 1) print(t) table.insert(t, 2)")
+        => "local t = { 1 } print(t) table.insert(t, 2)",
+    stop_at_positional_insert("local t = {} table.insert(t, 1) table.insert(t, 1, 2)")
+        => "local t = { 1 } table.insert(t, 1, 2)",
+    stop_at_value_referencing_table("local t = {} table.insert(t, 1) table.insert(t, #t)")
+        => "local t = { 1 } table.insert(t, #t)",
+    nested_block("do local t = {} table.insert(t, 1) end") => "do local t = { 1 } end",
+    in_function("local function f() local t = {} table.insert(t, 1) return t end")
+        => "local function f() local t = { 1 } return t end",
+);
+
+test_rule_snapshot!(
+    fold_table_insert,
+    FoldTableInsert::default(),
+    fold_consecutive_inserts("local list = {}\ntable.insert(list, 'a')\ntable.insert(list, 'b')\ntable.insert(list, 'c')\nreturn list\n"),
+    fold_until_positional_insert("local list = { 'a' }\ntable.insert(list, 'b')\ntable.insert(list, 1, 'c')\ntable.insert(list, 'd')\nreturn list\n"),
+);
+
+test_rule_without_effects!(
+    FoldTableInsert::default(),
+    positional_insert("local t = {} table.insert(t, 1, 2)"),
+    insert_without_table_constructor("local t = create() table.insert(t, 1)"),
+    insert_in_table_with_index_entry("local t = { [1] = true } table.insert(t, 1)"),
+    insert_in_table_with_possibly_nil_value("local t = { value } table.insert(t, 1)"),
+    insert_in_table_with_call_value("local t = { call() } table.insert(t, 1)"),
+    insert_in_multiple_variables("local t, u = {}, {} table.insert(t, 1)"),
+    insert_value_referencing_table("local t = {} table.insert(t, t)"),
+    insert_value_referencing_table_in_function(
+        "local t = {} table.insert(t, function() return t end)"
+    ),
+    insert_with_method_call("local t = {} table:insert(t, 1)"),
+    table_library_shadowed("local table = {} local t = {} table.insert(t, 1)"),
+    table_library_shadowed_by_parameter(
+        "local function f(table) local t = {} table.insert(t, 1) end"
+    ),
+    table_library_shadowed_by_local_function(
+        "local function table() end local t = {} table.insert(t, 1)"
+    ),
+    not_consecutive("local t = {} print('hello') table.insert(t, 1)"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_table_insert',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_table_insert'").unwrap();
+}
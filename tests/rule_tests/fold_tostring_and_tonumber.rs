@@ -0,0 +1,57 @@
+use darklua_core::rules::{FoldTostringAndTonumber, Rule};
+
+test_rule!(
+    fold_tostring_and_tonumber,
+    FoldTostringAndTonumber::default(),
+    tostring_true("return tostring(true)") => "return 'true'",
+    tostring_false("return tostring(false)") => "return 'false'",
+    tostring_nil("return tostring(nil)") => "return 'nil'",
+    tostring_integer("return tostring(5)") => "return '5'",
+    tostring_negative_integer("return tostring(-12)") => "return '-12'",
+    tostring_computed_integer("return tostring(2 + 3)") => "return '5'",
+    tostring_string("return tostring('hello')") => "return 'hello'",
+    tostring_string_call("return tostring'hello'") => "return 'hello'",
+    tonumber_integer("return tonumber(5)") => "return 5",
+    tonumber_decimal_string("return tonumber('12.5')") => "return 12.5",
+    tonumber_string_with_spaces("return tonumber('  42  ')") => "return 42",
+    tonumber_hex_string("return tonumber('0x10')") => "return 16",
+    tonumber_string_call("return tonumber'8'") => "return 8",
+    tonumber_non_numeric_string("return tonumber('hello')") => "return nil",
+    tonumber_empty_string("return tonumber('')") => "return nil",
+    tonumber_boolean("return tonumber(true)") => "return nil",
+    tonumber_nil("return tonumber(nil)") => "return nil",
+    nested_calls("return tonumber(tostring(10))") => "return 10",
+);
+
+test_rule_without_effects!(
+    FoldTostringAndTonumber::default(),
+    tostring_without_arguments("return tostring()"),
+    tostring_float("return tostring(0.5)"),
+    tostring_large_integer("return tostring(1e15)"),
+    tostring_table("return tostring({})"),
+    tostring_unknown_value("return tostring(value)"),
+    tostring_with_side_effects("return tostring(call())"),
+    tostring_is_local("local tostring = print return tostring(true)"),
+    tostring_method("return object:tostring(true)"),
+    tonumber_with_base("return tonumber('10', 16)"),
+    tonumber_negative_hex_string("return tonumber('-0x10')"),
+    tonumber_string_with_hex_digits("return tonumber('abc')"),
+    tonumber_infinity_string("return tonumber('inf')"),
+    tonumber_table("return tonumber({})"),
+    tonumber_is_local("local tonumber = print return tonumber('1')"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_tostring_and_tonumber',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_tostring_and_tonumber'").unwrap();
+}
@@ -0,0 +1,49 @@
+use darklua_core::rules::{FoldTypeCalls, Rule};
+
+test_rule!(
+    fold_type_calls,
+    FoldTypeCalls::default(),
+    type_of_number("return type(5)") => "return 'number'",
+    type_of_string("return type('x')") => "return 'string'",
+    type_of_string_call("return type'x'") => "return 'string'",
+    type_of_nil("return type(nil)") => "return 'nil'",
+    type_of_true("return type(true)") => "return 'boolean'",
+    type_of_false("return type(false)") => "return 'boolean'",
+    type_of_table("return type({})") => "return 'table'",
+    type_of_table_call("return type{}") => "return 'table'",
+    type_of_function("return type(function() end)") => "return 'function'",
+    type_of_computed_number("return type(2 + 3)") => "return 'number'",
+    type_of_concatenation("return type('a' .. 'b')") => "return 'string'",
+    nested_type_calls("return type(type(1))") => "return 'string'",
+    type_in_condition("if type(5) == 'number' then print('number') end")
+        => "if 'number' == 'number' then print('number') end",
+);
+
+test_rule_without_effects!(
+    FoldTypeCalls::default(),
+    type_without_arguments("return type()"),
+    type_with_two_arguments("return type(1, 2)"),
+    type_of_unknown_value("return type(value)"),
+    type_of_call("return type(call())"),
+    type_of_variadic_arguments("local function f(...) return type(...) end"),
+    type_of_table_with_side_effects("return type({ call() })"),
+    type_is_local("local type = print return type(5)"),
+    type_is_parameter("local function f(type) return type(5) end"),
+    type_method("return object:type(5)"),
+    type_field("return object.type(5)"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'fold_type_calls',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'fold_type_calls'").unwrap();
+}
@@ -0,0 +1,52 @@
+use darklua_core::rules::{HoistCommonSubexpressions, Rule};
+
+test_rule!(
+    hoist_common_subexpressions,
+    HoistCommonSubexpressions::default(),
+    repeated_field_read("local a = t.a.b + t.a.b")
+        => "local __DARKLUA_SHARED_1 = t.a.b local a = __DARKLUA_SHARED_1 + __DARKLUA_SHARED_1",
+    repeated_field_prefix("local a = t.a.b + t.a.c")
+        => "local __DARKLUA_SHARED_1 = t.a local a = __DARKLUA_SHARED_1.b + __DARKLUA_SHARED_1.c",
+    repeated_index_read("return list[i] * list[i]")
+        => "local __DARKLUA_SHARED_1 = list[i] return __DARKLUA_SHARED_1 * __DARKLUA_SHARED_1",
+    repeated_in_call_arguments("print(t.x, t.x)")
+        => "local __DARKLUA_SHARED_1 = t.x print(__DARKLUA_SHARED_1, __DARKLUA_SHARED_1)",
+    repeated_in_assignment("value = p.x * p.x + p.y * p.y")
+        => "local __DARKLUA_SHARED_1 = p.x local __DARKLUA_SHARED_2 = p.y \
+            value = __DARKLUA_SHARED_1 * __DARKLUA_SHARED_1 + __DARKLUA_SHARED_2 * __DARKLUA_SHARED_2",
+    repeated_in_nested_block("local function f(t) return t.a + t.a end")
+        => "local function f(t) local __DARKLUA_SHARED_1 = t.a return __DARKLUA_SHARED_1 + __DARKLUA_SHARED_1 end",
+    multiple_statements("local a = t.a + t.a local b = t.b + t.b")
+        => "local __DARKLUA_SHARED_1 = t.a local a = __DARKLUA_SHARED_1 + __DARKLUA_SHARED_1 \
+            local __DARKLUA_SHARED_2 = t.b local b = __DARKLUA_SHARED_2 + __DARKLUA_SHARED_2",
+);
+
+test_rule_without_effects!(
+    HoistCommonSubexpressions::default(),
+    single_field_read("local a = t.a + t.b"),
+    repeated_call("local a = f() + f()"),
+    repeated_field_with_call("local a = t.a + f() + t.a"),
+    repeated_method_call("local a = t:get() + t:get()"),
+    repeated_field_in_call_prefix("local a = t.a() + t.a()"),
+    repeated_field_after_and("local a = t.a and t.a.b"),
+    repeated_field_after_or("local a = t.a or t.a"),
+    repeated_field_in_function("local a = t.a + function() return t.a end"),
+    repeated_field_in_if_expression("local a = if t.a then t.a else nil"),
+    repeated_field_in_different_statements("local a = t.a local b = t.a"),
+    repeated_field_in_if_statement("if t.a == t.a then end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'hoist_common_subexpressions',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'hoist_common_subexpressions'").unwrap();
+}
@@ -0,0 +1,98 @@
+use darklua_core::{rules::Rule, Resources};
+
+use super::memory_resources;
+
+test_rule!(
+    inline_constant_require,
+    json5::from_str::<Box<dyn Rule>>("'inline_constant_require'").unwrap(),
+    resources = memory_resources!(
+        "src/constants.lua" => "return { A = 1, B = 'two', C = true, D = nil, E = 2 ^ 3 }",
+        "src/empty.lua" => "return {}",
+        "src/state.lua" => "local state = { A = 1 } return state",
+        "src/values.lua" => "return { A = 1, [2] = 'two' }",
+        "src/nested.lua" => "return { A = 1, B = { C = 2 } }",
+        "src/calls.lua" => "return { A = 1, B = compute() }",
+        "src/function.lua" => "return { A = 1, B = function() end }",
+        "src/multiple.lua" => "return { A = 1 }, { B = 2 }",
+        "src/data.json" => "{ \"A\": 1 }",
+    ),
+    test_file_name = "src/main.lua",
+    field_access("local m = require('./constants') return m.A")
+        => "return 1",
+    multiple_fields("local m = require('./constants') print(m.A, m.B, m.C, m.D)")
+        => "print(1, 'two', true, nil)",
+    computed_constant("local m = require('./constants') return m.E")
+        => "return 8",
+    field_used_as_prefix("local m = require('./constants') return m.B:upper()")
+        => "return ('two'):upper()",
+    field_in_nested_function("local m = require('./constants') local function f() return m.A end")
+        => "local function f() return 1 end",
+    require_with_extension("local m = require('./constants.lua') return m.A")
+        => "return 1",
+    unused_module("local m = require('./empty')")
+        => "",
+    nested_block("do local m = require('./constants') return m.A end")
+        => "do return 1 end",
+    module_shadowed_after("local m = require('./constants') local a = m.A local m = {} return m.A")
+        => "local a = 1 local m = {} return m.A",
+    module_shadowed_in_function("local m = require('./constants') local function f(m) return m.A end return m.B")
+        => "local function f(m) return m.A end return 'two'",
+    module_passed_to_function("local m = require('./constants') print(m)")
+        => "local m = require('./constants') print(m)",
+    module_returned("local m = require('./constants') return m")
+        => "local m = require('./constants') return m",
+    module_field_assigned("local m = require('./constants') m.A = 2 return m.A")
+        => "local m = require('./constants') m.A = 2 return m.A",
+    module_reassigned("local m = require('./constants') m = {} return m.A")
+        => "local m = require('./constants') m = {} return m.A",
+    unknown_field("local m = require('./constants') return m.Z")
+        => "local m = require('./constants') return m.Z",
+    indexed_module("local m = require('./constants') return m['A']")
+        => "local m = require('./constants') return m['A']",
+    method_call_on_module("local m = require('./constants') return m:A()")
+        => "local m = require('./constants') return m:A()",
+    module_with_statements("local m = require('./state') return m.A")
+        => "local m = require('./state') return m.A",
+    module_with_index_entries("local m = require('./values') return m.A")
+        => "local m = require('./values') return m.A",
+    module_with_nested_table("local m = require('./nested') return m.A")
+        => "local m = require('./nested') return m.A",
+    module_with_call("local m = require('./calls') return m.A")
+        => "local m = require('./calls') return m.A",
+    module_with_function("local m = require('./function') return m.A")
+        => "local m = require('./function') return m.A",
+    module_with_multiple_values("local m = require('./multiple') return m.A")
+        => "local m = require('./multiple') return m.A",
+    json_module("local m = require('./data.json') return m.A")
+        => "local m = require('./data.json') return m.A",
+    unknown_module("local m = require('./unknown') return m.A")
+        => "local m = require('./unknown') return m.A",
+    require_is_local("local require = function() end local m = require('./constants') return m.A")
+        => "local require = function() end local m = require('./constants') return m.A",
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'inline_constant_require',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'inline_constant_require'").unwrap();
+}
+
+#[test]
+fn deserialize_with_require_mode() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'inline_constant_require',
+        require_mode: { name: 'path', module_folder_name: 'index' },
+    }"#,
+    )
+    .unwrap();
+}
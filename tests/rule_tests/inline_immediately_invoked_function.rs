@@ -0,0 +1,57 @@
+use darklua_core::rules::{InlineImmediatelyInvokedFunction, Rule};
+
+test_rule!(
+    inline_immediately_invoked_function,
+    InlineImmediatelyInvokedFunction::default(),
+    return_identifier("return (function() return x end)()") => "return x",
+    return_call("return (function() return f() end)()") => "return f()",
+    return_parenthese_call("return (function() return (f()) end)()") => "return (f())",
+    return_binary("return 1 + (function() return a * b end)()") => "return 1 + a * b",
+    local_assign("local value = (function() return { a = 1 } end)()") => "local value = { a = 1 }",
+    call_argument("print((function() return 'hello' end)())") => "print('hello')",
+    variadic_function_without_variable_arguments("return (function(...) return x end)()")
+        => "return x",
+    nested_functions("return (function() return (function() return x end)() end)()")
+        => "return x",
+    inner_function_returned("return (function() return function() return x end end)()")
+        => "return function() return x end",
+);
+
+test_rule_snapshot!(
+    inline_immediately_invoked_function,
+    InlineImmediatelyInvokedFunction::default(),
+    single_return("local value = (function()\n    return compute(1, 2)\nend)()\n"),
+    multiple_statements(
+        "local value = (function()\n    local result = compute(1, 2)\n    return result\nend)()\n"
+    ),
+);
+
+test_rule_without_effects!(
+    InlineImmediatelyInvokedFunction::default(),
+    call_statement("(function() return x end)()"),
+    function_with_statements("return (function() local a = 1 return a end)()"),
+    function_with_parameters("return (function(a) return a end)(1)"),
+    function_with_arguments("return (function() return x end)(1)"),
+    returns_multiple_values("return (function() return a, b end)()"),
+    returns_nothing("return (function() return end)()"),
+    without_return("return (function() end)()"),
+    returns_variable_arguments("return (function(...) return ... end)()"),
+    returns_variable_arguments_in_table("return (function(...) return { ... } end)()"),
+    method_call("return (function() return x end):method()"),
+    identifier_call("return f()"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'inline_immediately_invoked_function',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'inline_immediately_invoked_function'").unwrap();
+}
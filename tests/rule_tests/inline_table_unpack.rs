@@ -0,0 +1,56 @@
+use darklua_core::rules::{InlineTableUnpack, Rule};
+
+test_rule!(
+    inline_table_unpack,
+    InlineTableUnpack::default(),
+    table_unpack_array("f(table.unpack({1, 2, 3}))") => "f(1, 2, 3)",
+    global_unpack_array("f(unpack({1, 2, 3}))") => "f(1, 2, 3)",
+    unpack_table_argument("f(table.unpack { 'a', 'b' })") => "f('a', 'b')",
+    unpack_after_arguments("f(0, table.unpack({1, 2}))") => "f(0, 1, 2)",
+    unpack_empty_table("f(0, table.unpack({}))") => "f(0)",
+    unpack_method_call("object:method(table.unpack({true, 'a'}))") => "object:method(true, 'a')",
+    unpack_in_expression("local value = f(table.unpack({1, 2}))") => "local value = f(1, 2)",
+    unpack_nested_calls("f(g(table.unpack({1, 2})))") => "f(g(1, 2))",
+    unpack_table_values("f(table.unpack({ {}, function() end }))") => "f({}, function() end)",
+);
+
+test_rule_snapshot!(
+    inline_table_unpack,
+    InlineTableUnpack::default(),
+    unpack_array_into_call("print(table.unpack({ 'a', 'b', 'c' }))"),
+    unpack_table_with_hash_entry("print(table.unpack({ 'a', 'b', key = 'c' }))"),
+);
+
+test_rule_without_effects!(
+    InlineTableUnpack::default(),
+    table_with_field_entry("f(table.unpack({1, 2, n = 2}))"),
+    table_with_index_entry("f(table.unpack({1, [2] = 2}))"),
+    unpack_not_last_argument("f(table.unpack({1, 2}), 3)"),
+    unpack_with_range("f(table.unpack({1, 2, 3}, 2))"),
+    unpack_with_start_and_end("f(table.unpack({1, 2, 3}, 1, 2))"),
+    unpack_variable("f(table.unpack(list))"),
+    table_with_unknown_value("f(table.unpack({1, value}))"),
+    table_with_nil_value("f(table.unpack({1, nil, 3}))"),
+    table_ending_with_call("f(table.unpack({1, g()}))"),
+    table_ending_with_variable_arguments("local function run(...) f(table.unpack({...})) end"),
+    unpack_returned("return table.unpack({1, 2})"),
+    shadowed_unpack("local unpack = function() end f(unpack({1, 2}))"),
+    shadowed_table("local table = {} f(table.unpack({1, 2}))"),
+    unpack_method("f(table:unpack({1, 2}))"),
+    other_table_function("f(table.pack({1, 2}))"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'inline_table_unpack',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'inline_table_unpack'").unwrap();
+}
@@ -0,0 +1,93 @@
+use darklua_core::rules::{LocalizeGlobals, Rule};
+
+test_rule!(
+    localize_globals,
+    LocalizeGlobals::default(),
+    global_called_twice("print('a') print('b')")
+        => "local print = print print('a') print('b')",
+    global_library("return math.floor(math.random())")
+        => "local math = math return math.floor(math.random())",
+    globals_sorted_by_name("print(type(a)) print(type(b))")
+        => "local print = print local type = type print(type(a)) print(type(b))",
+    global_in_function("local function log(value) print(value) end print('start')")
+        => "local print = print local function log(value) print(value) end print('start')",
+    global_shadowed_in_nested_scope("print(1) do local print = nil print(2) end print(3)")
+        => "local print = print print(1) do local print = nil print(2) end print(3)",
+    global_function_field("function module.a() end function module.b() end")
+        => "local module = module function module.a() end function module.b() end",
+    global_field_assigned("config.value = 1 config.other = 2")
+        => "local config = config config.value = 1 config.other = 2",
+);
+
+test_rule_snapshot!(
+    localize_globals,
+    LocalizeGlobals::default(),
+    module_with_globals("local module = {}\n\nfunction module.round(value)\n    return math.floor(value + 0.5)\nend\n\nfunction module.clamp(value, min, max)\n    return math.max(min, math.min(max, value))\nend\n\nreturn module\n"),
+);
+
+test_rule!(
+    localize_globals_with_minimum_references,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'localize_globals',
+        minimum_references: 3,
+    }"#,
+    )
+    .unwrap(),
+    below_minimum("print(1) print(2)") => "print(1) print(2)",
+    at_minimum("print(1) print(2) print(3)") => "local print = print print(1) print(2) print(3)",
+);
+
+test_rule!(
+    localize_globals_with_include,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'localize_globals',
+        include: ['math'],
+    }"#,
+    )
+    .unwrap(),
+    included_global("print(math.pi) print(math.huge)") => "local math = math print(math.pi) print(math.huge)",
+);
+
+test_rule!(
+    localize_globals_with_exclude,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'localize_globals',
+        exclude: ['print'],
+    }"#,
+    )
+    .unwrap(),
+    excluded_global("print(math.pi) print(math.huge)") => "local math = math print(math.pi) print(math.huge)",
+);
+
+test_rule_without_effects!(
+    LocalizeGlobals::default(),
+    single_reference("print('a')"),
+    local_variable("local print = print print('a') print('b')"),
+    local_function("local function log() end log() log()"),
+    function_parameter("local function run(print) print(1) print(2) end"),
+    numeric_for_variable("for i = 1, 10 do call(i) end local _ = i"),
+    generic_for_variable("for _, print in list do print(1) print(2) end"),
+    assigned_global("counter = 0 counter = counter + 1"),
+    global_assigned_in_function("local function reset() state = nil end return state, state"),
+    global_function_statement("function helper() end helper() helper()"),
+    compound_assigned_global("counter += 1 return counter"),
+    global_assigned_in_multiple_assignment("a, b = b, a"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'localize_globals',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'localize_globals'").unwrap();
+}
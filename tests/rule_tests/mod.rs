@@ -416,12 +416,38 @@ macro_rules! test_rule_snapshot {
 }
 
 mod append_text_comment;
+mod collapse_else_if;
 mod compute_expression;
+mod convert_concat_to_interpolated_string;
+mod convert_concat_to_table_concat;
 mod convert_index_to_field;
+mod convert_interpolation_to_tostring;
 mod convert_require;
+mod convert_ternary_to_if_expression;
+mod convert_to_compound_assignment;
+mod convert_while_to_numeric_for;
+mod deduplicate_functions;
+mod extract_messages;
 mod filter_early_return;
+mod fold_bit32_functions;
+mod fold_length_operator;
+mod fold_logical_operators;
+mod fold_math_functions;
+mod fold_next_nil_check;
+mod fold_select;
+mod fold_string_char_and_byte;
+mod fold_string_rep;
+mod fold_table_access;
+mod fold_table_insert;
+mod fold_tostring_and_tonumber;
+mod fold_type_calls;
 mod group_local_assignment;
+mod hoist_common_subexpressions;
 mod inject_value;
+mod inline_constant_require;
+mod inline_immediately_invoked_function;
+mod inline_table_unpack;
+mod localize_globals;
 mod no_local_function;
 mod remove_assertions;
 mod remove_call_parens;
@@ -430,13 +456,24 @@ mod remove_compound_assignment;
 mod remove_continue;
 mod remove_debug_profiling;
 mod remove_empty_do;
+mod remove_empty_if_branches;
 mod remove_floor_division;
 mod remove_if_expression;
 mod remove_interpolated_string;
 mod remove_method_definition;
 mod remove_nil_declaration;
+mod remove_redundant_return;
 mod remove_types;
 mod remove_unused_if_branch;
+mod remove_unused_parameters;
+mod remove_unused_pure_call;
 mod remove_unused_variable;
 mod remove_unused_while;
 mod rename_variables;
+mod reorder_local_assignments;
+mod shorten_number_literals;
+mod shorten_string_escapes;
+mod simplify_boolean_comparison;
+mod simplify_boolean_if_expression;
+mod sort_table_fields;
+mod unroll_ipairs_loop;
@@ -16,6 +16,39 @@ test_rule_without_effects!(
     two_local_using_the_other("local function foo() foo() end")
 );
 
+test_rule!(
+    convert_recursive_local_function_to_assign,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_local_function_to_assign',
+        convert_recursive_functions: true,
+    }"#,
+    )
+    .unwrap(),
+    recursive_function("local function foo() foo() end") => "local foo foo = function() foo() end",
+    non_recursive_function("local function foo() return true end") => "local foo = function() return true end",
+    name_in_parameters("local function foo(foo) return foo end") => "local foo = function(foo) return foo end",
+    nested_recursive_function("local function foo() local function bar() bar() end end")
+        => "local foo = function() local bar bar = function() bar() end end",
+    recursive_function_between_statements("print(1) local function foo() foo() end print(2)")
+        => "print(1) local foo foo = function() foo() end print(2)",
+);
+
+test_rule_snapshot!(
+    convert_local_function_to_assign,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_local_function_to_assign',
+        convert_recursive_functions: true,
+    }"#,
+    )
+    .unwrap(),
+    recursive_function(
+        "local function countdown(n) if n > 0 then countdown(n - 1) end end\ncountdown(3)\n"
+    ),
+    non_recursive_function("local function add(a, b) return a + b end\nprint(add(1, 2))\n"),
+);
+
 #[test]
 fn deserialize_from_object_notation() {
     json5::from_str::<Box<dyn Rule>>(
@@ -26,6 +59,17 @@ fn deserialize_from_object_notation() {
     .unwrap();
 }
 
+#[test]
+fn deserialize_with_recursive_functions_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'convert_local_function_to_assign',
+        convert_recursive_functions: true,
+    }"#,
+    )
+    .unwrap();
+}
+
 #[test]
 fn deserialize_from_string() {
     json5::from_str::<Box<dyn Rule>>("'convert_local_function_to_assign'").unwrap();
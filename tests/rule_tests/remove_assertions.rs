@@ -32,6 +32,55 @@ test_rule!(
     remove_variable_condition_with_function_call_message("assert(condition, formatter(condition))") => "do end",
 );
 
+test_rule!(
+    remove_assertions_with_custom_functions,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_assertions',
+        functions: ['assert', 'debug.assert'],
+    }"#,
+    )
+    .unwrap(),
+    remove_assert_call("assert(condition)") => "do end",
+    remove_debug_assert_call("debug.assert(condition, 'message')") => "do end",
+    remove_debug_assert_call_with_function_call_condition("debug.assert(validate(value))") => "validate(value)",
+    as_expression_remove_assert("return assert(condition)") => "return condition",
+);
+
+test_rule_snapshot!(
+    remove_assertions_with_custom_functions,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_assertions',
+        functions: ['debug.assert'],
+    }"#,
+    )
+    .unwrap(),
+    remove_custom_assertion_with_pure_arguments(
+        "debug.assert(value, 'value is nil')\nprint(value)"
+    ),
+    remove_custom_assertion_with_side_effect_arguments(
+        "debug.assert(object.value, format(object))\nprint(object)"
+    ),
+    custom_assertion_used_as_expression(
+        "local result = debug.assert(value, 'message')\nreturn result"
+    ),
+);
+
+test_rule_without_effects!(
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_assertions',
+        functions: ['debug.assert'],
+    }"#,
+    )
+    .unwrap(),
+    custom_assertion_used_as_expression("return debug.assert(value, 'message')"),
+    custom_assertion_used_as_argument("print(debug.assert(value))"),
+    custom_assertion_with_local_library("local debug = {} debug.assert(value)"),
+    assert_not_in_functions("assert(value)"),
+);
+
 test_rule_without_effects!(
     RemoveAssertions::default(),
     assert_function_used("local function assert() end assert('label')"),
@@ -7,9 +7,23 @@ test_rule!(
     decrease_identifier("a -= 1") => "a = a - 1",
     multiply_identifier("a *= 2") => "a = a * 2",
     divide_identifier("a /= 2") => "a = a / 2",
+    floor_divide_identifier("a //= 2") => "a = a // 2",
     mod_identifier("a %= 2") => "a = a % 2",
     exp_identifier("a ^= 2") => "a = a ^ 2",
     concat_identifier("a ..= 'suffix'") => "a = a .. 'suffix'",
+    increase_identifier_with_addition("a += b + c") => "a = a + (b + c)",
+    decrease_identifier_with_subtraction("a -= b - c") => "a = a - (b - c)",
+    decrease_identifier_with_addition("a -= b + c") => "a = a - (b + c)",
+    multiply_identifier_with_addition("a *= b + c") => "a = a * (b + c)",
+    divide_identifier_with_multiplication("a /= b * c") => "a = a / (b * c)",
+    floor_divide_identifier_with_multiplication("a //= b * c") => "a = a // (b * c)",
+    mod_identifier_with_addition("a %= b + c") => "a = a % (b + c)",
+    exp_identifier_with_exponent("a ^= b ^ c") => "a = a ^ b ^ c",
+    exp_identifier_with_multiplication("a ^= b * c") => "a = a ^ (b * c)",
+    concat_identifier_with_concat("a ..= b .. c") => "a = a .. b .. c",
+    concat_identifier_with_logical_or("a ..= b or c") => "a = a .. (b or c)",
+    increase_identifier_with_if_expression("a += if b then 1 else 2")
+        => "a = a + if b then 1 else 2",
     increase_index_without_side_effect("a[prop] += 1") => "a[prop] = a[prop] + 1",
     increase_index_with_true("a[true] += 1") => "a[true] = a[true] + 1",
     increase_index_with_false("a[false] += 1") => "a[false] = a[false] + 1",
@@ -17,12 +31,32 @@ test_rule!(
     increase_field("a.counter += 1") => "a.counter = a.counter + 1",
     increase_field_on_function_call("getObject().counter += 1")
         => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.counter = __DARKLUA_VAR.counter + 1 end",
-    increase_field_on_parentheses("(if condition then a else b).counter += 1")
-        => "do local __DARKLUA_VAR = if condition then a else b __DARKLUA_VAR.counter = __DARKLUA_VAR.counter + 1 end",
+    increase_field_on_parentheses("(if condition then getA() else b).counter += 1")
+        => "do local __DARKLUA_VAR = if condition then getA() else b __DARKLUA_VAR.counter = __DARKLUA_VAR.counter + 1 end",
+    increase_field_on_parentheses_without_side_effects("(if condition then a else b).counter += 1")
+        => "(if condition then a else b).counter = (if condition then a else b).counter + 1",
+    increase_field_on_table("({ counter = 0 }).counter += 1")
+        => "do local __DARKLUA_VAR = { counter = 0 } __DARKLUA_VAR.counter = __DARKLUA_VAR.counter + 1 end",
     increase_identifier_in_parenthese_for_field("(a).counter += 1") => "a.counter = a.counter + 1",
     increase_false_in_parenthese_for_field("(false).counter += 1") => "(false).counter = (false).counter + 1",
     increase_identifier_in_parenthese_for_index("(a)['counter'] += 1") => "a['counter'] = a['counter'] + 1",
     increase_true_in_parenthese_for_index("(true)['counter'] += 1") => "(true)['counter'] = (true)['counter'] + 1",
+    increase_index_with_interpolated_string_without_values("a[`key`] += 1")
+        => "a[`key`] = a[`key`] + 1",
+    increase_index_with_side_effects_in_interpolated_string("a[`key{call()}`] += 1")
+        => "do local __DARKLUA_VAR = `key{call()}` a[__DARKLUA_VAR] = a[__DARKLUA_VAR] + 1 end",
+    increase_index_with_negative_number("a[-1] += 1") => "a[-1] = a[-1] + 1",
+    increase_index_with_constant_expression("a[2 * 4] += 1") => "a[2 * 4] = a[2 * 4] + 1",
+    increase_index_with_logical_expression("a[key or 'default'] += 1")
+        => "a[key or 'default'] = a[key or 'default'] + 1",
+    increase_index_with_arithmetic_on_unknown_value("a[key + 1] += 1")
+        => "do local __DARKLUA_VAR = key + 1 a[__DARKLUA_VAR] = a[__DARKLUA_VAR] + 1 end",
+    increase_index_with_table("a[{}] += 1")
+        => "do local __DARKLUA_VAR = {} a[__DARKLUA_VAR] = a[__DARKLUA_VAR] + 1 end",
+    increase_index_with_function("a[function() end] += 1")
+        => "do local __DARKLUA_VAR = function() end a[__DARKLUA_VAR] = a[__DARKLUA_VAR] + 1 end",
+    increase_index_with_table_in_logical_expression("a[key or {}] += 1")
+        => "do local __DARKLUA_VAR = key or {} a[__DARKLUA_VAR] = a[__DARKLUA_VAR] + 1 end",
     increase_index_with_side_effects_in_index("a[call()] += 1")
         => "do local __DARKLUA_VAR = call() a[__DARKLUA_VAR] = a[__DARKLUA_VAR] + 1 end",
     increase_index_with_side_effects_in_prefix("object[call()][key] += 1")
@@ -31,6 +65,24 @@ test_rule!(
         => "do local __DARKLUA_VAR, __DARKLUA_VAR0 = object[call()], getKey() __DARKLUA_VAR[__DARKLUA_VAR0] = __DARKLUA_VAR[__DARKLUA_VAR0] + 1 end",
     nested_field_expressions("var.object.prop += 1")
         => "do local __DARKLUA_VAR = var.object __DARKLUA_VAR.prop = __DARKLUA_VAR.prop + 1 end",
+    decrease_field_on_function_call("getObject().counter -= 1")
+        => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.counter = __DARKLUA_VAR.counter - 1 end",
+    multiply_field_on_function_call("getObject().counter *= 2")
+        => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.counter = __DARKLUA_VAR.counter * 2 end",
+    divide_field_on_function_call("getObject().counter /= 2")
+        => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.counter = __DARKLUA_VAR.counter / 2 end",
+    floor_divide_field_on_function_call("getObject().counter //= 2")
+        => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.counter = __DARKLUA_VAR.counter // 2 end",
+    mod_field_on_function_call("getObject().counter %= 2")
+        => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.counter = __DARKLUA_VAR.counter % 2 end",
+    exp_field_on_function_call("getObject().counter ^= 2")
+        => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.counter = __DARKLUA_VAR.counter ^ 2 end",
+    concat_field_on_function_call("getObject().name ..= 'suffix'")
+        => "do local __DARKLUA_VAR = getObject() __DARKLUA_VAR.name = __DARKLUA_VAR.name .. 'suffix' end",
+    floor_divide_index_with_side_effects_in_prefix_and_index("object[call()][getKey()] //= b + c")
+        => "do local __DARKLUA_VAR, __DARKLUA_VAR0 = object[call()], getKey() __DARKLUA_VAR[__DARKLUA_VAR0] = __DARKLUA_VAR[__DARKLUA_VAR0] // (b + c) end",
+    concat_index_with_side_effects_in_index("a[call()] ..= b .. c")
+        => "do local __DARKLUA_VAR = call() a[__DARKLUA_VAR] = a[__DARKLUA_VAR] .. b .. c end",
     consecutive_nested_field_assignments("a.object.counter += 1 b.object.counter -= 1")
         => "do local __DARKLUA_VAR = a.object __DARKLUA_VAR.counter = __DARKLUA_VAR.counter + 1 end do local __DARKLUA_VAR0 = b.object __DARKLUA_VAR0.counter = __DARKLUA_VAR0.counter - 1 end",
 );
@@ -44,6 +96,37 @@ test_rule_with_tokens!(
     comment_after_variable("i --[[ comment ]] += 1") => "i --[[ comment ]] =i+ 1",
 );
 
+mod remove_compound_assignment_snapshots {
+    use super::*;
+
+    snapshot_rule_with_generator!(
+        RemoveCompoundAssignment::default(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        identifier_target,
+        "local count = 0\ncount += 1\nprint(count)\n"
+    );
+
+    snapshot_rule_with_generator!(
+        RemoveCompoundAssignment::default(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        field_target,
+        "local object = { count = 0 }\nobject.count += 1\n"
+    );
+
+    snapshot_rule_with_generator!(
+        RemoveCompoundAssignment::default(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        side_effect_index_target,
+        "t[f()] += 1\n"
+    );
+}
+
 #[test]
 fn deserialize_from_object_notation() {
     json5::from_str::<Box<dyn Rule>>(
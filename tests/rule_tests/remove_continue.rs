@@ -139,6 +139,88 @@ test_rule_snapshot!(
     end
     "#
     ),
+    generalized_iteration_continue_with_locals(
+        r#"
+    for key, value in items do
+        local name = tostring(key)
+        if value == nil then
+            continue
+        end
+        print(name, value)
+    end
+    "#
+    ),
+    numeric_for_continue_in_nested_block(
+        r#"
+    for i = 1, 10 do
+        do
+            local half = i / 2
+            if half % 1 ~= 0 then
+                continue
+            end
+        end
+        print(i)
+    end
+    "#
+    ),
+    repeat_until_continue(
+        r#"
+    local i = 0
+    repeat
+        i += 1
+        if i % 2 == 0 then
+            continue
+        end
+        print(i)
+    until i >= 10
+    "#
+    ),
+    repeat_until_continue_with_condition_using_local(
+        r#"
+    repeat
+        local value = read()
+        if value == "" then
+            continue
+        end
+        print(value)
+    until value == nil
+    "#
+    ),
+    repeat_until_ends_with_continue(
+        r#"
+    repeat
+        local value = read()
+        print(value)
+        continue
+    until value == nil
+    "#
+    ),
+    repeat_until_continue_and_break(
+        r#"
+    repeat
+        local value = read()
+        if value == "" then
+            continue
+        elseif value == "stop" then
+            break
+        end
+        print(value)
+    until value == nil
+    "#
+    ),
+    repeat_until_continue_in_nested_loop(
+        r#"
+    repeat
+        local values = read()
+        for _, value in values do
+            if value == "" then
+                continue
+            end
+            print(value)
+        end
+    until #values == 0
+    "#
+    ),
     for_loop_continue_in_function_statement(
         r#"
     for i = 1, 10 do
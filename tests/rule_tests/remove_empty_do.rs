@@ -7,7 +7,13 @@ test_rule!(
     empty_do_statement_in_numeric_for("for i=a, b do do end end") => "for i=a, b do end",
     empty_do_statements_in_local_function("local function foo() do end do do end end end")
         => "local function foo() end",
-    empty_do_statement_in_generic_for("for k,v in pairs({}) do do end end") => "for k,v in pairs({}) do end"
+    empty_do_statement_in_generic_for("for k,v in pairs({}) do do end end") => "for k,v in pairs({}) do end",
+    adjacent_empty_do_statements_between_calls("foo() do end do end do end bar()") => "foo() bar()",
+    trailing_empty_do_statement("local a = 1 print(a) do end") => "local a = 1 print(a)",
+    trailing_empty_do_statement_before_return("local a = 1 do end return a") => "local a = 1 return a",
+    nested_empty_do_statement_followed_by_non_empty_do("do do end end do do print() end end")
+        => "do do print() end end",
+    empty_do_statements_around_non_empty_do("do end do print() end do end") => "do print() end",
 );
 
 #[test]
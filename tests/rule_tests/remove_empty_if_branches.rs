@@ -0,0 +1,52 @@
+use darklua_core::rules::{RemoveEmptyIfBranches, Rule};
+
+test_rule!(
+    remove_empty_if_branches,
+    RemoveEmptyIfBranches::default(),
+    empty_else("if a then print(a) else end") => "if a then print(a) end",
+    empty_last_elseif("if a then print(a) elseif b then end") => "if a then print(a) end",
+    empty_last_elseif_and_else("if a then print(a) elseif b then else end") => "if a then print(a) end",
+    multiple_empty_last_elseif("if a then print(a) elseif b then elseif c then end")
+        => "if a then print(a) end",
+    empty_if("if a then end") => "",
+    empty_if_with_empty_branches("if a then elseif b then else end") => "",
+    empty_if_in_nested_block("do if a then end end") => "do end",
+    nested_empty_if("if a then if b then end end") => "",
+    empty_if_with_call_condition("if call() then end") => "call()",
+    empty_if_with_call_conditions("if a then elseif call() then end") => "local _ = a or call()",
+    empty_if_with_method_call_condition("if obj:check() then end") => "obj:check()",
+);
+
+test_rule_snapshot!(
+    remove_empty_if_branches,
+    RemoveEmptyIfBranches::default(),
+    empty_else_drop("if isReady then\n    start()\nelse\nend\n"),
+    empty_pure_elseif_drop("if isFast then\n    runFast()\nelseif isSlow then\nelse\nend\n"),
+    side_effect_condition_preserved("if isFast then\nelseif update() then\nend\n"),
+);
+
+test_rule_without_effects!(
+    RemoveEmptyIfBranches::default(),
+    if_with_content("if a then print(a) end"),
+    if_with_else_content("if a then else print(a) end"),
+    empty_elseif_before_non_empty_branch(
+        "if a then print(a) elseif b then elseif c then print(c) end"
+    ),
+    empty_elseif_before_else("if a then print(a) elseif b then else print(b) end"),
+    empty_last_elseif_with_side_effect("if a then print(a) elseif call() then end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_empty_if_branches',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'remove_empty_if_branches'").unwrap();
+}
@@ -1,4 +1,9 @@
-use darklua_core::rules::{RemoveIfExpression, Rule};
+use darklua_core::{
+    generator::{DenseLuaGenerator, LuaGenerator},
+    nodes::{BinaryOperator, Expression, LastStatement, Prefix, TableEntry, UnaryOperator},
+    rules::{ContextBuilder, RemoveIfExpression, Rule},
+    Parser, Resources,
+};
 
 test_rule!(
     remove_if_expression,
@@ -12,11 +17,19 @@ test_rule!(
     if_with_nil_result_else_false("local a = if condition() then nil else false")
         => "local a = (condition() and { nil } or { false })[1]",
     if_with_false_result_else_truthy("local a = if condition() then false else true")
-        => "local a = (condition() and { false } or { true })[1]",
+        => "local a = not condition() and true or false",
+    if_with_unknown_result_else_truthy("local a = if condition() then value else 0")
+        => "local a = not condition() and 0 or value",
+    if_with_negated_condition_else_truthy("local a = if not ready then value else 0")
+        => "local a = ready and 0 or value",
+    if_with_binary_condition_else_truthy("local a = if x or y then value else 0")
+        => "local a = not (x or y) and 0 or value",
     if_with_unknown_result_else_unknown("local a = if condition() then update() else default()")
         => "local a = (condition() and { (update()) } or { (default()) })[1]",
     assign_if_expression_with_elseif("local a = if true then 1 elseif false then 2 else 3")
         => "local a = true and 1 or (false and 2 or 3)",
+    assign_if_expression_with_multiple_elseif("local a = if x then 1 elseif y then 2 elseif z then 3 else 4")
+        => "local a = x and 1 or (y and 2 or (z and 3 or 4))",
     if_expression_with_varargs("local function f(...: string) return if condition(...) then ... else transform(...) end")
         => "local function f(...: string) return (condition(...) and {(...)} or {(transform(...))})[1] end",
     if_expression_with_varargs_elseif("local function f(...: string) return if condition(...) then ... elseif condition2(...) then ... else transform(...) end")
@@ -37,3 +50,166 @@ fn deserialize_from_object_notation() {
 fn deserialize_from_string() {
     json5::from_str::<Box<dyn Rule>>("'remove_if_expression'").unwrap();
 }
+
+fn remove_if_expressions(code: &str) -> String {
+    let resources = Resources::from_memory();
+    let context = ContextBuilder::new("test.lua", &resources, code).build();
+    let mut block = Parser::default().parse(code).unwrap();
+
+    RemoveIfExpression::default()
+        .process(&mut block, &context)
+        .unwrap();
+
+    let mut generator = DenseLuaGenerator::default();
+    generator.write_block(&block);
+    generator.into_string()
+}
+
+fn assert_vanilla_lua(code: &str) {
+    if let Err(errors) =
+        full_moon::parse_fallible(code, full_moon::LuaVersion::lua51()).into_result()
+    {
+        panic!("unable to parse `{}` as Lua 5.1: {:?}", code, errors);
+    }
+}
+
+#[test]
+fn snapshot_truthy_result() {
+    let code = remove_if_expressions("local a = if condition() then 1 else nil");
+    assert_vanilla_lua(&code);
+    insta::assert_snapshot!("remove_if_expression_truthy_result", code);
+}
+
+#[test]
+fn snapshot_truthy_else_result() {
+    let code = remove_if_expressions("local a = if condition() then value else 0");
+    assert_vanilla_lua(&code);
+    insta::assert_snapshot!("remove_if_expression_truthy_else_result", code);
+}
+
+#[test]
+fn snapshot_unknown_results() {
+    let code = remove_if_expressions("local a = if condition() then value else nil");
+    assert_vanilla_lua(&code);
+    insta::assert_snapshot!("remove_if_expression_unknown_results", code);
+}
+
+#[test]
+fn snapshot_elseif_chain() {
+    let code = remove_if_expressions(
+        "local a = if x then value elseif y then 1 elseif z then nil else other",
+    );
+    assert_vanilla_lua(&code);
+    insta::assert_snapshot!("remove_if_expression_elseif_chain", code);
+}
+
+/// A minimal evaluator for the constant expressions produced by the rule.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Nil,
+    Boolean(bool),
+    Number(f64),
+    Table(Vec<Value>),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Boolean(false))
+    }
+}
+
+fn evaluate(expression: &Expression) -> Value {
+    match expression {
+        Expression::Nil(_) => Value::Nil,
+        Expression::True(_) => Value::Boolean(true),
+        Expression::False(_) => Value::Boolean(false),
+        Expression::Number(number) => Value::Number(number.compute_value()),
+        Expression::Parenthese(parenthese) => evaluate(parenthese.inner_expression()),
+        Expression::Table(table) => Value::Table(
+            table
+                .get_entries()
+                .iter()
+                .map(|entry| match entry {
+                    TableEntry::Value(value) => evaluate(value),
+                    _ => panic!("unexpected table entry"),
+                })
+                .collect(),
+        ),
+        Expression::Index(index) => {
+            let values = match index.get_prefix() {
+                Prefix::Parenthese(parenthese) => evaluate(parenthese.inner_expression()),
+                _ => panic!("unexpected index prefix"),
+            };
+            match (values, evaluate(index.get_index())) {
+                (Value::Table(values), Value::Number(key)) => {
+                    values.get(key as usize - 1).cloned().unwrap_or(Value::Nil)
+                }
+                _ => panic!("unexpected index expression"),
+            }
+        }
+        Expression::Unary(unary) if unary.operator() == UnaryOperator::Not => {
+            Value::Boolean(!evaluate(unary.get_expression()).is_truthy())
+        }
+        Expression::Binary(binary) => {
+            let left = evaluate(binary.left());
+            match binary.operator() {
+                BinaryOperator::And if left.is_truthy() => evaluate(binary.right()),
+                BinaryOperator::Or if !left.is_truthy() => evaluate(binary.right()),
+                BinaryOperator::And | BinaryOperator::Or => left,
+                _ => panic!("unexpected binary operator"),
+            }
+        }
+        Expression::If(if_expression) => {
+            if evaluate(if_expression.get_condition()).is_truthy() {
+                return evaluate(if_expression.get_result());
+            }
+            for branch in if_expression.iter_branches() {
+                if evaluate(branch.get_condition()).is_truthy() {
+                    return evaluate(branch.get_result());
+                }
+            }
+            evaluate(if_expression.get_else_result())
+        }
+        _ => panic!("unexpected expression"),
+    }
+}
+
+fn evaluate_returned_value(code: &str) -> Value {
+    let block = Parser::default().parse(code).unwrap();
+    match block.get_last_statement() {
+        Some(LastStatement::Return(statement)) => {
+            evaluate(statement.iter_expressions().next().unwrap())
+        }
+        _ => panic!("expected a return statement"),
+    }
+}
+
+#[test]
+fn converted_expressions_evaluate_to_the_same_value() {
+    let values = ["nil", "false", "true", "1"];
+
+    for condition in values {
+        for elseif_condition in values {
+            for result in values {
+                for elseif_result in values {
+                    for else_result in values {
+                        let code = format!(
+                            "return if {} then {} elseif {} then {} else {}",
+                            condition, result, elseif_condition, elseif_result, else_result
+                        );
+                        let converted = remove_if_expressions(&code);
+                        assert_vanilla_lua(&converted);
+
+                        pretty_assertions::assert_eq!(
+                            evaluate_returned_value(&converted),
+                            evaluate_returned_value(&code),
+                            "\nconverted `{}` into `{}`",
+                            code,
+                            converted
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use darklua_core::rules::{RemoveRedundantReturn, Rule};
+
+test_rule!(
+    remove_redundant_return,
+    RemoveRedundantReturn::default(),
+    main_chunk_return("print('a') return") => "print('a')",
+    only_return("return") => "",
+    local_function_return("local function f() print('a') return end")
+        => "local function f() print('a') end",
+    function_statement_return("function f() return end") => "function f() end",
+    method_return("function object:f() self.value = 1 return end")
+        => "function object:f() self.value = 1 end",
+    function_expression_return("local f = function() call() return end")
+        => "local f = function() call() end",
+    nested_function_return("local function f() return function() return end end")
+        => "local function f() return function() end end",
+    return_with_semicolon("local function f() print('a') return; end")
+        => "local function f() print('a') end",
+);
+
+test_rule_snapshot!(
+    remove_redundant_return,
+    RemoveRedundantReturn::default(),
+    trailing_bare_return("local function update(state)\n    state.count += 1\n    return\nend\n"),
+    return_with_value_preserved("local function get(state)\n    return state.count\nend\n"),
+);
+
+test_rule_without_effects!(
+    RemoveRedundantReturn::default(),
+    return_value("return true"),
+    return_values_in_function("local function f() return 1, 2 end"),
+    return_call_in_function("local function f() return call() end"),
+    return_in_if_branch("local function f() if a then return end print('b') end"),
+    return_in_do_block("local function f() do return end end"),
+    return_in_loop("local function f() while true do return end end"),
+    return_in_last_if_branch("local function f() if a then return end end"),
+    return_in_repeat("local function f() repeat return until true end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_redundant_return',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'remove_redundant_return'").unwrap();
+}
@@ -11,6 +11,20 @@ test_rule!(
     one_truthy_branch_remove_else_block("if true then break else end") => "do break end",
     remove_falsy_elseif_branch("if foo then break elseif false then end") => "if foo then break end",
     remove_falsy_elseif_branch_and_empty_else("if foo then break elseif false then else end") => "if foo then break end",
+    truthy_branch_is_spliced("if true then call() end") => "call()",
+    truthy_branch_is_spliced_between_statements("first() if true then second() end third()")
+        => "first() second() third()",
+    falsy_branch_with_else_block_is_spliced("if false then a() else b() end") => "b()",
+    truthy_elseif_branch_is_spliced("if false then a() elseif true then b() else c() end")
+        => "b()",
+    nested_truthy_branches_are_spliced("if true then if false then a() else b() end end")
+        => "b()",
+    truthy_branch_with_local_converts_to_do("if true then local a = 1 print(a) end")
+        => "do local a = 1 print(a) end",
+    truthy_branch_with_local_function_converts_to_do("if true then local function f() end f() end")
+        => "do local function f() end f() end",
+    truthy_branch_with_type_declaration_converts_to_do("if true then type T = string end")
+        => "do type T = string end",
     remove_branch_after_truthy_branch("if foo then break elseif true then return elseif foo then end")
         => "if foo then break else return end",
     // if expressions
@@ -45,6 +59,52 @@ test_rule!(
     ) => "return if var then 'first' else 'third'",
 );
 
+test_rule_without_effects!(
+    RemoveUnusedIfBranch::default(),
+    truthy_condition_with_side_effects("if { call() } then a() end"),
+    unknown_condition("if condition then a() else b() end"),
+);
+
+mod remove_unused_if_branch_snapshots {
+    use super::*;
+
+    snapshot_rule_with_generator!(
+        RemoveUnusedIfBranch::default(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        always_false,
+        "local value = compute()\nif false then\n    print('never')\nelse\n    print(value)\nend\n"
+    );
+
+    snapshot_rule_with_generator!(
+        RemoveUnusedIfBranch::default(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        always_true,
+        "if true then\n    local message = 'hello'\n    print(message)\nend\n"
+    );
+
+    snapshot_rule_with_generator!(
+        RemoveUnusedIfBranch::default(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        elseif_chain,
+        "if false then\n    print('a')\nelseif true then\n    print('b')\nelse\n    print('c')\nend\nprint('done')\n"
+    );
+
+    snapshot_rule_with_generator!(
+        RemoveUnusedIfBranch::default(),
+        darklua_core::Resources::from_memory(),
+        |_| darklua_core::generator::DenseLuaGenerator::default(),
+        "src/test.lua",
+        elseif_chain_with_unknown_condition,
+        "if ready then\n    start()\nelseif true then\n    wait()\nelse\n    fail()\nend\n"
+    );
+}
+
 #[test]
 fn deserialize_from_object_notation() {
     json5::from_str::<Box<dyn Rule>>(
@@ -0,0 +1,71 @@
+use darklua_core::rules::{RemoveUnusedParameters, Rule};
+
+test_rule!(
+    remove_unused_parameters,
+    RemoveUnusedParameters::default(),
+    trailing_parameter("local function f(a, b) return a end return f(1, 2)")
+        => "local function f(a) return a end return f(1)",
+    all_parameters("local function f(a, b) print('x') end f(1, 2)")
+        => "local function f() print('x') end f()",
+    only_trailing_parameters("local function f(a, b, c) return b end f(1, 2, 3)")
+        => "local function f(a, b) return b end f(1, 2)",
+    call_with_fewer_arguments("local function f(a, b) return a end f(1) f(1, 2)")
+        => "local function f(a) return a end f(1) f(1)",
+    call_with_extra_arguments("local function f(a, b) return a end f(1, 2, 3)")
+        => "local function f(a) return a end f(1)",
+    extra_variable_arguments("local function f(a, b) return a end return function(...) return f(1, 2, ...) end")
+        => "local function f(a) return a end return function(...) return f(1) end",
+    recursive_call("local function f(n, unused) if n > 0 then f(n - 1, true) end end f(3, false)")
+        => "local function f(n) if n > 0 then f(n - 1) end end f(3)",
+    call_in_nested_function("local function f(a, b) return a end local function g() return f(1, 2) end return g()")
+        => "local function f(a) return a end local function g() return f(1) end return g()",
+    parameter_shadowed_in_body("local function f(a, b) local b = 1 return a + b end f(1, 2)")
+        => "local function f(a) local b = 1 return a + b end f(1)",
+    function_shadowed_in_nested_block("local function f(a, b) return a end do local f = print f(1, 2) end f(1, 2)")
+        => "local function f(a) return a end do local f = print f(1, 2) end f(1)",
+    string_argument("local function f(a) end f 'x'") => "local function f() end f()",
+    table_argument("local function f(a) end f {}") => "local function f() end f()",
+    unused_function("local function f(a) end") => "local function f() end",
+    call_in_repeat_condition("repeat local function f(a, b) return a end until f(true, false)")
+        => "repeat local function f(a) return a end until f(true)",
+);
+
+test_rule_with_tokens!(
+    remove_unused_parameters_with_tokens,
+    RemoveUnusedParameters::default(),
+    trailing_parameter("local function f(a, b) return a end return f(1, 2)")
+        => "local function f(a) return a end return f(1)",
+);
+
+test_rule_without_effects!(
+    RemoveUnusedParameters::default(),
+    all_parameters_used("local function f(a, b) return a + b end f(1, 2)"),
+    returned_function("local function f(a, b) return a end return f"),
+    function_passed_as_argument("local function f(a, b) return a end call(f)"),
+    function_assigned_to_variable("local function f(a, b) return a end local g = f g(1, 2)"),
+    function_reassigned("local function f(a, b) return a end f = print f(1, 2)"),
+    method_call("local function f(a, b) return a end f:method()"),
+    variadic_function("local function f(a, b, ...) return a end f(1, 2, 3)"),
+    spread_call_argument("local function f(a, b) return a end f(call())"),
+    spread_call_argument_in_removed_position("local function f(a, b) return a end f(1, call())"),
+    spread_variable_arguments(
+        "local function f(a, b) return a end return function(...) return f(...) end"
+    ),
+    argument_with_side_effects("local function f(a, b) return a end f(1, print('x'), 2)"),
+    global_function("function f(a, b) return a end f(1, 2)"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_unused_parameters',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'remove_unused_parameters'").unwrap();
+}
@@ -0,0 +1,63 @@
+use darklua_core::rules::{RemoveUnusedPureCall, Rule};
+
+test_rule!(
+    remove_unused_pure_call,
+    RemoveUnusedPureCall::default(),
+    math_floor("math.floor(1.5)") => "",
+    tostring_identifier("tostring(value)") => "",
+    type_with_string_argument("type'abc'") => "",
+    tostring_in_nested_block("do tostring(value) end") => "do end",
+    keep_other_statements("tostring(value) print(value)") => "print(value)",
+    math_max_with_call_argument("math.max(1, getValue())") => "getValue()",
+    tostring_with_index_argument("tostring(object.value)") => "local _ = object.value",
+    math_min_with_multiple_calls("math.min(getA(), getB())") => "do getA() getB() end",
+);
+
+test_rule_snapshot!(
+    remove_unused_pure_call,
+    RemoveUnusedPureCall::default(),
+    pure_call_with_pure_arguments("math.floor(10 / 3)\nprint('done')\n"),
+    pure_call_with_side_effect_argument(
+        "tostring(compute(value))\nlocal _ = math.abs(object.field)\nmath.abs(object.field)\n"
+    ),
+    unknown_call("compute(value)\nobject.method(value)\nmath.random(1, 10)\n"),
+);
+
+test_rule!(
+    remove_unused_pure_call_with_functions,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_unused_pure_call',
+        functions: ['string.len'],
+    }"#,
+    )
+    .unwrap(),
+    string_len("string.len('abc')") => "",
+);
+
+test_rule_without_effects!(
+    RemoveUnusedPureCall::default(),
+    result_used_in_local("local value = tostring(x)"),
+    result_returned("return math.floor(x)"),
+    method_call("math:floor(x)"),
+    unknown_function("print(x)"),
+    library_is_local("local math = {} math.floor(x)"),
+    function_is_local("local function tostring() end tostring(x)"),
+    function_is_parameter("local function fn(type) type(x) end"),
+    function_not_in_configured_list("string.len('abc')"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_unused_pure_call',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'remove_unused_pure_call'").unwrap();
+}
@@ -81,6 +81,14 @@ test_rule!(
     // ) => "print('foo')",
 );
 
+test_rule_snapshot!(
+    remove_unused_variable,
+    RemoveUnusedVariable::default(),
+    local_shadowed_before_read("local x = 1\nlocal x = 2\nreturn x\n"),
+    local_with_side_effect_shadowed_before_read("local x = compute()\nlocal x = 2\nreturn x\n"),
+    local_read_before_shadowed("local x = 1\nprint(x)\nlocal x = 2\nreturn x\n"),
+);
+
 test_rule_without_effects!(
     RemoveUnusedVariable::default(),
     keep_returning_local_function("local function foo() end return foo"),
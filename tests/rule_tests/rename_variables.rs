@@ -96,6 +96,62 @@ test_rule!(
         => "local a = require('@pkg/@jsdotlua/react') type Props = { children: a.ReactNode }",
 );
 
+test_rule!(
+    rename_variables_scoping,
+    RenameVariables::default(),
+    shadowed_local_in_nested_block("local foo do local foo = foo return foo end return foo")
+        => "local a do local b = a return b end return a",
+    shadowed_parameter("local foo = 1 return function(foo) return foo end")
+        => "local a = 1 return function(b) return b end",
+    shadowed_local_in_for_loop("local i = 0 for i = 1, 10 do print(i) end return i")
+        => "local a = 0 for b = 1, 10 do print(b) end return a",
+    closure_captures_outer_local("local foo = 1 local bar = function() local baz = 2 return foo + baz end")
+        => "local a = 1 local b = function() local b = 2 return a + b end",
+    closure_captures_parameter("return function(foo) return function(bar) return foo + bar end end")
+        => "return function(a) return function(b) return a + b end end",
+    closure_captures_loop_variable("for key, value in pairs(t) do callbacks[key] = function() return value end end")
+        => "for a, b in pairs(t) do callbacks[a] = function() return b end end",
+    reuse_names_of_disjoint_scopes("do local foo end do local bar end return function(baz) end")
+        => "do local a end do local a end return function(a) end",
+    keep_outer_local_live_in_nested_function("local foo do local bar end return function() local baz return foo end")
+        => "local a do local b end return function() local b return a end",
+    avoid_global_used_in_code("local foo = 1 print(a, foo)") => "local b = 1 print(a, b)",
+    avoid_global_used_in_nested_function("local foo = 1 return function() return a + foo end")
+        => "local b = 1 return function() return a + b end",
+    avoid_global_assigned_in_code("local foo = 1 a = foo") => "local b = 1 a = b",
+    avoid_global_function_name("function a() end local foo = 1") => "function a() end local b = 1",
+    avoid_global_used_before_local_declaration("print(a) local a = 1 return a")
+        => "print(a) local b = 1 return b",
+);
+
+test_rule!(
+    rename_variables_with_reserved_globals,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'rename_variables',
+        globals: ['$default', 'a', 'c'],
+    }"#,
+    ).unwrap(),
+    avoid_reserved_name("local foo") => "local b",
+    avoid_multiple_reserved_names("local foo, bar, baz") => "local b, d, e",
+    avoid_reserved_name_in_function_parameters("return function(foo, bar) end")
+        => "return function(b, d) end",
+    avoid_reserved_name_when_reusing_names("do local foo, bar end local baz")
+        => "do local b, d end local b",
+);
+
+test_rule!(
+    rename_variables_without_default_globals,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'rename_variables',
+        globals: [],
+    }"#,
+    ).unwrap(),
+    avoid_global_used_in_code_even_if_not_reserved("local foo = 1 return a(foo)")
+        => "local b = 1 return a(b)",
+);
+
 test_rule_without_effects!(
     RenameVariables::default(),
     local_function_name("local function foo() end"),
@@ -0,0 +1,95 @@
+use darklua_core::{
+    generator::{DenseLuaGenerator, LuaGenerator},
+    rules::{ContextBuilder, GroupLocalAssignment, ReorderLocalAssignments, Rule},
+    Parser, Resources,
+};
+
+test_rule!(
+    reorder_local_assignments,
+    ReorderLocalAssignments::default(),
+    independent_declaration_moved_before_dependency("local a = 1 local b = a local c = 2")
+        => "local a = 1 local c = 2 local b = a",
+    multiple_levels("local a = 1 local b = a local c = b local d = 2 local e = 3")
+        => "local a = 1 local d = 2 local e = 3 local b = a local c = b",
+    dependent_declarations_grouped("local a = 1 local b = a local c = 2 local d = c")
+        => "local a = 1 local c = 2 local b = a local d = c",
+    declaration_without_value("local a = 1 local b = a local c")
+        => "local a = 1 local c local b = a",
+    reorder_inside_function("local function f() local a = {} local b = { a } local c = 'c' return b, c end")
+        => "local function f() local a = {} local c = 'c' local b = { a } return b, c end",
+);
+
+test_rule_without_effects!(
+    ReorderLocalAssignments::default(),
+    single_declaration("local a = 1"),
+    independent_declarations("local a = 1 local b = 2 local c = 3"),
+    dependency_chain("local a = 1 local b = a local c = b"),
+    dependency_forbids_reorder("local a = 1 local b = a + 1 local c = b * 2"),
+    declaration_shadows_read_variable("local a = value local b = a local value = 2"),
+    declarations_with_same_name("local a = 1 local b = a local a = 2"),
+    function_capturing_later_declaration(
+        "local a = 1 local f = function() return a + b end local b = 2"
+    ),
+    call_between_declarations("local a = 1 local b = a print(a) local c = 2"),
+    assignment_between_declarations("local a = 1 local b = a x = 1 local c = x"),
+    declaration_with_call("local a = 1 local b = a local c = f()"),
+    declaration_with_call_before("local a = f() local b = a local c = 2"),
+    declaration_with_field_access("local a = 1 local b = a local c = object.value"),
+);
+
+fn process_with_rules(code: &str, rules: &[&dyn Rule]) -> String {
+    let resources = Resources::from_memory();
+    let context = ContextBuilder::new("test.lua", &resources, code).build();
+    let mut block = Parser::default().parse(code).unwrap();
+
+    for rule in rules {
+        rule.process(&mut block, &context).unwrap();
+    }
+
+    let mut generator = DenseLuaGenerator::default();
+    generator.write_block(&block);
+    generator.into_string()
+}
+
+#[test]
+fn reorder_enables_group_local_assignment() {
+    let code = "local a = 1 local b = a local c = 2";
+    let group = GroupLocalAssignment::default();
+    let reorder = ReorderLocalAssignments::default();
+
+    pretty_assertions::assert_eq!(
+        process_with_rules(code, &[&group]),
+        "local a=1 local b,c=a,2"
+    );
+    pretty_assertions::assert_eq!(
+        process_with_rules(code, &[&reorder, &group]),
+        "local a,c=1,2 local b=a"
+    );
+}
+
+#[test]
+fn dependency_forbids_reorder_and_merge() {
+    let code = "local a = 1 local b = a local c = b";
+    let group = GroupLocalAssignment::default();
+    let reorder = ReorderLocalAssignments::default();
+
+    pretty_assertions::assert_eq!(
+        process_with_rules(code, &[&reorder, &group]),
+        "local a=1 local b=a local c=b"
+    );
+}
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'reorder_local_assignments',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'reorder_local_assignments'").unwrap();
+}
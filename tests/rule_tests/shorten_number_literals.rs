@@ -0,0 +1,213 @@
+use darklua_core::{
+    generator::{DenseLuaGenerator, LuaGenerator, LuaVersion, TokenBasedLuaGenerator},
+    nodes::{Expression, LastStatement},
+    rules::{ContextBuilder, Rule, ShortenNumberLiterals},
+    Parser, Resources,
+};
+
+test_rule!(
+    shorten_number_literals,
+    ShortenNumberLiterals::default(),
+    large_exponent("return 1e300") => "return 1e300",
+    hex_to_decimal("return 0x10") => "return 16",
+    binary_to_decimal("return 0b101") => "return 5",
+    large_hex("return 0xFFFFFFFFFFFF") => "return 0xFFFFFFFFFFFF",
+    small_value("return 0.0000001") => "return 1e-7",
+);
+
+test_rule_without_effects!(
+    ShortenNumberLiterals::default(),
+    integer("return 100"),
+    zero("return 0"),
+    decimal("return 0.5"),
+    negative_integer("return -100"),
+    thousand("return 1e3"),
+    large_integer("return 1000000"),
+    integer_exceeding_exact_integers("return 9007199254740993"),
+);
+
+fn get_return_value(code: &str) -> f64 {
+    let block = Parser::default()
+        .parse(code)
+        .unwrap_or_else(|err| panic!("unable to parse `{}`: {}", code, err));
+
+    match block.get_last_statement() {
+        Some(LastStatement::Return(statement)) => match statement.iter_expressions().next() {
+            Some(Expression::Number(number)) => number.compute_value(),
+            _ => panic!("expected number in `{}`", code),
+        },
+        _ => panic!("expected return statement in `{}`", code),
+    }
+}
+
+fn shorten(code: &str, target: Option<LuaVersion>) -> String {
+    let resources = Resources::from_memory();
+    let mut context_builder = ContextBuilder::new("test.lua", &resources, code);
+    if let Some(target) = target {
+        context_builder = context_builder.with_target(target);
+    }
+    let context = context_builder.build();
+    let mut block = Parser::default().parse(code).unwrap();
+
+    ShortenNumberLiterals::default()
+        .process(&mut block, &context)
+        .unwrap();
+
+    let mut generator = DenseLuaGenerator::default();
+    generator.write_block(&block);
+    let shortened = generator.into_string();
+
+    pretty_assertions::assert_eq!(
+        get_return_value(&shortened).to_bits(),
+        get_return_value(code).to_bits(),
+        "`{}` does not evaluate to the same value as `{}`",
+        shortened,
+        code
+    );
+
+    shortened
+}
+
+macro_rules! test_shortest_form {
+    ($target:expr => $($name:ident ($input:literal) => $output:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                pretty_assertions::assert_eq!(shorten($input, $target), $output);
+            }
+        )*
+    };
+}
+
+mod shortest_form {
+    use super::*;
+
+    test_shortest_form!(None =>
+        trailing_zero_decimal("return 100.0") => "return 100",
+        integer_with_exponent("return 10e1") => "return 1e2",
+        thousand("return 1000") => "return 1000",
+        hex_small_integer("return 0x10") => "return 16",
+        hex_kept_when_shorter("return 0xFFFFFFFFFFFF") => "return 0xffffffffffff",
+        million("return 1000000") => "return 1000000",
+        tiny_value("return 0.00000015") => "return 1.5e-7",
+        fraction("return 0.25") => "return 0.25",
+        large_value("return 1e300") => "return 1e300",
+        scientific_fraction("return 12.5e-1") => "return 1.25",
+        same_length_is_kept("return 1E2") => "return 1E2",
+        pi("return 3.141592653589793") => "return 3.141592653589793",
+    );
+}
+
+mod shortest_form_with_luau_target {
+    use super::*;
+
+    test_shortest_form!(Some(LuaVersion::Luau) =>
+        trailing_zero_decimal("return 100.0") => "return 100",
+        integer_with_exponent("return 10e1") => "return 100",
+        thousand("return 1000") => "return 1e3",
+        million("return 1000000") => "return 1e6",
+        zero_with_exponent("return 0e10") => "return 0",
+    );
+}
+
+mod shortest_form_with_lua54_target {
+    use super::*;
+
+    test_shortest_form!(Some(LuaVersion::Lua54) =>
+        integer_is_not_written_with_exponent("return 1000") => "return 1000",
+        large_integer_is_not_written_with_exponent("return 1000000") => "return 1000000",
+        float_is_written_with_exponent("return 0.00000015") => "return 1.5e-7",
+    );
+}
+
+mod shortest_form_with_lua51_target {
+    use super::*;
+
+    test_shortest_form!(Some(LuaVersion::Lua51) =>
+        thousand("return 1000") => "return 1e3",
+    );
+}
+
+fn shorten_with_tokens(code: &str, target: Option<LuaVersion>) -> String {
+    let resources = Resources::from_memory();
+    let mut context_builder = ContextBuilder::new("test.lua", &resources, code);
+    if let Some(target) = target {
+        context_builder = context_builder.with_target(target);
+    }
+    let context = context_builder.build();
+    let mut block = Parser::default().preserve_tokens().parse(code).unwrap();
+
+    ShortenNumberLiterals::default()
+        .process(&mut block, &context)
+        .unwrap();
+
+    let mut generator = TokenBasedLuaGenerator::new(code);
+    generator.write_block(&block);
+    generator.into_string()
+}
+
+macro_rules! test_number_subtype {
+    ($target:expr => $($name:ident ($input:literal) => $output:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                pretty_assertions::assert_eq!(shorten_with_tokens($input, $target), $output);
+            }
+        )*
+    };
+}
+
+mod number_subtype {
+    use super::*;
+
+    test_number_subtype!(None =>
+        float_is_not_converted_to_integer("return 1.0") => "return 1.0",
+        integer_is_not_converted_to_float("return 1000") => "return 1000",
+        float_is_shortened_to_float("return 100.0") => "return 1e2",
+        integer_is_shortened_to_integer("return 0x10") => "return 16",
+        large_integer_is_not_shortened("return 9007199254740993") => "return 9007199254740993",
+    );
+}
+
+mod number_subtype_with_lua54_target {
+    use super::*;
+
+    test_number_subtype!(Some(LuaVersion::Lua54) =>
+        float_is_not_converted_to_integer("return 1.0") => "return 1.0",
+        integer_is_not_converted_to_float("return 1000") => "return 1000",
+        large_integer_is_not_shortened("return 9007199254740993") => "return 9007199254740993",
+    );
+}
+
+mod number_subtype_with_luau_target {
+    use super::*;
+
+    test_number_subtype!(Some(LuaVersion::Luau) =>
+        float_is_converted_to_integer("return 1.0") => "return 1",
+        integer_is_converted_to_float("return 1000") => "return 1e3",
+    );
+}
+
+mod number_subtype_with_lua51_target {
+    use super::*;
+
+    test_number_subtype!(Some(LuaVersion::Lua51) =>
+        float_is_converted_to_integer("return 1.0") => "return 1",
+        integer_is_converted_to_float("return 1000") => "return 1e3",
+    );
+}
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'shorten_number_literals',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'shorten_number_literals'").unwrap();
+}
@@ -0,0 +1,33 @@
+use darklua_core::rules::{Rule, ShortenStringEscapes};
+
+test_rule_with_tokens!(
+    shorten_string_escapes,
+    ShortenStringEscapes::default(),
+    decimal_escape_to_letter("return '\\097'") => "return 'a'",
+    decimal_escapes_to_word("return \"\\104\\105\"") => "return \"hi\"",
+    hexadecimal_escape_to_letter("return '\\x62'") => "return 'b'",
+    control_character("return '\\001'") => "return '\\1'",
+    control_character_followed_by_digit("return '\\0012'") => "return '\\0012'",
+    tab_character("return '\\009'") => "return '\\t'",
+    same_quote_character("return '\\039'") => "return '\\''",
+    other_quote_character("return '\\034'") => "return '\"'",
+    backslash_character("return '\\092'") => "return '\\\\'",
+    string_without_escapes("return 'abc'") => "return 'abc'",
+    long_string("return [[\\097]]") => "return [[\\097]]",
+    string_in_call("print('\\072ello')") => "print('Hello')",
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'shorten_string_escapes',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'shorten_string_escapes'").unwrap();
+}
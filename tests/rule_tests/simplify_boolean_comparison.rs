@@ -0,0 +1,104 @@
+use darklua_core::rules::{Rule, SimplifyBooleanComparison};
+
+test_rule!(
+    simplify_boolean_comparison,
+    SimplifyBooleanComparison::default(),
+    comparison_equal_true("return (a == b) == true") => "return (a == b)",
+    comparison_equal_false("return (a == b) == false") => "return not (a == b)",
+    comparison_not_equal_true("return (a < b) ~= true") => "return not (a < b)",
+    comparison_not_equal_false("return (a < b) ~= false") => "return (a < b)",
+    true_equal_comparison("return true == (a >= b)") => "return (a >= b)",
+    false_equal_comparison("return false == (a >= b)") => "return not (a >= b)",
+    negation_equal_true("return not a == true") => "return not a",
+    negation_equal_false("return (not a) == false") => "return not (not a)",
+    and_of_booleans_equal_true("return (a == 1 and b == 2) == true") => "return (a == 1 and b == 2)",
+    type_cast_equal_true("return (value :: boolean) == true") => "return (value :: boolean)",
+    type_cast_to_union_equal_false("return (value :: true | false) == false")
+        => "return not (value :: true | false)",
+    if_expression_equal_true("return (if a then true else b == 1) == true")
+        => "return (if a then true else b == 1)",
+    annotated_local_equal_true("local a: boolean = call() return a == true")
+        => "local a: boolean = call() return a",
+    annotated_local_equal_false("local a: boolean = call() return a == false")
+        => "local a: boolean = call() return not a",
+    annotated_local_not_equal_true("local a: boolean = call() return a ~= true")
+        => "local a: boolean = call() return not a",
+    annotated_parameter_equal_true("local function f(a: boolean) return a == true end")
+        => "local function f(a: boolean) return a end",
+    annotated_function_expression_parameter("return function(a: boolean, b) return a == true end")
+        => "return function(a: boolean, b) return a end",
+    annotated_second_local("local a, b: boolean = call() return b == true")
+        => "local a, b: boolean = call() return b",
+    annotated_generic_for_identifier("for _, a: boolean in values do print(a == false) end")
+        => "for _, a: boolean in values do print(not a) end",
+    annotated_local_in_nested_function(
+        "local a: boolean = call() local function f() return a == true end"
+    ) => "local a: boolean = call() local function f() return a end",
+    nested_comparison("local a: boolean = call() return (a == true) == true")
+        => "local a: boolean = call() return (a)",
+    double_negation_of_comparison("return not not (a == b)") => "return (a == b)",
+    double_negation_of_annotated_local("local a: boolean = call() return not not a")
+        => "local a: boolean = call() return a",
+    in_if_condition("local a: boolean = call() if a == true then end")
+        => "local a: boolean = call() if a then end",
+    double_negation_in_if_condition("if not not a then end") => "if a then end",
+    double_negation_in_elseif_condition("if a then elseif not not b then end")
+        => "if a then elseif b then end",
+    double_negation_in_while_condition("while not not a do end") => "while a do end",
+    double_negation_in_repeat_condition("repeat until not not a") => "repeat until a",
+    double_negation_in_if_expression_condition("return if not not a then 1 else 2")
+        => "return if a then 1 else 2",
+    double_negation_in_elseif_expression_condition("return if a then 1 elseif not not b then 2 else 3")
+        => "return if a then 1 elseif b then 2 else 3",
+    double_negation_in_parenthese_condition("if (not not a) then end") => "if (a) then end",
+    double_negation_in_and_condition("if not not a and not not b then end") => "if a and b then end",
+    double_negation_in_or_condition("while not not a or b do end") => "while a or b do end",
+    quadruple_negation_in_condition("if not not not not a then end") => "if a then end",
+    triple_negation("return not not not a") => "return not a",
+    negation_of_parenthese_double_negation("return not (not not a)") => "return not (a)",
+    negation_of_and_with_double_negation("return not (not not a and b)") => "return not (a and b)",
+);
+
+test_rule_snapshot!(
+    simplify_boolean_comparison,
+    SimplifyBooleanComparison::default(),
+    double_negation_in_if_condition("if not not value then\n    print('ok')\nend"),
+    double_negation_assigned_to_local("local result = not not value\nreturn result"),
+    double_negation_of_boolean_assigned_to_local(
+        "local value: boolean = call()\nlocal result = not not value\nreturn result"
+    ),
+);
+
+test_rule_without_effects!(
+    SimplifyBooleanComparison::default(),
+    unknown_identifier_equal_true("return a == true"),
+    unknown_identifier_equal_false("return a == false"),
+    unknown_identifier_not_equal_true("return a ~= true"),
+    number_equal_true("return 1 == true"),
+    call_equal_true("return call() == true"),
+    field_equal_true("return object.value == true"),
+    unannotated_local_equal_true("local a = call() return a == true"),
+    local_initialized_with_boolean_equal_true("local a = false return a == true"),
+    local_annotated_with_other_type("local a: number = call() return a == true"),
+    local_annotated_with_optional_boolean("local a: boolean? = call() return a == true"),
+    annotated_local_shadowed("local a: boolean = call() local a = call() return a == true"),
+    annotated_local_shadowed_by_parameter(
+        "local a: boolean = call() local function f(a) return a == true end"
+    ),
+    annotated_local_shadowed_by_local_function(
+        "local a: boolean = call() local function a() end return a == true"
+    ),
+    annotated_local_out_of_scope("do local a: boolean = call() end return a == true"),
+    annotated_local_used_in_its_own_value("local a: boolean = a == true"),
+    and_with_unknown_value("return (a == 1 and b) == true"),
+    double_negation_of_unknown_value("return not not a"),
+    double_negation_assigned_to_local("local b = not not a"),
+    double_negation_passed_as_argument("print(not not a)"),
+    double_negation_in_and_value("return not not a and b"),
+    double_negation_in_if_expression_result("return if a then not not b else false"),
+    comparison_with_non_boolean_literal("return (a == b) == 1"),
+    unknown_identifier_equal_nil("return a == nil"),
+    unknown_identifier_not_equal_nil("return a ~= nil"),
+    annotated_local_equal_nil("local a: boolean = call() return a == nil"),
+    optional_boolean_equal_nil("local a: boolean? = call() return a == nil"),
+);
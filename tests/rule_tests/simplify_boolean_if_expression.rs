@@ -0,0 +1,57 @@
+use darklua_core::rules::{Rule, SimplifyBooleanIfExpression};
+
+test_rule!(
+    simplify_boolean_if_expression,
+    SimplifyBooleanIfExpression::default(),
+    comparison_condition("return if a == b then true else false") => "return a == b",
+    negated_comparison_condition("return if a == b then false else true") => "return not (a == b)",
+    negated_unknown_condition("return if value then false else true") => "return not value",
+    not_condition("return if not value then true else false") => "return not value",
+    boolean_type_cast_condition("return if value :: boolean then true else false")
+        => "return value :: boolean",
+    parenthese_condition("return if (a < b) then true else false") => "return (a < b)",
+    unknown_condition_in_if_statement("if (if value then true else false) then end")
+        => "if (value) then end",
+    unknown_condition_in_while("while if value then true else false do end")
+        => "while value do end",
+    unknown_condition_in_repeat("repeat until if value then true else false")
+        => "repeat until value",
+    unknown_condition_in_not("return not (if value then true else false)") => "return not (value)",
+    unknown_condition_in_if_expression_condition(
+        "return if (if value then true else false) then 1 else 2"
+    ) => "return if (value) then 1 else 2",
+);
+
+test_rule_snapshot!(
+    simplify_boolean_if_expression,
+    SimplifyBooleanIfExpression::default(),
+    provably_boolean_condition(
+        "local isGreater = if a > b then true else false\nprint(isGreater)\n"
+    ),
+    negated_condition("local isBlocked = if isReady then false else true\nprint(isBlocked)\n"),
+    unknown_condition_bail("local enabled = if value then true else false\nprint(enabled)\n"),
+);
+
+test_rule_without_effects!(
+    SimplifyBooleanIfExpression::default(),
+    unknown_condition("return if value then true else false"),
+    call_condition("return if call() then true else false"),
+    elseif_branch("return if a then true elseif b then false else false"),
+    non_boolean_results("return if a == b then 1 else 0"),
+    same_results("return if a == b then true else true"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'simplify_boolean_if_expression',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'simplify_boolean_if_expression'").unwrap();
+}
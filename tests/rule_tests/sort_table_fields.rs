@@ -0,0 +1,48 @@
+use darklua_core::rules::{Rule, SortTableFields};
+
+test_rule!(
+    sort_table_fields,
+    SortTableFields::default(),
+    two_fields("return { b = 1, a = 2 }") => "return { a = 2, b = 1 }",
+    reversed_fields("return { c = true, b = false, a = nil }")
+        => "return { a = nil, b = false, c = true }",
+    fields_with_variables("return { value = value, key = key }")
+        => "return { key = key, value = value }",
+    fields_with_functions("return { update = function() end, new = function() end }")
+        => "return { new = function() end, update = function() end }",
+    uppercase_before_lowercase("return { name = 'a', Name = 'b' }")
+        => "return { Name = 'b', name = 'a' }",
+    nested_tables("return { z = { d = 1, c = 2 }, y = {} }")
+        => "return { y = {}, z = { c = 2, d = 1 } }",
+    inner_table_of_bail("return { 1, { b = 1, a = 2 } }") => "return { 1, { a = 2, b = 1 } }",
+    table_in_call("call({ y = 1, x = 2 })") => "call({ x = 2, y = 1 })",
+);
+
+test_rule_without_effects!(
+    SortTableFields::default(),
+    empty_table("return {}"),
+    single_field("return { a = 1 }"),
+    sorted_fields("return { a = 1, b = 2, c = 3 }"),
+    array_entries("return { 1, 2, 3 }"),
+    fields_with_array_entry("return { b = 1, 'value', a = 2 }"),
+    fields_with_index_entry("return { b = 1, ['c'] = 2, a = 3 }"),
+    duplicate_fields("return { b = 1, a = 2, b = 3 }"),
+    field_with_call("return { b = call(), a = 2 }"),
+    fields_with_calls("return { b = first(), a = second() }"),
+    field_with_field_access("return { b = object.value, a = 2 }"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'sort_table_fields',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'sort_table_fields'").unwrap();
+}
@@ -0,0 +1,79 @@
+use darklua_core::rules::{Rule, UnrollIpairsLoop};
+
+test_rule!(
+    unroll_ipairs_loop,
+    UnrollIpairsLoop::default(),
+    index_and_value("for i, v in ipairs({ 'a', 'b' }) do print(i, v) end")
+        => "do print(1, 'a') print(2, 'b') end",
+    value_only("for _, name in ipairs({ 'x', 'y', 'z' }) do print(name) end")
+        => "do print('x') print('y') print('z') end",
+    index_only("for i in ipairs({ true, false }) do print(i) end")
+        => "do print(1) print(2) end",
+    table_call_argument("for _, v in ipairs{ 10, 20 } do print(v) end")
+        => "do print(10) print(20) end",
+    computed_values("for _, v in ipairs({ 1 + 1, 'a' .. 'b' }) do print(v) end")
+        => "do print(2) print('ab') end",
+    empty_table("for _, v in ipairs({}) do print(v) end") => "do end",
+    body_with_local("for _, v in ipairs({ 1, 2 }) do local x = v * 2 print(x) end")
+        => "do do local x = 1 * 2 print(x) end do local x = 2 * 2 print(x) end end",
+    body_with_return("local function f() for _, v in ipairs({ 1, 2 }) do return v end end")
+        => "local function f() do do return 1 end do return 2 end end end",
+    value_as_call_prefix("for _, v in ipairs({ 'a' }) do v:upper() end")
+        => "do ('a'):upper() end",
+    shadowed_value("for _, v in ipairs({ 1 }) do local v = v + 1 print(v) end")
+        => "do do local v = 1 + 1 print(v) end end",
+    value_in_closure("for _, v in ipairs({ 1 }) do call(function() return v end) end")
+        => "do call(function() return 1 end) end",
+    break_in_nested_loop("for _, v in ipairs({ 1 }) do while true do break end print(v) end")
+        => "do while true do break end print(1) end",
+    nested_loops("for _, a in ipairs({ 1, 2 }) do for _, b in ipairs({ 3 }) do print(a, b) end end")
+        => "do do print(1, 3) end do print(2, 3) end end",
+);
+
+test_rule!(
+    unroll_ipairs_loop_with_maximum_length,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+            rule: 'unroll_ipairs_loop',
+            maximum_length: 2,
+        }"#
+    ).unwrap(),
+    array_at_maximum_length("for _, v in ipairs({ 1, 2 }) do print(v) end")
+        => "do print(1) print(2) end",
+);
+
+test_rule_without_effects!(
+    UnrollIpairsLoop::default(),
+    table_above_maximum_length("for _, v in ipairs({ 1, 2, 3, 4, 5 }) do print(v) end"),
+    pairs_call("for k, v in pairs({ a = 1, b = 2 }) do print(k, v) end"),
+    pairs_call_on_array("for i, v in pairs({ 1, 2 }) do print(i, v) end"),
+    unknown_table("for _, v in ipairs(list) do print(v) end"),
+    table_with_field("for _, v in ipairs({ 1, key = 2 }) do print(v) end"),
+    table_with_unknown_value("for _, v in ipairs({ 1, value }) do print(v) end"),
+    table_with_nil_value("for _, v in ipairs({ 1, nil }) do print(v) end"),
+    table_with_call("for _, v in ipairs({ call() }) do print(v) end"),
+    table_with_table_value("for _, v in ipairs({ {} }) do print(v) end"),
+    value_reassigned("for _, v in ipairs({ 1 }) do v = v + 1 print(v) end"),
+    index_reassigned("for i in ipairs({ 1 }) do i = 2 print(i) end"),
+    value_reassigned_in_closure("for _, v in ipairs({ 1 }) do call(function() v = 2 end) end"),
+    body_with_break("for _, v in ipairs({ 1, 2 }) do if v then break end end"),
+    body_with_continue("for _, v in ipairs({ 1, 2 }) do if v then continue end end"),
+    ipairs_is_local("local ipairs = pairs for _, v in ipairs({ 1 }) do print(v) end"),
+    ipairs_with_two_arguments("for _, v in ipairs({ 1 }, 2) do print(v) end"),
+    three_identifiers("for a, b, c in ipairs({ 1 }) do print(a, b, c) end"),
+);
+
+#[test]
+fn deserialize_from_object_notation() {
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'unroll_ipairs_loop',
+    }"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn deserialize_from_string() {
+    json5::from_str::<Box<dyn Rule>>("'unroll_ipairs_loop'").unwrap();
+}